@@ -0,0 +1,53 @@
+//! Benchmarks of advancing a [`Board`] by a generation, compared against the simple implementation.
+//!
+//! Run with `cargo bench -p gol_dense`. Both implementations are measured within the same group, so their times can
+//! be compared directly, such as `cargo bench -p gol_dense -- filled_rectangle`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use gol_dense::Board;
+use gol_lib::{Area, Automaton, Simulator};
+
+/// Measures a single tick of each implementation, starting from a clone of the same board each time.
+fn compare_tick(criterion: &mut Criterion, name: &str, area: Area, density: f32) {
+    let mut dense = Board::new(Default::default());
+    let mut simple = gol_simple::Board::new(Default::default());
+    dense.randomize(area, density, 0);
+    simple.randomize(area, density, 0);
+
+    let mut group = criterion.benchmark_group(name);
+    // The larger boards take a long time to tick, so fewer samples are taken.
+    group.sample_size(10);
+    group.bench_function("dense", |bencher| {
+        bencher.iter_batched(
+            || dense.clone(),
+            |mut board| board.tick(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("simple", |bencher| {
+        bencher.iter_batched(
+            || simple.clone(),
+            |mut board| board.tick(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn tick(criterion: &mut Criterion) {
+    compare_tick(
+        criterion,
+        "filled_rectangle",
+        Area::new((0, 0), (999, 499)),
+        1.0,
+    );
+    compare_tick(
+        criterion,
+        "random_field",
+        Area::new((0, 0), (255, 255)),
+        0.5,
+    );
+}
+
+criterion_group!(benches, tick);
+criterion_main!(benches);
@@ -0,0 +1,612 @@
+//! An implementation of [`Simulator`] that stores the board as chunks of bits.
+//! Each cell takes up a single bit, so densely packed patterns use far less memory than storing the position of each
+//! alive cell.
+
+use std::collections::{HashMap, HashSet};
+
+use gol_lib::{Area, Automaton, BoardDisplay, Cell, GlobalPosition, SharedDisplay, Simulator};
+
+/// Logged when the display is recovered after the ui panicked while holding it.
+const DISPLAY_POISONED: &str =
+    "The ui panicked while updating the display; recovering the display.";
+
+/// The default maximum amount of cells the display may contain.
+/// At one byte per cell, this limits the display to 16MiB.
+pub const DEFAULT_MAX_DISPLAY_CELLS: usize = 1 << 24;
+
+/// The width & height of each chunk, in cells.
+const CHUNK_SIZE: i32 = 64;
+
+/// A square of cells, with each row stored as the bits of a [`u64`].
+/// The least significant bit of each row is the left most cell.
+type Chunk = [u64; CHUNK_SIZE as usize];
+
+/// The position of a chunk, measured in chunks.
+type ChunkPosition = (i32, i32);
+
+/// Represents a board that the cells inhabit.
+///
+/// Only the eight cells surrounding a cell are counted as its neighbours.
+#[derive(Clone)]
+pub struct Board {
+    /// The chunks containing alive cells. Chunks are removed once every cell within them is dead.
+    chunks: HashMap<ChunkPosition, Box<Chunk>>,
+    generation: u64,
+
+    display: SharedDisplay,
+    display_size_buf: Area,
+    /// The maximum amount of alive cells, or [`None`] for no limit.
+    cell_limit: Option<usize>,
+    /// The maximum amount of cells the display may contain.
+    max_display_cells: usize,
+}
+
+impl Automaton for Board {
+    fn tick(&mut self) {
+        // Cells can only be born next to alive cells, so only the chunks next to a chunk with alive cells can change.
+        let candidates: HashSet<ChunkPosition> = self
+            .chunks
+            .keys()
+            .flat_map(|(x, y)| {
+                (-1..=1).flat_map(move |x_offset| {
+                    (-1..=1).map(move |y_offset| (x + x_offset, y + y_offset))
+                })
+            })
+            .collect();
+
+        self.chunks = candidates
+            .into_iter()
+            .filter_map(|position| {
+                let chunk = self.next_chunk(position);
+                chunk
+                    .iter()
+                    .any(|row| *row != 0)
+                    .then_some((position, chunk))
+            })
+            .collect();
+        self.generation += 1;
+    }
+
+    fn set(&mut self, position: GlobalPosition, cell: Cell) {
+        let (chunk_position, (x, y)) = split(position);
+        match cell {
+            Cell::Alive => {
+                self.chunks
+                    .entry(chunk_position)
+                    .or_insert_with(|| Box::new([0; CHUNK_SIZE as usize]))[y] |= 1 << x;
+            }
+            Cell::Dead => {
+                let Some(chunk) = self.chunks.get_mut(&chunk_position) else {
+                    return;
+                };
+
+                chunk[y] &= !(1 << x);
+                if chunk.iter().all(|row| *row == 0) {
+                    self.chunks.remove(&chunk_position);
+                }
+            }
+        }
+    }
+
+    fn get(&self, position: GlobalPosition) -> Cell {
+        let (chunk_position, (x, y)) = split(position);
+        self.chunks
+            .get(&chunk_position)
+            .is_some_and(|chunk| chunk[y] >> x & 1 == 1)
+            .into()
+    }
+
+    fn get_generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    fn reset(&mut self) {
+        self.chunks = HashMap::new();
+        self.generation = 0;
+    }
+
+    fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    fn population(&self) -> u64 {
+        self.chunks
+            .values()
+            .flat_map(|chunk| chunk.iter())
+            .map(|row| row.count_ones() as u64)
+            .sum()
+    }
+
+    fn alive_positions(&self) -> Vec<GlobalPosition> {
+        self.chunks
+            .iter()
+            .flat_map(|((chunk_x, chunk_y), chunk)| {
+                chunk.iter().enumerate().flat_map(move |(y, row)| {
+                    (0..CHUNK_SIZE)
+                        .filter(move |x| row >> x & 1 == 1)
+                        .map(move |x| {
+                            GlobalPosition::new(
+                                chunk_x * CHUNK_SIZE + x,
+                                chunk_y * CHUNK_SIZE + y as i32,
+                            )
+                        })
+                })
+            })
+            .collect()
+    }
+
+    fn get_board_area(&self) -> Area {
+        // (min x, min y, max x, max y)
+        let bounds = self
+            .chunks
+            .iter()
+            .flat_map(|((chunk_x, chunk_y), chunk)| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| **row != 0)
+                    .map(move |(y, row)| {
+                        let origin_x = chunk_x * CHUNK_SIZE;
+                        let y = chunk_y * CHUNK_SIZE + y as i32;
+                        (
+                            origin_x + row.trailing_zeros() as i32,
+                            y,
+                            origin_x + (CHUNK_SIZE - 1) - row.leading_zeros() as i32,
+                            y,
+                        )
+                    })
+            })
+            .reduce(|(min_x, min_y, max_x, max_y), (x_start, y, x_end, _)| {
+                (
+                    min_x.min(x_start),
+                    min_y.min(y),
+                    max_x.max(x_end),
+                    max_y.max(y),
+                )
+            });
+
+        match bounds {
+            Some((min_x, min_y, max_x, max_y)) => Area::new((min_x, min_y), (max_x, max_y)),
+            None => Area::default(),
+        }
+    }
+}
+
+impl Simulator for Board {
+    fn update_display(&mut self) {
+        // Attempts to acquire the lock on the display.
+        // If a lock could not be acquired the method returns early.
+        use std::sync::TryLockError;
+        let mut display = match self.display.try_lock() {
+            Ok(display) => display,
+            Err(TryLockError::WouldBlock) => {
+                return;
+            }
+            // The ui panicking while holding the display does not affect the board, so the simulation continues.
+            Err(TryLockError::Poisoned(poisoned)) => {
+                log::warn!("{}", DISPLAY_POISONED);
+                let display = poisoned.into_inner();
+                self.display.clear_poison();
+                display
+            }
+        };
+
+        // If the ui has not taken the display return early.
+        if display.is_some() {
+            return;
+        }
+
+        let mut board_build = Vec::new();
+
+        // A display area too large to allocate is displayed without any cells, which the ui shows as dead cells.
+        if self.display_size_buf.cell_count() <= self.max_display_cells as u64 {
+            let from = self.display_size_buf.get_min();
+            let to = self.display_size_buf.get_max();
+            board_build.reserve(self.display_size_buf.x_difference() as usize + 1);
+            // The display includes the maximum row & column of the area, the same as iterating over it.
+            for x in from.get_x()..=to.get_x() {
+                let column: Box<[Cell]> = (from.get_y()..=to.get_y())
+                    .map(|y| self.get(GlobalPosition::new(x, y)))
+                    .collect();
+                board_build.push(column);
+            }
+        }
+
+        // Updates the board to display.
+        *display = Some(BoardDisplay::new(
+            self.generation,
+            self.display_size_buf,
+            board_build,
+        ));
+    }
+
+    fn new(display: SharedDisplay) -> Self {
+        Self {
+            chunks: HashMap::new(),
+            generation: 0,
+            display,
+            display_size_buf: Default::default(),
+            cell_limit: None,
+            max_display_cells: DEFAULT_MAX_DISPLAY_CELLS,
+        }
+    }
+
+    fn set_display_area(&mut self, new_area: Area) {
+        self.display_size_buf = new_area;
+    }
+
+    fn set_cell_limit(&mut self, limit: Option<usize>) {
+        self.cell_limit = limit;
+    }
+
+    fn cell_limit_exceeded(&self) -> Option<u64> {
+        let limit = self.cell_limit?;
+        let population = self.population();
+        (population > limit as u64).then_some(population)
+    }
+
+    fn max_display_cells(&self) -> Option<usize> {
+        Some(self.max_display_cells)
+    }
+}
+
+impl Board {
+    /// Calculates the next generation of the chunk at the given position.
+    fn next_chunk(&self, position: ChunkPosition) -> Box<Chunk> {
+        let mut next = Box::new([0; CHUNK_SIZE as usize]);
+
+        let mut above = self.window(position, -1);
+        let mut current = self.window(position, 0);
+        for (y, row) in next.iter_mut().enumerate() {
+            let below = self.window(position, y as i32 + 1);
+            *row = next_row(above, current, below);
+            above = current;
+            current = below;
+        }
+
+        next
+    }
+
+    /// Gets the row at the given y within the chunk, along with the cells either side of the row.
+    ///
+    /// The cell to the left of the row is bit 0, followed by the cells of the row, then the cell to the right of the
+    /// row is bit 65. A y outside of the chunk reads from the chunk above or below.
+    fn window(&self, (chunk_x, chunk_y): ChunkPosition, y: i32) -> u128 {
+        let chunk_y = chunk_y + y.div_euclid(CHUNK_SIZE);
+        let y = y.rem_euclid(CHUNK_SIZE) as usize;
+        let row = |chunk_x| {
+            self.chunks
+                .get(&(chunk_x, chunk_y))
+                .map_or(0, |chunk| chunk[y])
+        };
+
+        (row(chunk_x - 1) >> (CHUNK_SIZE - 1)) as u128
+            | (row(chunk_x) as u128) << 1
+            | ((row(chunk_x + 1) & 1) as u128) << (CHUNK_SIZE + 1)
+    }
+
+    /// Sets the maximum amount of cells the display may contain. Display areas with more cells are displayed without
+    /// any cells, to avoid allocating the display.
+    ///
+    /// Defaults to [`DEFAULT_MAX_DISPLAY_CELLS`].
+    pub fn set_max_display_cells(&mut self, max_display_cells: usize) {
+        self.max_display_cells = max_display_cells;
+    }
+
+    /// The approximate amount of heap memory used to store the board, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        // Each entry of the map has a control byte, along with the key & the pointer to the chunk.
+        let entry = size_of::<ChunkPosition>() + size_of::<Box<Chunk>>() + 1;
+        self.chunks.capacity() * entry + self.chunks.len() * size_of::<Chunk>()
+    }
+}
+
+/// Splits a position into the position of the chunk containing it, & the position within that chunk.
+fn split(position: GlobalPosition) -> (ChunkPosition, (usize, usize)) {
+    let (x, y) = (position.get_x(), position.get_y());
+    (
+        (x.div_euclid(CHUNK_SIZE), y.div_euclid(CHUNK_SIZE)),
+        (
+            x.rem_euclid(CHUNK_SIZE) as usize,
+            y.rem_euclid(CHUNK_SIZE) as usize,
+        ),
+    )
+}
+
+/// Calculates the next generation of a row, given the windows of the rows above, of, & below it.
+/// See [`Board::window`] for the layout of the windows.
+fn next_row(above: u128, current: u128, below: u128) -> u64 {
+    // The neighbours of the cell at bit x of the row are at bits x, x + 1, & x + 2 of the windows, except for the cell
+    // itself.
+    let neighbours = [
+        above,
+        above >> 1,
+        above >> 2,
+        current,
+        current >> 2,
+        below,
+        below >> 1,
+        below >> 2,
+    ];
+
+    // The amount of alive neighbours of every cell in the row is counted at once, with each bit of the count being
+    // stored in a separate integer. The exact count above three is not needed, so is only recorded as being above three.
+    let (mut ones, mut twos, mut above_three) = (0u64, 0u64, 0u64);
+    for neighbour in neighbours {
+        let neighbour = neighbour as u64;
+        let carry = ones & neighbour;
+        ones ^= neighbour;
+        above_three |= twos & carry;
+        twos ^= carry;
+    }
+
+    let alive = (current >> 1) as u64;
+    // Cells with three neighbours are alive, as are alive cells with two neighbours.
+    !above_three & twos & (ones | alive)
+}
+
+#[cfg(test)]
+mod tests {
+    use gol_lib::{
+        persistence::{presets, rle::parse_rle},
+        LoadError, LoadStatus, Rule,
+    };
+
+    use super::*;
+
+    /// Gets the positions of every alive cell on the board, ordered by position.
+    fn alive(board: &Board) -> Vec<GlobalPosition> {
+        let mut alive = board.alive_positions();
+        alive.sort_by_key(|position| (position.get_x(), position.get_y()));
+        alive
+    }
+
+    /// Asserts that both boards have the same alive cells.
+    fn assert_same(dense: &Board, simple: &gol_simple::Board, message: &str) {
+        assert_eq!(dense.population(), simple.population(), "{message}");
+        for position in alive(dense) {
+            assert_eq!(simple.get(position), Cell::Alive, "{position}, {message}");
+        }
+    }
+
+    /// Creates a board of each implementation with the same randomly generated cells.
+    fn random_boards(area: Area, seed: u64) -> (Board, gol_simple::Board) {
+        let mut dense = Board::new(Default::default());
+        let mut simple = gol_simple::Board::new(Default::default());
+        dense.randomize(area, 0.4, seed);
+        simple.randomize(area, 0.4, seed);
+        (dense, simple)
+    }
+
+    #[test]
+    fn dead_by_default() {
+        let board = Board::new(Default::default());
+        assert_eq!(board.get((0, 0).into()), Cell::Dead);
+        assert_eq!(board.population(), 0);
+    }
+
+    #[test]
+    /// Cells can be set & unset on either side of chunk boundaries.
+    fn set_across_chunks() {
+        let mut board = Board::new(Default::default());
+        let positions = [
+            (0, 0),
+            (-1, -1),
+            (63, 63),
+            (64, 64),
+            (-65, 10),
+            (i32::MIN, i32::MAX),
+        ];
+        for position in positions {
+            board.set(position.into(), Cell::Alive);
+        }
+
+        for position in positions {
+            assert_eq!(board.get(position.into()), Cell::Alive, "{position:?}");
+        }
+        assert_eq!(board.get((1, 0).into()), Cell::Dead);
+        assert_eq!(board.population(), positions.len() as u64);
+
+        for position in positions {
+            board.set(position.into(), Cell::Dead);
+        }
+        assert_eq!(board.population(), 0);
+        // Chunks without any alive cells are discarded.
+        assert!(board.chunks.is_empty());
+    }
+
+    #[test]
+    /// The area covers exactly the alive cells, including across chunks.
+    fn get_board_area() {
+        let mut board = Board::new(Default::default());
+        assert_eq!(board.get_board_area(), Area::default());
+
+        board.set((-70, 5).into(), Cell::Alive);
+        board.set((3, -2).into(), Cell::Alive);
+        board.set((130, 64).into(), Cell::Alive);
+        assert_eq!(board.get_board_area(), Area::new((-70, -2), (130, 64)));
+    }
+
+    #[test]
+    /// Every generation matches the simple implementation, for cells straddling the chunk boundaries.
+    fn matches_simple() {
+        for seed in 0..2 {
+            let (mut dense, mut simple) = random_boards(Area::new((-40, -40), (40, 40)), seed);
+
+            for generation in 1..=30 {
+                dense.tick();
+                simple.tick();
+                assert_same(
+                    &dense,
+                    &simple,
+                    &format!("Seed {seed}, generation {generation}"),
+                );
+            }
+            assert_eq!(dense.get_generation(), simple.get_generation());
+        }
+    }
+
+    #[test]
+    /// Mirroring & rotating matches the simple implementation.
+    fn transforms_match_simple() {
+        let (mut dense, mut simple) = random_boards(Area::new((-40, -3), (25, 40)), 0);
+
+        for axis in gol_lib::Axis::ALL {
+            dense.mirror(axis);
+            simple.mirror(axis);
+            assert_same(&dense, &simple, &format!("{axis:?}"));
+        }
+        for quarter_turns in 1..=3 {
+            dense.rotate(quarter_turns).unwrap();
+            simple.rotate(quarter_turns).unwrap();
+            assert_same(&dense, &simple, &format!("{quarter_turns} quarter turns"));
+        }
+    }
+
+    #[test]
+    /// Cells far apart are mirrored & rotated without reading the empty space between them.
+    fn transforms_sparse() {
+        let mut board = Board::new(Default::default());
+        let far = [(-1_000_000_000, 0), (1_000_000_000, 1_000_000_000)].map(GlobalPosition::from);
+        for position in far {
+            board.set(position, Cell::Alive);
+        }
+
+        board.mirror(gol_lib::Axis::Vertical);
+        let mirrored =
+            [(-1_000_000_000, 1_000_000_000), (1_000_000_000, 0)].map(GlobalPosition::from);
+        assert_eq!(alive(&board), mirrored);
+
+        // Turning two opposite corners by half a turn swaps them.
+        board.rotate(2).unwrap();
+        assert_eq!(alive(&board), mirrored);
+    }
+
+    #[test]
+    /// A glider keeps its shape as it travels through many chunks.
+    fn glider_across_chunks() {
+        let mut board = Board::new(Default::default());
+        let glider = parse_rle(presets::GLIDER).unwrap();
+        assert_eq!(
+            board.load_blueprint((-100, -100).into(), glider),
+            LoadStatus::Success
+        );
+        let start = alive(&board);
+
+        // A glider moves one cell diagonally every four generations.
+        board.tick_n(4 * 200);
+        let moved: Vec<GlobalPosition> = start
+            .iter()
+            .map(|position| *position + (200, 200))
+            .collect();
+        assert_eq!(alive(&board), moved);
+    }
+
+    #[test]
+    /// Saves made by each implementation are the same, & can be loaded into the other.
+    fn save_load_simple() {
+        let (dense, mut simple) = random_boards(Area::new((-30, -30), (100, 20)), 7);
+        let save = dense.save_board();
+        assert_same(&dense, &simple, "Randomized");
+
+        simple.clear();
+        assert_eq!(simple.load_board(save), LoadStatus::Success);
+        assert_same(&dense, &simple, "Loaded into simple");
+
+        let mut loaded = Board::new(Default::default());
+        assert_eq!(loaded.load_board(simple.save_board()), LoadStatus::Success);
+        assert_same(&loaded, &simple, "Loaded into dense");
+    }
+
+    #[test]
+    /// Boards with rules other than Conway's game of life are rejected, as they can't be simulated.
+    fn load_unsupported_rule() {
+        let mut simple = gol_simple::Board::new(Default::default());
+        simple.set_rule(Rule::HIGHLIFE);
+        simple.set((0, 0).into(), Cell::Alive);
+
+        let mut dense = Board::new(Default::default());
+        dense.set((5, 5).into(), Cell::Alive);
+        assert_eq!(
+            dense.load_board(simple.save_board()),
+            LoadStatus::Fail {
+                reason: LoadError::UnsupportedRule {
+                    rule: Rule::HIGHLIFE
+                }
+            }
+        );
+        assert!(dense.get((5, 5).into()).is_alive());
+    }
+
+    #[test]
+    fn reset() {
+        let (mut board, _) = random_boards(Area::new((0, 0), (10, 10)), 1);
+        board.tick();
+        board.reset();
+        assert_eq!(board.population(), 0);
+        assert_eq!(board.get_generation(), 0);
+    }
+
+    #[test]
+    fn cell_limit() {
+        let (mut board, _) = random_boards(Area::new((0, 0), (10, 10)), 1);
+        let population = board.population();
+        assert_eq!(board.cell_limit_exceeded(), None);
+
+        board.set_cell_limit(Some(population as usize));
+        assert_eq!(board.cell_limit_exceeded(), None);
+        board.set_cell_limit(Some(population as usize - 1));
+        assert_eq!(board.cell_limit_exceeded(), Some(population));
+    }
+
+    #[test]
+    /// The display contains every cell within the display area, including the maximum row & column.
+    fn update_display() {
+        let display = SharedDisplay::default();
+        let mut board = Board::new(display.clone());
+        let area = Area::new((-2, -2), (2, 2));
+        board.set_display_area(area);
+        board.set((-1, -1).into(), Cell::Alive);
+        board.set((2, 2).into(), Cell::Alive);
+        board.set((3, 3).into(), Cell::Alive);
+
+        board.update_display();
+        let board_display = display.lock().unwrap().take().expect("Display is updated");
+        assert_eq!(board_display.get_x().get(), 5);
+        assert_eq!(board_display.get_y().get(), 5);
+        assert_eq!(
+            (board_display.get_x().get() * board_display.get_y().get()) as u64,
+            area.cell_count()
+        );
+        assert_eq!(
+            board_display.alive_positions().collect::<Vec<_>>(),
+            [(-1, -1).into(), (2, 2).into()]
+        );
+    }
+
+    #[test]
+    /// A filled rectangle uses less memory than the simple implementation, both before & after being ticked.
+    fn filled_rectangle_memory() {
+        let area = Area::new((0, 0), (255, 127));
+        let mut dense = Board::new(Default::default());
+        let mut simple = gol_simple::Board::new(Default::default());
+        dense.randomize(area, 1.0, 0);
+        simple.randomize(area, 1.0, 0);
+        assert!(dense.memory_usage() < simple.memory_usage());
+
+        dense.tick();
+        simple.tick();
+        assert_same(&dense, &simple, "Filled rectangle");
+        assert!(dense.memory_usage() < simple.memory_usage());
+    }
+}
+
+/// The tests every simulator must pass, run against this board.
+#[cfg(test)]
+mod suite {
+    gol_lib::simulator_test_suite!(crate::Board);
+}
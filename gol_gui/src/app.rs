@@ -1,19 +1,45 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
 use crate::{
-    file_management::{Load, Save},
+    autosave::{self, Autosave, SaveRequest},
+    edit::{
+        axis_label, symmetry_label, BlueprintRequest, Clipboard, EditState, Selection,
+        SelectionEdit, Stroke,
+    },
+    file_management::{LoadMenu, Save},
+    focus::{FocusAction, FocusPause},
+    history::History,
+    import::BlueprintImport,
     lang,
-    settings::Settings,
-    DEFAULT_SAVE_PATH,
+    pattern::PasteDialog,
+    recording::{CellColours, GifRecorder},
+    rewind::GenerationSlider,
+    settings::{CellSettings, Keybind, Settings, WorkspaceSettings},
+    speed::SpeedControl,
+    statistics::StatisticsRecorder,
+    tags::{complete_tag, split_tags, KnownTags},
+    toast::Toasts,
+    unsaved::UnsavedChanges,
+    AUTOSAVE_PATH,
 };
-use egui::{pos2, Color32, Id, Painter, Rect};
+use egui::{pos2, Color32, Id, Painter, Pos2, Rect, Vec2};
 use egui_keybind::Bind;
 use gol_lib::{
-    communication::{SimulatorPacket, UiPacket},
-    persistence::{self, SaveBuilder},
-    Area, BoardDisplay, Cell, GlobalPosition, SharedDisplay, SimulatorReceiver, UiSender,
+    communication::{SimulatorPacket, StopReason, UiPacket},
+    persistence::{
+        self,
+        board_save::BoardSaveError,
+        pattern::Pattern,
+        preview::{BlueprintPreview, SavePreview},
+        BlueprintBuilder, SaveBuilder, SimulationSave,
+    },
+    Area, Axis, BoardDisplay, Cell, GlobalPosition, LoadStatus, SharedDisplay, SimulatorReceiver,
+    Symmetry, UiSender,
 };
 use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    path::{Path, PathBuf},
     sync::mpsc::TryRecvError,
     time::{Duration, Instant},
 };
@@ -22,25 +48,38 @@ use std::{
 const BOARD_ID: &str = "board";
 /// The egui id for the top panel.
 const TOP_PANEL: &str = "Top_Panel";
+/// The egui id for the tooltip showing the cell under the cursor.
+const CELL_TOOLTIP: &str = "Cell_Tooltip";
+/// The egui id for the modal confirming the board should be cleared.
+const CLEAR_MODAL: &str = "Clear_Modal";
+/// The egui id for the modal confirming the application should close with unsaved changes.
+const CLOSE_MODAL: &str = "Close_Modal";
+/// The egui id for the modal offering to restore an autosave.
+const RESTORE_MODAL: &str = "Restore_Modal";
 /// The egui id for the settings panel.
 pub(crate) const SETTINGS_PANEL: &str = "Settings_Panel";
 /// The egui id for the debug window.
 #[cfg(debug_assertions)]
 const DEBUG_WINDOW: &str = "Debug_Window";
+/// How long to wait for the final state of the board when closing, before giving up on autosaving it.
+const FINAL_STATE_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// The struct that contains the data for the gui of my app.
-pub struct MyApp<'a> {
-    label: &'a str,
-
+pub struct MyApp {
     /// Whether the debug window is open or not.
     #[cfg(debug_assertions)]
     debug_menu_open: bool,
     /// Time since last frame.
     #[cfg(debug_assertions)]
     last_frame_time: Duration,
+    /// The last target & achieved ticks per second reported by the simulator.
+    #[cfg(debug_assertions)]
+    tick_rate: Option<(Option<u32>, f32)>,
 
     /// Stores relevant information for unrecoverable errors.
     error_occurred: Option<ErrorData>,
+    /// Short lived messages for the user.
+    toasts: Toasts,
 
     /// The updated display produced by the simulator.
     display_update: SharedDisplay,
@@ -52,6 +91,69 @@ pub struct MyApp<'a> {
     x_offset: f32,
     /// The y offset from the board being displayed.
     y_offset: f32,
+    /// The generation entered into the "Run until" input.
+    run_until: u64,
+    /// The amount of generations entered into the "Step" input.
+    step_count: u64,
+    /// The speed the simulation is set to run at.
+    speed: SpeedControl,
+    /// The current & target generation of a run started with [`UiPacket::StartUntil`], if one is in progress.
+    progress: Option<(u64, u64)>,
+    /// The chance of each cell being alive when randomising.
+    random_density: f32,
+    /// The symmetry of randomised cells.
+    symmetry: Symmetry,
+    /// The size of the area the board was drawn in on the last frame.
+    board_size: Vec2,
+    /// The name entered for a new bookmark.
+    bookmark_name: String,
+    /// Whether the user is being asked to confirm clearing the board.
+    confirm_clear: bool,
+    /// Whether the board has been edited since it was last saved.
+    unsaved: UnsavedChanges,
+    /// Whether the user is being asked to confirm closing with unsaved changes.
+    confirm_close: bool,
+    /// Whether the user has chosen to close the application, discarding any unsaved changes.
+    close_confirmed: bool,
+    /// Whether the window title currently shows that there are unsaved changes.
+    title_dirty: bool,
+    /// Regularly saves the board into the autosave slots.
+    autosave: Autosave,
+    /// The board saves requested from the simulator that it has not yet replied to, oldest first.
+    save_requests: VecDeque<SaveRequest>,
+    /// The autosave offered to be restored on startup, until the user accepts or dismisses it.
+    restore_autosave: Option<PathBuf>,
+    /// Whether the simulation has been started, & not since stopped.
+    running: bool,
+    /// Whether the simulation was stopped due to the window losing focus.
+    focus_pause: FocusPause,
+    /// Whether the simulator has been asked to include the age of each cell in the display.
+    display_ages: bool,
+    /// Whether the simulator has been asked to include the cells born & died in the display.
+    display_changes: bool,
+    /// The amount of previous generations the simulator has been asked to keep.
+    generation_history: usize,
+    /// Rewinds the board to the generations kept by the simulator.
+    rewind: GenerationSlider,
+    /// The position entered into the "Go to" input.
+    goto_position: (i32, i32),
+
+    /// How the user is interacting with the board.
+    edit_state: EditState,
+    /// The area of the board selected in [`EditState::Select`].
+    selection: Selection,
+    /// Holds copied areas of the board.
+    clipboard: Clipboard,
+    /// The name given to blueprints saved from the selection.
+    blueprint_name: String,
+    /// The comma separated tags entered for saving the selection as a blueprint.
+    blueprint_tags: String,
+    /// The tags of the existing blueprints, suggested when entering tags.
+    known_tags: KnownTags,
+    /// The brush stroke being drawn in [`EditState::Draw`].
+    stroke: Option<Stroke>,
+    /// The edits made by the user, which can be undone.
+    history: History,
 
     /// A channel to send data to the simulator.
     ui_sender: UiSender,
@@ -60,14 +162,24 @@ pub struct MyApp<'a> {
 
     /// The menu & options for saving files.
     save: Save,
-    /// The menu & options for loading files.
-    load: Load,
+    /// The menu for loading board saves.
+    load_board: LoadMenu<SavePreview>,
+    /// The menu for loading blueprints.
+    load_blueprint: LoadMenu<BlueprintPreview>,
+    /// The dialog for pasting the text of a pattern.
+    paste_pattern: PasteDialog,
+    /// Imports a directory of patterns as blueprints.
+    import: BlueprintImport,
+    /// Records the displayed board into a GIF.
+    recorder: GifRecorder,
+    /// Records the population of each generation into a CSV file.
+    statistics: StatisticsRecorder,
 
     /// The persistent settings.
     settings: Settings,
 }
 
-impl MyApp<'static> {
+impl MyApp {
     pub fn new(
         creation_context: &eframe::CreationContext<'_>,
         display: SharedDisplay,
@@ -75,22 +187,60 @@ impl MyApp<'static> {
         simulator_receiver: SimulatorReceiver,
     ) -> Self {
         let mut my_app = MyApp {
-            label: "Hello world",
             display_update: display,
             display_cache: Default::default(),
             ui_sender,
             simulator_receiver,
             error_occurred: None,
+            toasts: Toasts::default(),
             #[cfg(debug_assertions)]
             debug_menu_open: true,
             x_offset: 0.0,
             y_offset: 0.0,
+            run_until: 0,
+            step_count: 1,
+            speed: SpeedControl::default(),
+            progress: None,
+            random_density: 0.5,
+            symmetry: Symmetry::default(),
+            board_size: Vec2::ZERO,
+            goto_position: (0, 0),
+            bookmark_name: String::new(),
+            confirm_clear: false,
+            unsaved: UnsavedChanges::default(),
+            confirm_close: false,
+            close_confirmed: false,
+            title_dirty: false,
+            autosave: Autosave::default(),
+            save_requests: VecDeque::new(),
+            restore_autosave: None,
+            running: false,
+            focus_pause: FocusPause::default(),
+            display_ages: false,
+            display_changes: false,
+            generation_history: 0,
+            rewind: GenerationSlider::default(),
+            edit_state: EditState::default(),
+            selection: Selection::default(),
+            clipboard: Clipboard::default(),
+            blueprint_name: String::new(),
+            blueprint_tags: String::new(),
+            known_tags: KnownTags::default(),
+            stroke: None,
+            history: History::default(),
             display_area: Area::new((-10, -10), (10, 10)),
             #[cfg(debug_assertions)]
             last_frame_time: Duration::new(0, 0),
+            #[cfg(debug_assertions)]
+            tick_rate: None,
             settings: Settings::default(),
             save: Save::default(),
-            load: Default::default(),
+            load_board: LoadMenu::new(lang::LOAD_BOARD_WINDOW),
+            load_blueprint: LoadMenu::new(lang::LOAD_BLUEPRINT_WINDOW),
+            paste_pattern: PasteDialog::default(),
+            import: BlueprintImport::default(),
+            recorder: GifRecorder::default(),
+            statistics: StatisticsRecorder::default(),
         };
 
         // Load stored configurations
@@ -100,27 +250,27 @@ impl MyApp<'static> {
             };
         }
 
-        my_app
-            .ui_sender
-            .send(UiPacket::Set {
-                position: (0, 0).into(),
-                cell_state: Cell::Alive,
-            })
-            .unwrap();
+        // Offer to restore the board from when the application was last open.
+        let file = &my_app.settings.file;
+        if file.autosave_interval != 0 {
+            my_app.restore_autosave = autosave::latest(&AUTOSAVE_PATH, file.autosave_slots);
+        }
 
-        my_app
-            .ui_sender
-            .send(UiPacket::Set {
-                position: (0, 1).into(),
-                cell_state: Cell::Alive,
-            })
-            .unwrap();
+        // Restore the workspace from when the application was last closed.
+        let workspace = &my_app.settings.workspace;
+        my_app.edit_state = workspace.edit_state;
+        if let Some(view_centre) = workspace.view_centre {
+            my_app.display_area = centre_area_on(my_app.display_area, view_centre);
+        }
 
         my_app
             .ui_sender
-            .send(UiPacket::Set {
-                position: (0, 2).into(),
-                cell_state: Cell::Alive,
+            .send(UiPacket::SetMany {
+                cells: Box::new([
+                    ((0, 0).into(), Cell::Alive),
+                    ((0, 1).into(), Cell::Alive),
+                    ((0, 2).into(), Cell::Alive),
+                ]),
             })
             .unwrap();
 
@@ -169,7 +319,7 @@ impl MyApp<'static> {
                 ui.heading("Internal Values");
                 ui.label(format!(
                     "Error Occurred: {}\n\
-                        Display Area: {:?}\n\
+                        Display Area: {}\n\
                         X Offset: {}\n\
                         Y Offset: {}\n\
                         Cell Alive Colour: {:#?}\n\
@@ -186,6 +336,11 @@ impl MyApp<'static> {
                     self.settings.cell.dead_colour,
                     self.settings.cell.size
                 ));
+                let (actions, bytes) = self.history.usage();
+                ui.label(format!(
+                    "History: {actions} actions, {:.2} MiB",
+                    bytes as f64 / (1024.0 * 1024.0)
+                ));
                 ui.label(format!(
                     "Cursor Position: {}",
                     match ctx.pointer_latest_pos() {
@@ -200,22 +355,478 @@ impl MyApp<'static> {
                     let fps = 1.0 / secs_f64;
                     ui.label(fps.to_string());
                 }
+
+                ui.separator();
+                ui.label(match self.tick_rate {
+                    Some((target, achieved)) => format!(
+                        "Ticks per second: {achieved:.1} / {}",
+                        match target {
+                            Some(target) => target.to_string(),
+                            None => "Uncapped".to_owned(),
+                        }
+                    ),
+                    None => "Ticks per second: Not running".to_owned(),
+                });
+            });
+    }
+
+    /// Draws the cached display board within the given rect.
+    fn draw_board(&mut self, ctx: &egui::Context, board_rect: Rect) {
+        // Creates the painter for the board display.
+        let layer_painter = Painter::new(
+            ctx.clone(), // ctx is cloned in egui implementations.
+            egui::LayerId::new(egui::Order::Background, BOARD_ID.into()),
+            board_rect,
+        );
+
+        // Number of cell in x axis
+        let x_cells = (board_rect.right() / self.settings.cell.size).ceil() as i32;
+        // Number of cells in y axis
+        let y_cells = (board_rect.bottom() / self.settings.cell.size).floor() as i32;
+
+        // Modify displayed area to follow cells displayed, which includes its maximum row & column.
+        self.display_area
+            .modify_x(x_cells - 1 - self.display_area.x_difference());
+        self.display_area
+            .modify_y(y_cells - 1 - self.display_area.y_difference());
+
+        layer_painter.extend(board_shapes(
+            &self.display_cache,
+            &self.settings.cell,
+            x_cells,
+            y_cells,
+        ));
+        layer_painter.extend(grid_shapes(
+            &self.settings.cell,
+            self.display_area.get_min(),
+            x_cells,
+            y_cells,
+        ));
+        self.draw_origin(&layer_painter, x_cells, y_cells);
+        self.draw_selection(&layer_painter);
+        self.draw_paste_preview(ctx, &layer_painter, board_rect);
+        if self.settings.display.generation_overlay {
+            self.draw_overlay(ctx, &layer_painter, board_rect);
+        }
+    }
+
+    /// Draws the alive cells of the blueprint being pasted translucently over the board, with its top left at the
+    /// cursor.
+    fn draw_paste_preview(&self, ctx: &egui::Context, painter: &Painter, board_rect: Rect) {
+        let Some(pointer) = ctx
+            .pointer_hover_pos()
+            .filter(|pointer| board_rect.contains(*pointer))
+        else {
+            return;
+        };
+
+        let origin = self.display_area.get_min();
+        let size = self.settings.cell.size;
+        let colour = self
+            .settings
+            .cell
+            .alive_colour
+            .gamma_multiply(PASTE_PREVIEW_OPACITY);
+        for position in self.clipboard.preview(self.board_position(pointer)) {
+            let min = cell_screen_position(origin, size, position);
+            painter.rect_filled(
+                Rect::from_min_size(min, Vec2::splat(size)),
+                egui::Rounding::ZERO,
+                colour,
+            );
+        }
+    }
+
+    /// Draws the generation of the displayed board in the top left corner of the board.
+    fn draw_overlay(&self, ctx: &egui::Context, painter: &Painter, board_rect: Rect) {
+        let visuals = &ctx.style().visuals;
+        let galley = painter.layout_no_wrap(
+            overlay_text(&self.display_cache),
+            egui::FontId::monospace(14.0),
+            visuals.text_color(),
+        );
+
+        // The text is given a background so it can be read over alive cells.
+        let position = board_rect.left_top() + Vec2::splat(OVERLAY_MARGIN);
+        let background = Rect::from_min_size(position, galley.size()).expand(OVERLAY_MARGIN / 2.0);
+        painter.rect_filled(background, egui::Rounding::same(2.0), visuals.window_fill);
+        painter.galley(position, galley, visuals.text_color());
+    }
+
+    /// Draws the origin marker & axis lines, if enabled, over the given amount of displayed cells.
+    fn draw_origin(&self, painter: &Painter, x_cells: i32, y_cells: i32) {
+        let cell = &self.settings.cell;
+        let origin = cell_screen_position(
+            self.display_area.get_min(),
+            cell.size,
+            GlobalPosition::new(0, 0),
+        );
+        let stroke = egui::Stroke::new(2.0, cell.origin_colour);
+
+        // Lines are drawn through the centre of the cells on each axis. Off screen lines are clipped by the painter.
+        if cell.axis_lines {
+            let centre = origin + Vec2::splat(cell.size / 2.0);
+            painter.vline(centre.x, 0.0..=y_cells as f32 * cell.size, stroke);
+            painter.hline(0.0..=x_cells as f32 * cell.size, centre.y, stroke);
+        }
+
+        if cell.origin_marker {
+            let rect = Rect::from_min_size(origin, Vec2::splat(cell.size));
+            painter.rect_stroke(rect, egui::Rounding::ZERO, stroke);
+        }
+    }
+
+    /// Draws an outline around the selected area of the board.
+    fn draw_selection(&self, painter: &Painter) {
+        let Some(selection) = self.selection.area() else {
+            return;
+        };
+
+        let origin = self.display_area.get_min();
+        let size = self.settings.cell.size;
+        let min = cell_screen_position(origin, size, selection.get_min());
+        // The selection includes the max position, so the outline extends to the far edge of that cell.
+        let max = cell_screen_position(origin, size, selection.get_max()) + Vec2::splat(size);
+        let rect = Rect::from_two_pos(min, max);
+
+        painter.rect_stroke(
+            rect,
+            egui::Rounding::ZERO,
+            egui::Stroke::new(2.0, Color32::LIGHT_BLUE),
+        );
+
+        // The size is shown below the selection, with a background so it can be read over alive cells.
+        let visuals = &painter.ctx().style().visuals;
+        let galley = painter.layout_no_wrap(
+            selection_text(selection, &self.display_cache),
+            egui::FontId::monospace(12.0),
+            visuals.text_color(),
+        );
+        let position = rect.left_bottom() + Vec2::splat(OVERLAY_MARGIN);
+        let background = Rect::from_min_size(position, galley.size()).expand(OVERLAY_MARGIN / 2.0);
+        painter.rect_filled(background, egui::Rounding::same(2.0), visuals.window_fill);
+        painter.galley(position, galley, visuals.text_color());
+    }
+
+    /// Converts a position on the screen into the position of the cell on the board beneath it.
+    fn board_position(&self, screen_position: Pos2) -> GlobalPosition {
+        cell_under(
+            self.display_area.get_min(),
+            self.settings.cell.size,
+            screen_position,
+        )
+    }
+
+    /// Asks the user to confirm clearing the board, if they have requested to.
+    ///
+    /// Clearing is not recorded in the history, as cells outside of the displayed area are not known to the ui.
+    fn clear_modal(&mut self, ctx: &egui::Context, to_send: &mut Vec<UiPacket>) {
+        if !self.confirm_clear {
+            return;
+        }
+
+        let modal = egui::Modal::new(Id::new(CLEAR_MODAL)).show(ctx, |ui| {
+            ui.label(lang::CLEAR_BOARD_CONFIRM);
+            ui.horizontal(|ui| {
+                if ui.button(lang::CLEAR_BOARD).clicked() {
+                    to_send.push(UiPacket::Clear);
+                    // The simulator discards the generations kept of the cleared board.
+                    self.rewind.reset();
+                    self.confirm_clear = false;
+                }
+                if ui.button(lang::CANCEL).clicked() {
+                    self.confirm_clear = false;
+                }
+            });
+        });
+
+        if modal.should_close() {
+            self.confirm_clear = false;
+        }
+    }
+
+    /// Stops the application closing with unsaved changes, asking the user whether to save them first.
+    fn close_modal(&mut self, ctx: &egui::Context) {
+        let close_requested = ctx.input(|input| input.viewport().close_requested());
+        if close_requested && self.unsaved.is_dirty() && !self.close_confirmed {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.confirm_close = true;
+        }
+
+        if !self.confirm_close {
+            return;
+        }
+
+        let modal = egui::Modal::new(Id::new(CLOSE_MODAL)).show(ctx, |ui| {
+            ui.label(lang::UNSAVED_CHANGES);
+            ui.horizontal(|ui| {
+                if ui.button(lang::SAVE_CHANGES).clicked() {
+                    self.save.show = true;
+                    self.confirm_close = false;
+                }
+                if ui.button(lang::DISCARD_CHANGES).clicked() {
+                    self.close_confirmed = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+                if ui.button(lang::CANCEL).clicked() {
+                    self.confirm_close = false;
+                }
+            });
+        });
+
+        if modal.should_close() {
+            self.confirm_close = false;
+        }
+    }
+
+    /// Writes the board into the next autosave slot.
+    fn write_autosave(&self, simulation_save: SimulationSave) -> Result<Box<Path>, BoardSaveError> {
+        let path = autosave::next_slot_path(&AUTOSAVE_PATH, self.settings.file.autosave_slots);
+        SaveBuilder::new(simulation_save)
+            .name(lang::AUTOSAVE_NAME)
+            .view_position(area_centre(self.display_area))
+            .save_to_file(path)
+    }
+
+    /// Offers to load the most recent autosave, such as after the application closed unexpectedly.
+    fn restore_modal(&mut self, ctx: &egui::Context, to_send: &mut Vec<UiPacket>) {
+        let Some(path) = self.restore_autosave.clone() else {
+            return;
+        };
+
+        let modal = egui::Modal::new(Id::new(RESTORE_MODAL)).show(ctx, |ui| {
+            ui.label(lang::RESTORE_AUTOSAVE);
+            ui.horizontal(|ui| {
+                if ui.button(lang::RESTORE).clicked() {
+                    self.load_save(&path, to_send);
+                    self.restore_autosave = None;
+                    // The board is only offered to be restored once.
+                    remove_autosaves();
+                }
+                if ui.button(lang::CANCEL).clicked() {
+                    self.restore_autosave = None;
+                }
             });
+        });
+
+        if modal.should_close() {
+            self.restore_autosave = None;
+        }
+    }
+
+    /// Marks the window title with an asterisk whilst there are unsaved changes.
+    fn update_title(&mut self, ctx: &egui::Context) {
+        let dirty = self.unsaved.is_dirty();
+        if dirty == self.title_dirty {
+            return;
+        }
+
+        self.title_dirty = dirty;
+        let title = match dirty {
+            true => format!("{}*", lang::APP_NAME),
+            false => lang::APP_NAME.to_owned(),
+        };
+        ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+    }
+
+    /// Shows a tooltip at the pointer with the position & state of the cell beneath it.
+    fn cell_tooltip(&self, ctx: &egui::Context, layer: egui::LayerId, pointer: Pos2) {
+        let position = self.board_position(pointer);
+        let cell = displayed_cell(&self.display_cache, self.display_area.get_min(), position);
+
+        egui::show_tooltip_at_pointer(ctx, layer, Id::new(CELL_TOOLTIP), |ui| {
+            ui.label(format!("{position}\n{cell:?}"));
+        });
+    }
+
+    /// Paints with the brush centred on the given screen position, starting a new stroke if one is not in progress.
+    fn paint(&mut self, screen_position: Pos2, to_send: &mut Vec<UiPacket>) {
+        let centre = self.board_position(screen_position);
+        let display_min = self.display_area.get_min();
+        let display_cache = &self.display_cache;
+        let current =
+            |position: GlobalPosition| displayed_cell(display_cache, display_min, position);
+
+        // The stroke sets cells to the opposite of the state of the cell it started on.
+        let stroke = self
+            .stroke
+            .get_or_insert_with(|| Stroke::new(current(centre).invert()));
+
+        let brush = self
+            .settings
+            .cell
+            .brush_shape
+            .positions(centre, self.settings.cell.brush_size);
+        stroke.paint(brush, current, to_send);
+    }
+
+    /// Ends the current brush stroke, recording it in the history.
+    fn finish_stroke(&mut self) {
+        if let Some(stroke) = self.stroke.take() {
+            self.history.push(stroke.finish());
+        }
+    }
+
+    /// Changes the cell size in response to the user scrolling, keeping the cell under the pointer in place.
+    fn zoom(&mut self, pointer: Pos2, scroll: f32, to_send: &mut Vec<UiPacket>) {
+        let range = CellSettings::SIZE_RANGE;
+        let old_size = self.settings.cell.size;
+        let new_size = (old_size * (scroll * ZOOM_SPEED).exp()).clamp(*range.start(), *range.end());
+        if new_size == old_size {
+            return;
+        }
+
+        let display_min = zoom_anchor(self.display_area.get_min(), pointer, old_size, new_size);
+        let (dx, dy) = display_min - self.display_area.get_min();
+        self.display_area = self.display_area.translated(dx, dy);
+        self.settings.cell.size = new_size;
+        // The display is now aligned to the cells, so any partial scroll is discarded.
+        self.x_offset = 0.0;
+        self.y_offset = 0.0;
+
+        to_send.push(UiPacket::DisplayArea {
+            new_area: self.display_area,
+        });
+    }
+
+    /// Moves the displayed area so that the given position is at the centre of the board.
+    fn jump_to(&mut self, position: GlobalPosition, to_send: &mut Vec<UiPacket>) {
+        self.display_area = centre_area_on(self.display_area, position);
+        // The display is now aligned to the cells, so any partial scroll is discarded.
+        self.x_offset = 0.0;
+        self.y_offset = 0.0;
+
+        to_send.push(UiPacket::DisplayArea {
+            new_area: self.display_area,
+        });
+    }
+
+    /// Loads the save file at the given path into the simulation.
+    fn load_save(&mut self, path: &Path, to_send: &mut Vec<UiPacket>) {
+        match persistence::load_save(path) {
+            Ok(save) => {
+                let view_position = save.view_position();
+                to_send.push(UiPacket::LoadBoard {
+                    board: save.into_simulation_save(),
+                });
+
+                if let Some(view_position) = view_position {
+                    self.jump_to(view_position, to_send);
+                }
+            }
+            Err(err) => self.toasts.error(format!("{}{err}", lang::LOAD_FAIL)),
+        }
+    }
+
+    /// Loads the blueprint file at the given path into the simulation, with its top left at the centre of the display.
+    fn load_blueprint(&mut self, path: &Path, to_send: &mut Vec<UiPacket>) {
+        match persistence::load_blueprint(path) {
+            Ok(blueprint) => self.load_pattern(blueprint.into_pattern(), to_send),
+            Err(err) => self.toasts.error(format!("{}{err}", lang::LOAD_FAIL)),
+        }
+    }
+
+    /// Loads the pattern into the simulation, with its top left at the centre of the display.
+    ///
+    /// The board keeps its rule, so the user is told the rule of the pattern if it has one.
+    fn load_pattern(&mut self, pattern: Pattern, to_send: &mut Vec<UiPacket>) {
+        if let Some(rule) = pattern.get_rule() {
+            self.toasts.info(format!(
+                "{}{rule}{}",
+                lang::PATTERN_RULE,
+                lang::PATTERN_RULE_KEPT
+            ));
+        }
+
+        to_send.push(UiPacket::LoadBlueprint {
+            load_position: area_centre(self.display_area),
+            blueprint: pattern.into_blueprint(),
+        });
+    }
+
+    /// Stops the simulation while the window is unfocused, if enabled in the settings.
+    fn check_focus(&mut self, ctx: &egui::Context, to_send: &mut Vec<UiPacket>) {
+        // Disabling the setting while paused is treated as regaining focus, so the simulation is resumed.
+        let focused = !self.settings.pause_unfocused || ctx.input(|input| input.focused);
+        match self.focus_pause.update(focused, self.running) {
+            Some(FocusAction::Pause) => {
+                // The ui still considers the simulation to be running, as it will be resumed.
+                to_send.push(UiPacket::Stop);
+            }
+            // Stopping clears the target generation, so it is restored when resuming.
+            Some(FocusAction::Resume) => to_send.push(match self.progress {
+                Some((_, target)) => UiPacket::StartUntil { generation: target },
+                None => UiPacket::Start,
+            }),
+            None => {}
+        }
     }
 
     /// Checks if any keybinds have been pressed & executes the corresponding action.
-    fn check_keybinds(&mut self, ctx: &egui::Context) {
-        let keybind = &mut self.settings.keybind;
+    fn check_keybinds(&mut self, ctx: &egui::Context, to_send: &mut Vec<UiPacket>) {
+        // Keys typed into text fields are not keybinds.
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let keybinds = &self.settings.keybind;
+        let pressed: Vec<Keybind> = ctx.input_mut(|input| {
+            enum_iterator::all::<Keybind>()
+                .filter(|keybind| keybinds.get(*keybind).pressed(input))
+                .collect()
+        });
 
-        ctx.input_mut(|input| {
-            if keybind.settings_menu.pressed(input) {
-                self.settings.open = !self.settings.open;
+        for keybind in pressed {
+            match keybind {
+                Keybind::SettingsMenu => self.settings.open = !self.settings.open,
+                Keybind::ToggleSimulation => {
+                    if self.running {
+                        // Stopping clears the target generation, as the stop button does.
+                        self.progress = None;
+                    }
+                    to_send.push(toggle_simulation(&mut self.running));
+                }
+                Keybind::Step => to_send.push(UiPacket::Step { count: 1 }),
+                Keybind::CycleEditMode => self.edit_state = self.edit_state.next(),
+                Keybind::ClearSelection => {
+                    self.selection.clear();
+                    self.clipboard.cancel_paste();
+                }
+                Keybind::Undo => self.history.undo(to_send),
+                Keybind::Redo => self.history.redo(to_send),
+                Keybind::PasteBlueprint => paste_clipboard(&mut self.clipboard, &mut self.toasts),
             }
-        })
+        }
+
+        if self.edit_state == EditState::Select {
+            self.nudge_selection(ctx);
+        }
+    }
+
+    /// Moves the selection by one cell for each arrow key pressed, or resizes it whilst shift is held.
+    fn nudge_selection(&mut self, ctx: &egui::Context) {
+        let (offset, resize) = ctx.input(|input| {
+            let offset = [
+                (egui::Key::ArrowLeft, (-1, 0)),
+                (egui::Key::ArrowRight, (1, 0)),
+                (egui::Key::ArrowUp, (0, -1)),
+                (egui::Key::ArrowDown, (0, 1)),
+            ]
+            .into_iter()
+            .filter(|(key, _)| input.key_pressed(*key))
+            .fold((0, 0), |(x, y), (_, (dx, dy))| (x + dx, y + dy));
+            (offset, input.modifiers.shift)
+        });
+
+        match (offset, resize) {
+            ((0, 0), _) => {}
+            (offset, true) => self.selection.resize(offset),
+            (offset, false) => self.selection.nudge(offset),
+        }
     }
 }
 
-impl eframe::App for MyApp<'static> {
+impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         #[cfg(debug_assertions)]
         let start_time = Instant::now();
@@ -268,10 +879,70 @@ impl eframe::App for MyApp<'static> {
             return;
         }
 
-        self.check_keybinds(ctx);
+        self.history.set_limit(self.settings.history.limit());
+        self.check_keybinds(ctx, &mut to_send);
+        self.check_focus(ctx, &mut to_send);
+
+        // Ages are only needed by the simulator while cells are coloured by age.
+        if self.display_ages != self.settings.cell.age_colours {
+            self.display_ages = self.settings.cell.age_colours;
+            to_send.push(UiPacket::DisplayAges {
+                enabled: self.display_ages,
+            });
+        }
+        // Likewise, changes are only needed while they are coloured.
+        if self.display_changes != self.settings.cell.change_colours {
+            self.display_changes = self.settings.cell.change_colours;
+            to_send.push(UiPacket::DisplayChanges {
+                enabled: self.display_changes,
+            });
+        }
+        if self.generation_history != self.settings.history.generations {
+            self.generation_history = self.settings.history.generations;
+            to_send.push(UiPacket::GenerationHistory {
+                capacity: self.generation_history,
+            });
+        }
 
         self.save.draw(ctx, &mut to_send, &mut self.settings);
-        self.load.draw(ctx);
+        if let Some(path) =
+            self.load_board
+                .draw(ctx, &self.settings.file.save_location, &mut self.toasts)
+        {
+            self.load_save(&path, &mut to_send);
+        }
+        if let Some(path) = self.load_blueprint.draw(
+            ctx,
+            &self.settings.file.blueprint_location,
+            &mut self.toasts,
+        ) {
+            self.load_blueprint(&path, &mut to_send);
+        }
+        if let Some(pattern) = self.paste_pattern.draw(ctx) {
+            if let Some(name) = pattern.get_name() {
+                self.toasts.info(format!("{}{name}", lang::PASTED_PATTERN));
+            }
+            // Pasted the same as blueprint files.
+            self.load_pattern(pattern, &mut to_send);
+        }
+        self.import.draw(
+            ctx,
+            &self.settings.file.blueprint_location,
+            &mut self.toasts,
+        );
+        if self.import.poll(&mut self.toasts) {
+            // The imported blueprints are listed once the menu is reloaded.
+            self.load_blueprint
+                .reload(&self.settings.file.blueprint_location);
+        }
+        self.recorder.poll(&mut self.toasts);
+        self.statistics.poll(&mut self.toasts);
+        self.known_tags.poll();
+        self.toasts.draw(ctx);
+        self.clear_modal(ctx, &mut to_send);
+        self.close_modal(ctx);
+        self.restore_modal(ctx, &mut to_send);
+        self.update_title(ctx);
 
         // Stores the size the board will take up.
         let mut board_rect = Rect::from_min_max(
@@ -289,10 +960,55 @@ impl eframe::App for MyApp<'static> {
             ui.horizontal(|ui| {
                 if ui.button("Start").clicked() {
                     to_send.push(UiPacket::Start);
+                    self.running = true;
                 };
                 if ui.button("Stop").clicked() {
                     to_send.push(UiPacket::Stop);
+                    self.progress = None;
+                    self.running = false;
+                }
+
+                if ui.button(lang::STEP_BUTTON).clicked() {
+                    to_send.push(UiPacket::Step {
+                        count: self.step_count,
+                    });
+                }
+                ui.add(egui::DragValue::new(&mut self.step_count).range(1..=u64::MAX));
+
+                ui.label(lang::RUN_UNTIL);
+                ui.add(egui::DragValue::new(&mut self.run_until));
+                if ui.button(lang::RUN_UNTIL_BUTTON).clicked() {
+                    to_send.push(UiPacket::StartUntil {
+                        generation: self.run_until,
+                    });
+                    self.running = true;
+                }
+                if let Some((current, target)) = self.progress {
+                    ui.add(
+                        egui::ProgressBar::new(current as f32 / target as f32)
+                            .desired_width(150.0)
+                            .text(format!("{current} / {target}")),
+                    );
+                    if ui.button(lang::CLEAR_TARGET).clicked() {
+                        to_send.push(UiPacket::ClearTarget);
+                        self.progress = None;
+                    }
+                }
+                if self.generation_history > 0 {
+                    let generation = self.display_cache.get_generation();
+                    if let Some(generation) = self.rewind.draw(ui, generation) {
+                        // Rewinding stops the simulation, along with any run towards a target.
+                        to_send.push(UiPacket::GotoGeneration { generation });
+                        self.progress = None;
+                        self.running = false;
+                    }
+                }
+
+                ui.separator();
+                if let Some(speed) = self.speed.draw(ui) {
+                    to_send.push(UiPacket::SimulationSpeed { speed });
                 }
+                ui.separator();
 
                 if ui.button("Settings").clicked() {
                     self.settings.open = !self.settings.open;
@@ -300,159 +1016,382 @@ impl eframe::App for MyApp<'static> {
 
                 if ui.button("Save").clicked() {
                     self.save.show = !self.save.show;
+                    // Warn about an unusable save location before the user fills in the save.
+                    if self.save.show {
+                        if let Err(err) =
+                            persistence::check_writable(&self.settings.file.save_location)
+                        {
+                            self.toasts
+                                .warning(format!("{}{err}", lang::SAVE_LOCATION_UNWRITABLE));
+                        }
+                    }
                 }
 
                 if ui.button("Load").clicked() {
-                    self.load.show = !self.load.show
+                    self.load_board.show = !self.load_board.show
                 }
 
-                #[cfg(debug_assertions)]
+                if ui.button(lang::BLUEPRINTS).clicked() {
+                    self.load_blueprint.show = !self.load_blueprint.show
+                }
+                if ui.button(lang::PASTE_PATTERN).clicked() {
+                    self.paste_pattern.show = !self.paste_pattern.show
+                }
+                if ui
+                    .add_enabled(
+                        !self.import.is_importing(),
+                        egui::Button::new(lang::IMPORT_FOLDER),
+                    )
+                    .clicked()
                 {
-                    if ui.button("Debug Menu").clicked() {
-                        self.debug_menu_open = !self.debug_menu_open
-                    }
+                    self.import.open();
                 }
-            })
-        });
-
-        let top_size = show.response.rect.size();
-
-        // Account for top panel.
-        *board_rect.top_mut() += top_size.y;
-        *board_rect.bottom_mut() += top_size.y;
-
-        // board_rect must not change after this point
-        let board_rect = board_rect;
-
-        // Draws the central panel to provide the area for user interaction.
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let interact = ui.interact(
-                board_rect,
-                Id::new("Board_Drag_Sense"),
-                egui::Sense::click_and_drag(),
-            );
-
-            // Scroll the display in response to user dragging mouse
-            if interact.dragged() {
-                let drag_delta = interact.drag_delta();
-                self.x_offset += drag_delta.x;
-                self.y_offset += drag_delta.y;
-
-                let mut modified_display = false;
+                ui.menu_button(lang::PRESETS, |ui| {
+                    for (name, blueprint) in persistence::presets::all() {
+                        if ui.button(name).clicked() {
+                            // Loaded the same as blueprint files.
+                            to_send.push(UiPacket::LoadBlueprint {
+                                load_position: area_centre(self.display_area),
+                                blueprint,
+                            });
+                            ui.close_menu();
+                        }
+                    }
+                });
 
-                // While loops are used as display can be dragged further than one cell in one frame.
-                while self.x_offset % self.settings.cell.size > 0.0 {
-                    self.display_area.translate_x(-1);
-                    self.x_offset -= self.settings.cell.size;
-                    modified_display = true;
+                ui.separator();
+                ui.label(lang::GOTO);
+                ui.add(egui::DragValue::new(&mut self.goto_position.0).prefix("x: "));
+                ui.add(egui::DragValue::new(&mut self.goto_position.1).prefix("y: "));
+                if ui.button(lang::GOTO_BUTTON).clicked() {
+                    self.jump_to(self.goto_position.into(), &mut to_send);
                 }
-
-                while self.x_offset % self.settings.cell.size < 0.0 {
-                    self.display_area.translate_x(1);
-                    self.x_offset += self.settings.cell.size;
-                    modified_display = true;
+                if ui.button(lang::CENTRE_ORIGIN).clicked() {
+                    self.jump_to((0, 0).into(), &mut to_send);
                 }
-
-                while self.y_offset % self.settings.cell.size > 0.0 {
-                    self.display_area.translate_y(-1);
-                    self.y_offset -= self.settings.cell.size;
-                    modified_display = true;
+                if ui.button(lang::FRAME_ALL).clicked() {
+                    to_send.push(UiPacket::RequestBoardArea);
                 }
+                ui.menu_button(lang::BOOKMARKS, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(lang::BOOKMARK_NAME);
+                        ui.text_edit_singleline(&mut self.bookmark_name);
+                    });
+                    if ui.button(lang::ADD_BOOKMARK).clicked() {
+                        self.settings
+                            .bookmarks
+                            .push((std::mem::take(&mut self.bookmark_name), self.display_area));
+                    }
 
-                while self.y_offset % self.settings.cell.size < 0.0 {
-                    self.display_area.translate_y(1);
-                    self.y_offset += self.settings.cell.size;
-                    modified_display = true;
+                    ui.separator();
+                    let mut jump = None;
+                    self.settings.bookmarks.retain(|(name, area)| {
+                        ui.horizontal(|ui| {
+                            if ui.button(name).on_hover_text(area.to_string()).clicked() {
+                                jump = Some(*area);
+                            }
+                            // Removes the bookmark.
+                            !ui.small_button("🗑").clicked()
+                        })
+                        .inner
+                    });
+                    if let Some(area) = jump {
+                        self.jump_to(area_centre(area), &mut to_send);
+                        ui.close_menu();
+                    }
+                });
+                ui.separator();
+                for edit_state in EditState::ALL {
+                    ui.selectable_value(&mut self.edit_state, edit_state, edit_state.label());
+                }
+                if ui
+                    .add_enabled(self.history.can_undo(), egui::Button::new(lang::UNDO))
+                    .clicked()
+                {
+                    self.history.undo(&mut to_send);
+                }
+                if ui
+                    .add_enabled(self.history.can_redo(), egui::Button::new(lang::REDO))
+                    .clicked()
+                {
+                    self.history.redo(&mut to_send);
                 }
 
-                if modified_display {
-                    to_send.push(UiPacket::DisplayArea {
-                        new_area: self.display_area,
+                if let Some(area) = self.selection.area() {
+                    ui.separator();
+                    if ui.button(lang::COPY).clicked() {
+                        self.clipboard
+                            .request(BlueprintRequest::Copy, area, &mut to_send);
+                    }
+                    for edit in SelectionEdit::ALL {
+                        if ui.button(edit.label()).clicked() {
+                            let origin = area.get_min();
+                            self.clipboard.request(
+                                BlueprintRequest::Edit { edit, origin },
+                                area,
+                                &mut to_send,
+                            );
+                        }
+                    }
+                    ui.label(lang::BLUEPRINT_NAME);
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.blueprint_name).desired_width(100.0),
+                    );
+                    ui.label(lang::BLUEPRINT_TAGS);
+                    let tags = ui.add(
+                        egui::TextEdit::singleline(&mut self.blueprint_tags).desired_width(100.0),
+                    );
+                    if tags.has_focus() {
+                        self.known_tags
+                            .scan(self.settings.file.blueprint_location.clone());
+                    }
+                    let mut completed = None;
+                    for suggestion in self.known_tags.suggestions(&self.blueprint_tags) {
+                        if ui.small_button(suggestion).clicked() {
+                            completed = Some(complete_tag(&self.blueprint_tags, suggestion));
+                        }
+                    }
+                    if let Some(completed) = completed {
+                        self.blueprint_tags = completed;
+                    }
+                    if ui.button(lang::SAVE_BLUEPRINT).clicked() {
+                        self.clipboard
+                            .request(BlueprintRequest::Save, area, &mut to_send);
+                    }
+                    if ui.button(lang::CLEAR_SELECTION).clicked() {
+                        self.selection.clear();
+                    }
+                }
+
+                ui.separator();
+                if ui.button(lang::CLEAR_BOARD).clicked() {
+                    self.confirm_clear = true;
+                }
+                // While pasting, the blueprint being pasted is transformed instead of the board.
+                for axis in Axis::ALL {
+                    if ui.button(axis_label(axis)).clicked() {
+                        match self.clipboard.is_pasting() {
+                            true => self.clipboard.mirror(axis),
+                            false => to_send.push(UiPacket::Mirror { axis }),
+                        }
+                    }
+                }
+                for (label, quarter_turns) in
+                    [(lang::ROTATE_CLOCKWISE, 1), (lang::ROTATE_ANTICLOCKWISE, 3)]
+                {
+                    if ui.button(label).clicked() {
+                        match self.clipboard.is_pasting() {
+                            true => self.clipboard.rotate(quarter_turns),
+                            false => to_send.push(UiPacket::Rotate { quarter_turns }),
+                        }
+                    }
+                }
+                if ui.button(lang::RANDOMISE).clicked() {
+                    to_send.push(UiPacket::Randomize {
+                        // Fills the selection if there is one, otherwise the displayed board.
+                        area: self.selection.area().unwrap_or(self.display_area),
+                        density: self.random_density,
+                        seed: fastrand::u64(..),
+                        symmetry: self.symmetry,
+                    });
+                }
+                ui.label(lang::DENSITY);
+                ui.add(egui::Slider::new(&mut self.random_density, 0.0..=1.0));
+                egui::ComboBox::from_id_salt("Symmetry")
+                    .selected_text(symmetry_label(self.symmetry))
+                    .show_ui(ui, |ui| {
+                        for symmetry in Symmetry::ALL {
+                            ui.selectable_value(
+                                &mut self.symmetry,
+                                symmetry,
+                                symmetry_label(symmetry),
+                            );
+                        }
                     });
+
+                if self.clipboard.is_pasting() {
+                    if ui.button(lang::CANCEL_PASTE).clicked() {
+                        self.clipboard.cancel_paste();
+                    }
+                } else if ui
+                    .add_enabled(
+                        self.clipboard.has_blueprint(),
+                        egui::Button::new(lang::PASTE),
+                    )
+                    .clicked()
+                {
+                    self.clipboard.start_paste();
+                }
+                if self.clipboard.is_copying() {
+                    ui.spinner();
+                }
+
+                ui.separator();
+
+                let mut recording = self.recorder.is_recording();
+                if ui.toggle_value(&mut recording, lang::RECORD_GIF).changed() {
+                    if recording {
+                        self.recorder.start();
+                    } else {
+                        self.recorder.stop(
+                            self.settings.file.recording_location.clone(),
+                            CellColours {
+                                alive: self.settings.cell.alive_colour,
+                                dead: self.settings.cell.dead_colour,
+                            },
+                            self.settings.recording.frame_delay,
+                            &mut self.toasts,
+                        );
+                    }
+                }
+
+                let mut recording = self.statistics.is_recording();
+                if ui
+                    .toggle_value(&mut recording, lang::RECORD_STATISTICS)
+                    .on_hover_text(lang::RECORD_STATISTICS_HOVER)
+                    .changed()
+                {
+                    to_send.push(UiPacket::RecordStatistics { enabled: recording });
+                    if recording {
+                        self.statistics.start();
+                    } else {
+                        self.statistics.stop(
+                            self.settings.file.recording_location.clone(),
+                            &mut self.toasts,
+                        );
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                {
+                    if ui.button("Debug Menu").clicked() {
+                        self.debug_menu_open = !self.debug_menu_open
+                    }
+                }
+            })
+        });
+
+        let top_size = show.response.rect.size();
+
+        // Account for top panel.
+        *board_rect.top_mut() += top_size.y;
+        *board_rect.bottom_mut() += top_size.y;
+
+        // board_rect must not change after this point
+        let board_rect = board_rect;
+        self.board_size = board_rect.size();
+
+        // Draws the central panel to provide the area for user interaction.
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let interact = ui.interact(
+                board_rect,
+                Id::new("Board_Drag_Sense"),
+                egui::Sense::click_and_drag(),
+            );
+
+            // Select an area of the board in response to the user dragging the mouse.
+            let selecting = self.edit_state == EditState::Select
+                && interact.dragged_by(egui::PointerButton::Primary);
+            if selecting {
+                if interact.drag_started() {
+                    if let Some(origin) = ctx.input(|input| input.pointer.press_origin()) {
+                        self.selection.start(self.board_position(origin));
+                    }
+                }
+                if let Some(position) = interact.interact_pointer_pos() {
+                    self.selection.update(self.board_position(position));
                 }
             }
 
-            // Toggles the state of a cell when it is clicked.
-            if interact.clicked() {
+            // Draw with the brush in response to the user dragging the mouse.
+            let drawing = self.edit_state == EditState::Draw
+                && !self.clipboard.is_pasting()
+                && interact.dragged_by(egui::PointerButton::Primary);
+            if drawing {
+                if interact.drag_started() {
+                    if let Some(origin) = ctx.input(|input| input.pointer.press_origin()) {
+                        self.paint(origin, &mut to_send);
+                    }
+                }
                 if let Some(position) = interact.interact_pointer_pos() {
-                    // Position of cell
-                    let cell_x = (position.x / self.settings.cell.size).trunc() as i32;
-                    let cell_y = (position.y / self.settings.cell.size).trunc() as i32;
-
-                    // Position of displayed board
-                    let origin_x = self.display_area.get_min().get_x();
-                    let origin_y = self.display_area.get_min().get_y();
-
-                    let position = GlobalPosition::new(cell_x + origin_x, cell_y + origin_y);
-                    let cell_state = self.display_cache.get_cell((cell_x, cell_y)).invert();
-                    to_send.push(UiPacket::Set {
-                        position,
-                        cell_state,
-                    });
+                    self.paint(position, &mut to_send);
                 }
             }
-        });
+            if interact.drag_stopped() {
+                self.finish_stroke();
+            }
 
-        // Creates the painter for the board display.
-        let layer_painter = Painter::new(
-            ctx.clone(), // ctx is cloned in egui implementations.
-            egui::LayerId::new(egui::Order::Background, BOARD_ID.into()),
-            board_rect,
-        );
+            if self.settings.display.hover_tooltip && !interact.dragged() {
+                if let Some(pointer) = interact.hover_pos() {
+                    self.cell_tooltip(ctx, ui.layer_id(), pointer);
+                }
+            }
 
-        // Number of cell in x axis
-        let x_cells = (board_rect.right() / self.settings.cell.size).ceil() as i32;
-        // Create iterator of x position for cells
-        let x_iter = (0..x_cells).map(|x| {
-            let mut x_cell = x as f32;
-            x_cell *= self.settings.cell.size;
-            x_cell
-        });
+            // Zoom in response to the user scrolling over the board.
+            if let Some(pointer) = interact.hover_pos() {
+                let scroll = ctx.input(|input| input.smooth_scroll_delta.y);
+                if scroll != 0.0 {
+                    self.zoom(pointer, scroll, &mut to_send);
+                }
+            }
 
-        // Number of cells in y axis
-        let y_cells = (board_rect.bottom() / self.settings.cell.size).floor() as i32;
-        // Create iterator of y position for cells
-        let y_iter = (0..y_cells).map(|y| {
-            let mut y_cell = y as f32;
-            y_cell *= self.settings.cell.size;
-            y_cell
-        });
+            // Scroll the display in response to user dragging mouse
+            if interact.dragged() && !selecting && !drawing {
+                let drag_delta = interact.drag_delta();
+                self.x_offset += drag_delta.x;
+                self.y_offset += drag_delta.y;
 
-        // Modify displayed area to follow cells displayed.
-        self.display_area
-            .modify_x(x_cells - self.display_area.x_difference());
-        self.display_area
-            .modify_y(y_cells - self.display_area.y_difference());
-
-        // Draw the display board.
-        for (x_index, x_origin) in x_iter.enumerate() {
-            for (y_index, y_origin) in y_iter.clone().enumerate() {
-                let rect = Rect::from_two_pos(
-                    pos2(x_origin, y_origin),
-                    pos2(
-                        x_origin + self.settings.cell.size,
-                        y_origin + self.settings.cell.size,
-                    ),
-                );
+                let mut modified_display = false;
 
-                let rect = egui::epaint::RectShape::new(
-                    rect,
-                    egui::Rounding::ZERO,
-                    {
-                        match self
-                            .display_cache
-                            .get_cell((x_index as i32, y_index as i32))
-                        {
-                            Cell::Alive => self.settings.cell.alive_colour,
-                            Cell::Dead => self.settings.cell.dead_colour,
-                        }
-                    },
-                    egui::Stroke::new(1.0, Color32::GRAY),
-                );
+                // While loops are used as display can be dragged further than one cell in one frame.
+                while self.x_offset % self.settings.cell.size > 0.0 {
+                    self.display_area.translate_x(-1);
+                    self.x_offset -= self.settings.cell.size;
+                    modified_display = true;
+                }
+
+                while self.x_offset % self.settings.cell.size < 0.0 {
+                    self.display_area.translate_x(1);
+                    self.x_offset += self.settings.cell.size;
+                    modified_display = true;
+                }
 
-                layer_painter.add(rect);
+                while self.y_offset % self.settings.cell.size > 0.0 {
+                    self.display_area.translate_y(-1);
+                    self.y_offset -= self.settings.cell.size;
+                    modified_display = true;
+                }
+
+                while self.y_offset % self.settings.cell.size < 0.0 {
+                    self.display_area.translate_y(1);
+                    self.y_offset += self.settings.cell.size;
+                    modified_display = true;
+                }
+
+                if modified_display {
+                    to_send.push(UiPacket::DisplayArea {
+                        new_area: self.display_area,
+                    });
+                }
             }
-        }
+
+            if interact.clicked() {
+                if let Some(position) = interact.interact_pointer_pos() {
+                    let board_position = self.board_position(position);
+
+                    // A pending paste takes priority over the edit state.
+                    if let Some(packet) = self.clipboard.click(board_position) {
+                        to_send.push(packet);
+                    } else if self.edit_state == EditState::Draw {
+                        // Stamps the brush once when the board is clicked.
+                        self.paint(position, &mut to_send);
+                        self.finish_stroke();
+                    }
+                }
+            }
+        });
+
+        self.draw_board(ctx, board_rect);
 
         // If update is not requested the board will become outdated.
         // This causes higher cpu usage, but only by one/two %.
@@ -465,6 +1404,9 @@ impl eframe::App for MyApp<'static> {
             Ok(mut board) => {
                 if let Some(board) = board.take() {
                     self.display_cache = board;
+                    self.rewind
+                        .update(self.display_cache.get_generation(), self.generation_history);
+                    self.recorder.record(&self.display_cache, &mut self.toasts);
                 }
             }
             Err(std::sync::TryLockError::WouldBlock) => {
@@ -481,12 +1423,30 @@ impl eframe::App for MyApp<'static> {
 
         // Process user interaction
         for message in to_send {
+            self.unsaved.observe(&message);
+            if let UiPacket::SaveBoard = message {
+                self.save_requests.push_back(SaveRequest::Manual);
+            }
             if let Err(err) = self.ui_sender.send(message) {
                 self.error_occurred = Some(ErrorData::from_error_and_log(lang::SEND_ERROR, err));
                 return;
             }
         }
 
+        // Autosaves are only needed if there are changes that could be lost.
+        let interval = self.settings.file.autosave_interval;
+        if self.autosave.due(interval, Instant::now()) && self.unsaved.is_dirty() {
+            // Not observed as unsaved changes, as autosaves don't save the board for the user.
+            if let Err(err) = self.ui_sender.send(UiPacket::SaveBoard) {
+                self.error_occurred = Some(ErrorData::from_error_and_log(lang::SEND_ERROR, err));
+                return;
+            }
+            self.save_requests.push_back(SaveRequest::Autosave);
+        }
+        if interval != 0 {
+            ctx.request_repaint_after(Duration::from_secs(interval.into()));
+        }
+
         loop {
             // Receive packets from simulatior
             let simulator_packet = match self.simulator_receiver.try_recv() {
@@ -502,17 +1462,219 @@ impl eframe::App for MyApp<'static> {
 
             // Act on the simulator packets
             match simulator_packet {
+                SimulatorPacket::Fatal { message } => {
+                    self.error_occurred = Some(ErrorData::from_error(format!(
+                        "{}{message}",
+                        lang::SIMULATOR_PANICKED
+                    )));
+                    return;
+                }
+                SimulatorPacket::BoardSave {
+                    board: simulation_save,
+                } if self.save_requests.pop_front() == Some(SaveRequest::Autosave) => {
+                    if let Err(err) = self.write_autosave(simulation_save) {
+                        self.toasts.error(format!("{}{err}", lang::AUTOSAVE_FAIL))
+                    }
+                }
+                // The final state is only requested as the application closes, which is handled by `on_exit`.
+                SimulatorPacket::FinalState { .. } => {}
                 SimulatorPacket::BoardSave {
                     board: simulation_save,
                 } => {
-                    SaveBuilder::new(simulation_save)
+                    let save = SaveBuilder::new(simulation_save)
                         .name(self.save.save_name.clone())
                         .desciprtion(self.save.save_description.clone())
+                        .view_position(area_centre(self.display_area))
+                        .require_name()
+                        .cancel_flag(self.save.cancel.clone())
                         .save(self.settings.file.save_location.clone());
+                    self.unsaved.save_finished(save.is_ok());
+                    // The changes the autosaves kept are now saved by the user.
+                    if save.is_ok() && !self.unsaved.is_dirty() {
+                        remove_autosaves();
+                    }
+
+                    match save {
+                        Ok(path) => {
+                            self.toasts
+                                .info(format!("{}{}", lang::SAVE_SUCCESS, path.display()))
+                        }
+                        Err(BoardSaveError::Cancelled) => self.toasts.info(lang::SAVE_CANCELLED),
+                        Err(err) => self.toasts.error(format!("{}{err}", lang::SAVE_FAIL)),
+                    }
 
                     self.save.save_requested = false;
                 }
-                SimulatorPacket::BlueprintSave { blueprint } => todo!(),
+                SimulatorPacket::BlueprintSave { blueprint } => {
+                    let blueprint = match self.clipboard.receive(blueprint) {
+                        None => {
+                            self.toasts.info(lang::COPY_SUCCESS);
+                            continue;
+                        }
+                        Some((BlueprintRequest::Edit { edit, origin }, blueprint)) => {
+                            let action = edit.action(origin, &blueprint);
+                            if action.is_empty() {
+                                continue;
+                            }
+                            let packet = action.packet();
+                            self.unsaved.observe(&packet);
+                            if let Err(err) = self.ui_sender.send(packet) {
+                                self.error_occurred =
+                                    Some(ErrorData::from_error_and_log(lang::SEND_ERROR, err));
+                                return;
+                            }
+                            self.history.push(action);
+                            continue;
+                        }
+                        Some((_, blueprint)) => blueprint,
+                    };
+
+                    let pattern = Pattern::new(blueprint).with_name(self.blueprint_name.clone());
+                    let save = split_tags(&self.blueprint_tags)
+                        .fold(BlueprintBuilder::from_pattern(pattern), |builder, tag| {
+                            builder.tag(tag)
+                        })
+                        .save(self.settings.file.blueprint_location.clone());
+                    // The new blueprint may have new tags.
+                    self.known_tags.invalidate();
+
+                    match save {
+                        Ok(path) => self.toasts.info(format!(
+                            "{}{}",
+                            lang::BLUEPRINT_SAVE_SUCCESS,
+                            path.display()
+                        )),
+                        Err(err) => self
+                            .toasts
+                            .error(format!("{}{err}", lang::BLUEPRINT_SAVE_FAIL)),
+                    }
+                }
+                SimulatorPacket::BoardArea { area } => {
+                    let (cell_size, display_area) = frame_area(area, self.board_size);
+                    self.settings.cell.size = cell_size;
+                    self.display_area = display_area;
+                    self.x_offset = 0.0;
+                    self.y_offset = 0.0;
+
+                    if let Err(err) = self.ui_sender.send(UiPacket::DisplayArea {
+                        new_area: self.display_area,
+                    }) {
+                        self.error_occurred =
+                            Some(ErrorData::from_error_and_log(lang::SEND_ERROR, err));
+                        return;
+                    }
+                }
+                SimulatorPacket::BoardLoadResult { status } => match status {
+                    LoadStatus::Success => {
+                        self.unsaved.loaded();
+                        // The generations kept are of the previous board.
+                        self.rewind.reset();
+                    }
+                    LoadStatus::Fail { reason } => self
+                        .toasts
+                        .error(format!("{}{reason}", lang::BOARD_LOAD_FAIL)),
+                },
+                SimulatorPacket::BlueprintLoadResult { status } => {
+                    if let LoadStatus::Fail { reason } = status {
+                        self.toasts
+                            .error(format!("{}{reason}", lang::BLUEPRINT_LOAD_FAIL));
+                    }
+                }
+                SimulatorPacket::RotateFailed { reason } => {
+                    self.toasts.error(format!("{}{reason}", lang::ROTATE_FAIL))
+                }
+                #[cfg(debug_assertions)]
+                SimulatorPacket::TickRate { target, achieved } => {
+                    self.tick_rate = Some((target, achieved));
+                }
+                #[cfg(not(debug_assertions))]
+                SimulatorPacket::TickRate { .. } => {}
+                SimulatorPacket::Progress { current, target } => {
+                    self.progress = (current < target).then_some((current, target));
+                    // The simulation stops itself once the target is reached.
+                    self.running &= current < target;
+                }
+                SimulatorPacket::CellLimitReached {
+                    generation,
+                    population,
+                } => {
+                    // The run is stopped before reaching its target.
+                    self.progress = None;
+                    self.running = false;
+                    self.toasts.warning(format!(
+                        "{}{generation}{}{population}{}",
+                        lang::STOPPED_AT,
+                        lang::CELL_LIMIT_POPULATION,
+                        lang::CELL_LIMIT_CELLS
+                    ));
+                }
+                SimulatorPacket::Stopped { reason, generation } => {
+                    self.progress = None;
+                    self.running = false;
+                    match reason {
+                        StopReason::Condition => self.toasts.info(format!(
+                            "{}{generation}{}",
+                            lang::STOPPED_AT,
+                            lang::STOPPED_CONDITION
+                        )),
+                    };
+                }
+                SimulatorPacket::Statistics {
+                    generation,
+                    population,
+                } => self.statistics.record(generation, population),
+                SimulatorPacket::GenerationUnavailable {
+                    requested,
+                    earliest,
+                } => {
+                    self.rewind.unavailable(earliest);
+                    self.toasts.warning(format!(
+                        "{}{requested}{}{earliest}{}",
+                        lang::GENERATION_UNAVAILABLE,
+                        lang::GENERATION_UNAVAILABLE_EARLIEST,
+                        lang::GENERATION_UNAVAILABLE_END
+                    ));
+                }
+                SimulatorPacket::DisplayTooLarge { cells, max } => {
+                    self.toasts.warning(format!(
+                        "{}{cells}{}{max}{}",
+                        lang::DISPLAY_TOO_LARGE,
+                        lang::DISPLAY_TOO_LARGE_LIMIT,
+                        lang::DISPLAY_TOO_LARGE_ZOOM
+                    ));
+                }
+                SimulatorPacket::SpaceshipDetected {
+                    period,
+                    dx,
+                    dy,
+                    generation,
+                } => {
+                    self.toasts.info(format!(
+                        "{}{}{}({dx}, {dy}){}{period}{}{generation}{}",
+                        lang::SPACESHIP,
+                        spaceship_speed(period, dx, dy),
+                        lang::SPACESHIP_MOVING,
+                        lang::SPACESHIP_EVERY,
+                        lang::SPACESHIP_GENERATION,
+                        lang::SPACESHIP_END
+                    ));
+                }
+                SimulatorPacket::PeriodDetected { period, generation } => {
+                    if period == 1 {
+                        self.toasts.info(format!(
+                            "{}{generation}{}",
+                            lang::STILL_LIFE,
+                            lang::STILL_LIFE_END
+                        ));
+                    } else {
+                        self.toasts.info(format!(
+                            "{}{period}{}{generation}{}",
+                            lang::PERIODIC,
+                            lang::PERIODIC_GENERATION,
+                            lang::PERIODIC_END
+                        ));
+                    }
+                }
             }
         }
 
@@ -525,15 +1687,57 @@ impl eframe::App for MyApp<'static> {
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.settings.workspace = WorkspaceSettings {
+            edit_state: self.edit_state,
+            view_centre: Some(area_centre(self.display_area)),
+        };
         eframe::set_value(storage, Settings::SAVE_KEY, &self.settings);
     }
+
+    /// Autosaves the final state of the board if it has unsaved changes, so they can be restored when next launched.
+    /// Otherwise the autosaves are removed, as there is nothing to restore.
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // The user chose to discard the changes, so they are not autosaved either.
+        if !self.unsaved.is_dirty() || self.close_confirmed {
+            remove_autosaves();
+            return;
+        }
+        if self.settings.file.autosave_interval == 0 {
+            return;
+        }
+        if self.ui_sender.send(UiPacket::Terminate).is_err() {
+            return;
+        }
+
+        // Any packets sent before the final state are no longer needed.
+        let simulation_save = loop {
+            match self.simulator_receiver.recv_timeout(FINAL_STATE_TIMEOUT) {
+                Ok(SimulatorPacket::FinalState { board }) => break board,
+                Ok(_) => continue,
+                Err(err) => {
+                    log::error!("{}{err}", lang::AUTOSAVE_FAIL);
+                    return;
+                }
+            }
+        };
+        if let Err(err) = self.write_autosave(simulation_save) {
+            log::error!("{}{err}", lang::AUTOSAVE_FAIL);
+        }
+    }
+}
+
+/// Removes every autosave, once the board no longer needs to be restored from them.
+fn remove_autosaves() {
+    if let Err(err) = autosave::remove_all(&AUTOSAVE_PATH) {
+        log::error!("{}{err}", lang::AUTOSAVE_REMOVE_FAIL);
+    }
 }
 
 /// Stores relevant information for unrecoverable errors.
 #[cfg_attr(debug_assertions, derive(Debug))]
 struct ErrorData {
     /// The error message.
-    error_message: &'static str,
+    error_message: Cow<'static, str>,
     /// The size of the window displaying the error the previous frame.
     ///
     /// This is used to centre the window.
@@ -542,9 +1746,9 @@ struct ErrorData {
 
 impl ErrorData {
     /// Creates a new [`ErrorData`] with the given sing as the error message.
-    pub fn from_error(error_message: &'static str) -> Self {
+    pub fn from_error(error_message: impl Into<Cow<'static, str>>) -> Self {
         ErrorData {
-            error_message,
+            error_message: error_message.into(),
             window_size: None,
         }
     }
@@ -556,3 +1760,626 @@ impl ErrorData {
         Self::from_error(error_message)
     }
 }
+
+/// The packet that toggles the simulation between running & stopped, given whether the ui last told the simulator to
+/// run. The running state is flipped to match the packet.
+fn toggle_simulation(running: &mut bool) -> UiPacket {
+    *running = !*running;
+    match running {
+        true => UiPacket::Start,
+        false => UiPacket::Stop,
+    }
+}
+
+/// Starts pasting the copied blueprint, so the next click on the board pastes it. The user is told if nothing has
+/// been copied.
+fn paste_clipboard(clipboard: &mut Clipboard, toasts: &mut Toasts) {
+    match clipboard.has_blueprint() {
+        true => clipboard.start_paste(),
+        false => toasts.info(lang::NOTHING_COPIED),
+    }
+}
+
+/// Returns an area the same size as the given area, with the given position at its centre.
+///
+/// The board draws every cell from the minimum position up to & including the maximum position. So for areas with an
+/// even difference the position is at the centre of the drawn cells, & for an odd difference the position is the cell
+/// just before the centre.
+///
+/// Near the edges of the board the area stops at the edge, so the position is not at the centre.
+fn centre_area_on(area: Area, position: GlobalPosition) -> Area {
+    let (width, height) = (area.x_difference(), area.y_difference());
+    // Calculated as i64s, as positions near the edges would overflow.
+    let min = |position: i32, size: i32| {
+        (position as i64 - (size / 2) as i64).clamp(i32::MIN as i64, (i32::MAX - size) as i64)
+            as i32
+    };
+
+    let min = GlobalPosition::new(min(position.get_x(), width), min(position.get_y(), height));
+    Area::new(min, min + (width, height))
+}
+
+/// The space, in points, between the overlay & the edge of the board.
+const OVERLAY_MARGIN: f32 = 6.0;
+
+/// The opacity of the alive cells of the blueprint being pasted, so the board remains visible beneath it.
+const PASTE_PREVIEW_OPACITY: f32 = 0.5;
+
+/// The text shown in the overlay on the board for the given displayed board.
+fn overlay_text(display: &BoardDisplay) -> String {
+    format!("{}{}", lang::GENERATION, display.get_generation())
+}
+
+/// Describes the speed & direction of a spaceship moving by `dx` & `dy` every `period` generations, such as
+/// `c/4 diagonal` for a glider.
+///
+/// The speed is reduced to its simplest form, so moving 2 cells every 4 generations is `c/2`.
+fn spaceship_speed(period: u64, dx: i32, dy: i32) -> String {
+    let distance = u64::from(dx.unsigned_abs().max(dy.unsigned_abs()));
+    let divisor = gcd(distance, period).max(1);
+    let (distance, period) = (distance / divisor, period / divisor);
+    let direction = match (dx, dy) {
+        (0, _) | (_, 0) => lang::ORTHOGONAL,
+        (dx, dy) if dx.unsigned_abs() == dy.unsigned_abs() => lang::DIAGONAL,
+        _ => lang::OBLIQUE,
+    };
+
+    match distance {
+        1 => format!("c/{period} {direction}"),
+        distance => format!("{distance}c/{period} {direction}"),
+    }
+}
+
+/// The greatest common divisor of the numbers, which is 0 only if both are 0.
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The text shown below the selection, giving its width & height in cells, & the amount of alive cells within it.
+///
+/// Only the alive cells within the displayed board are counted, as the rest of the board is not known to the ui.
+fn selection_text(selection: Area, display: &BoardDisplay) -> String {
+    let alive = display
+        .alive_positions()
+        .filter(|position| {
+            selection.x_range().contains(&position.get_x())
+                && selection.y_range().contains(&position.get_y())
+        })
+        .count();
+
+    // The size is found in i64, as the difference between the corners can overflow an i32.
+    let (min, max) = (selection.get_min(), selection.get_max());
+    format!(
+        "{}×{}, {alive}{}",
+        max.get_x() as i64 - min.get_x() as i64 + 1,
+        max.get_y() as i64 - min.get_y() as i64 + 1,
+        lang::ALIVE_CELLS
+    )
+}
+
+/// How quickly scrolling changes the cell size.
+const ZOOM_SPEED: f32 = 0.002;
+
+/// Calculates the minimum position of the display area that keeps the cell under the pointer in the same place when
+/// the cell size changes from the old size to the new size.
+fn zoom_anchor(
+    display_min: GlobalPosition,
+    pointer: Pos2,
+    old_size: f32,
+    new_size: f32,
+) -> GlobalPosition {
+    let anchor = cell_under(display_min, old_size, pointer);
+    anchor.saturating_sub(cell_under(display_min, new_size, pointer) - display_min)
+}
+
+/// Converts a position on the screen into the position of the cell beneath it, for a board displayed from the given
+/// minimum position with the given cell size.
+fn cell_under(
+    display_min: GlobalPosition,
+    cell_size: f32,
+    screen_position: Pos2,
+) -> GlobalPosition {
+    display_min.saturating_add((
+        (screen_position.x / cell_size).trunc() as i32,
+        (screen_position.y / cell_size).trunc() as i32,
+    ))
+}
+
+/// Converts the position of a cell into the screen position of its top left corner, for a board displayed from the
+/// given minimum position with the given cell size. This is the inverse of [`cell_under`].
+fn cell_screen_position(
+    display_min: GlobalPosition,
+    cell_size: f32,
+    position: GlobalPosition,
+) -> Pos2 {
+    let (x, y) = display_offset(display_min, position);
+    pos2(x as f32 * cell_size, y as f32 * cell_size)
+}
+
+/// Gets the cell at the position on the board from the display, which starts at the given minimum position.
+///
+/// Cells outside of the display are dead, the same as [`BoardDisplay::get_cell`].
+fn displayed_cell(
+    display: &BoardDisplay,
+    display_min: GlobalPosition,
+    position: GlobalPosition,
+) -> Cell {
+    let (x, y) = display_offset(display_min, position);
+    match (i32::try_from(x), i32::try_from(y)) {
+        (Ok(x), Ok(y)) => display.get_cell((x, y)),
+        _ => Cell::Dead,
+    }
+}
+
+/// The x & y offset of the position from the minimum position of the display.
+///
+/// This is computed as i64, as positions on opposite sides of the board are further apart than an i32 can hold.
+fn display_offset(display_min: GlobalPosition, position: GlobalPosition) -> (i64, i64) {
+    (
+        position.get_x() as i64 - display_min.get_x() as i64,
+        position.get_y() as i64 - display_min.get_y() as i64,
+    )
+}
+
+/// The shapes of the given amount of displayed cells, starting with a background in the dead colour.
+///
+/// Cells in the dead colour are left as the background. If dead cells are not filled, only the alive cells are drawn,
+/// so the amount of shapes grows with the amount of alive cells rather than the amount of displayed cells.
+fn board_shapes(
+    display: &BoardDisplay,
+    cell: &CellSettings,
+    x_cells: i32,
+    y_cells: i32,
+) -> Vec<egui::Shape> {
+    let mut shapes = vec![egui::Shape::rect_filled(
+        Rect::from_min_size(
+            pos2(0.0, 0.0),
+            Vec2::new(x_cells as f32, y_cells as f32) * cell.size,
+        ),
+        egui::Rounding::ZERO,
+        cell.dead_colour,
+    )];
+
+    let columns = display.get_columns().iter().take(x_cells.max(0) as usize);
+    for (x, column) in columns.enumerate() {
+        for (y, state) in column.iter().take(y_cells.max(0) as usize).enumerate() {
+            if state.is_dead() && !cell.draw_died {
+                continue;
+            }
+
+            let position = (x as i32, y as i32);
+            let colour = cell.cell_colour(
+                *state,
+                display.get_age(position),
+                display.get_change(position),
+            );
+            if colour != cell.dead_colour {
+                let rect = Rect::from_min_size(
+                    pos2(x as f32 * cell.size, y as f32 * cell.size),
+                    Vec2::splat(cell.size),
+                );
+                shapes.push(cell.shape.shape(rect, colour));
+            }
+        }
+    }
+    shapes
+}
+
+/// The gridlines around the given amount of cells, displayed from the given minimum position.
+///
+/// Gridlines are decided by board position, so major gridlines move with the board.
+fn grid_shapes(
+    cell: &CellSettings,
+    display_min: GlobalPosition,
+    x_cells: i32,
+    y_cells: i32,
+) -> Vec<egui::Shape> {
+    let width = x_cells as f32 * cell.size;
+    let height = y_cells as f32 * cell.size;
+
+    let vertical = (0..=x_cells).filter_map(|x| {
+        let stroke = cell.gridline_stroke(display_min.get_x() + x)?;
+        Some(egui::Shape::vline(
+            x as f32 * cell.size,
+            0.0..=height,
+            stroke,
+        ))
+    });
+    let horizontal = (0..=y_cells).filter_map(|y| {
+        let stroke = cell.gridline_stroke(display_min.get_y() + y)?;
+        Some(egui::Shape::hline(
+            0.0..=width,
+            y as f32 * cell.size,
+            stroke,
+        ))
+    });
+    vertical.chain(horizontal).collect()
+}
+
+/// The margin, in cells, left around the board when it is framed.
+const FRAME_MARGIN: i32 = 2;
+
+/// Calculates the cell size & display area that fit the given board area, along with a margin, within a viewport of
+/// the given size.
+///
+/// The cell size is kept within [`CellSettings::SIZE_RANGE`], so very large boards may not fit. If there is no board
+/// area, the origin is centred at the default cell size.
+fn frame_area(board_area: Option<Area>, viewport: Vec2) -> (f32, Area) {
+    let (cell_size, centre) = match board_area {
+        Some(board_area) => {
+            let framed = board_area.expanded(FRAME_MARGIN);
+            let x_fit = viewport.x / (framed.x_difference() + 1) as f32;
+            let y_fit = viewport.y / (framed.y_difference() + 1) as f32;
+            let range = CellSettings::SIZE_RANGE;
+
+            (
+                x_fit.min(y_fit).clamp(*range.start(), *range.end()),
+                area_centre(board_area),
+            )
+        }
+        None => (CellSettings::default().size, GlobalPosition::new(0, 0)),
+    };
+
+    // The same amount of cells as the board will draw.
+    let x_cells = (viewport.x / cell_size).ceil() as i32;
+    let y_cells = (viewport.y / cell_size).floor() as i32;
+    let display_area = Area::new((0, 0), ((x_cells - 1).max(0), (y_cells - 1).max(0)));
+
+    (cell_size, centre_area_on(display_area, centre))
+}
+
+/// Returns the position at the centre of the given area.
+///
+/// This is the position the area would be centred on by [`centre_area_on`].
+fn area_centre(area: Area) -> GlobalPosition {
+    area.get_min() + (area.x_difference() / 2, area.y_difference() / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The position is placed at the centre of the area.
+    fn centre_area_on_position() {
+        let area = Area::new((-10, -10), (10, 10));
+
+        let centred = centre_area_on(area, (100, -50).into());
+        assert_eq!(centred, Area::new((90, -60), (110, -40)));
+    }
+
+    #[test]
+    /// The size of the area does not change when centring.
+    fn centre_area_keeps_size() {
+        let area = Area::new((3, 7), (40, 22));
+
+        let centred = centre_area_on(area, (-5, 5).into());
+        assert_eq!(centred.x_difference(), area.x_difference());
+        assert_eq!(centred.y_difference(), area.y_difference());
+
+        // The centre of the drawn cells is the requested position.
+        let drawn_centre =
+            centred.get_min() + (centred.x_difference() / 2, centred.y_difference() / 2);
+        assert_eq!(drawn_centre, (-5, 5).into());
+    }
+
+    #[test]
+    /// Centring on the origin places the origin at the centre.
+    fn centre_area_on_origin() {
+        let area = Area::new((50, 50), (70, 60));
+
+        let centred = centre_area_on(area, (0, 0).into());
+        assert_eq!(centred, Area::new((-10, -5), (10, 5)));
+    }
+
+    #[test]
+    /// Centring on positions at the edges of the board stops the area at the edge, rather than overflowing.
+    fn centre_area_on_extremes() {
+        let area = Area::new((-10, -10), (10, 10));
+
+        let centred = centre_area_on(area, (i32::MAX, i32::MIN).into());
+        assert_eq!(
+            centred,
+            Area::new((i32::MAX - 20, i32::MIN), (i32::MAX, i32::MIN + 20))
+        );
+
+        // Moving between the opposite edges is further than an i32 can hold.
+        let centred = centre_area_on(centred, (i32::MIN, i32::MAX).into());
+        assert_eq!(
+            centred,
+            Area::new((i32::MIN, i32::MAX - 20), (i32::MIN + 20, i32::MAX))
+        );
+    }
+
+    #[test]
+    /// Cells beneath the pointer & zooming at the edges of the board stop at the edge.
+    fn navigation_extremes() {
+        let display_min = GlobalPosition::new(i32::MAX - 2, i32::MAX - 2);
+
+        assert_eq!(
+            cell_under(display_min, 10.0, pos2(100.0, 100.0)),
+            (i32::MAX, i32::MAX).into()
+        );
+        // Zooming out would move the display past the minimum edge.
+        let display_min = GlobalPosition::new(i32::MIN, i32::MIN);
+        assert_eq!(
+            zoom_anchor(display_min, pos2(100.0, 100.0), 50.0, 10.0),
+            display_min
+        );
+    }
+
+    #[test]
+    /// Centring an area on its own centre does not move it, so a saved view position restores the same view.
+    fn area_centre_round_trip() {
+        for area in [
+            Area::new((-10, -10), (10, 10)),
+            Area::new((3, 7), (40, 22)),
+            Area::new((-7, 2), (0, 3)),
+        ] {
+            assert_eq!(centre_area_on(area, area_centre(area)), area);
+        }
+    }
+
+    #[test]
+    /// The cell size is chosen so the board & its margin fill the viewport, with the board at the centre.
+    fn frame_area_fits_board() {
+        let board_area = Area::new((0, 0), (9, 9));
+
+        // The board with its margin is 14 cells wide.
+        let (cell_size, display_area) = frame_area(Some(board_area), Vec2::new(280.0, 420.0));
+        assert_eq!(cell_size, 20.0);
+        assert_eq!(display_area, Area::new((-2, -6), (11, 14)));
+    }
+
+    #[test]
+    /// The cell size does not go below the smallest selectable size for large boards.
+    fn frame_area_large_board() {
+        let (cell_size, display_area) = frame_area(
+            Some(Area::new((-1000, -1000), (1000, 1000))),
+            Vec2::new(200.0, 200.0),
+        );
+
+        assert_eq!(cell_size, *CellSettings::SIZE_RANGE.start());
+        assert_eq!(display_area, Area::new((-9, -9), (10, 10)));
+    }
+
+    #[test]
+    /// An empty board centres on the origin at the default size.
+    fn frame_area_empty() {
+        let (cell_size, display_area) = frame_area(None, Vec2::new(300.0, 300.0));
+
+        assert_eq!(cell_size, CellSettings::default().size);
+        assert_eq!(display_area, Area::new((-9, -9), (10, 10)));
+    }
+
+    #[test]
+    /// The cell under the pointer stays under the pointer after zooming.
+    fn zoom_keeps_pointer_cell() {
+        let display_min = GlobalPosition::new(-20, 4);
+        let pointer = pos2(105.0, 55.0);
+
+        // The pointer is over the cell 10 across & 5 down at a size of 10.
+        let zoomed_in = zoom_anchor(display_min, pointer, 10.0, 20.0);
+        assert_eq!(zoomed_in, GlobalPosition::new(-15, 7));
+        assert_eq!(zoomed_in + (5, 2), display_min + (10, 5));
+
+        let zoomed_out = zoom_anchor(zoomed_in, pointer, 20.0, 10.0);
+        assert_eq!(zoomed_out, display_min);
+    }
+
+    #[test]
+    /// Jumping to a bookmark centres the current view on the bookmarked view.
+    fn jump_to_bookmark() {
+        let display_area = Area::new((0, 0), (20, 10));
+        let bookmark = Area::new((100, 100), (120, 110));
+
+        assert_eq!(
+            centre_area_on(display_area, area_centre(bookmark)),
+            bookmark
+        );
+    }
+
+    #[test]
+    /// The cell under the cursor is offset by the displayed area, including into negative positions.
+    fn cell_under_pointer() {
+        let display_min = GlobalPosition::new(-20, -5);
+
+        assert_eq!(
+            cell_under(display_min, 10.0, pos2(0.0, 0.0)),
+            GlobalPosition::new(-20, -5)
+        );
+        assert_eq!(
+            cell_under(display_min, 10.0, pos2(35.0, 99.0)),
+            GlobalPosition::new(-17, 4)
+        );
+        assert_eq!(
+            cell_under(display_min, 25.0, pos2(35.0, 99.0)),
+            GlobalPosition::new(-19, -2)
+        );
+    }
+
+    #[test]
+    /// The overlay shows the generation of the displayed board.
+    fn overlay_generation() {
+        let board: Vec<Box<[Cell]>> = vec![Box::new([Cell::Alive])];
+        let display = BoardDisplay::new(42, Area::new((0, 0), (1, 1)), board);
+
+        assert_eq!(overlay_text(&display), "Generation: 42");
+    }
+
+    #[test]
+    /// Spaceship speeds are described by their distance per period & direction.
+    fn spaceship_speeds() {
+        assert_eq!(spaceship_speed(4, 1, 1), "c/4 diagonal");
+        assert_eq!(spaceship_speed(4, -2, 0), "c/2 orthogonal");
+        assert_eq!(spaceship_speed(6, 0, -1), "c/6 orthogonal");
+        assert_eq!(spaceship_speed(7, 2, 1), "2c/7 oblique");
+        assert_eq!(spaceship_speed(6, 4, 0), "2c/3 orthogonal");
+        assert_eq!(
+            spaceship_speed(1, i32::MIN, i32::MIN),
+            "2147483648c/1 diagonal"
+        );
+    }
+
+    #[test]
+    /// The origin is drawn at its offset from the displayed area, which changes as the board is scrolled.
+    fn origin_screen_position() {
+        let origin = GlobalPosition::new(0, 0);
+
+        assert_eq!(
+            cell_screen_position((0, 0).into(), 10.0, origin),
+            pos2(0.0, 0.0)
+        );
+        assert_eq!(
+            cell_screen_position((-20, -5).into(), 10.0, origin),
+            pos2(200.0, 50.0)
+        );
+        // The origin is off screen when scrolled past it.
+        assert_eq!(
+            cell_screen_position((3, 4).into(), 15.0, origin),
+            pos2(-45.0, -60.0)
+        );
+
+        // The cell under the drawn origin is the origin.
+        let display_min = GlobalPosition::new(-7, 12);
+        let screen_position = cell_screen_position(display_min, 25.0, origin);
+        assert_eq!(cell_under(display_min, 25.0, screen_position), origin);
+
+        // Cells on the opposite side of the board to the display don't overflow.
+        let far = cell_screen_position(
+            (i32::MIN, i32::MIN).into(),
+            1.0,
+            (i32::MAX, i32::MAX).into(),
+        );
+        assert_eq!(far, pos2(u32::MAX as f32, u32::MAX as f32));
+    }
+
+    #[test]
+    /// Cells are read from the display relative to its minimum position, & cells too far away to be displayed are dead.
+    fn displayed_cells() {
+        let mut cells = vec![vec![Cell::Dead; 2].into_boxed_slice(); 2];
+        cells[1][0] = Cell::Alive;
+        let display_min = GlobalPosition::new(i32::MAX - 1, 5);
+        let display = BoardDisplay::new(0, Area::new(display_min, (i32::MAX, 6)), cells);
+
+        assert_eq!(
+            displayed_cell(&display, display_min, (i32::MAX, 5).into()),
+            Cell::Alive
+        );
+        assert_eq!(
+            displayed_cell(&display, display_min, (i32::MAX - 1, 5).into()),
+            Cell::Dead
+        );
+        assert_eq!(
+            displayed_cell(&display, display_min, (i32::MIN, 5).into()),
+            Cell::Dead
+        );
+    }
+
+    #[test]
+    /// The selection text gives the size of the selection & the alive cells within it.
+    fn selection_size() {
+        // Alive cells on the diagonal from (10, 10) to (13, 13).
+        let board: Vec<Box<[Cell]>> = (0..4)
+            .map(|x| (0..4).map(|y| Cell::from(x == y)).collect::<Box<[Cell]>>())
+            .collect();
+        let display = BoardDisplay::new(0, Area::new((10, 10), (13, 13)), board);
+
+        assert_eq!(
+            selection_text(Area::new((10, 10), (11, 12)), &display),
+            "2×3, 2 alive"
+        );
+        // Cells outside the selection are not counted.
+        assert_eq!(
+            selection_text(Area::new((13, 10), (13, 12)), &display),
+            "1×3, 0 alive"
+        );
+        // Selections may extend past the displayed board.
+        assert_eq!(
+            selection_text(Area::new((0, 0), (19, 11)), &display),
+            "20×12, 2 alive"
+        );
+        // Selections spanning every position don't overflow.
+        assert_eq!(
+            selection_text(Area::new((i32::MIN, 0), (i32::MAX, 0)), &display),
+            "4294967296×1, 0 alive"
+        );
+    }
+
+    #[test]
+    /// Toggling alternates between starting & stopping the simulation.
+    fn toggle_simulation_alternates() {
+        let mut running = false;
+
+        assert!(matches!(toggle_simulation(&mut running), UiPacket::Start));
+        assert!(running);
+        assert!(matches!(toggle_simulation(&mut running), UiPacket::Stop));
+        assert!(!running);
+        assert!(matches!(toggle_simulation(&mut running), UiPacket::Start));
+    }
+
+    #[test]
+    /// The paste keybind starts pasting once a blueprint has been copied, & otherwise only tells the user.
+    fn paste_keybind() {
+        let mut clipboard = Clipboard::default();
+        let mut toasts = Toasts::default();
+
+        paste_clipboard(&mut clipboard, &mut toasts);
+        assert!(!clipboard.is_pasting());
+        assert_eq!(toasts.len(), 1);
+
+        clipboard.request(BlueprintRequest::Copy, Area::default(), &mut Vec::new());
+        clipboard.receive(gol_lib::persistence::SimulationBlueprint::new(
+            0,
+            0,
+            bitvec::bitbox![usize, bitvec::order::Lsb0; 1],
+        ));
+        paste_clipboard(&mut clipboard, &mut toasts);
+        assert!(clipboard.is_pasting());
+        assert_eq!(toasts.len(), 1);
+
+        // The blueprint is kept after pasting, so it can be stamped repeatedly.
+        assert!(clipboard.click((0, 0).into()).is_some());
+        paste_clipboard(&mut clipboard, &mut toasts);
+        assert!(clipboard.is_pasting());
+    }
+
+    #[test]
+    /// Without drawing died cells or the grid, a shape is drawn for each alive cell along with the background.
+    fn board_shapes_scale_with_alive_cells() {
+        let mut cells = vec![vec![Cell::Dead; 20].into_boxed_slice(); 20];
+        for (x, y) in [(0, 0), (3, 4), (19, 19), (7, 2)] {
+            cells[x][y] = Cell::Alive;
+        }
+        let changes = vec![vec![gol_lib::CellChange::Died; 20].into_boxed_slice(); 20];
+        let display =
+            BoardDisplay::new(0, Area::new((0, 0), (19, 19)), cells).with_changes(changes);
+        let mut cell = CellSettings {
+            change_colours: true,
+            grid: false,
+            draw_died: false,
+            ..Default::default()
+        };
+
+        let shapes = |cell: &CellSettings| {
+            board_shapes(&display, cell, 20, 20).len()
+                + grid_shapes(cell, GlobalPosition::new(0, 0), 20, 20).len()
+        };
+        assert_eq!(shapes(&cell), 4 + 1);
+
+        // Cells outside of the displayed cells are not drawn.
+        assert_eq!(board_shapes(&display, &cell, 10, 10).len(), 3 + 1);
+
+        // Every cell that just died is filled in the died colour.
+        cell.draw_died = true;
+        assert_eq!(shapes(&cell), 400 + 1);
+
+        // A gridline is drawn on both sides of every cell.
+        cell.draw_died = false;
+        cell.grid = true;
+        assert_eq!(shapes(&cell), 4 + 1 + 21 * 2);
+    }
+}
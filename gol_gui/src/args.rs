@@ -1,6 +1,7 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
+use gol_lib::Rule;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -8,4 +9,140 @@ pub struct Args {
     /// The path to the directory which will contain the user configuration data.
     #[arg(short, long, value_name = "DIR")]
     pub(crate) config_path: Option<PathBuf>,
+
+    /// The implementation used to simulate the board.
+    #[arg(short, long, value_enum, default_value_t = Backend::Simple, global = true)]
+    pub(crate) backend: Backend,
+
+    /// The rule the board is simulated with, in the B/S notation such as B36/S23.
+    #[arg(short, long, global = true)]
+    pub(crate) rule: Option<Rule>,
+
+    /// A save, RLE or plaintext pattern file to load once the simulation starts.
+    #[arg(short, long, value_name = "FILE")]
+    pub(crate) load: Option<PathBuf>,
+
+    /// Runs the simulation without opening a window.
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
+}
+
+/// The actions that can be run without opening a window.
+#[derive(Subcommand, Debug)]
+pub(crate) enum Command {
+    /// Advances a pattern by an amount of generations, writing the result as an RLE pattern.
+    Evolve(EvolveArgs),
+}
+
+/// The arguments of [`Command::Evolve`].
+#[derive(clap::Args, Debug)]
+pub(crate) struct EvolveArgs {
+    /// The save, RLE or plaintext pattern file to advance.
+    #[arg(short, long, value_name = "FILE")]
+    pub(crate) load: PathBuf,
+
+    /// The amount of generations to advance by.
+    #[arg(short, long)]
+    pub(crate) generations: u64,
+
+    /// The file the result is written to.
+    #[arg(short, long, value_name = "FILE")]
+    pub(crate) out: PathBuf,
+}
+
+/// How a file given at startup is loaded, depending on its extension.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum LoadAction {
+    /// A `.save` file, which replaces the board.
+    Save,
+    /// An `.rle` pattern, which is loaded as a blueprint.
+    Rle,
+    /// A `.cells` plaintext pattern, which is loaded as a blueprint.
+    Plaintext,
+}
+
+impl LoadAction {
+    /// How the file at the given path is loaded, or [`None`] if files with its extension can't be loaded.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        match extension.as_str() {
+            "save" => Some(LoadAction::Save),
+            "rle" => Some(LoadAction::Rle),
+            "cells" => Some(LoadAction::Plaintext),
+            _ => None,
+        }
+    }
+}
+
+/// The implementations of the simulator that can be selected at startup.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Backend {
+    /// Stores the position of each alive cell. Efficient for sparse boards.
+    Simple,
+    /// Stores the board as chunks of bits. Efficient for densely packed boards.
+    Dense,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The rule is parsed from the B/S notation, & invalid rules are rejected.
+    fn rule_arg() {
+        let args = Args::try_parse_from(["game_of_life", "--rule", "B36/S23"]).unwrap();
+        assert_eq!(args.rule, Some(Rule::HIGHLIFE));
+
+        let args = Args::try_parse_from(["game_of_life"]).unwrap();
+        assert_eq!(args.rule, None);
+
+        assert!(Args::try_parse_from(["game_of_life", "--rule", "B9/S23"]).is_err());
+    }
+
+    #[test]
+    /// The evolve subcommand takes its own arguments, along with the backend & rule.
+    fn evolve_args() {
+        let args = Args::try_parse_from([
+            "game_of_life",
+            "evolve",
+            "--load",
+            "glider.rle",
+            "--generations",
+            "4",
+            "--out",
+            "result.rle",
+            "--backend",
+            "dense",
+        ])
+        .unwrap();
+
+        assert_eq!(args.backend, Backend::Dense);
+        let Some(Command::Evolve(evolve)) = args.command else {
+            panic!("{:?}", args.command);
+        };
+        assert_eq!(evolve.load, Path::new("glider.rle"));
+        assert_eq!(evolve.generations, 4);
+        assert_eq!(evolve.out, Path::new("result.rle"));
+
+        // The amount of generations is required.
+        assert!(
+            Args::try_parse_from(["game_of_life", "evolve", "-l", "a.rle", "-o", "b.rle"]).is_err()
+        );
+    }
+
+    #[test]
+    /// Files are loaded depending on their extension, in any case.
+    fn load_action() {
+        for (path, action) in [
+            ("autosave.save", Some(LoadAction::Save)),
+            ("patterns/glider.rle", Some(LoadAction::Rle)),
+            ("GLIDER.RLE", Some(LoadAction::Rle)),
+            ("blinker.cells", Some(LoadAction::Plaintext)),
+            ("glider.blueprint", None),
+            ("notes.txt", None),
+            ("rle", None),
+        ] {
+            assert_eq!(LoadAction::from_path(Path::new(path)), action, "{path}");
+        }
+    }
 }
@@ -0,0 +1,193 @@
+//! Contains [`Autosave`].
+//! See its documentation for more information.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// The start of the filename of every autosave slot, which is followed by the slot number.
+const AUTOSAVE_PREFIX: &str = "autosave_";
+/// The extension of autosave files, which is the same as other board saves so they can be loaded normally.
+const AUTOSAVE_EXTENSION: &str = "save";
+/// The maximum amount of autosave slots.
+pub(crate) const MAX_SLOTS: u32 = 10;
+
+/// Why the board was requested from the simulator to be saved.
+///
+/// The simulator replies to save requests in the order they were sent, so the requests are queued to match each reply
+/// with its request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SaveRequest {
+    /// The user saved the board through the save menu.
+    Manual,
+    /// The board was saved by [`Autosave`].
+    Autosave,
+}
+
+/// Regularly saves the board into a rotating set of autosave slots, so work is not lost if the application closes
+/// unexpectedly.
+///
+/// Each autosave overwrites the slot that was least recently written. The autosaves are removed once they are no
+/// longer needed, such as after the board is saved.
+#[derive(Debug)]
+pub(crate) struct Autosave {
+    /// When the last autosave was requested.
+    last: Instant,
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self {
+            last: Instant::now(),
+        }
+    }
+}
+
+impl Autosave {
+    /// Whether an autosave is due, given the interval in seconds between autosaves. An interval of 0 disables
+    /// autosaving.
+    ///
+    /// Returns true at most once per interval.
+    pub(crate) fn due(&mut self, interval: u32, now: Instant) -> bool {
+        if interval == 0
+            || now.saturating_duration_since(self.last) < Duration::from_secs(interval.into())
+        {
+            return false;
+        }
+
+        self.last = now;
+        true
+    }
+}
+
+/// The path of the given autosave slot within the autosave directory.
+pub(crate) fn slot_path(directory: &Path, slot: u32) -> PathBuf {
+    directory
+        .join(format!("{AUTOSAVE_PREFIX}{slot}"))
+        .with_extension(AUTOSAVE_EXTENSION)
+}
+
+/// The time each existing autosave slot within the autosave directory was last written.
+fn existing_slots(directory: &Path, slots: u32) -> Vec<(u32, SystemTime)> {
+    (0..slots)
+        .filter_map(|slot| {
+            let modified = std::fs::metadata(slot_path(directory, slot))
+                .and_then(|metadata| metadata.modified())
+                .ok()?;
+            Some((slot, modified))
+        })
+        .collect()
+}
+
+/// Selects the slot the next autosave overwrites, given the existing slots & when they were last written.
+///
+/// Unused slots are filled first, then the least recently written slot is overwritten.
+fn next_slot(existing: &[(u32, SystemTime)], slots: u32) -> u32 {
+    (0..slots)
+        .find(|slot| !existing.iter().any(|(existing, _)| existing == slot))
+        .or_else(|| {
+            existing
+                .iter()
+                .min_by_key(|(_, modified)| *modified)
+                .map(|(slot, _)| *slot)
+        })
+        .unwrap_or(0)
+}
+
+/// The path the next autosave within the autosave directory is written to.
+pub(crate) fn next_slot_path(directory: &Path, slots: u32) -> PathBuf {
+    let slots = slots.max(1);
+    slot_path(
+        directory,
+        next_slot(&existing_slots(directory, slots), slots),
+    )
+}
+
+/// The path to the most recently written autosave within the autosave directory, if there are any.
+pub(crate) fn latest(directory: &Path, slots: u32) -> Option<PathBuf> {
+    existing_slots(directory, slots)
+        .into_iter()
+        .max_by_key(|(_, modified)| *modified)
+        .map(|(slot, _)| slot_path(directory, slot))
+}
+
+/// Removes every autosave within the autosave directory, including those in slots beyond the current amount.
+pub(crate) fn remove_all(directory: &Path) -> std::io::Result<()> {
+    for slot in 0..MAX_SLOTS {
+        match std::fs::remove_file(slot_path(directory, slot)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Unused slots are filled in order before any are overwritten.
+    fn fills_unused_slots() {
+        let time = SystemTime::UNIX_EPOCH;
+
+        assert_eq!(next_slot(&[], 3), 0);
+        assert_eq!(next_slot(&[(0, time)], 3), 1);
+        assert_eq!(next_slot(&[(0, time), (2, time)], 3), 1);
+    }
+
+    #[test]
+    /// Once every slot is used, the least recently written is overwritten.
+    fn overwrites_oldest() {
+        let time = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+
+        assert_eq!(next_slot(&[(0, time(5)), (1, time(2)), (2, time(9))], 3), 1);
+        assert_eq!(next_slot(&[(0, time(5)), (1, time(7)), (2, time(9))], 3), 0);
+    }
+
+    #[test]
+    /// Autosaves are due once per interval, & never when disabled.
+    fn due() {
+        let start = Instant::now();
+        let mut autosave = Autosave { last: start };
+
+        assert!(!autosave.due(10, start + Duration::from_secs(5)));
+        assert!(autosave.due(10, start + Duration::from_secs(10)));
+        assert!(!autosave.due(10, start + Duration::from_secs(15)));
+        assert!(autosave.due(10, start + Duration::from_secs(21)));
+        assert!(!autosave.due(0, start + Duration::from_secs(100)));
+    }
+
+    #[test]
+    /// The most recent autosave is found from the slots within the autosave directory.
+    fn latest_autosave() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        assert_eq!(latest(temp_dir.path(), 3), None);
+
+        let path = next_slot_path(temp_dir.path(), 3);
+        assert_eq!(path, slot_path(temp_dir.path(), 0));
+        std::fs::write(&path, "").expect("Can write file");
+
+        assert_eq!(latest(temp_dir.path(), 3), Some(path));
+        assert_eq!(
+            next_slot_path(temp_dir.path(), 3),
+            slot_path(temp_dir.path(), 1)
+        );
+    }
+
+    #[test]
+    /// Every autosave slot is removed, whilst other files are kept.
+    fn remove_all_slots() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        for slot in [0, 2, MAX_SLOTS - 1] {
+            std::fs::write(slot_path(temp_dir.path(), slot), "").expect("Can write file");
+        }
+        let other = temp_dir.path().join("other.save");
+        std::fs::write(&other, "").expect("Can write file");
+
+        remove_all(temp_dir.path()).expect("Can remove autosaves");
+        assert_eq!(latest(temp_dir.path(), MAX_SLOTS), None);
+        assert!(other.exists());
+    }
+}
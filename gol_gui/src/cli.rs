@@ -0,0 +1,193 @@
+//! Contains the actions taken for the command line arguments, such as [`evolve`].
+//! See their documentation for more information.
+
+use std::{error::Error, path::Path};
+
+use gol_lib::{
+    communication::UiPacket,
+    persistence::{self, pattern::Pattern, plaintext::parse_plaintext, SimulationSave},
+    LoadStatus, Rule, SharedDisplay, Simulator,
+};
+
+use crate::{
+    args::{EvolveArgs, LoadAction},
+    error_text,
+};
+
+/// A file given on the command line, read from disk.
+pub(crate) enum LoadedFile {
+    /// A save, which replaces the board.
+    Save(SimulationSave),
+    /// A pattern, which replaces the board with the pattern centred on the origin.
+    Pattern(Pattern),
+}
+
+impl LoadedFile {
+    /// Reads the file at the given path, depending on its extension.
+    pub(crate) fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let Some(action) = LoadAction::from_path(path) else {
+            return Err(format!("{}{}", error_text::UNKNOWN_EXTENSION, path.display()).into());
+        };
+
+        Ok(match action {
+            LoadAction::Save => {
+                LoadedFile::Save(persistence::load_save(path)?.into_simulation_save())
+            }
+            LoadAction::Rle => {
+                LoadedFile::Pattern(Pattern::from_rle(&std::fs::read_to_string(path)?)?)
+            }
+            LoadAction::Plaintext => LoadedFile::Pattern(Pattern::new(parse_plaintext(
+                &std::fs::read_to_string(path)?,
+            )?)),
+        })
+    }
+
+    /// The board the file is loaded as.
+    ///
+    /// The given rule is used if there is one, otherwise the rule of the file is used. Patterns without a rule use the
+    /// default rule.
+    pub(crate) fn into_board(self, rule: Option<Rule>) -> SimulationSave {
+        match self {
+            LoadedFile::Save(board) => match rule {
+                Some(rule) => board.with_rule(rule),
+                None => board,
+            },
+            LoadedFile::Pattern(pattern) => {
+                let rule = rule.or(pattern.get_rule()).unwrap_or_default();
+                let blueprint = pattern.into_blueprint();
+                let area = blueprint.get_area();
+                let centred = area.translated(-area.x_difference() / 2, -area.y_difference() / 2);
+                SimulationSave::new(0, centred, blueprint.get_blueprint_data().to_bitvec())
+                    .with_rule(rule)
+            }
+        }
+    }
+
+    /// The packet that loads the file into the simulation, as given by [`Self::into_board`].
+    pub(crate) fn into_packet(self, rule: Option<Rule>) -> UiPacket {
+        UiPacket::LoadBoard {
+            board: self.into_board(rule),
+        }
+    }
+}
+
+/// Creates the simulator, applying the rule given at startup if there is one.
+pub(crate) fn create_simulator<S: Simulator>(
+    display: SharedDisplay,
+    rule: Option<Rule>,
+) -> Result<S, Box<dyn Error>> {
+    let mut simulator = S::new(display);
+    if let Some(rule) = rule {
+        apply_rule(&mut simulator, rule)?;
+    }
+    Ok(simulator)
+}
+
+/// Sets the rule of the simulator, if it supports the rule.
+fn apply_rule(simulator: &mut impl Simulator, rule: Rule) -> Result<(), Box<dyn Error>> {
+    if !simulator.supports_rule(rule) {
+        return Err(format!("{}{rule}", error_text::UNSUPPORTED_RULE).into());
+    }
+    simulator.set_rule(rule);
+    Ok(())
+}
+
+/// Loads the file into a new simulator, advances it by the given amount of generations without the ui, & writes the
+/// final board to the output file as an RLE pattern.
+///
+/// The file is loaded the same as with the ui, so the given rule is used if there is one, otherwise the rule of the
+/// file is used. See [`LoadedFile::into_board`].
+pub(crate) fn evolve<S: Simulator>(
+    args: &EvolveArgs,
+    rule: Option<Rule>,
+) -> Result<(), Box<dyn Error>> {
+    let mut simulator: S = create_simulator(SharedDisplay::default(), None)?;
+
+    let board = LoadedFile::read(&args.load)?.into_board(rule);
+    if let LoadStatus::Fail { reason } = simulator.load_board(board) {
+        return Err(reason.into());
+    }
+
+    let save = gol_lib::run_headless(&mut simulator, args.generations);
+    let pattern = Pattern::new(save.to_blueprint()).with_rule(save.get_rule());
+    std::fs::write(&args.out, pattern.to_rle())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A glider, which moves one cell diagonally every 4 generations.
+    const GLIDER: &str = "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+
+    /// Evolves the file with the given name & text for the amount of generations, returning the written pattern.
+    fn evolve_file(
+        name: &str,
+        text: &str,
+        generations: u64,
+        rule: Option<Rule>,
+    ) -> Result<Pattern, Box<dyn Error>> {
+        let dir = tempfile::tempdir().expect("Able to create a temp dir");
+        let load = dir.path().join(name);
+        std::fs::write(&load, text).expect("Can write file");
+        let args = EvolveArgs {
+            load,
+            generations,
+            out: dir.path().join("result.rle"),
+        };
+
+        evolve::<gol_simple::Board>(&args, rule)?;
+        let written = std::fs::read_to_string(&args.out).expect("The result is written");
+        Ok(Pattern::from_rle(&written).expect("The result is a valid pattern"))
+    }
+
+    #[test]
+    /// A glider returns to its original shape after 4 generations.
+    fn evolve_glider() {
+        let pattern = evolve_file("glider.rle", GLIDER, 4, None).unwrap();
+        assert_eq!(pattern.to_rle(), GLIDER);
+
+        let pattern = evolve_file("glider.rle", GLIDER, 1, None).unwrap();
+        assert_ne!(pattern.to_rle(), GLIDER);
+    }
+
+    #[test]
+    /// Plaintext patterns are evolved, & the given rule is used & written with the result.
+    fn evolve_plaintext_rule() {
+        // With HighLife, a blinker still oscillates.
+        let pattern = evolve_file("blinker.cells", "OOO", 2, Some(Rule::HIGHLIFE)).unwrap();
+        assert_eq!(pattern.get_rule(), Some(Rule::HIGHLIFE));
+        assert_eq!(pattern.get_blueprint().get_area().cell_count(), 3);
+    }
+
+    #[test]
+    /// The given rule replaces the rule of the file, & patterns are centred on the origin.
+    fn into_board_rule() {
+        let glider = || LoadedFile::Pattern(Pattern::from_rle(GLIDER).unwrap());
+
+        let board = glider().into_board(None);
+        assert_eq!(board.get_rule(), Rule::CONWAY);
+        assert_eq!(board.get_board_area(), gol_lib::Area::new((-1, -1), (1, 1)));
+        assert_eq!(
+            glider().into_board(Some(Rule::HIGHLIFE)).get_rule(),
+            Rule::HIGHLIFE
+        );
+
+        let highlife = LoadedFile::Save(board.clone().with_rule(Rule::HIGHLIFE));
+        assert_eq!(highlife.into_board(None).get_rule(), Rule::HIGHLIFE);
+        let overridden = LoadedFile::Save(board.with_rule(Rule::HIGHLIFE));
+        assert_eq!(
+            overridden.into_board(Some(Rule::CONWAY)).get_rule(),
+            Rule::CONWAY
+        );
+    }
+
+    #[test]
+    /// Invalid patterns, unknown extensions & unsupported rules are errors.
+    fn evolve_errors() {
+        assert!(evolve_file("broken.rle", "x = 3, y = 1\n4o!", 1, None).is_err());
+        assert!(evolve_file("glider.txt", GLIDER, 1, None).is_err());
+        assert!(evolve_file("glider.rle", GLIDER, 1, Some(Rule::new(&[0], &[]))).is_err());
+    }
+}
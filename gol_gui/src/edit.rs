@@ -0,0 +1,803 @@
+//! Contains the state used for editing the board, such as [`EditState`], [`Stroke`], [`Selection`] & [`Clipboard`].
+//! See their documentation for more information.
+
+use std::collections::{HashSet, VecDeque};
+
+use gol_lib::{
+    communication::UiPacket, persistence::SimulationBlueprint, Area, Axis, Cell, GlobalPosition,
+    Symmetry,
+};
+
+use crate::{history::Action, lang};
+
+lang! {
+    PREVIEW, "Preview";
+    DRAW, "Draw";
+    SELECT, "Select";
+    SQUARE, "Square";
+    CIRCLE, "Circle";
+    NO_SYMMETRY, "No symmetry";
+    C2, "C2 (half turn)";
+    C4, "C4 (quarter turn)";
+    D2, "D2 (two mirrors)";
+    D4, "D4 (four mirrors)";
+    MIRROR_VERTICAL, "Flip left/right";
+    MIRROR_HORIZONTAL, "Flip top/bottom";
+    CLEAR_CELLS, "Clear";
+    FILL_CELLS, "Fill";
+    INVERT_CELLS, "Invert"
+}
+
+/// How the user interacts with the board.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) enum EditState {
+    /// The board can only be moved around.
+    Preview,
+    /// Clicking on a cell toggles its state.
+    #[default]
+    Draw,
+    /// Dragging over the board selects an area of it.
+    Select,
+}
+
+impl EditState {
+    /// All the edit states, in the order they are cycled through.
+    pub(crate) const ALL: [EditState; 3] = [EditState::Preview, EditState::Draw, EditState::Select];
+
+    /// The edit state after this one when cycling through them.
+    pub(crate) fn next(self) -> Self {
+        match self {
+            EditState::Preview => EditState::Draw,
+            EditState::Draw => EditState::Select,
+            EditState::Select => EditState::Preview,
+        }
+    }
+
+    /// The name of the edit state displayed to the user.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            EditState::Preview => PREVIEW,
+            EditState::Draw => DRAW,
+            EditState::Select => SELECT,
+        }
+    }
+}
+
+/// The name of the symmetry displayed to the user.
+pub(crate) fn symmetry_label(symmetry: Symmetry) -> &'static str {
+    match symmetry {
+        Symmetry::None => NO_SYMMETRY,
+        Symmetry::C2 => C2,
+        Symmetry::C4 => C4,
+        Symmetry::D2 => D2,
+        Symmetry::D4 => D4,
+    }
+}
+
+/// The name displayed to the user for mirroring the board across the axis.
+pub(crate) fn axis_label(axis: Axis) -> &'static str {
+    match axis {
+        Axis::Vertical => MIRROR_VERTICAL,
+        Axis::Horizontal => MIRROR_HORIZONTAL,
+    }
+}
+
+/// The shape of the area covered by the brush in [`EditState::Draw`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) enum BrushShape {
+    #[default]
+    Square,
+    Circle,
+}
+
+impl BrushShape {
+    /// All the brush shapes.
+    pub(crate) const ALL: [BrushShape; 2] = [BrushShape::Square, BrushShape::Circle];
+
+    /// The name of the brush shape displayed to the user.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            BrushShape::Square => SQUARE,
+            BrushShape::Circle => CIRCLE,
+        }
+    }
+
+    /// The positions covered by a brush of this shape with the given radius, centred on the given position.
+    ///
+    /// A radius of 0 covers only the centre.
+    pub(crate) fn positions(
+        self,
+        centre: GlobalPosition,
+        radius: u32,
+    ) -> impl Iterator<Item = GlobalPosition> {
+        let radius = radius.min(i32::MAX as u32) as i32;
+        let radius_squared = radius as i64 * radius as i64;
+
+        // The brush stops at the edges of the board.
+        Area::new(
+            centre.saturating_sub((radius, radius)),
+            centre.saturating_add((radius, radius)),
+        )
+        .iterate_over()
+        .filter(move |position| match self {
+            BrushShape::Square => true,
+            BrushShape::Circle => {
+                let (x, y) = *position - centre;
+                (x as i64 * x as i64) + (y as i64 * y as i64) <= radius_squared
+            }
+        })
+    }
+}
+
+/// A brush stroke being drawn by the user.
+///
+/// Every cell the brush passes over is set to the same state, & the whole stroke is recorded as a single [`Action`].
+pub(crate) struct Stroke {
+    /// The state the cells are set to.
+    cell_state: Cell,
+    /// The changes made so far.
+    action: Action,
+    /// The positions already painted, so each cell is only changed once.
+    painted: HashSet<GlobalPosition>,
+}
+
+impl Stroke {
+    /// Starts a stroke that sets cells to the given state.
+    pub(crate) fn new(cell_state: Cell) -> Self {
+        Self {
+            cell_state,
+            action: Action::default(),
+            painted: HashSet::new(),
+        }
+    }
+
+    /// Sets the cells at the given positions, adding the packets to do so to `to_send`.
+    ///
+    /// `current` returns the current state of the cell at a position, which is used to skip cells that already have
+    /// the state of the stroke.
+    pub(crate) fn paint(
+        &mut self,
+        positions: impl IntoIterator<Item = GlobalPosition>,
+        current: impl Fn(GlobalPosition) -> Cell,
+        to_send: &mut Vec<UiPacket>,
+    ) {
+        let mut cells = Vec::new();
+        for position in positions {
+            if !self.painted.insert(position) {
+                continue;
+            }
+
+            let before = current(position);
+            if before == self.cell_state {
+                continue;
+            }
+
+            self.action.push(position, before, self.cell_state);
+            cells.push((position, self.cell_state));
+        }
+
+        if !cells.is_empty() {
+            to_send.push(UiPacket::SetMany {
+                cells: cells.into(),
+            });
+        }
+    }
+
+    /// Ends the stroke, returning the changes it made.
+    pub(crate) fn finish(self) -> Action {
+        self.action
+    }
+}
+
+/// An area of the board selected by the user.
+///
+/// Both corners of the selection are included within it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) struct Selection {
+    /// The position the selection started at.
+    start: Option<GlobalPosition>,
+    /// The position the selection currently ends at.
+    end: Option<GlobalPosition>,
+}
+
+impl Selection {
+    /// Starts a new selection at the given position, discarding the previous selection.
+    pub(crate) fn start(&mut self, position: GlobalPosition) {
+        self.start = Some(position);
+        self.end = Some(position);
+    }
+
+    /// Moves the end of the selection to the given position.
+    ///
+    /// This does nothing if a selection has not been started.
+    pub(crate) fn update(&mut self, position: GlobalPosition) {
+        if self.start.is_some() {
+            self.end = Some(position);
+        }
+    }
+
+    /// Discards the selection.
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The selected area, if there is a selection.
+    pub(crate) fn area(&self) -> Option<Area> {
+        Some(Area::new(self.start?, self.end?))
+    }
+
+    /// Moves the whole selection by the given x & y offset, stopping at the edges of the board.
+    ///
+    /// This does nothing if there is no selection.
+    pub(crate) fn nudge(&mut self, (x, y): (i32, i32)) {
+        let Some(mut area) = self.area() else {
+            return;
+        };
+        area.translate_x(x);
+        area.translate_y(y);
+        self.set_area(area);
+    }
+
+    /// Moves the far corner of the selection by the given x & y offset, without moving past the near corner.
+    ///
+    /// This does nothing if there is no selection.
+    pub(crate) fn resize(&mut self, (x, y): (i32, i32)) {
+        let Some(mut area) = self.area() else {
+            return;
+        };
+        area.modify_x(x);
+        area.modify_y(y);
+        self.set_area(area);
+    }
+
+    /// Replaces the selection with the area, starting at its near corner.
+    fn set_area(&mut self, area: Area) {
+        self.start = Some(area.get_min());
+        self.end = Some(area.get_max());
+    }
+}
+
+/// An edit applied to every cell within the selection.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum SelectionEdit {
+    /// Every cell is set dead.
+    Clear,
+    /// Every cell is set alive.
+    Fill,
+    /// Every cell is toggled.
+    Invert,
+}
+
+impl SelectionEdit {
+    /// All the selection edits, in the order they are displayed.
+    pub(crate) const ALL: [SelectionEdit; 3] = [
+        SelectionEdit::Clear,
+        SelectionEdit::Fill,
+        SelectionEdit::Invert,
+    ];
+
+    /// The text displayed for the edit.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SelectionEdit::Clear => CLEAR_CELLS,
+            SelectionEdit::Fill => FILL_CELLS,
+            SelectionEdit::Invert => INVERT_CELLS,
+        }
+    }
+
+    /// The state of a cell after the edit is applied to it.
+    pub(crate) fn apply(self, cell: Cell) -> Cell {
+        match self {
+            SelectionEdit::Clear => Cell::Dead,
+            SelectionEdit::Fill => Cell::Alive,
+            SelectionEdit::Invert => cell.invert(),
+        }
+    }
+
+    /// The changes made by applying the edit to the cells of a blueprint of the selection, which starts at `origin`.
+    ///
+    /// The current state of the cells is read from the blueprint rather than the display, so selections extending
+    /// beyond the displayed area are edited correctly. Cells the edit doesn't change are not included.
+    pub(crate) fn action(self, origin: GlobalPosition, blueprint: &SimulationBlueprint) -> Action {
+        let mut action = Action::default();
+        let cells = blueprint
+            .get_area()
+            .iterate_over()
+            .zip(blueprint.get_blueprint_data().iter().by_vals());
+
+        for (offset, alive) in cells {
+            let before = Cell::from(alive);
+            let after = self.apply(before);
+            if before != after {
+                action.push(origin + offset, before, after);
+            }
+        }
+
+        action
+    }
+}
+
+/// What a requested blueprint will be used for once the simulator sends it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum BlueprintRequest {
+    /// The blueprint is stored in the clipboard.
+    Copy,
+    /// The blueprint is saved to a file.
+    Save,
+    /// The edit is applied to the area the blueprint was taken of, which starts at `origin`.
+    Edit {
+        edit: SelectionEdit,
+        origin: GlobalPosition,
+    },
+}
+
+/// Holds a copied area of the board in memory so that it can be pasted elsewhere.
+///
+/// Copying an area requests a blueprint of it from the simulator, which is stored once it is received. Pasting waits
+/// for the next click on the board to determine where the blueprint is loaded.
+#[derive(Default)]
+pub(crate) struct Clipboard {
+    /// The copied blueprint.
+    blueprint: Option<SimulationBlueprint>,
+    /// The requested blueprints that have not been received from the simulator, in the order they were requested.
+    requests: VecDeque<BlueprintRequest>,
+    /// Whether the next click on the board pastes the copied blueprint.
+    paste_pending: bool,
+}
+
+impl Clipboard {
+    /// Requests a blueprint of the given area from the simulator.
+    pub(crate) fn request(
+        &mut self,
+        request: BlueprintRequest,
+        area: Area,
+        to_send: &mut Vec<UiPacket>,
+    ) {
+        self.requests.push_back(request);
+        to_send.push(UiPacket::SaveBlueprint { area });
+    }
+
+    /// Whether a copy has been requested but not yet received.
+    pub(crate) fn is_copying(&self) -> bool {
+        self.requests.contains(&BlueprintRequest::Copy)
+    }
+
+    /// Handles a blueprint received from the simulator.
+    ///
+    /// If the blueprint was requested to be copied it is stored in the clipboard, otherwise it is returned along with
+    /// what it was requested for. Unrequested blueprints are returned to be saved.
+    pub(crate) fn receive(
+        &mut self,
+        blueprint: SimulationBlueprint,
+    ) -> Option<(BlueprintRequest, SimulationBlueprint)> {
+        match self.requests.pop_front() {
+            Some(BlueprintRequest::Copy) => {
+                self.blueprint = Some(blueprint);
+                None
+            }
+            Some(BlueprintRequest::Save) | None => Some((BlueprintRequest::Save, blueprint)),
+            Some(request @ BlueprintRequest::Edit { .. }) => Some((request, blueprint)),
+        }
+    }
+
+    /// Whether there is a copied blueprint that can be pasted.
+    pub(crate) fn has_blueprint(&self) -> bool {
+        self.blueprint.is_some()
+    }
+
+    /// The next click on the board will paste the copied blueprint.
+    ///
+    /// This does nothing if nothing has been copied.
+    pub(crate) fn start_paste(&mut self) {
+        self.paste_pending = self.has_blueprint();
+    }
+
+    /// Stops the next click on the board from pasting.
+    pub(crate) fn cancel_paste(&mut self) {
+        self.paste_pending = false;
+    }
+
+    /// Whether the next click on the board will paste the copied blueprint.
+    pub(crate) fn is_pasting(&self) -> bool {
+        self.paste_pending
+    }
+
+    /// The positions of the alive cells the pending paste would load with its top left at the position.
+    ///
+    /// This is empty if no paste is pending.
+    pub(crate) fn preview(&self, position: GlobalPosition) -> Vec<GlobalPosition> {
+        match &self.blueprint {
+            Some(blueprint) if self.paste_pending => blueprint.alive_cells(position).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Mirrors the copied blueprint across the axis through its centre.
+    pub(crate) fn mirror(&mut self, axis: Axis) {
+        if let Some(blueprint) = &mut self.blueprint {
+            *blueprint = blueprint.mirrored(axis);
+        }
+    }
+
+    /// Rotates the copied blueprint clockwise by the given amount of quarter turns.
+    pub(crate) fn rotate(&mut self, quarter_turns: u8) {
+        if let Some(blueprint) = &mut self.blueprint {
+            *blueprint = blueprint.rotated(quarter_turns);
+        }
+    }
+
+    /// Handles a click on the board at the given position.
+    ///
+    /// If a paste is pending, the packet to load the copied blueprint with its top left at the position is returned.
+    /// The blueprint is kept so that it can be pasted again.
+    pub(crate) fn click(&mut self, position: GlobalPosition) -> Option<UiPacket> {
+        if !self.paste_pending {
+            return None;
+        }
+
+        self.paste_pending = false;
+        Some(UiPacket::LoadBlueprint {
+            load_position: position,
+            blueprint: self.blueprint.clone()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A blueprint of a 2 by 1 area with one alive cell.
+    fn blueprint() -> SimulationBlueprint {
+        SimulationBlueprint::new(1, 0, bitvec::bitbox![usize, bitvec::order::Lsb0; 1, 0])
+    }
+
+    #[test]
+    /// Cycling visits every edit state before returning to the first.
+    fn edit_state_cycles() {
+        let mut edit_state = EditState::Preview;
+        for expected in EditState::ALL.into_iter().cycle().skip(1).take(4) {
+            edit_state = edit_state.next();
+            assert_eq!(edit_state, expected);
+        }
+    }
+
+    #[test]
+    /// A radius 2 circular brush covers the positions within 2 cells of the centre.
+    fn circle_brush() {
+        let mut positions: Vec<(i32, i32)> = BrushShape::Circle
+            .positions((10, -10).into(), 2)
+            .map(|position| position - GlobalPosition::new(10, -10))
+            .collect();
+        positions.sort();
+
+        #[rustfmt::skip]
+        let expected = [
+            (-2, 0),
+            (-1, -1), (-1, 0), (-1, 1),
+            (0, -2), (0, -1), (0, 0), (0, 1), (0, 2),
+            (1, -1), (1, 0), (1, 1),
+            (2, 0),
+        ];
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    /// A square brush covers every position within its radius.
+    fn square_brush() {
+        let positions: HashSet<GlobalPosition> =
+            BrushShape::Square.positions((0, 0).into(), 2).collect();
+
+        assert_eq!(positions.len(), 25);
+        assert!(positions.contains(&(-2, -2).into()));
+        assert!(positions.contains(&(2, 2).into()));
+    }
+
+    #[test]
+    /// A brush with no radius covers only the centre.
+    fn brush_radius_zero() {
+        for shape in BrushShape::ALL {
+            let positions: Vec<GlobalPosition> = shape.positions((3, 4).into(), 0).collect();
+            assert_eq!(positions, [(3, 4).into()]);
+        }
+    }
+
+    #[test]
+    /// A brush at the edge of the board only covers the cells on the board.
+    fn brush_at_edge() {
+        let corner = GlobalPosition::new(i32::MAX, i32::MIN);
+        for shape in BrushShape::ALL {
+            let positions: Vec<GlobalPosition> = shape.positions(corner, 1).collect();
+            assert_eq!(
+                positions.len(),
+                4 - usize::from(shape == BrushShape::Circle),
+                "{shape:?}"
+            );
+            assert!(positions.contains(&corner));
+        }
+    }
+
+    #[test]
+    /// A stroke changes each cell once, skipping cells already in the stroke's state.
+    fn stroke_paints_once() {
+        let mut stroke = Stroke::new(Cell::Alive);
+        let mut to_send = Vec::new();
+        // (1, 0) is already alive.
+        let current = |position: GlobalPosition| Cell::from(position == (1, 0).into());
+
+        stroke.paint([(0, 0).into(), (1, 0).into()], current, &mut to_send);
+        stroke.paint([(0, 0).into(), (2, 0).into()], current, &mut to_send);
+
+        let expected = {
+            let mut action = Action::default();
+            action.push((0, 0).into(), Cell::Dead, Cell::Alive);
+            action.push((2, 0).into(), Cell::Dead, Cell::Alive);
+            action
+        };
+        // Each paint is sent as one packet.
+        assert_eq!(to_send.len(), 2);
+        assert_eq!(stroke.finish(), expected);
+    }
+
+    #[test]
+    /// A selection covers the area between where it started & where it was dragged to.
+    fn selection_area() {
+        let mut selection = Selection::default();
+        assert_eq!(selection.area(), None);
+
+        // Updating without starting does nothing.
+        selection.update((5, 5).into());
+        assert_eq!(selection.area(), None);
+
+        selection.start((3, 4).into());
+        assert_eq!(selection.area(), Some(Area::new((3, 4), (3, 4))));
+
+        selection.update((-1, 8).into());
+        assert_eq!(selection.area(), Some(Area::new((-1, 4), (3, 8))));
+
+        selection.clear();
+        assert_eq!(selection.area(), None);
+    }
+
+    #[test]
+    /// Nudging moves both corners of the selection, whilst resizing only moves the far corner.
+    fn selection_nudge_resize() {
+        let mut selection = Selection::default();
+        selection.nudge((1, 0));
+        selection.resize((1, 0));
+        assert_eq!(selection.area(), None);
+
+        // Dragged from the far corner to the near corner.
+        selection.start((5, 6).into());
+        selection.update((2, 3).into());
+
+        selection.nudge((1, -1));
+        assert_eq!(selection.area(), Some(Area::new((3, 2), (6, 5))));
+
+        selection.resize((2, -1));
+        assert_eq!(selection.area(), Some(Area::new((3, 2), (8, 4))));
+
+        // The far corner stops at the near corner.
+        selection.resize((-10, 0));
+        assert_eq!(selection.area(), Some(Area::new((3, 2), (3, 4))));
+
+        // The selection stops at the edge of the board without shrinking.
+        selection.start((i32::MAX - 1, 0).into());
+        selection.update((i32::MAX, 1).into());
+        selection.nudge((1, 0));
+        assert_eq!(
+            selection.area(),
+            Some(Area::new((i32::MAX - 1, 0), (i32::MAX, 1)))
+        );
+    }
+
+    #[test]
+    /// A copied blueprint is stored when received, then pasted at the next click.
+    fn copy_then_paste() {
+        let mut clipboard = Clipboard::default();
+        let mut to_send = Vec::new();
+        let area = Area::new((0, 0), (1, 0));
+
+        clipboard.request(BlueprintRequest::Copy, area, &mut to_send);
+        assert!(matches!(
+            to_send.as_slice(),
+            [UiPacket::SaveBlueprint { area: sent }] if *sent == area
+        ));
+        assert!(clipboard.is_copying());
+        assert!(!clipboard.has_blueprint());
+
+        assert!(clipboard.receive(blueprint()).is_none());
+        assert!(!clipboard.is_copying());
+        assert!(clipboard.has_blueprint());
+
+        // Clicking before pasting does nothing.
+        assert!(clipboard.click((9, 9).into()).is_none());
+
+        clipboard.start_paste();
+        assert!(clipboard.is_pasting());
+        let packet = clipboard.click((5, -2).into()).expect("Pastes");
+        assert!(matches!(
+            packet,
+            UiPacket::LoadBlueprint { load_position, blueprint: pasted }
+                if load_position == (5, -2).into() && pasted == blueprint()
+        ));
+
+        // Only the next click pastes, but the blueprint can be pasted again.
+        assert!(!clipboard.is_pasting());
+        assert!(clipboard.click((5, -2).into()).is_none());
+        clipboard.start_paste();
+        assert!(clipboard.click((0, 0).into()).is_some());
+    }
+
+    /// A clipboard pasting an L shaped blueprint, with the cells `(0, 0)`, `(0, 1)`, `(1, 1)` & `(2, 1)` alive.
+    fn pasting_l_shape() -> Clipboard {
+        let mut clipboard = Clipboard::default();
+        clipboard.request(BlueprintRequest::Copy, Area::default(), &mut Vec::new());
+        clipboard.receive(SimulationBlueprint::new(
+            2,
+            1,
+            bitvec::bitbox![usize, bitvec::order::Lsb0; 1, 0, 0, 1, 1, 1],
+        ));
+        clipboard.start_paste();
+        clipboard
+    }
+
+    #[test]
+    /// The preview shows the alive cells of the blueprint with its top left at the cursor.
+    fn paste_preview() {
+        let clipboard = pasting_l_shape();
+
+        assert_eq!(
+            clipboard.preview((10, -3).into()),
+            [
+                (10, -3).into(),
+                (10, -2).into(),
+                (11, -2).into(),
+                (12, -2).into()
+            ]
+        );
+        assert!(Clipboard::default().preview((0, 0).into()).is_empty());
+    }
+
+    #[test]
+    /// Rotating & mirroring the blueprint changes both the preview & what is pasted.
+    fn paste_preview_transformed() {
+        let mut clipboard = pasting_l_shape();
+
+        clipboard.rotate(1);
+        let rotated: Vec<GlobalPosition> = vec![
+            (10, -3).into(),
+            (11, -3).into(),
+            (10, -2).into(),
+            (10, -1).into(),
+        ];
+        assert_eq!(clipboard.preview((10, -3).into()), rotated);
+        assert!(matches!(
+            clipboard.click((10, -3).into()),
+            Some(UiPacket::LoadBlueprint { load_position, blueprint })
+                if blueprint.alive_cells(load_position).eq(rotated.iter().copied())
+        ));
+
+        clipboard.rotate(3);
+        clipboard.mirror(Axis::Vertical);
+        clipboard.start_paste();
+        assert_eq!(
+            clipboard.preview((0, 0).into()),
+            [(2, 0).into(), (0, 1).into(), (1, 1).into(), (2, 1).into()]
+        );
+    }
+
+    #[test]
+    /// The preview near the edge of the board only contains the cells on the board.
+    fn paste_preview_at_edge() {
+        let clipboard = pasting_l_shape();
+
+        let preview = clipboard.preview((i32::MAX - 1, i32::MAX).into());
+        let full = clipboard.preview((0, 0).into());
+        assert!(!preview.is_empty());
+        assert!(preview.len() < full.len(), "{preview:?}");
+    }
+
+    #[test]
+    /// Pasting is not possible before anything has been copied.
+    fn paste_without_copy() {
+        let mut clipboard = Clipboard::default();
+
+        clipboard.start_paste();
+        assert!(!clipboard.is_pasting());
+        assert!(clipboard.click((0, 0).into()).is_none());
+    }
+
+    #[test]
+    /// A pending paste can be cancelled.
+    fn cancel_paste() {
+        let mut clipboard = Clipboard::default();
+        clipboard.request(BlueprintRequest::Copy, Area::default(), &mut Vec::new());
+        clipboard.receive(blueprint());
+
+        clipboard.start_paste();
+        clipboard.cancel_paste();
+        assert!(clipboard.click((0, 0).into()).is_none());
+    }
+
+    #[test]
+    /// Blueprints are routed to the request they were received for, in order.
+    fn requests_in_order() {
+        let mut clipboard = Clipboard::default();
+        let mut to_send = Vec::new();
+
+        clipboard.request(BlueprintRequest::Save, Area::default(), &mut to_send);
+        clipboard.request(BlueprintRequest::Copy, Area::default(), &mut to_send);
+        assert_eq!(to_send.len(), 2);
+
+        // The first blueprint is to be saved, so is returned.
+        assert!(matches!(
+            clipboard.receive(blueprint()),
+            Some((BlueprintRequest::Save, _))
+        ));
+        assert!(!clipboard.has_blueprint());
+
+        // The second is copied.
+        assert!(clipboard.receive(blueprint()).is_none());
+        assert!(clipboard.has_blueprint());
+
+        // Unrequested blueprints are returned to be saved.
+        assert!(clipboard.receive(blueprint()).is_some());
+    }
+
+    #[test]
+    /// Each selection edit changes only the cells whose state differs, relative to the selection origin.
+    fn selection_edits() {
+        // A 2 by 2 area with the top left & bottom right cells alive.
+        let blueprint = SimulationBlueprint::new(
+            1,
+            1,
+            bitvec::bitbox![usize, bitvec::order::Lsb0; 1, 0, 0, 1],
+        );
+        let origin = GlobalPosition::new(5, -3);
+        let changes = |edit: SelectionEdit, positions: &[(i32, i32)], before, after| {
+            let mut expected = Action::default();
+            for &position in positions {
+                expected.push(origin + position, before, after);
+            }
+            assert_eq!(edit.action(origin, &blueprint), expected, "{edit:?}");
+        };
+
+        changes(
+            SelectionEdit::Clear,
+            &[(0, 0), (1, 1)],
+            Cell::Alive,
+            Cell::Dead,
+        );
+        changes(
+            SelectionEdit::Fill,
+            &[(1, 0), (0, 1)],
+            Cell::Dead,
+            Cell::Alive,
+        );
+
+        let mut expected = Action::default();
+        expected.push(origin, Cell::Alive, Cell::Dead);
+        expected.push(origin + (1, 0), Cell::Dead, Cell::Alive);
+        expected.push(origin + (0, 1), Cell::Dead, Cell::Alive);
+        expected.push(origin + (1, 1), Cell::Alive, Cell::Dead);
+        assert_eq!(SelectionEdit::Invert.action(origin, &blueprint), expected);
+    }
+
+    #[test]
+    /// Blueprints requested for an edit are returned with the edit to apply.
+    fn edit_request() {
+        let mut clipboard = Clipboard::default();
+        let request = BlueprintRequest::Edit {
+            edit: SelectionEdit::Fill,
+            origin: (1, 1).into(),
+        };
+        clipboard.request(request, Area::default(), &mut Vec::new());
+
+        assert!(matches!(
+            clipboard.receive(blueprint()),
+            Some((received, _)) if received == request
+        ));
+        assert!(!clipboard.has_blueprint());
+    }
+}
@@ -1,21 +1,51 @@
-use std::path::Path;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+};
 
 use egui_file_dialog::FileDialog;
-use gol_lib::persistence::preview::PreviewParseError;
 use gol_lib::{
     communication::UiPacket,
-    persistence::{self, preview::SavePreview},
+    persistence::{
+        iter_blueprint_previews, iter_previews,
+        preview::{BlueprintPreview, PreviewParseError, SavePreview},
+        quarantine, SaveCancel,
+    },
 };
 
-use crate::{lang, settings::Settings};
+use crate::{lang, settings::Settings, toast::Toasts};
 
 lang! {
     WINDOW, "Save Board";
     NAME, "Name:";
     DESCRIPTION, "Description:";
-    BUTTON, "Save"
+    BUTTON, "Save";
+    CANCEL, "Cancel";
+    LOAD_RELOAD, "Reload";
+    LOAD_SELECT_ALL, "Select All";
+    LOAD_DELETE_SELECTED, "Delete Selected";
+    LOAD_DELETE_FAIL, "Unable to delete ";
+    LOAD_DELETE_BY_TAG, "Delete by tag";
+    LOAD_DELETE_TAG_CONFIRM, "Permanently delete every file tagged ";
+    LOAD_DELETE, "Delete";
+    LOAD_BUTTON, "Load";
+    LOAD_NO_FILES, "No files found.";
+    LOAD_UNTAGGED, "Untagged";
+    LOAD_FILTER, "Filter:";
+    LOAD_QUARANTINE, "Move to quarantine";
+    LOAD_QUARANTINE_SUCCESS, "Moved invalid file to: ";
+    LOAD_QUARANTINE_FAIL, "Unable to move invalid file "
 }
 
+/// The id of the modal confirming the deletion of every file with a tag.
+const DELETE_TAG_MODAL: &str = "Delete_Tag_Modal";
+
+/// The outcome of moving an invalid file into quarantine on a separate thread.
+/// Either the new path of the file, or the original path & the error that occurred.
+type QuarantineResult = Result<PathBuf, (Box<Path>, std::io::Error)>;
+
 #[derive(Default)]
 pub(crate) struct Save {
     pub(crate) show: bool,
@@ -24,6 +54,8 @@ pub(crate) struct Save {
     pub(crate) save_description: String,
 
     pub(crate) save_requested: bool,
+    /// Cancels the requested save before it is written.
+    pub(crate) cancel: SaveCancel,
 
     file_dialog: FileDialog,
 }
@@ -56,12 +88,20 @@ impl Save {
                 // Only allow one save to be requested at a time
                 if ui.button(BUTTON).clicked() && !self.save_requested {
                     self.save_requested = true;
+                    self.cancel = SaveCancel::default();
                     to_send.push(UiPacket::SaveBoard);
                 }
 
                 // Show a spinner whilst waiting for save
                 if self.save_requested {
-                    ui.spinner();
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        let cancel =
+                            ui.add_enabled(!self.cancel.is_cancelled(), egui::Button::new(CANCEL));
+                        if cancel.clicked() {
+                            self.cancel.cancel();
+                        }
+                    });
                 }
 
                 // Constrain the file picker to the save directory
@@ -89,33 +129,783 @@ impl Save {
     }
 }
 
-pub(crate) struct Load {
+/// A file that can be listed & loaded by a [`LoadMenu`].
+pub(crate) trait Loadable: Sized + Send + 'static {
+    /// Whether the menu groups the files by their tags.
+    const CATEGORISED: bool = false;
+
+    /// Finds and parses the previews recursively from the given directory, parsing each as it is iterated over.
+    fn iter_previews(location: PathBuf) -> impl Iterator<Item = Result<Self, PreviewParseError>>;
+
+    /// The name of the file. This is not the filename.
+    fn name(&self) -> &str;
+    /// The description of the file.
+    fn description(&self) -> &str;
+    /// The path to the file.
+    fn path(&self) -> &Path;
+    /// The tags the file is categorised by.
+    fn tags(&self) -> &[Box<str>] {
+        &[]
+    }
+
+    /// The text the menu filter is matched against.
+    fn search_text(&self) -> String {
+        let mut text = format!("{}\n{}", self.name(), self.description());
+        for tag in self.tags() {
+            text.push('\n');
+            text.push_str(tag);
+        }
+        text
+    }
+}
+
+/// Whether the preview is shown by the given filter.
+///
+/// The filter is matched case-insensitively against any part of the searchable text. An empty filter matches all
+/// previews.
+fn matches_filter(preview: &impl Loadable, filter: &str) -> bool {
+    preview
+        .search_text()
+        .to_lowercase()
+        .contains(&filter.to_lowercase())
+}
+
+impl Loadable for SavePreview {
+    fn iter_previews(location: PathBuf) -> impl Iterator<Item = Result<Self, PreviewParseError>> {
+        iter_previews(location)
+    }
+
+    fn name(&self) -> &str {
+        self.get_save_name()
+    }
+
+    fn description(&self) -> &str {
+        self.get_save_description()
+    }
+
+    fn path(&self) -> &Path {
+        self.get_save_path()
+    }
+}
+
+impl Loadable for BlueprintPreview {
+    const CATEGORISED: bool = true;
+
+    fn iter_previews(location: PathBuf) -> impl Iterator<Item = Result<Self, PreviewParseError>> {
+        iter_blueprint_previews(location)
+    }
+
+    fn name(&self) -> &str {
+        self.get_blueprint_name()
+    }
+
+    fn description(&self) -> &str {
+        self.get_blueprint_description()
+    }
+
+    fn path(&self) -> &Path {
+        self.get_blueprint_path()
+    }
+
+    fn tags(&self) -> &[Box<str>] {
+        self.get_tags()
+    }
+}
+
+/// The group a file is listed under in a categorised [`LoadMenu`].
+///
+/// Untagged files are ordered after all tags.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+enum Category<'a> {
+    Tag(&'a str),
+    Untagged,
+}
+
+/// Whether the preview has the given tag.
+fn has_tag(preview: &impl Loadable, tag: &str) -> bool {
+    preview
+        .tags()
+        .iter()
+        .any(|preview_tag| &**preview_tag == tag)
+}
+
+/// Deletes the files, reporting any that could not be deleted.
+fn delete_files(paths: impl IntoIterator<Item = Box<Path>>, toasts: &mut Toasts) {
+    for path in paths {
+        if let Err(err) = std::fs::remove_file(&path) {
+            toasts.error(format!("{LOAD_DELETE_FAIL}{}: {err}", path.display()));
+        }
+    }
+}
+
+/// Groups the previews by their tags. A preview is listed under every tag it has.
+fn categorise<'a, P: Loadable>(
+    previews: impl IntoIterator<Item = &'a P>,
+) -> BTreeMap<Category<'a>, Vec<&'a P>> {
+    let mut categories: BTreeMap<Category, Vec<&P>> = BTreeMap::new();
+
+    for preview in previews {
+        if preview.tags().is_empty() {
+            categories
+                .entry(Category::Untagged)
+                .or_default()
+                .push(preview);
+        }
+
+        for tag in preview.tags() {
+            let bucket = categories.entry(Category::Tag(tag)).or_default();
+            // A file with a duplicated tag is only listed once.
+            if !bucket
+                .last()
+                .is_some_and(|last| std::ptr::eq(*last, preview))
+            {
+                bucket.push(preview);
+            }
+        }
+    }
+
+    categories
+}
+
+/// The menu for listing, deleting & loading files.
+pub(crate) struct LoadMenu<P> {
     pub(crate) show: bool,
+    /// The title of the menu window.
+    title: &'static str,
 
-    saves: Option<Box<[Result<SavePreview, PreviewParseError>]>>,
+    /// The parsed files, or [`None`] if they have not been parsed yet.
+    previews: Option<Vec<Result<P, PreviewParseError>>>,
+    /// Receives the files as they are parsed, if they are being parsed.
+    loading: Option<mpsc::Receiver<Result<P, PreviewParseError>>>,
+    /// The paths of the selected files.
+    selected: HashSet<Box<Path>>,
+    /// Only files matching this filter are displayed.
+    filter: String,
+    /// The tag chosen for deletion & the paths of the files with it, whilst the user is asked to confirm deleting them.
+    /// These are kept apart from the selection, so the selection is unchanged if the deletion is cancelled.
+    delete_tag: Option<(Box<str>, HashSet<Box<Path>>)>,
+
+    /// Sender for the quarantining threads to report their results.
+    quarantine_sender: mpsc::Sender<QuarantineResult>,
+    /// Receives the results of the quarantining threads.
+    quarantine_receiver: mpsc::Receiver<QuarantineResult>,
 }
 
-impl Default for Load {
-    fn default() -> Self {
+impl<P: Loadable> LoadMenu<P> {
+    /// Creates a new hidden menu with the given title.
+    pub(crate) fn new(title: &'static str) -> Self {
+        let (quarantine_sender, quarantine_receiver) = mpsc::channel();
         Self {
             show: false,
-            saves: None,
+            title,
+            previews: None,
+            loading: None,
+            selected: HashSet::new(),
+            filter: String::new(),
+            delete_tag: None,
+            quarantine_sender,
+            quarantine_receiver,
         }
     }
-}
 
-impl Load {
-    pub(crate) fn load_saves(&mut self, save_root: &Path) {
-        todo!()
+    /// Parses the files within the given directory, replacing any previously parsed files.
+    ///
+    /// The files are parsed on a separate thread, with each being added to the menu by [`Self::poll_previews`] as it
+    /// is parsed.
+    pub(crate) fn reload(&mut self, location: &Path) {
+        let (sender, receiver) = mpsc::channel();
+        self.previews = Some(Vec::new());
+
+        let thread_location = location.to_path_buf();
+        let spawn = thread::Builder::new()
+            .name("Preview_Loader".into())
+            .spawn(move || {
+                for preview in P::iter_previews(thread_location) {
+                    // The menu being reloaded again or closed is not an error.
+                    if sender.send(preview).is_err() {
+                        return;
+                    }
+                }
+            });
+
+        match spawn {
+            Ok(_) => self.loading = Some(receiver),
+            // The files can still be parsed, at the cost of freezing the ui while doing so.
+            Err(_) => {
+                self.previews = Some(P::iter_previews(location.to_path_buf()).collect());
+                self.loading = None;
+                self.retain_selected();
+            }
+        }
+    }
+
+    /// Deselects any files that are no longer listed.
+    fn retain_selected(&mut self) {
+        let previews = self.previews.as_deref().unwrap_or_default();
+        self.selected.retain(|path| {
+            previews
+                .iter()
+                .flatten()
+                .any(|preview| preview.path() == &**path)
+        });
+    }
+
+    /// The paths of every file that has the given tag, including files hidden by the filter.
+    fn tagged_paths(&self, tag: &str) -> HashSet<Box<Path>> {
+        let previews = self.previews.iter().flatten().flatten();
+        previews
+            .filter(|preview| has_tag(*preview, tag))
+            .map(|preview| preview.path().into())
+            .collect()
+    }
+
+    /// Removes the selected files that are shown by the filter from the selection, returning their paths.
+    /// Selected files hidden by the filter stay selected.
+    fn take_visible_selection(&mut self) -> HashSet<Box<Path>> {
+        let visible: HashSet<&Path> = self
+            .previews
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|preview| matches_filter(*preview, &self.filter))
+            .map(|preview| preview.path())
+            .collect();
+
+        let (shown, hidden) = self
+            .selected
+            .drain()
+            .partition(|path| visible.contains(&**path));
+        self.selected = hidden;
+        shown
     }
 
-    pub(crate) fn draw(&mut self, ctx: &egui::Context) {
-        egui::Window::new("load")
-            .open(&mut self.show)
+    /// Deletes the selected files that are shown by the filter, reporting any that could not be deleted.
+    /// Selected files hidden by the filter stay selected.
+    ///
+    /// The files must be reloaded afterwards, as the previews of the deleted files are kept.
+    fn delete_selected_previews(&mut self, toasts: &mut Toasts) {
+        let shown = self.take_visible_selection();
+        delete_files(shown, toasts);
+    }
+
+    /// Asks the user to confirm deleting the files with the tag chosen for deletion, if one has been chosen.
+    ///
+    /// Returns whether the files were deleted.
+    fn delete_tag_modal(&mut self, ctx: &egui::Context, toasts: &mut Toasts) -> bool {
+        let Some((tag, paths)) = &self.delete_tag else {
+            return false;
+        };
+
+        let mut delete = false;
+        let mut cancel = false;
+        let modal = egui::Modal::new(egui::Id::new(DELETE_TAG_MODAL)).show(ctx, |ui| {
+            ui.label(format!(
+                "{LOAD_DELETE_TAG_CONFIRM}\"{tag}\"? ({})",
+                paths.len()
+            ));
+            ui.horizontal(|ui| {
+                delete = ui.button(LOAD_DELETE).clicked();
+                cancel = ui.button(CANCEL).clicked();
+            });
+        });
+
+        if !delete && !cancel && !modal.should_close() {
+            return false;
+        }
+        let Some((_, paths)) = self.delete_tag.take() else {
+            return false;
+        };
+        if delete {
+            // The deleted files can no longer be selected.
+            self.selected.retain(|path| !paths.contains(path));
+            delete_files(paths, toasts);
+        }
+        delete
+    }
+
+    /// Moves the invalid file into quarantine within the given location on a separate thread.
+    ///
+    /// The outcome is displayed, & the files reloaded, once it is received by [`Self::draw`].
+    fn quarantine(&self, location: &Path, path: Box<Path>, toasts: &mut Toasts) {
+        let location = location.to_path_buf();
+        let display = path.display().to_string();
+        let sender = self.quarantine_sender.clone();
+        let spawn = thread::Builder::new()
+            .name("Quarantine".into())
+            .spawn(move || {
+                let result = quarantine(&location, &path).map_err(|err| (path, err));
+                // The ui closing before the file is moved is not an error.
+                let _ = sender.send(result);
+            });
+
+        if let Err(err) = spawn {
+            toasts.error(format!("{LOAD_QUARANTINE_FAIL}{display}: {err}"));
+        }
+    }
+
+    /// Adds any files that have been parsed since the last poll to the menu.
+    fn poll_previews(&mut self) {
+        let Some(loading) = &self.loading else {
+            return;
+        };
+        let previews = self.previews.get_or_insert_with(Vec::new);
+
+        loop {
+            match loading.try_recv() {
+                Ok(preview) => previews.push(preview),
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => break,
+            }
+        }
+
+        // Files that no longer exist cannot be selected, which is only known once every file is parsed.
+        self.loading = None;
+        self.retain_selected();
+    }
+
+    /// Draws the menu if it is open.
+    ///
+    /// The returned value is the path of the file the user requested to load, if any.
+    pub(crate) fn draw(
+        &mut self,
+        ctx: &egui::Context,
+        location: &Path,
+        toasts: &mut Toasts,
+    ) -> Option<Box<Path>> {
+        if !self.show {
+            return None;
+        }
+
+        if self.previews.is_none() {
+            self.reload(location);
+        }
+        self.poll_previews();
+
+        let mut reload = false;
+        while let Ok(result) = self.quarantine_receiver.try_recv() {
+            match result {
+                Ok(path) => toasts.info(format!("{LOAD_QUARANTINE_SUCCESS}{}", path.display())),
+                Err((path, err)) => {
+                    toasts.error(format!("{LOAD_QUARANTINE_FAIL}{}: {err}", path.display()))
+                }
+            }
+            reload = true;
+        }
+
+        let mut to_load = None;
+        let mut to_quarantine = None;
+        let mut delete_selected = false;
+        let mut delete_tag = None;
+        let mut show = self.show;
+
+        egui::Window::new(self.title)
+            .open(&mut show)
             .show(ctx, |ui| {
-                if ui.button("Load saves").clicked() {
-                    // todo!()
+                ui.horizontal(|ui| {
+                    ui.label(LOAD_FILTER);
+                    ui.text_edit_singleline(&mut self.filter);
+                });
+
+                let filter = self.filter.as_str();
+                let visible = || {
+                    self.previews
+                        .iter()
+                        .flatten()
+                        .flatten()
+                        .filter(|preview| matches_filter(*preview, filter))
+                };
+
+                ui.horizontal(|ui| {
+                    if ui.button(LOAD_RELOAD).clicked() {
+                        reload = true;
+                    }
+
+                    // Only the displayed files are selected.
+                    if ui.button(LOAD_SELECT_ALL).clicked() {
+                        self.selected
+                            .extend(visible().map(|preview| preview.path().into()));
+                    }
+
+                    if ui.button(LOAD_DELETE_SELECTED).clicked() {
+                        delete_selected = true;
+                    }
+
+                    if P::CATEGORISED {
+                        let tags: BTreeSet<&str> = self
+                            .previews
+                            .iter()
+                            .flatten()
+                            .flatten()
+                            .flat_map(|preview| preview.tags().iter().map(|tag| &**tag))
+                            .collect();
+                        egui::ComboBox::from_id_salt(LOAD_DELETE_BY_TAG)
+                            .selected_text(LOAD_DELETE_BY_TAG)
+                            .show_ui(ui, |ui| {
+                                for tag in tags {
+                                    if ui.selectable_label(false, tag).clicked() {
+                                        delete_tag = Some(Box::<str>::from(tag));
+                                    }
+                                }
+                            });
+                    }
+                });
+
+                ui.separator();
+
+                // Files are listed as they are parsed, with a spinner showing more may follow.
+                if self.loading.is_some() {
+                    ui.spinner();
+                }
+
+                let previews = self.previews.as_deref().unwrap_or_default();
+                if previews.is_empty() {
+                    if self.loading.is_none() {
+                        ui.label(LOAD_NO_FILES);
+                    }
+                    return;
                 }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let valid = visible();
+
+                    if P::CATEGORISED {
+                        for (category, previews) in categorise(valid) {
+                            let heading = match category {
+                                Category::Tag(tag) => tag,
+                                Category::Untagged => LOAD_UNTAGGED,
+                            };
+
+                            egui::CollapsingHeader::new(heading)
+                                .id_salt(&category)
+                                .default_open(true)
+                                .show(ui, |ui| {
+                                    for preview in previews {
+                                        draw_preview(ui, preview, &mut self.selected, &mut to_load);
+                                    }
+                                });
+                        }
+                    } else {
+                        for preview in valid {
+                            draw_preview(ui, preview, &mut self.selected, &mut to_load);
+                        }
+                    }
+
+                    for error in previews.iter().filter_map(|preview| preview.as_ref().err()) {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(ui.visuals().error_fg_color, error.to_string())
+                                .on_hover_text(
+                                    error
+                                        .path()
+                                        .map(|path| path.display().to_string())
+                                        .unwrap_or_default(),
+                                );
+
+                            // Errors whilst searching for files may not be caused by a file.
+                            if let Some(path) = error.path() {
+                                if ui.small_button(LOAD_QUARANTINE).clicked() {
+                                    to_quarantine = Some(Box::<Path>::from(path));
+                                }
+                            }
+                        });
+                    }
+                });
             });
+
+        self.show = show;
+        if delete_selected {
+            self.delete_selected_previews(toasts);
+            reload = true;
+        }
+        if let Some(tag) = delete_tag {
+            let paths = self.tagged_paths(&tag);
+            self.delete_tag = Some((tag, paths));
+        }
+        reload |= self.delete_tag_modal(ctx, toasts);
+        if let Some(path) = to_quarantine {
+            self.quarantine(location, path, toasts);
+        }
+        if reload {
+            self.reload(location);
+        }
+
+        to_load
+    }
+}
+
+/// Draws a single file within a [`LoadMenu`].
+fn draw_preview<P: Loadable>(
+    ui: &mut egui::Ui,
+    preview: &P,
+    selected: &mut HashSet<Box<Path>>,
+    to_load: &mut Option<Box<Path>>,
+) {
+    ui.horizontal(|ui| {
+        let mut is_selected = selected.contains(preview.path());
+        if ui.checkbox(&mut is_selected, "").changed() {
+            if is_selected {
+                selected.insert(preview.path().into());
+            } else {
+                selected.remove(preview.path());
+            }
+        }
+
+        ui.vertical(|ui| {
+            ui.strong(preview.name());
+            if !preview.description().is_empty() {
+                ui.weak(preview.description());
+            }
+        });
+
+        if ui.button(LOAD_BUTTON).clicked() {
+            *to_load = Some(preview.path().into());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A preview for testing that does not exist on disk.
+    struct TestPreview {
+        name: &'static str,
+        description: &'static str,
+        tags: Box<[Box<str>]>,
+    }
+
+    impl TestPreview {
+        fn new(name: &'static str, tags: &[&str]) -> Self {
+            Self {
+                name,
+                description: "",
+                tags: tags.iter().map(|tag| (*tag).into()).collect(),
+            }
+        }
+
+        fn with_description(mut self, description: &'static str) -> Self {
+            self.description = description;
+            self
+        }
+    }
+
+    impl Loadable for TestPreview {
+        const CATEGORISED: bool = true;
+
+        fn iter_previews(
+            _location: PathBuf,
+        ) -> impl Iterator<Item = Result<Self, PreviewParseError>> {
+            std::iter::empty()
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            self.description
+        }
+
+        fn path(&self) -> &Path {
+            Path::new(self.name)
+        }
+
+        fn tags(&self) -> &[Box<str>] {
+            &self.tags
+        }
+    }
+
+    /// Converts the categories into the names of the previews within them.
+    fn names<'a>(
+        categories: BTreeMap<Category<'a>, Vec<&TestPreview>>,
+    ) -> Vec<(Category<'a>, Vec<&'static str>)> {
+        categories
+            .into_iter()
+            .map(|(category, previews)| {
+                (
+                    category,
+                    previews.into_iter().map(|preview| preview.name).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    /// Previews are grouped under each of their tags, with untagged previews last.
+    fn categorise_by_tags() {
+        let previews = [
+            TestPreview::new("glider", &["Spaceship", "Small"]),
+            TestPreview::new("blinker", &["Oscillator", "Small"]),
+            TestPreview::new("lwss", &["Spaceship"]),
+            TestPreview::new("random", &[]),
+        ];
+
+        assert_eq!(
+            names(categorise(&previews)),
+            [
+                (Category::Tag("Oscillator"), vec!["blinker"]),
+                (Category::Tag("Small"), vec!["glider", "blinker"]),
+                (Category::Tag("Spaceship"), vec!["glider", "lwss"]),
+                (Category::Untagged, vec!["random"]),
+            ]
+        );
+    }
+
+    #[test]
+    /// A preview with a duplicated tag is only listed once under it.
+    fn categorise_duplicate_tag() {
+        let previews = [TestPreview::new("glider", &["Small", "Small"])];
+
+        assert_eq!(
+            names(categorise(&previews)),
+            [(Category::Tag("Small"), vec!["glider"])]
+        );
+    }
+
+    #[test]
+    /// No previews produce no categories.
+    fn categorise_empty() {
+        let previews: [TestPreview; 0] = [];
+        assert!(categorise(&previews).is_empty());
+    }
+
+    #[test]
+    /// The filter matches the name, description & tags regardless of case.
+    fn filter_matches_fields() {
+        let glider =
+            TestPreview::new("Glider", &["Spaceship"]).with_description("Moves diagonally");
+
+        assert!(matches_filter(&glider, "glid"));
+        assert!(matches_filter(&glider, "DIAGONAL"));
+        assert!(matches_filter(&glider, "spaceSHIP"));
+        assert!(!matches_filter(&glider, "oscillator"));
+    }
+
+    #[test]
+    /// An empty filter matches every preview.
+    fn filter_empty() {
+        let previews = [
+            TestPreview::new("Glider", &["Spaceship"]),
+            TestPreview::new("", &[]),
+        ];
+
+        assert!(previews.iter().all(|preview| matches_filter(preview, "")));
+    }
+
+    #[test]
+    /// Only the previews containing the filter are matched.
+    fn filter_previews() {
+        let previews = [
+            TestPreview::new("Glider", &["Spaceship"]),
+            TestPreview::new("Blinker", &["Oscillator"]).with_description("Period 2"),
+            TestPreview::new("Toad", &["Oscillator"]),
+            TestPreview::new("Block", &[]).with_description("Still life"),
+        ];
+
+        let matched: Vec<_> = previews
+            .iter()
+            .filter(|preview| matches_filter(*preview, "oscillator"))
+            .map(|preview| preview.name)
+            .collect();
+        assert_eq!(matched, ["Blinker", "Toad"]);
+
+        let matched: Vec<_> = previews
+            .iter()
+            .filter(|preview| matches_filter(*preview, "li"))
+            .map(|preview| preview.name)
+            .collect();
+        assert_eq!(matched, ["Glider", "Blinker", "Block"]);
+    }
+
+    #[test]
+    /// Files are added to the menu as they are received, before every file has been parsed.
+    fn previews_incremental() {
+        let mut menu = LoadMenu::<TestPreview>::new("Test");
+        let (sender, receiver) = mpsc::channel();
+        menu.previews = Some(Vec::new());
+        menu.loading = Some(receiver);
+        menu.selected.insert(Path::new("glider").into());
+        menu.selected.insert(Path::new("deleted").into());
+
+        sender.send(Ok(TestPreview::new("glider", &[]))).unwrap();
+        menu.poll_previews();
+        assert_eq!(menu.previews.as_ref().unwrap().len(), 1);
+        assert!(menu.loading.is_some());
+
+        sender.send(Ok(TestPreview::new("blinker", &[]))).unwrap();
+        menu.poll_previews();
+        assert_eq!(menu.previews.as_ref().unwrap().len(), 2);
+        // Selections are kept until every file has been parsed.
+        assert_eq!(menu.selected.len(), 2);
+
+        drop(sender);
+        menu.poll_previews();
+        assert!(menu.loading.is_none());
+        assert_eq!(
+            menu.selected,
+            HashSet::from([Box::<Path>::from(Path::new("glider"))])
+        );
+    }
+
+    #[test]
+    /// Only the selected files shown by the filter are taken for deletion, with hidden files staying selected.
+    fn visible_selection() {
+        let mut menu = LoadMenu::<TestPreview>::new("Test");
+        menu.previews = Some(
+            [
+                TestPreview::new("glider", &[]),
+                TestPreview::new("blinker", &[]),
+                TestPreview::new("toad", &[]),
+            ]
+            .into_iter()
+            .map(Ok)
+            .collect(),
+        );
+        let paths = |names: &[&str]| -> HashSet<Box<Path>> {
+            names.iter().map(|name| Path::new(name).into()).collect()
+        };
+        menu.selected = paths(&["glider", "blinker"]);
+        menu.filter = "glid".into();
+
+        assert_eq!(menu.take_visible_selection(), paths(&["glider"]));
+        assert_eq!(menu.selected, paths(&["blinker"]));
+    }
+
+    #[test]
+    /// Choosing a tag finds exactly the previews with that tag, regardless of the filter, without changing the
+    /// selection.
+    fn find_by_tag() {
+        let previews = [
+            TestPreview::new("glider", &["Spaceship", "Small"]),
+            TestPreview::new("blinker", &["Oscillator", "Small"]),
+            TestPreview::new("lwss", &["Spaceship"]),
+            TestPreview::new("random", &[]),
+            TestPreview::new("spaceship", &["spaceship"]),
+        ];
+        let tagged = |tag| -> Vec<&str> {
+            previews
+                .iter()
+                .filter(|preview| has_tag(*preview, tag))
+                .map(|preview| preview.name)
+                .collect()
+        };
+
+        assert_eq!(tagged("Spaceship"), ["glider", "lwss"]);
+        assert_eq!(tagged("Small"), ["glider", "blinker"]);
+        // Tags are matched exactly, rather than as a filter.
+        assert_eq!(tagged("Space"), [] as [&str; 0]);
+
+        let mut menu = LoadMenu::<TestPreview>::new("Test");
+        menu.previews = Some(previews.into_iter().map(Ok).collect());
+        menu.filter = "blinker".into();
+        menu.selected.insert(Path::new("random").into());
+
+        assert_eq!(
+            menu.tagged_paths("Spaceship"),
+            HashSet::from(["glider", "lwss"].map(|name| Box::<Path>::from(Path::new(name))))
+        );
+        assert_eq!(
+            menu.selected,
+            HashSet::from([Box::<Path>::from(Path::new("random"))])
+        );
     }
 }
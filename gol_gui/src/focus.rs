@@ -0,0 +1,66 @@
+//! Contains [`FocusPause`].
+//! See its documentation for more information.
+
+/// What should happen to the simulation after the window's focus changed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum FocusAction {
+    /// The running simulation should be stopped.
+    Pause,
+    /// The simulation stopped by [`FocusAction::Pause`] should be started again.
+    Resume,
+}
+
+/// Tracks whether the simulation was stopped due to the window losing focus, so that it is only started again if it
+/// was running beforehand.
+#[derive(Default, Debug)]
+pub(crate) struct FocusPause {
+    /// Whether the simulation has been stopped due to the window losing focus.
+    paused: bool,
+}
+
+impl FocusPause {
+    /// Updates the tracked state with whether the window is focused & whether the simulation is running, returning
+    /// the action to take, if any.
+    pub(crate) fn update(&mut self, focused: bool, running: bool) -> Option<FocusAction> {
+        match (focused, self.paused) {
+            // A stopped simulation is left stopped, so that it is not started on regaining focus.
+            (false, false) if running => {
+                self.paused = true;
+                Some(FocusAction::Pause)
+            }
+            (true, true) => {
+                self.paused = false;
+                Some(FocusAction::Resume)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// A running simulation is paused on losing focus, & resumed on regaining it.
+    fn pause_resume() {
+        let mut focus = FocusPause::default();
+        assert_eq!(focus.update(true, true), None);
+
+        assert_eq!(focus.update(false, true), Some(FocusAction::Pause));
+        // Remaining unfocused does nothing further.
+        assert_eq!(focus.update(false, false), None);
+
+        assert_eq!(focus.update(true, false), Some(FocusAction::Resume));
+        assert_eq!(focus.update(true, true), None);
+    }
+
+    #[test]
+    /// A stopped simulation is not started when the window regains focus.
+    fn stopped_stays_stopped() {
+        let mut focus = FocusPause::default();
+
+        assert_eq!(focus.update(false, false), None);
+        assert_eq!(focus.update(true, false), None);
+    }
+}
@@ -0,0 +1,357 @@
+//! Contains [`History`].
+//! See its documentation for more information.
+
+use std::collections::VecDeque;
+
+use gol_lib::{communication::UiPacket, Cell, GlobalPosition};
+
+/// A change of a single cell made by the user.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct CellChange {
+    position: GlobalPosition,
+    /// The state of the cell before the change.
+    before: Cell,
+    /// The state of the cell after the change.
+    after: Cell,
+}
+
+/// A group of changes that are undone & redone together, such as a single brush stroke.
+#[derive(Default, PartialEq, Debug)]
+pub(crate) struct Action {
+    changes: Vec<CellChange>,
+}
+
+impl Action {
+    /// Records that the cell at the given position was changed.
+    pub(crate) fn push(&mut self, position: GlobalPosition, before: Cell, after: Cell) {
+        self.changes.push(CellChange {
+            position,
+            before,
+            after,
+        });
+    }
+
+    /// Whether no changes have been recorded.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The approximate amount of memory used by the action, in bytes.
+    pub(crate) fn size(&self) -> usize {
+        size_of::<Action>() + self.changes.capacity() * size_of::<CellChange>()
+    }
+
+    /// The packet that applies the changes of the action.
+    pub(crate) fn packet(&self) -> UiPacket {
+        UiPacket::SetMany {
+            cells: self
+                .changes
+                .iter()
+                .map(|change| (change.position, change.after))
+                .collect(),
+        }
+    }
+}
+
+/// The maximum size of the [`History`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct HistoryLimit {
+    /// The maximum amount of actions that can be undone & redone.
+    pub(crate) actions: usize,
+    /// The maximum approximate amount of memory used by the actions, in bytes.
+    pub(crate) bytes: usize,
+}
+
+impl Default for HistoryLimit {
+    fn default() -> Self {
+        Self {
+            actions: 1000,
+            bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// The edits the user has made to the board, which can be undone & redone.
+///
+/// Once the [`HistoryLimit`] is exceeded the oldest actions are discarded, so they can no longer be undone. The last
+/// remaining action is always kept, so the latest edit can be undone even if it alone exceeds the limit.
+#[derive(Default)]
+pub(crate) struct History {
+    /// The actions that can be undone, with the most recent at the end.
+    undo: VecDeque<Action>,
+    /// The actions that have been undone, with the most recently undone at the end.
+    redo: VecDeque<Action>,
+    /// The maximum size of the history.
+    limit: HistoryLimit,
+    /// The approximate amount of memory used by the actions, in bytes.
+    bytes: usize,
+}
+
+impl History {
+    /// Records a new action. Any undone actions can no longer be redone.
+    ///
+    /// Empty actions are ignored.
+    pub(crate) fn push(&mut self, action: Action) {
+        if action.is_empty() {
+            return;
+        }
+
+        for undone in self.redo.drain(..) {
+            self.bytes -= undone.size();
+        }
+        self.bytes += action.size();
+        self.undo.push_back(action);
+        self.trim();
+    }
+
+    /// Sets the maximum size of the history, discarding the oldest actions if it is exceeded.
+    pub(crate) fn set_limit(&mut self, limit: HistoryLimit) {
+        if self.limit != limit {
+            self.limit = limit;
+            self.trim();
+        }
+    }
+
+    /// The amount of actions stored, & the approximate amount of memory they use in bytes.
+    #[cfg(any(test, debug_assertions))]
+    pub(crate) fn usage(&self) -> (usize, usize) {
+        (self.undo.len() + self.redo.len(), self.bytes)
+    }
+
+    /// Discards actions until the history is within its limit.
+    ///
+    /// The oldest undoable actions are discarded first. Undone actions are only discarded once there are no undoable
+    /// actions left, starting with the one furthest from being redone, so the remaining actions can still be undone &
+    /// redone in order. The last remaining action is never discarded.
+    fn trim(&mut self) {
+        while self.undo.len() + self.redo.len() > self.limit.actions.max(1)
+            || (self.bytes > self.limit.bytes && self.undo.len() + self.redo.len() > 1)
+        {
+            let Some(action) = self.undo.pop_front().or_else(|| self.redo.pop_front()) else {
+                break;
+            };
+            self.bytes -= action.size();
+        }
+    }
+
+    /// Whether there is an action that can be undone.
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is an action that can be redone.
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the most recent action, adding the packets to do so to `to_send`.
+    pub(crate) fn undo(&mut self, to_send: &mut Vec<UiPacket>) {
+        let Some(action) = self.undo.pop_back() else {
+            return;
+        };
+
+        // Changes are reverted in reverse order in case a cell was changed more than once.
+        to_send.push(UiPacket::SetMany {
+            cells: action
+                .changes
+                .iter()
+                .rev()
+                .map(|change| (change.position, change.before))
+                .collect(),
+        });
+        self.redo.push_back(action);
+    }
+
+    /// Reapplies the most recently undone action, adding the packets to do so to `to_send`.
+    pub(crate) fn redo(&mut self, to_send: &mut Vec<UiPacket>) {
+        let Some(action) = self.redo.pop_back() else {
+            return;
+        };
+
+        to_send.push(action.packet());
+        self.undo.push_back(action);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Converts the set packets into the positions & states they set.
+    fn sets(packets: &[UiPacket]) -> Vec<(GlobalPosition, Cell)> {
+        packets
+            .iter()
+            .flat_map(|packet| match packet {
+                UiPacket::SetMany { cells } => cells.iter().copied(),
+                _ => panic!("Only set packets are sent"),
+            })
+            .collect()
+    }
+
+    /// An action that sets the given positions alive from dead.
+    fn action(positions: &[(i32, i32)]) -> Action {
+        let mut action = Action::default();
+        for position in positions {
+            action.push((*position).into(), Cell::Dead, Cell::Alive);
+        }
+        action
+    }
+
+    #[test]
+    /// Undoing reverts every change of an action, & redoing reapplies them.
+    fn undo_redo() {
+        let mut history = History::default();
+        assert!(!history.can_undo());
+        assert!(!history.can_redo());
+
+        history.push(action(&[(0, 0), (1, 0)]));
+        assert!(history.can_undo());
+
+        let mut to_send = Vec::new();
+        history.undo(&mut to_send);
+        assert_eq!(
+            sets(&to_send),
+            [((1, 0).into(), Cell::Dead), ((0, 0).into(), Cell::Dead)]
+        );
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+
+        let mut to_send = Vec::new();
+        history.redo(&mut to_send);
+        assert_eq!(
+            sets(&to_send),
+            [((0, 0).into(), Cell::Alive), ((1, 0).into(), Cell::Alive)]
+        );
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    /// Actions are undone from the most recent.
+    fn undo_order() {
+        let mut history = History::default();
+        history.push(action(&[(0, 0)]));
+        history.push(action(&[(5, 5)]));
+
+        let mut to_send = Vec::new();
+        history.undo(&mut to_send);
+        assert_eq!(sets(&to_send), [((5, 5).into(), Cell::Dead)]);
+    }
+
+    #[test]
+    /// A new action discards the undone actions.
+    fn push_clears_redo() {
+        let mut history = History::default();
+        history.push(action(&[(0, 0)]));
+        history.undo(&mut Vec::new());
+        assert!(history.can_redo());
+
+        history.push(action(&[(1, 1)]));
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    /// Empty actions are not recorded.
+    fn empty_action() {
+        let mut history = History::default();
+        history.push(Action::default());
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    /// Pushing beyond the limit on actions discards the oldest action first.
+    fn evicts_oldest() {
+        let mut history = History::default();
+        history.set_limit(HistoryLimit {
+            actions: 2,
+            ..Default::default()
+        });
+        history.push(action(&[(0, 0)]));
+        history.push(action(&[(1, 1)]));
+        history.push(action(&[(2, 2)]));
+        assert_eq!(history.usage().0, 2);
+
+        let mut to_send = Vec::new();
+        history.undo(&mut to_send);
+        history.undo(&mut to_send);
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+        assert_eq!(
+            sets(&to_send),
+            [((2, 2).into(), Cell::Dead), ((1, 1).into(), Cell::Dead)]
+        );
+    }
+
+    #[test]
+    /// The memory used by the actions is kept within the limit, & tracked as actions are added & removed.
+    fn evicts_by_memory() {
+        let large = action(&[(0, 0), (1, 0), (2, 0), (3, 0)]);
+        let small = action(&[(5, 5)]);
+        let (large_size, small_size) = (large.size(), small.size());
+
+        let mut history = History::default();
+        history.push(large);
+        history.push(small);
+        assert_eq!(history.usage(), (2, large_size + small_size));
+
+        history.set_limit(HistoryLimit {
+            bytes: large_size + small_size - 1,
+            ..Default::default()
+        });
+        assert_eq!(history.usage(), (1, small_size));
+
+        let mut to_send = Vec::new();
+        history.undo(&mut to_send);
+        assert_eq!(sets(&to_send), [((5, 5).into(), Cell::Dead)]);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    /// An action larger than the memory limit is kept until a newer action is pushed, so it can still be undone.
+    fn keeps_newest() {
+        let large = action(&[(0, 0), (1, 0), (2, 0), (3, 0)]);
+        let small = action(&[(5, 5)]);
+        let small_size = small.size();
+
+        let mut history = History::default();
+        history.set_limit(HistoryLimit {
+            bytes: small_size,
+            ..Default::default()
+        });
+        history.push(small);
+        history.push(large);
+        assert_eq!(history.usage().0, 1);
+
+        let mut to_send = Vec::new();
+        history.undo(&mut to_send);
+        assert_eq!(sets(&to_send).len(), 4);
+        assert!(!history.can_undo());
+
+        history.push(action(&[(5, 5)]));
+        assert_eq!(history.usage(), (1, small_size));
+    }
+
+    #[test]
+    /// Undone actions are only discarded once no undoable actions remain, from the furthest from being redone.
+    fn evicts_redo_in_order() {
+        let mut history = History::default();
+        history.push(action(&[(0, 0)]));
+        history.push(action(&[(1, 1)]));
+        history.push(action(&[(2, 2)]));
+        history.undo(&mut Vec::new());
+        history.undo(&mut Vec::new());
+
+        history.set_limit(HistoryLimit {
+            actions: 1,
+            ..Default::default()
+        });
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+
+        // The first undone action is the one furthest from being redone, so only the next one remains.
+        let mut to_send = Vec::new();
+        history.redo(&mut to_send);
+        assert_eq!(sets(&to_send), [((1, 1).into(), Cell::Alive)]);
+        assert!(!history.can_redo());
+    }
+}
@@ -0,0 +1,253 @@
+//! Contains [`BlueprintImport`].
+//! See its documentation for more information.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+};
+
+use egui_file_dialog::FileDialog;
+use gol_lib::persistence::{pattern::Pattern, plaintext::parse_plaintext, BlueprintBuilder};
+
+use crate::{lang, toast::Toasts};
+
+lang! {
+    IMPORT_THREAD_ERROR, "Unable to create thread for importing the patterns.";
+    IMPORT_NO_FILES, "No RLE or plaintext patterns were found.";
+    IMPORT_SUCCESS, "Imported ";
+    IMPORT_PATTERNS, " patterns.";
+    IMPORT_FAIL, "Unable to import ";
+    IMPORT_FAIL_FILES, " files: "
+}
+
+/// The outcome of importing every pattern within a directory.
+#[derive(Debug, Default)]
+struct ImportSummary {
+    /// The amount of patterns saved as blueprints.
+    imported: usize,
+    /// The files that could not be imported, along with why.
+    failed: Vec<(PathBuf, String)>,
+}
+
+/// Imports a directory of pattern files as blueprints, such as a collection downloaded from a website.
+///
+/// The directory is chosen with a file dialog, then every RLE (`.rle`) & plaintext (`.cells`) pattern within it is
+/// saved as a blueprint on a separate thread. Each blueprint is named after the file it was imported from.
+#[derive(Default)]
+pub(crate) struct BlueprintImport {
+    /// The dialog for choosing the directory to import, whilst it is open.
+    dialog: Option<FileDialog>,
+    /// Receives the outcome of the importing thread, if an import is in progress.
+    import: Option<mpsc::Receiver<ImportSummary>>,
+}
+
+impl BlueprintImport {
+    /// Opens the dialog for choosing the directory to import.
+    pub(crate) fn open(&mut self) {
+        let mut dialog = FileDialog::new();
+        dialog.pick_directory();
+        self.dialog = Some(dialog);
+    }
+
+    /// Whether patterns are being imported.
+    pub(crate) fn is_importing(&self) -> bool {
+        self.import.is_some()
+    }
+
+    /// Draws the dialog if it is open, importing the patterns within the chosen directory into the blueprint location.
+    pub(crate) fn draw(
+        &mut self,
+        ctx: &egui::Context,
+        blueprint_location: &Path,
+        toasts: &mut Toasts,
+    ) {
+        let Some(dialog) = &mut self.dialog else {
+            return;
+        };
+
+        dialog.update(ctx);
+        let Some(directory) = dialog.take_picked() else {
+            return;
+        };
+        self.dialog = None;
+
+        let (sender, receiver) = mpsc::channel();
+        let blueprint_location = blueprint_location.to_path_buf();
+        let spawn = thread::Builder::new()
+            .name("Pattern_Import".into())
+            .spawn(move || {
+                let summary = import_directory(&directory, &blueprint_location);
+                // The ui closing before importing finishes is not an error.
+                let _ = sender.send(summary);
+            });
+
+        match spawn {
+            Ok(_) => self.import = Some(receiver),
+            Err(err) => toasts.error(format!("{IMPORT_THREAD_ERROR} {err}")),
+        }
+    }
+
+    /// Displays the outcome of a finished import, returning true if any blueprints were imported.
+    pub(crate) fn poll(&mut self, toasts: &mut Toasts) -> bool {
+        let Some(import) = &self.import else {
+            return false;
+        };
+
+        let summary = match import.try_recv() {
+            Ok(summary) => summary,
+            Err(mpsc::TryRecvError::Empty) => return false,
+            // The importing thread ended without sending its outcome.
+            Err(mpsc::TryRecvError::Disconnected) => ImportSummary::default(),
+        };
+        self.import = None;
+
+        // The reasons are only logged, as a toast listing each would be too long.
+        for (path, err) in &summary.failed {
+            log::warn!("{IMPORT_FAIL}{}: {err}", path.display());
+        }
+        match summary.failed.is_empty() {
+            true => toasts.info(summary_text(&summary)),
+            false => toasts.error(summary_text(&summary)),
+        }
+        summary.imported > 0
+    }
+}
+
+/// The text of the single toast summarising an import, giving the amount of imported patterns & the names of the
+/// files that could not be imported.
+fn summary_text(summary: &ImportSummary) -> String {
+    if summary.imported == 0 && summary.failed.is_empty() {
+        return IMPORT_NO_FILES.to_owned();
+    }
+
+    let mut text = format!("{IMPORT_SUCCESS}{}{IMPORT_PATTERNS}", summary.imported);
+    if !summary.failed.is_empty() {
+        let names: Vec<_> = summary
+            .failed
+            .iter()
+            .map(|(path, _)| {
+                path.file_name()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy()
+            })
+            .collect();
+        text += &format!(
+            " {IMPORT_FAIL}{}{IMPORT_FAIL_FILES}{}",
+            summary.failed.len(),
+            names.join(", ")
+        );
+    }
+    text
+}
+
+/// Saves every RLE & plaintext pattern directly within the source directory as a blueprint within the destination
+/// directory, named after its file. Other files are skipped.
+fn import_directory(source: &Path, destination: &Path) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+
+    let entries = match std::fs::read_dir(source) {
+        Ok(entries) => entries,
+        Err(err) => {
+            summary.failed.push((source.to_path_buf(), err.to_string()));
+            return summary;
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    // Imported in a consistent order, so failures are reported in the order they are listed.
+    paths.sort();
+
+    for path in paths {
+        let imported = match import_file(&path) {
+            Some(Ok(pattern)) => {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy();
+                BlueprintBuilder::from_pattern(pattern)
+                    .name(name)
+                    .save(destination)
+                    .map_err(|err| err.to_string())
+            }
+            Some(Err(err)) => Err(err),
+            None => continue,
+        };
+
+        match imported {
+            Ok(_) => summary.imported += 1,
+            Err(err) => summary.failed.push((path, err)),
+        }
+    }
+
+    summary
+}
+
+/// Parses the pattern file, depending on its extension. Returns [`None`] if the file is not a pattern.
+fn import_file(path: &Path) -> Option<Result<Pattern, String>> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    if !["rle", "cells"].contains(&extension.as_str()) {
+        return None;
+    }
+
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) => return Some(Err(err.to_string())),
+    };
+    Some(match extension.as_str() {
+        "rle" => Pattern::from_rle(&text).map_err(|err| err.to_string()),
+        _ => parse_plaintext(&text)
+            .map(Pattern::new)
+            .map_err(|err| err.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use gol_lib::persistence::presets;
+
+    use super::*;
+
+    #[test]
+    /// Valid patterns are imported, & invalid ones are reported, whilst other files are skipped.
+    fn import_summary() {
+        let source = tempfile::tempdir().expect("Able to create a temp dir");
+        let destination = tempfile::tempdir().expect("Able to create a temp dir");
+        for (name, text) in [
+            ("glider.rle", presets::GLIDER),
+            ("blinker.cells", "!Name: Blinker\nOOO"),
+            ("broken.rle", "x = 3, y = 1\n4o!"),
+            ("notes.txt", "Not a pattern"),
+        ] {
+            std::fs::write(source.path().join(name), text).expect("Can write file");
+        }
+
+        let summary = import_directory(source.path(), destination.path());
+
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].0, source.path().join("broken.rle"));
+
+        let saved = std::fs::read_dir(destination.path()).expect("Can read dir");
+        assert_eq!(saved.count(), 2);
+    }
+
+    #[test]
+    /// The summary gives the amount imported & the names of the files that failed.
+    fn summary_texts() {
+        let mut summary = ImportSummary::default();
+        assert_eq!(summary_text(&summary), IMPORT_NO_FILES);
+
+        summary.imported = 2;
+        assert_eq!(summary_text(&summary), "Imported 2 patterns.");
+
+        summary.failed = vec![
+            (PathBuf::from("dir/broken.rle"), "Invalid".into()),
+            (PathBuf::from("dir/empty.cells"), "Empty".into()),
+        ];
+        assert_eq!(
+            summary_text(&summary),
+            "Imported 2 patterns. Unable to import 2 files: broken.rle, empty.cells"
+        );
+    }
+}
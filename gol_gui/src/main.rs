@@ -1,39 +1,91 @@
-use std::{error::Error, path::PathBuf, sync::LazyLock, thread, time::Duration};
+use std::{error::Error, path::PathBuf, sync::LazyLock};
 
 use app::MyApp;
 use app_dirs2::{get_app_dir, get_app_root, AppDataType, AppInfo};
-use args::Args;
+use args::{Args, Backend, Command};
 use clap::Parser;
-use gol_lib::{communication::UiPacket, SharedDisplay, Simulator};
+use cli::{create_simulator, LoadedFile};
+use gol_lib::{communication::UiPacket, SharedDisplay};
 
 mod app;
 mod args;
+mod autosave;
+mod cli;
+mod edit;
 mod file_management;
+mod focus;
+mod history;
+mod import;
+mod pattern;
+mod recording;
+mod rewind;
 mod settings;
+mod speed;
+mod statistics;
+mod tags;
+mod toast;
+mod unsaved;
 
 fn main() -> Result<(), Box<dyn Error>> {
     env_logger::init();
 
     let args = Args::parse();
 
+    if let Some(Command::Evolve(evolve)) = &args.command {
+        return match args.backend {
+            Backend::Simple => cli::evolve::<gol_simple::Board>(evolve, args.rule),
+            Backend::Dense => cli::evolve::<gol_dense::Board>(evolve, args.rule),
+        }
+        .inspect_err(|err| eprintln!("{}{err}", error_text::EVOLVE_FAIL));
+    }
+
     let mut config_path = args.config_path.unwrap_or(USER_CONFIG_PATH.clone());
-    std::fs::create_dir_all(config_path.as_path())?;
+    std::fs::create_dir_all(config_path.as_path())
+        .inspect_err(|_| eprintln!("{}", error_text::DIRECTORY_CREATION))?;
     config_path.push("config_data.json");
 
+    // The file is read before anything starts, so that any problems with it are reported straight away.
+    let load_packet = match &args.load {
+        Some(path) => {
+            let file = LoadedFile::read(path)
+                .inspect_err(|err| eprintln!("{}{err}", error_text::LOAD_FAIL))?;
+            Some(file.into_packet(args.rule))
+        }
+        None => None,
+    };
+
     let shared_display = SharedDisplay::default();
-    let board = gol_simple::Board::new(shared_display.clone());
 
     let ((ui_sender, ui_receiver), (simulator_sender, simulator_receiver)) =
         gol_lib::create_channels();
 
     // Start Simulator.
-    let simulator = gol_lib::start_simulator(board, ui_receiver, simulator_sender)
-        .inspect_err(|_| eprintln!("{}", error_text::CREATE_SIMULATION_THREAD))?;
+    let simulator = match args.backend {
+        Backend::Simple => gol_lib::start_simulator(
+            create_simulator::<gol_simple::Board>(shared_display.clone(), args.rule)?,
+            ui_receiver,
+            simulator_sender,
+        ),
+        Backend::Dense => gol_lib::start_simulator(
+            create_simulator::<gol_dense::Board>(shared_display.clone(), args.rule)?,
+            ui_receiver,
+            simulator_sender,
+        ),
+    }
+    .inspect_err(|_| eprintln!("{}", error_text::CREATE_SIMULATION_THREAD))?;
+
+    if let Some(packet) = load_packet {
+        ui_sender
+            .send(packet)
+            .inspect_err(|_| eprintln!("{}", error_text::SEND_LOAD))?;
+    }
 
     // Start UI.
     let native_options = eframe::NativeOptions {
         // Takes path to file, not dir.
         persistence_path: Some(config_path),
+        // The size & position of the window are restored when relaunching.
+        persist_window: true,
         ..Default::default()
     };
 
@@ -53,9 +105,8 @@ fn main() -> Result<(), Box<dyn Error>> {
     .inspect_err(|_| eprintln!("{}", error_text::UI_INIT))?;
 
     // Command similator thread to terminate after the ui is closed.
-    if ui_sender.send(UiPacket::Terminate).is_err() {
-        log::error!("{}", error_text::COMMAND_SIM_THREAD_TERM)
-    };
+    // The ui may have already terminated it to autosave the final state of the board, in which case this fails.
+    let _ = ui_sender.send(UiPacket::Terminate);
 
     // The retuned error does not implement the Error trait so panic instead.
     simulator.join().expect(error_text::SIM_THREAD_TERM);
@@ -88,6 +139,17 @@ static DEFAULT_SAVE_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
     get_app_dir(AppDataType::UserData, &APP_INFO, "saves").unwrap()
 });
 
+/// The path to where autosaves will be stored.
+///
+/// This is separate from the save location, so autosaves are not listed alongside the saves made by the user.
+///
+/// On Linux:
+/// `/home/<user>/.local/share/game_of_life/autosaves`
+static AUTOSAVE_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+    // The only way this can error is if the APP_INFO has empty fields.
+    get_app_dir(AppDataType::UserData, &APP_INFO, "autosaves").unwrap()
+});
+
 /// The path to where blueprints will be stored.
 ///
 /// On Linux:
@@ -97,6 +159,15 @@ static DEFAULT_BLUEPRINT_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
     get_app_dir(AppDataType::UserData, &APP_INFO, "blueprints").unwrap()
 });
 
+/// The path to where GIF recordings will be stored.
+///
+/// On Linux:
+/// `/home/<user>/.local/share/game_of_life/recordings`
+static DEFAULT_RECORDING_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+    // The only way this can error is if the APP_INFO has empty fields.
+    get_app_dir(AppDataType::UserData, &APP_INFO, "recordings").unwrap()
+});
+
 /// Creates a public constant string with the name as the name of the constant
 /// and the text as the value of the string.
 ///
@@ -119,14 +190,16 @@ mod error_text {
         CREATE_SIMULATION_THREAD, "Unable to create thread for board simulation at OS level.";
         UI_INIT, "Unable to initialise UI graphical context.";
         SIM_THREAD_TERM, "Simulator thread was unable to gracefully terminate";
-        COMMAND_SIM_THREAD_TERM, "Unable to command similator thread to terminate.";
-        DIRECTORY_CREATION, "Unable to created required directory for this program to run."
+        DIRECTORY_CREATION, "Unable to created required directory for this program to run.";
+        UNSUPPORTED_RULE, "The chosen backend does not support the rule: ";
+        LOAD_FAIL, "Unable to load the file given at startup: ";
+        UNKNOWN_EXTENSION, "Only .save, .rle & .cells files can be loaded: ";
+        SEND_LOAD, "Unable to send the file given at startup to the simulation.";
+        EVOLVE_FAIL, "Unable to evolve the pattern: "
     }
 }
 
 mod lang {
-    use crate::lang;
-
     lang! {
         APP_NAME, "Game Of Life";
         UNRECOVERABLE_ERROR_HEADER, "Encountered Unrecoverable Error";
@@ -134,6 +207,91 @@ mod lang {
         ERROR_ADVICE, "Please restart the application.";
         SEND_ERROR, "Unable to send packet to simulation.";
         RECEIVE_ERROR, "Unable to receive data from simulation.";
-        SHARED_DISPLAY_POISIONED, "Unable to read board from simulation."
+        SIMULATOR_PANICKED, "The simulation stopped unexpectedly: ";
+        SHARED_DISPLAY_POISIONED, "Unable to read board from simulation.";
+        SAVE_SUCCESS, "Saved board to: ";
+        SAVE_FAIL, "Unable to save board: ";
+        SAVE_CANCELLED, "Cancelled saving the board.";
+        AUTOSAVE_NAME, "Autosave";
+        AUTOSAVE_FAIL, "Unable to autosave board: ";
+        AUTOSAVE_REMOVE_FAIL, "Unable to remove the autosaves: ";
+        RESTORE_AUTOSAVE, "Restore the most recent autosave?";
+        RESTORE, "Restore";
+        ORTHOGONAL, "orthogonal";
+        DIAGONAL, "diagonal";
+        OBLIQUE, "oblique";
+        UNSAVED_CHANGES, "The board has unsaved changes.";
+        SAVE_CHANGES, "Save";
+        DISCARD_CHANGES, "Discard";
+        SAVE_LOCATION_UNWRITABLE, "Boards can't be saved to the save location: ";
+        RECORD_GIF, "Record GIF";
+        RECORD_STATISTICS, "Record statistics";
+        RECORD_STATISTICS_HOVER, "Records the population of each generation, exporting them as CSV when stopped.";
+        GOTO, "Go to:";
+        GOTO_BUTTON, "Go";
+        CENTRE_ORIGIN, "Centre on origin";
+        FRAME_ALL, "Frame all";
+        BOOKMARKS, "Bookmarks";
+        BOOKMARK_NAME, "Name:";
+        ADD_BOOKMARK, "Bookmark current view";
+        STEP_BUTTON, "Step";
+        RUN_UNTIL, "Run until:";
+        RUN_UNTIL_BUTTON, "Run";
+        CLEAR_TARGET, "Clear target";
+        RANDOMISE, "Randomise";
+        DENSITY, "Density:";
+        BLUEPRINTS, "Blueprints";
+        PRESETS, "Presets";
+        LOAD_BOARD_WINDOW, "Load Board";
+        LOAD_BLUEPRINT_WINDOW, "Load Blueprint";
+        LOAD_FAIL, "Unable to load file: ";
+        BOARD_LOAD_FAIL, "The simulation was unable to load the board: ";
+        BLUEPRINT_LOAD_FAIL, "The simulation was unable to load the blueprint: ";
+        ROTATE_FAIL, "The board was not rotated: ";
+        STOPPED_AT, "The simulation was stopped at generation ";
+        CELL_LIMIT_POPULATION, " as the board grew to ";
+        CELL_LIMIT_CELLS, " cells.";
+        STOPPED_CONDITION, " as the stop condition was met.";
+        GENERATION_UNAVAILABLE, "Generation ";
+        GENERATION_UNAVAILABLE_EARLIEST, " is no longer kept. The earliest generation that can be rewound to is ";
+        GENERATION_UNAVAILABLE_END, ".";
+        DISPLAY_TOO_LARGE, "The board cannot be shown, as the ";
+        DISPLAY_TOO_LARGE_LIMIT, " displayed cells exceed the limit of ";
+        DISPLAY_TOO_LARGE_ZOOM, ". Zoom in to show the board.";
+        SPACESHIP, "The board became a ";
+        SPACESHIP_MOVING, " spaceship, moving by ";
+        SPACESHIP_EVERY, " every ";
+        SPACESHIP_GENERATION, " generations, at generation ";
+        SPACESHIP_END, ".";
+        STILL_LIFE, "The board became a still life at generation ";
+        STILL_LIFE_END, ".";
+        PERIODIC, "The board became periodic with a period of ";
+        PERIODIC_GENERATION, " at generation ";
+        PERIODIC_END, ".";
+        PASTE_PATTERN, "Paste Pattern";
+        PASTED_PATTERN, "Pasted pattern: ";
+        PATTERN_RULE, "The pattern is for the rule ";
+        PATTERN_RULE_KEPT, ", but the board keeps its own rule.";
+        IMPORT_FOLDER, "Import Folder";
+        ALIVE_CELLS, " alive";
+        COPY, "Copy";
+        PASTE, "Paste";
+        CANCEL_PASTE, "Cancel paste";
+        CLEAR_SELECTION, "Clear selection";
+        BLUEPRINT_NAME, "Blueprint name:";
+        BLUEPRINT_TAGS, "Tags:";
+        SAVE_BLUEPRINT, "Save blueprint";
+        COPY_SUCCESS, "Copied selection.";
+        NOTHING_COPIED, "Nothing has been copied to paste.";
+        BLUEPRINT_SAVE_SUCCESS, "Saved blueprint to: ";
+        BLUEPRINT_SAVE_FAIL, "Unable to save blueprint: ";
+        UNDO, "Undo";
+        REDO, "Redo";
+        CLEAR_BOARD, "Clear";
+        ROTATE_CLOCKWISE, "Rotate clockwise";
+        ROTATE_ANTICLOCKWISE, "Rotate anticlockwise";
+        CLEAR_BOARD_CONFIRM, "Kill every cell on the board? This cannot be undone.";
+        CANCEL, "Cancel";
+        GENERATION, "Generation: "
     }
 }
@@ -0,0 +1,201 @@
+//! Contains [`PasteDialog`].
+//! See its documentation for more information.
+
+use std::sync::Arc;
+
+use egui::{
+    text::{LayoutJob, TextFormat},
+    Galley, TextStyle,
+};
+use gol_lib::persistence::{
+    parse_error::PatternParseError,
+    pattern::Pattern,
+    plaintext::{parse_plaintext, PlaintextError},
+    rle::RleError,
+};
+
+use crate::lang;
+
+lang! {
+    WINDOW, "Paste Pattern";
+    HINT, "Paste a pattern in the RLE or plaintext format.";
+    BUTTON, "Paste"
+}
+
+/// The reason pasted text could not be parsed as a pattern.
+#[derive(Debug, PartialEq)]
+pub(crate) enum PatternError {
+    /// The text has an RLE header, but is not a valid RLE pattern.
+    Rle(PatternParseError<RleError>),
+    /// The text is not a valid plaintext pattern.
+    Plaintext(PatternParseError<PlaintextError>),
+}
+
+impl PatternError {
+    /// The line of the text the error occurred on, counted from 1.
+    fn line(&self) -> usize {
+        match self {
+            PatternError::Rle(err) => err.line,
+            PatternError::Plaintext(err) => err.line,
+        }
+    }
+}
+
+impl std::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternError::Rle(err) => err.fmt(f),
+            PatternError::Plaintext(err) => err.fmt(f),
+        }
+    }
+}
+
+/// Parses the text as an RLE pattern, or as a plaintext pattern if it is not RLE.
+///
+/// Text without a valid RLE header is not considered to be RLE, so the plaintext error is returned for it.
+pub(crate) fn parse_pattern(text: &str) -> Result<Pattern, PatternError> {
+    match Pattern::from_rle(text) {
+        Ok(pattern) => Ok(pattern),
+        Err(PatternParseError {
+            reason: RleError::MissingHeader | RleError::InvalidHeader(_),
+            ..
+        }) => parse_plaintext(text)
+            .map(Pattern::new)
+            .map_err(PatternError::Plaintext),
+        Err(err) => Err(PatternError::Rle(err)),
+    }
+}
+
+/// A dialog for pasting the text of a pattern, such as one copied from a website, onto the board.
+#[derive(Default)]
+pub(crate) struct PasteDialog {
+    pub(crate) show: bool,
+    /// The text of the pattern entered by the user.
+    text: String,
+    /// Why the entered text could not be parsed, if it couldn't.
+    error: Option<PatternError>,
+}
+
+impl PasteDialog {
+    /// Draws the dialog, returning the parsed pattern once the user submits a valid one.
+    pub(crate) fn draw(&mut self, ctx: &egui::Context) -> Option<Pattern> {
+        let mut pasted = None;
+
+        let error_line = self.error.as_ref().map(PatternError::line);
+        let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+            highlight_line(ui, text, wrap_width, error_line)
+        };
+
+        egui::Window::new(WINDOW)
+            .open(&mut self.show)
+            .show(ctx, |ui| {
+                ui.label(HINT);
+                let text = ui.add(
+                    egui::TextEdit::multiline(&mut self.text)
+                        .code_editor()
+                        .desired_rows(10)
+                        .layouter(&mut layouter),
+                );
+                // The error is for the text before it was edited.
+                if text.changed() {
+                    self.error = None;
+                }
+
+                if ui.button(BUTTON).clicked() {
+                    match parse_pattern(&self.text) {
+                        Ok(pattern) => pasted = Some(pattern),
+                        Err(err) => self.error = Some(err),
+                    }
+                }
+
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                }
+            });
+
+        if pasted.is_some() {
+            self.show = false;
+            self.text.clear();
+        }
+        pasted
+    }
+}
+
+/// The opacity of the highlight behind the line of the pattern that could not be parsed.
+const ERROR_LINE_OPACITY: f32 = 0.3;
+
+/// Lays out the text of the pattern, with the background of the given line, counted from 1, highlighted.
+fn highlight_line(ui: &egui::Ui, text: &str, wrap_width: f32, line: Option<usize>) -> Arc<Galley> {
+    let font_id = TextStyle::Monospace.resolve(ui.style());
+    let colour = ui.visuals().text_color();
+
+    let mut job = LayoutJob::default();
+    for (index, characters) in text.split_inclusive('\n').enumerate() {
+        let background = match line == Some(index + 1) {
+            true => ui
+                .visuals()
+                .error_fg_color
+                .gamma_multiply(ERROR_LINE_OPACITY),
+            false => egui::Color32::TRANSPARENT,
+        };
+        job.append(
+            characters,
+            0.0,
+            TextFormat {
+                font_id: font_id.clone(),
+                color: colour,
+                background,
+                ..Default::default()
+            },
+        );
+    }
+    job.wrap.max_width = wrap_width;
+
+    ui.fonts(|fonts| fonts.layout_job(job))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Text with an RLE header is parsed as RLE, & any other text as plaintext.
+    fn dispatch() {
+        let rle = parse_pattern("#N Glider\nx = 3, y = 3\nbob$2bo$3o!").expect("Valid RLE");
+        let plaintext = parse_pattern("!Name: Glider\n.O.\n..O\nOOO").expect("Valid plaintext");
+        assert_eq!(rle.get_name(), Some("Glider"));
+        assert_eq!(rle.get_blueprint(), plaintext.get_blueprint());
+    }
+
+    #[test]
+    /// Errors are reported for the format the text was parsed as.
+    fn dispatch_errors() {
+        let err = parse_pattern("x = 3, y = 1\n4o!").unwrap_err();
+        assert!(matches!(
+            &err,
+            PatternError::Rle(PatternParseError {
+                reason: RleError::OutOfBounds,
+                ..
+            })
+        ));
+        assert_eq!(err.line(), 2);
+
+        let err = parse_pattern(".O.\nbob").unwrap_err();
+        assert!(matches!(
+            &err,
+            PatternError::Plaintext(PatternParseError {
+                reason: PlaintextError::UnexpectedCharacter('b'),
+                ..
+            })
+        ));
+        assert_eq!(err.line(), 2);
+
+        assert!(matches!(
+            parse_pattern(""),
+            Err(PatternError::Plaintext(PatternParseError {
+                reason: PlaintextError::Empty,
+                ..
+            }))
+        ));
+    }
+}
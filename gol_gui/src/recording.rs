@@ -0,0 +1,224 @@
+//! Contains [`GifRecorder`].
+//! See its documentation for more information.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use egui::Color32;
+use gol_lib::BoardDisplay;
+
+use crate::{lang, toast::Toasts};
+
+lang! {
+    ENCODE_THREAD_ERROR, "Unable to create thread for encoding the recording.";
+    NO_FRAMES, "No generations were recorded.";
+    ENCODE_STARTED, "Encoding recording...";
+    ENCODE_SUCCESS, "Saved recording to: ";
+    ENCODE_FAIL, "Unable to save recording: ";
+    FRAME_LIMIT, "Recording reached its frame limit; later generations will not be recorded. The GIF will be up to \
+        roughly ";
+    FRAME_LIMIT_SIZE, " MB."
+}
+
+/// The maximum amount of frames held by a single recording.
+/// Once this is reached further generations are not recorded.
+pub(crate) const MAX_FRAMES: usize = 500;
+/// The width & height, in pixels, of each cell within the recording.
+const CELL_PIXELS: usize = 4;
+
+/// The result of encoding a recording on a separate thread.
+type EncodeResult = Result<Box<Path>, gif::EncodingError>;
+
+/// Records the boards displayed by the ui & encodes them into an animated GIF.
+///
+/// Only one frame is kept for each generation, so a paused simulation will not fill the recording.
+pub(crate) struct GifRecorder {
+    /// Whether new frames are being recorded.
+    recording: bool,
+    /// The frames recorded so far.
+    frames: Vec<BoardDisplay>,
+    /// The generation of the last recorded frame.
+    last_generation: Option<u64>,
+
+    /// Sender for the encoding threads to report their results.
+    result_sender: mpsc::Sender<EncodeResult>,
+    /// Receives the results of the encoding threads.
+    result_receiver: mpsc::Receiver<EncodeResult>,
+}
+
+impl Default for GifRecorder {
+    fn default() -> Self {
+        let (result_sender, result_receiver) = mpsc::channel();
+        Self {
+            recording: false,
+            frames: Vec::new(),
+            last_generation: None,
+            result_sender,
+            result_receiver,
+        }
+    }
+}
+
+impl GifRecorder {
+    /// Whether new frames are being recorded.
+    pub(crate) fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Discards any previously recorded frames & starts recording.
+    pub(crate) fn start(&mut self) {
+        self.recording = true;
+        self.frames.clear();
+        self.last_generation = None;
+    }
+
+    /// Records the given display as a frame, if it is of a new generation.
+    ///
+    /// A warning is displayed once the recording reaches [`MAX_FRAMES`].
+    pub(crate) fn record(&mut self, display: &BoardDisplay, toasts: &mut Toasts) {
+        if !self.recording
+            || self.frames.len() >= MAX_FRAMES
+            || self.last_generation == Some(display.get_generation())
+        {
+            return;
+        }
+
+        self.last_generation = Some(display.get_generation());
+        self.frames.push(display.clone());
+
+        if self.frames.len() == MAX_FRAMES {
+            let frame_pixels =
+                display.get_x().get() * display.get_y().get() * CELL_PIXELS * CELL_PIXELS;
+            // Each pixel is one of two colours, so takes around one bit before compression.
+            let megabytes = (MAX_FRAMES * frame_pixels) as f64 / 8.0 / 1_000_000.0;
+
+            toasts.warning(format!("{FRAME_LIMIT}{megabytes:.1}{FRAME_LIMIT_SIZE}"));
+        }
+    }
+
+    /// Stops recording & encodes the recorded frames into a GIF within the given directory.
+    ///
+    /// The encoding is performed on a separate thread, with the outcome being displayed once it is received by
+    /// [`Self::poll`].
+    pub(crate) fn stop(
+        &mut self,
+        directory: PathBuf,
+        colours: CellColours,
+        frame_delay_ms: u32,
+        toasts: &mut Toasts,
+    ) {
+        self.recording = false;
+        let frames = std::mem::take(&mut self.frames);
+
+        if frames.is_empty() {
+            toasts.info(NO_FRAMES);
+            return;
+        }
+
+        let result_sender = self.result_sender.clone();
+        // The GIF format stores the delay in hundredths of a second.
+        let frame_delay = (frame_delay_ms / 10).min(u16::MAX as u32) as u16;
+
+        let spawn = thread::Builder::new()
+            .name("Gif_Encoder".into())
+            .spawn(move || {
+                let result = save_gif(&frames, colours, frame_delay, directory);
+                // The ui closing before encoding finishes is not an error.
+                let _ = result_sender.send(result);
+            });
+
+        match spawn {
+            Ok(_) => toasts.info(ENCODE_STARTED),
+            Err(err) => toasts.error(format!("{ENCODE_THREAD_ERROR} {err}")),
+        }
+    }
+
+    /// Displays the outcome of any finished encodings.
+    pub(crate) fn poll(&mut self, toasts: &mut Toasts) {
+        while let Ok(result) = self.result_receiver.try_recv() {
+            match result {
+                Ok(path) => toasts.info(format!("{ENCODE_SUCCESS}{}", path.display())),
+                Err(err) => toasts.error(format!("{ENCODE_FAIL}{err}")),
+            }
+        }
+    }
+}
+
+/// The colours used to represent cells within a recording.
+#[derive(Clone, Copy)]
+pub(crate) struct CellColours {
+    pub(crate) alive: Color32,
+    pub(crate) dead: Color32,
+}
+
+/// Creates a new GIF file within the given directory containing the frames.
+///
+/// The returned value is the path to the created file.
+fn save_gif(
+    frames: &[BoardDisplay],
+    colours: CellColours,
+    frame_delay: u16,
+    mut path: PathBuf,
+) -> EncodeResult {
+    std::fs::create_dir_all(&path)?;
+
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    path.push(format!("recording_{}", time.as_secs()));
+    path.set_extension("gif");
+
+    let file = File::create_new(&path)?;
+    encode_gif(frames, colours, frame_delay, BufWriter::new(file))?;
+
+    Ok(path.into())
+}
+
+/// Encodes the frames as an animated GIF into the writer.
+///
+/// The size of the GIF is determined by the first frame. Later frames of a different size are cropped or padded with
+/// dead cells.
+fn encode_gif(
+    frames: &[BoardDisplay],
+    colours: CellColours,
+    frame_delay: u16,
+    writer: impl Write,
+) -> Result<(), gif::EncodingError> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+
+    let x_cells = first.get_x().get();
+    let y_cells = first.get_y().get();
+    let width = (x_cells * CELL_PIXELS).min(u16::MAX as usize) as u16;
+    let height = (y_cells * CELL_PIXELS).min(u16::MAX as usize) as u16;
+
+    // Index 0 is a dead cell, index 1 is an alive cell.
+    let [dead_r, dead_g, dead_b, _] = colours.dead.to_array();
+    let [alive_r, alive_g, alive_b, _] = colours.alive.to_array();
+    let palette = [dead_r, dead_g, dead_b, alive_r, alive_g, alive_b];
+
+    let mut encoder = gif::Encoder::new(writer, width, height, &palette)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    let mut pixels = vec![0u8; width as usize * height as usize];
+    for display in frames {
+        for (index, pixel) in pixels.iter_mut().enumerate() {
+            let x = (index % width as usize) / CELL_PIXELS;
+            let y = (index / width as usize) / CELL_PIXELS;
+            *pixel = bool::from(display.get_cell((x as i32, y as i32))) as u8;
+        }
+
+        let mut frame = gif::Frame::from_indexed_pixels(width, height, pixels.as_slice(), None);
+        frame.delay = frame_delay;
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
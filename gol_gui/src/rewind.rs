@@ -0,0 +1,97 @@
+//! Contains [`GenerationSlider`].
+//! See its documentation for more information.
+
+use crate::lang;
+
+lang! {
+    REWIND, "Rewind:"
+}
+
+/// A slider for returning the board to one of the generations kept by the simulator's generation history.
+///
+/// The simulator keeps the most recent generations it has advanced through, so the slider spans from the newest
+/// generation that has been displayed back by the amount of generations kept. If the simulator reports that a
+/// generation is no longer kept, the slider starts from the earliest generation it reported instead.
+#[derive(Default, Debug, PartialEq)]
+pub(crate) struct GenerationSlider {
+    /// The oldest generation that can be returned to.
+    earliest: u64,
+    /// The newest generation that has been displayed.
+    latest: u64,
+    /// The generation the slider is being dragged to, which is only requested once the drag is released.
+    dragging: Option<u64>,
+}
+
+impl GenerationSlider {
+    /// Updates the range of the slider with the generation of the displayed board, for a history keeping the given
+    /// amount of generations.
+    pub(crate) fn update(&mut self, generation: u64, kept: usize) {
+        self.latest = self.latest.max(generation);
+        let oldest_kept = (self.latest + 1).saturating_sub(kept as u64);
+        self.earliest = self.earliest.max(oldest_kept).min(self.latest);
+    }
+
+    /// Records that generations before the given generation are no longer kept by the simulator.
+    pub(crate) fn unavailable(&mut self, earliest: u64) {
+        self.earliest = earliest.min(self.latest);
+    }
+
+    /// Discards the range of the slider, for when the board is replaced.
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Draws the slider at the generation of the displayed board, returning the generation to return to if the user
+    /// chose one.
+    pub(crate) fn draw(&mut self, ui: &mut egui::Ui, generation: u64) -> Option<u64> {
+        ui.label(REWIND);
+        let mut selected = self.dragging.unwrap_or(generation);
+        let slider = ui.add(egui::Slider::new(
+            &mut selected,
+            self.earliest..=self.latest,
+        ));
+
+        if slider.dragged() {
+            self.dragging = Some(selected);
+            return None;
+        }
+        if slider.drag_stopped() || slider.changed() {
+            self.dragging = None;
+            return Some(selected);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The slider spans back from the newest generation by the amount of generations kept.
+    fn update_range() {
+        let mut slider = GenerationSlider::default();
+        slider.update(3, 10);
+        assert_eq!((slider.earliest, slider.latest), (0, 3));
+
+        slider.update(20, 10);
+        assert_eq!((slider.earliest, slider.latest), (11, 20));
+
+        // Returning to an earlier generation keeps the newer ones.
+        slider.update(15, 10);
+        assert_eq!((slider.earliest, slider.latest), (11, 20));
+    }
+
+    #[test]
+    /// The earliest generation reported by the simulator is used, & discarded along with the board.
+    fn unavailable() {
+        let mut slider = GenerationSlider::default();
+        slider.update(20, 10);
+        slider.unavailable(15);
+        assert_eq!(slider.earliest, 15);
+
+        slider.reset();
+        slider.update(2, 10);
+        assert_eq!((slider.earliest, slider.latest), (0, 2));
+    }
+}
@@ -1,11 +1,18 @@
 use std::path::{Path, PathBuf};
 
-use egui::{Color32, KeyboardShortcut};
+use egui::{Color32, KeyboardShortcut, Rect};
 use egui_file_dialog::FileDialog;
 use egui_keybind::Shortcut;
+use gol_lib::{Area, Cell, CellChange, GlobalPosition};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{app::SETTINGS_PANEL, lang, DEFAULT_BLUEPRINT_PATH, DEFAULT_SAVE_PATH};
+use crate::{
+    app::SETTINGS_PANEL,
+    autosave,
+    edit::{BrushShape, EditState},
+    history::HistoryLimit,
+    lang, DEFAULT_BLUEPRINT_PATH, DEFAULT_RECORDING_PATH, DEFAULT_SAVE_PATH,
+};
 
 lang! {
         CLOSE, "Close";
@@ -16,17 +23,60 @@ lang! {
         CELL_ALIVE_COLOUR, "Cell alive colour:";
         CELL_DEAD_COLOUR, "Cell dead colour:";
         CELL_SIZE, "Cell size:";
+        CELL_SHAPE, "Cell shape:";
+        CELL_SQUARE, "Square";
+        CELL_CIRCLE, "Circle";
+        CELL_ROUNDED_SQUARE, "Rounded square";
+        CELL_CORNER_RADIUS, "Corner radius (fraction of cell):";
+        BRUSH_SIZE, "Brush radius:";
+        BRUSH_SHAPE, "Brush shape:";
+        GRID, "Draw grid";
+        GRID_COLOUR, "Grid colour:";
+        MAJOR_GRID_INTERVAL, "Major gridline every (cells):";
+        MAJOR_GRID_COLOUR, "Major grid colour:";
+        ORIGIN_MARKER, "Mark the origin";
+        AXIS_LINES, "Draw axis lines";
+        ORIGIN_COLOUR, "Origin colour:";
+        AGE_COLOURS, "Colour cells by age";
+        AGE_YOUNG_COLOUR, "Newborn cell colour:";
+        AGE_OLD_COLOUR, "Old cell colour:";
+        AGE_MAX, "Fully old after (generations):";
+        CHANGE_COLOURS, "Highlight cells born & died in the last generation";
+        CHANGE_BORN_COLOUR, "Born cell colour:";
+        CHANGE_DIED_COLOUR, "Died cell colour:";
+        DRAW_DIED, "Draw cells that died";
+        DRAW_DIED_HOVER, "When disabled, cells that just died are not drawn in the died colour, so only alive cells are \
+            drawn over a background in the dead colour. This is quicker for large boards.";
         KEYBIND_SIMULATION_TOGGLE, "Toggle Simulation:";
         KEYBIND_SETTINGS_MENU_TOGGLE, "Toggle Settings Menu:";
+        KEYBIND_STEP, "Step Simulation:";
+        KEYBIND_CYCLE_EDIT_MODE, "Cycle Edit Mode:";
+        KEYBIND_CLEAR_SELECTION, "Clear Selection:";
+        KEYBIND_UNDO, "Undo:";
+        KEYBIND_REDO, "Redo:";
+        KEYBIND_PASTE_BLUEPRINT, "Paste Copied Blueprint:";
         FILE_HEADER, "Storage locations";
         FILE_SAVE_PATH, "Save Path:";
-        FILE_BLUEPRINT_PATH, "Blueprint Path:"
+        FILE_BLUEPRINT_PATH, "Blueprint Path:";
+        FILE_RECORDING_PATH, "Recording Path:";
+        AUTOSAVE_INTERVAL, "Autosave every (seconds, 0 disables):";
+        AUTOSAVE_SLOTS, "Autosaves kept:";
+        RECORDING_HEADER, "Recording";
+        RECORDING_FRAME_DELAY, "GIF frame delay (ms):";
+        DISPLAY_HEADER, "Display";
+        HISTORY_HEADER, "History";
+        HISTORY_ACTIONS, "Maximum undo steps:";
+        HISTORY_MEMORY, "Maximum memory (MiB):";
+        HISTORY_GENERATIONS, "Generations kept for rewinding:";
+        HOVER_TOOLTIP, "Show the cell under the cursor";
+        GENERATION_OVERLAY, "Show the generation on the board";
+        PAUSE_UNFOCUSED, "Pause when window unfocused"
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug, Default)]
 #[serde(default)]
 pub(crate) struct Settings {
-    #[serde(skip)]
+    /// Whether the settings menu is open.
     pub(crate) open: bool,
 
     /// The settings for cell aperance on the board.
@@ -34,6 +84,28 @@ pub(crate) struct Settings {
     /// The settings for keybinds.
     pub(crate) keybind: KeybindSettings,
     pub(crate) file: FileSettings,
+    /// The settings for recording the board.
+    pub(crate) recording: RecordingSettings,
+    /// Named views of the board the user can jump to.
+    pub(crate) bookmarks: Vec<(String, Area)>,
+    /// The settings for extra information shown on the board.
+    pub(crate) display: DisplaySettings,
+    /// The settings for the size of the undo history.
+    pub(crate) history: HistorySettings,
+    /// Whether the simulation is stopped while the window is unfocused.
+    pub(crate) pause_unfocused: bool,
+    /// The state of the workspace when the application was closed.
+    pub(crate) workspace: WorkspaceSettings,
+}
+
+/// The state of the workspace, which is restored when the application is relaunched.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Default, PartialEq)]
+#[serde(default)]
+pub(crate) struct WorkspaceSettings {
+    /// The selected edit state.
+    pub(crate) edit_state: EditState,
+    /// The centre of the displayed area of the board, or [`None`] if it has not been stored.
+    pub(crate) view_centre: Option<GlobalPosition>,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
@@ -43,8 +115,91 @@ pub(crate) struct CellSettings {
     pub(crate) alive_colour: Color32,
     /// The colour of dead cells.
     pub(crate) dead_colour: Color32,
+    /// Whether cells that just died are drawn in the died colour. Otherwise only alive cells are drawn over the
+    /// background.
+    pub(crate) draw_died: bool,
     /// The size of each cell.
     pub(crate) size: f32,
+    /// The shape alive cells are drawn as.
+    pub(crate) shape: CellShape,
+    /// The radius, in cells, of the brush used to draw cells.
+    pub(crate) brush_size: u32,
+    /// The shape of the brush used to draw cells.
+    pub(crate) brush_shape: BrushShape,
+    /// Whether gridlines are drawn between cells.
+    pub(crate) grid: bool,
+    /// The colour of the gridlines.
+    pub(crate) grid_colour: Color32,
+    /// The amount of cells between each major gridline, or 0 for no major gridlines.
+    pub(crate) major_grid_interval: u32,
+    /// The colour of the major gridlines.
+    pub(crate) major_grid_colour: Color32,
+    /// Whether the cell at the origin is outlined.
+    pub(crate) origin_marker: bool,
+    /// Whether lines are drawn through the origin along the x & y axes.
+    pub(crate) axis_lines: bool,
+    /// The colour of the origin marker & axis lines.
+    pub(crate) origin_colour: Color32,
+    /// Whether alive cells are coloured by their age, rather than by the alive colour.
+    pub(crate) age_colours: bool,
+    /// The colour of alive cells that were just born.
+    pub(crate) young_colour: Color32,
+    /// The colour of alive cells that are at least the maximum age.
+    pub(crate) old_colour: Color32,
+    /// The age at which cells are shown in the old colour.
+    pub(crate) max_age: u32,
+    /// Whether cells born or that died during the last generation are shown in the born & died colours.
+    pub(crate) change_colours: bool,
+    /// The colour of cells born during the last generation.
+    pub(crate) born_colour: Color32,
+    /// The colour of cells that died during the last generation.
+    pub(crate) died_colour: Color32,
+}
+
+/// The shape alive cells are drawn as on the board.
+#[derive(Clone, Copy, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) enum CellShape {
+    #[default]
+    Square,
+    Circle,
+    /// A square with rounded corners.
+    RoundedSquare {
+        /// The radius of the corners, as a fraction of the size of the cell.
+        radius: f32,
+    },
+}
+
+impl CellShape {
+    /// All the cell shapes, with the default corner radius for rounded squares.
+    pub(crate) const ALL: [CellShape; 3] = [
+        CellShape::Square,
+        CellShape::Circle,
+        CellShape::RoundedSquare { radius: 0.25 },
+    ];
+
+    /// The name of the cell shape displayed to the user.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            CellShape::Square => CELL_SQUARE,
+            CellShape::Circle => CELL_CIRCLE,
+            CellShape::RoundedSquare { .. } => CELL_ROUNDED_SQUARE,
+        }
+    }
+
+    /// The shape to draw for a cell filling the given rect, in the given colour.
+    pub(crate) fn shape(self, rect: Rect, colour: Color32) -> egui::Shape {
+        let rounding = match self {
+            CellShape::Square => egui::Rounding::ZERO,
+            CellShape::Circle => {
+                return egui::epaint::CircleShape::filled(rect.center(), rect.width() / 2.0, colour)
+                    .into()
+            }
+            CellShape::RoundedSquare { radius } => {
+                egui::Rounding::same(radius.clamp(0.0, 0.5) * rect.width())
+            }
+        };
+        egui::epaint::RectShape::new(rect, rounding, colour, egui::Stroke::NONE).into()
+    }
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
@@ -54,6 +209,64 @@ pub(crate) struct KeybindSettings {
     pub(crate) settings_menu: Shortcut,
     /// Keybind for toggling the simulation.
     pub(crate) toggle_simulation: Shortcut,
+    /// Keybind for advancing the simulation by one generation.
+    pub(crate) step: Shortcut,
+    /// Keybind for switching to the next edit mode.
+    pub(crate) cycle_edit_mode: Shortcut,
+    /// Keybind for clearing the selected area.
+    pub(crate) clear_selection: Shortcut,
+    /// Keybind for undoing the last edit.
+    pub(crate) undo: Shortcut,
+    /// Keybind for redoing the last undone edit.
+    pub(crate) redo: Shortcut,
+    /// Keybind for pasting the copied blueprint.
+    pub(crate) paste_blueprint: Shortcut,
+}
+
+/// The actions that can be bound to a keyboard shortcut.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, enum_iterator::Sequence)]
+pub(crate) enum Keybind {
+    SettingsMenu,
+    ToggleSimulation,
+    Step,
+    CycleEditMode,
+    ClearSelection,
+    Undo,
+    Redo,
+    PasteBlueprint,
+}
+
+impl Keybind {
+    /// The name of the keybind displayed to the user.
+    fn label(self) -> &'static str {
+        match self {
+            Keybind::SettingsMenu => KEYBIND_SETTINGS_MENU_TOGGLE,
+            Keybind::ToggleSimulation => KEYBIND_SIMULATION_TOGGLE,
+            Keybind::Step => KEYBIND_STEP,
+            Keybind::CycleEditMode => KEYBIND_CYCLE_EDIT_MODE,
+            Keybind::ClearSelection => KEYBIND_CLEAR_SELECTION,
+            Keybind::Undo => KEYBIND_UNDO,
+            Keybind::Redo => KEYBIND_REDO,
+            Keybind::PasteBlueprint => KEYBIND_PASTE_BLUEPRINT,
+        }
+    }
+
+    /// The shortcut the keybind is bound to by default.
+    fn default_shortcut(self) -> Shortcut {
+        let (modifiers, key) = match self {
+            Keybind::SettingsMenu => (egui::Modifiers::CTRL | egui::Modifiers::SHIFT, egui::Key::D),
+            Keybind::ToggleSimulation => (egui::Modifiers::NONE, egui::Key::Space),
+            Keybind::Step => (egui::Modifiers::NONE, egui::Key::N),
+            Keybind::CycleEditMode => (egui::Modifiers::NONE, egui::Key::E),
+            Keybind::ClearSelection => (egui::Modifiers::NONE, egui::Key::Escape),
+            Keybind::Undo => (egui::Modifiers::COMMAND, egui::Key::Z),
+            Keybind::Redo => (egui::Modifiers::COMMAND, egui::Key::Y),
+            // Ctrl + V is sent as a paste event rather than a key press, so can't be used.
+            Keybind::PasteBlueprint => (egui::Modifiers::NONE, egui::Key::V),
+        };
+
+        Shortcut::new(Some(KeyboardShortcut::new(modifiers, key)), None)
+    }
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
@@ -63,10 +276,16 @@ pub(crate) struct FileSettings {
     pub(crate) save_location: PathBuf,
     /// The location of the blueprint saves.
     pub(crate) blueprint_location: PathBuf,
+    /// The location of the GIF recordings.
+    pub(crate) recording_location: PathBuf,
+    /// The time between autosaves of the board, in seconds. Autosaving is disabled when 0.
+    pub(crate) autosave_interval: u32,
+    /// The amount of autosaves kept, with the oldest being overwritten.
+    pub(crate) autosave_slots: u32,
 
     #[serde(skip)]
     /// .0 : The directory picker for the file locations.
-    /// .1 : Whether the selected directory is for saves or blueprints.
+    /// .1 : Whether the selected directory is for saves, blueprints or recordings.
     dir_picker: Option<(FileDialog, Selected)>,
 }
 
@@ -74,6 +293,82 @@ pub(crate) struct FileSettings {
 enum Selected {
     Save,
     Blueprint,
+    Recording,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[serde(default)]
+pub(crate) struct RecordingSettings {
+    /// The time each frame of a GIF recording is shown for, in milliseconds.
+    pub(crate) frame_delay: u32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[serde(default)]
+pub(crate) struct DisplaySettings {
+    /// Whether a tooltip with the position & state of the cell under the cursor is shown.
+    pub(crate) hover_tooltip: bool,
+    /// Whether the generation of the displayed board is shown in the corner of the board.
+    pub(crate) generation_overlay: bool,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[serde(default)]
+pub(crate) struct HistorySettings {
+    /// The maximum amount of actions that can be undone & redone.
+    pub(crate) max_actions: usize,
+    /// The maximum approximate amount of memory used by the undo history, in mebibytes.
+    pub(crate) max_memory: usize,
+    /// The amount of previous generations the simulator keeps, so the board can be rewound to them.
+    pub(crate) generations: usize,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        let limit = HistoryLimit::default();
+        Self {
+            max_actions: limit.actions,
+            max_memory: limit.bytes / (1024 * 1024),
+            // Every generation kept is a full save of the board, so the history is opt-in.
+            generations: 0,
+        }
+    }
+}
+
+impl HistorySettings {
+    /// The limit of the undo history set by these settings.
+    pub(crate) fn limit(&self) -> HistoryLimit {
+        HistoryLimit {
+            actions: self.max_actions,
+            bytes: self.max_memory.saturating_mul(1024 * 1024),
+        }
+    }
+
+    fn draw(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(HISTORY_HEADER).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(HISTORY_ACTIONS);
+                ui.add(egui::DragValue::new(&mut self.max_actions).range(1..=100_000));
+            });
+            ui.horizontal(|ui| {
+                ui.label(HISTORY_MEMORY);
+                ui.add(egui::DragValue::new(&mut self.max_memory).range(1..=4096));
+            });
+            ui.horizontal(|ui| {
+                ui.label(HISTORY_GENERATIONS);
+                ui.add(egui::DragValue::new(&mut self.generations).range(0..=10_000));
+            });
+        });
+    }
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            hover_tooltip: false,
+            generation_overlay: true,
+        }
+    }
 }
 
 impl Settings {
@@ -95,19 +390,94 @@ impl Settings {
 
             ui.separator();
 
+            ui.checkbox(&mut self.pause_unfocused, PAUSE_UNFOCUSED);
             self.cell.draw(ui);
             self.keybind.draw(ui);
             self.file.draw(ui, ctx);
+            self.recording.draw(ui);
+            self.display.draw(ui);
+            self.history.draw(ui);
         })
     }
 }
 
+impl CellSettings {
+    /// The range of cell sizes that can be selected without overriding the slider.
+    pub(crate) const SIZE_RANGE: std::ops::RangeInclusive<f32> = 10.0..=50.0;
+
+    /// The stroke of the gridline before the cell at the given position along an axis, or [`None`] if the gridline is
+    /// not drawn.
+    pub(crate) fn gridline_stroke(&self, position: i32) -> Option<egui::Stroke> {
+        if !self.grid {
+            return None;
+        }
+
+        let major = self.major_grid_interval != 0
+            && position.rem_euclid(self.major_grid_interval as i32) == 0;
+        Some(match major {
+            true => egui::Stroke::new(2.0, self.major_grid_colour),
+            false => egui::Stroke::new(1.0, self.grid_colour),
+        })
+    }
+
+    /// The colour of an alive cell of the given age.
+    ///
+    /// If cells are not coloured by age, or the age is unknown, then the alive colour is used.
+    pub(crate) fn aged_colour(&self, age: Option<u32>) -> Color32 {
+        match (self.age_colours, age) {
+            (true, Some(age)) => {
+                let t = match self.max_age {
+                    0 => 1.0,
+                    max_age => age.min(max_age) as f32 / max_age as f32,
+                };
+                self.young_colour.lerp_to_gamma(self.old_colour, t)
+            }
+            _ => self.alive_colour,
+        }
+    }
+
+    /// The colour of a cell, given its age & how it changed during the last generation, if they are known.
+    ///
+    /// If changes are coloured, cells that were just born or that just died are shown in the born & died colours.
+    pub(crate) fn cell_colour(
+        &self,
+        cell: Cell,
+        age: Option<u32>,
+        change: Option<CellChange>,
+    ) -> Color32 {
+        match (cell, self.change_colours, change) {
+            (Cell::Alive, true, Some(CellChange::Born)) => self.born_colour,
+            (Cell::Dead, true, Some(CellChange::Died)) => self.died_colour,
+            (Cell::Alive, ..) => self.aged_colour(age),
+            (Cell::Dead, ..) => self.dead_colour,
+        }
+    }
+}
+
 impl Default for CellSettings {
     fn default() -> Self {
         Self {
             alive_colour: Color32::WHITE,
             dead_colour: Color32::BLACK,
+            draw_died: true,
             size: 15.0,
+            shape: CellShape::default(),
+            brush_size: 0,
+            brush_shape: BrushShape::default(),
+            grid: true,
+            grid_colour: Color32::GRAY,
+            major_grid_interval: 10,
+            major_grid_colour: Color32::LIGHT_GRAY,
+            origin_marker: false,
+            axis_lines: false,
+            origin_colour: Color32::LIGHT_BLUE,
+            age_colours: false,
+            young_colour: Color32::YELLOW,
+            old_colour: Color32::RED,
+            max_age: 50,
+            change_colours: false,
+            born_colour: Color32::GREEN,
+            died_colour: Color32::DARK_RED,
         }
     }
 }
@@ -134,7 +504,7 @@ impl CellSettings {
             ui.horizontal(|ui| {
                 ui.label(CELL_SIZE);
                 ui.add(
-                    egui::Slider::new(&mut self.size, 10.0..=50.0)
+                    egui::Slider::new(&mut self.size, CellSettings::SIZE_RANGE)
                         // Allow user override
                         .clamping(egui::SliderClamping::Never),
                 );
@@ -142,6 +512,135 @@ impl CellSettings {
                     self.size = CellSettings::default().size;
                 }
             });
+
+            ui.horizontal(|ui| {
+                ui.label(CELL_SHAPE);
+                for shape in CellShape::ALL {
+                    let selected =
+                        std::mem::discriminant(&self.shape) == std::mem::discriminant(&shape);
+                    if ui.selectable_label(selected, shape.label()).clicked() && !selected {
+                        self.shape = shape;
+                    }
+                }
+            });
+            if let CellShape::RoundedSquare { radius } = &mut self.shape {
+                ui.horizontal(|ui| {
+                    ui.label(CELL_CORNER_RADIUS);
+                    ui.add(egui::Slider::new(radius, 0.0..=0.5));
+                });
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(BRUSH_SIZE);
+                ui.add(egui::Slider::new(&mut self.brush_size, 0..=10));
+                if ui.button(RESET).clicked() {
+                    self.brush_size = CellSettings::default().brush_size;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(BRUSH_SHAPE);
+                for shape in BrushShape::ALL {
+                    ui.selectable_value(&mut self.brush_shape, shape, shape.label());
+                }
+            });
+
+            ui.checkbox(&mut self.grid, GRID);
+            ui.add_enabled_ui(self.grid, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(GRID_COLOUR);
+                    ui.color_edit_button_srgba(&mut self.grid_colour);
+                    if ui.small_button(RESET).clicked() {
+                        self.grid_colour = CellSettings::default().grid_colour;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(MAJOR_GRID_INTERVAL);
+                    ui.add(egui::DragValue::new(&mut self.major_grid_interval));
+                    if ui.small_button(RESET).clicked() {
+                        self.major_grid_interval = CellSettings::default().major_grid_interval;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(MAJOR_GRID_COLOUR);
+                    ui.color_edit_button_srgba(&mut self.major_grid_colour);
+                    if ui.small_button(RESET).clicked() {
+                        self.major_grid_colour = CellSettings::default().major_grid_colour;
+                    }
+                });
+            });
+
+            ui.checkbox(&mut self.origin_marker, ORIGIN_MARKER);
+            ui.checkbox(&mut self.axis_lines, AXIS_LINES);
+            ui.add_enabled_ui(self.origin_marker || self.axis_lines, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(ORIGIN_COLOUR);
+                    ui.color_edit_button_srgba(&mut self.origin_colour);
+                    if ui.small_button(RESET).clicked() {
+                        self.origin_colour = CellSettings::default().origin_colour;
+                    }
+                });
+            });
+
+            ui.checkbox(&mut self.age_colours, AGE_COLOURS);
+            ui.add_enabled_ui(self.age_colours, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(AGE_YOUNG_COLOUR);
+                    ui.color_edit_button_srgba(&mut self.young_colour);
+                    if ui.small_button(RESET).clicked() {
+                        self.young_colour = CellSettings::default().young_colour;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(AGE_OLD_COLOUR);
+                    ui.color_edit_button_srgba(&mut self.old_colour);
+                    if ui.small_button(RESET).clicked() {
+                        self.old_colour = CellSettings::default().old_colour;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(AGE_MAX);
+                    ui.add(egui::DragValue::new(&mut self.max_age));
+                    if ui.small_button(RESET).clicked() {
+                        self.max_age = CellSettings::default().max_age;
+                    }
+                });
+            });
+
+            ui.checkbox(&mut self.change_colours, CHANGE_COLOURS);
+            ui.add_enabled_ui(self.change_colours, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(CHANGE_BORN_COLOUR);
+                    ui.color_edit_button_srgba(&mut self.born_colour);
+                    if ui.small_button(RESET).clicked() {
+                        self.born_colour = CellSettings::default().born_colour;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(CHANGE_DIED_COLOUR);
+                    ui.color_edit_button_srgba(&mut self.died_colour);
+                    if ui.small_button(RESET).clicked() {
+                        self.died_colour = CellSettings::default().died_colour;
+                    }
+                });
+
+                ui.checkbox(&mut self.draw_died, DRAW_DIED)
+                    .on_hover_text(DRAW_DIED_HOVER);
+            });
+        });
+    }
+}
+
+impl DisplaySettings {
+    fn draw(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(DISPLAY_HEADER).show(ui, |ui| {
+            ui.checkbox(&mut self.hover_tooltip, HOVER_TOOLTIP);
+            ui.checkbox(&mut self.generation_overlay, GENERATION_OVERLAY);
         });
     }
 }
@@ -149,38 +648,60 @@ impl CellSettings {
 impl Default for KeybindSettings {
     fn default() -> Self {
         Self {
-            settings_menu: Shortcut::new(
-                Some(KeyboardShortcut::new(
-                    egui::Modifiers::CTRL | egui::Modifiers::SHIFT,
-                    egui::Key::D,
-                )),
-                None,
-            ),
-            toggle_simulation: Shortcut::new(
-                Some(KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::P)),
-                None,
-            ),
+            settings_menu: Keybind::SettingsMenu.default_shortcut(),
+            toggle_simulation: Keybind::ToggleSimulation.default_shortcut(),
+            step: Keybind::Step.default_shortcut(),
+            cycle_edit_mode: Keybind::CycleEditMode.default_shortcut(),
+            clear_selection: Keybind::ClearSelection.default_shortcut(),
+            undo: Keybind::Undo.default_shortcut(),
+            redo: Keybind::Redo.default_shortcut(),
+            paste_blueprint: Keybind::PasteBlueprint.default_shortcut(),
         }
     }
 }
 
 impl KeybindSettings {
+    /// The shortcut bound to the given keybind.
+    pub(crate) fn get(&self, keybind: Keybind) -> &Shortcut {
+        match keybind {
+            Keybind::SettingsMenu => &self.settings_menu,
+            Keybind::ToggleSimulation => &self.toggle_simulation,
+            Keybind::Step => &self.step,
+            Keybind::CycleEditMode => &self.cycle_edit_mode,
+            Keybind::ClearSelection => &self.clear_selection,
+            Keybind::Undo => &self.undo,
+            Keybind::Redo => &self.redo,
+            Keybind::PasteBlueprint => &self.paste_blueprint,
+        }
+    }
+
+    fn get_mut(&mut self, keybind: Keybind) -> &mut Shortcut {
+        match keybind {
+            Keybind::SettingsMenu => &mut self.settings_menu,
+            Keybind::ToggleSimulation => &mut self.toggle_simulation,
+            Keybind::Step => &mut self.step,
+            Keybind::CycleEditMode => &mut self.cycle_edit_mode,
+            Keybind::ClearSelection => &mut self.clear_selection,
+            Keybind::Undo => &mut self.undo,
+            Keybind::Redo => &mut self.redo,
+            Keybind::PasteBlueprint => &mut self.paste_blueprint,
+        }
+    }
+
     fn draw(&mut self, ui: &mut egui::Ui) {
         egui::CollapsingHeader::new(KEYBIND_HEADER).show(ui, |ui| {
-            ui.horizontal(|ui| {
-                ui.label(KEYBIND_SETTINGS_MENU_TOGGLE);
-                ui.add(egui_keybind::Keybind::new(
-                    &mut self.settings_menu,
-                    KEYBIND_SETTINGS_MENU_TOGGLE,
-                ));
-            });
-
-            ui.horizontal(|ui| {
-                ui.label(KEYBIND_SIMULATION_TOGGLE);
-                ui.add(egui_keybind::Keybind::new(
-                    &mut self.toggle_simulation,
-                    KEYBIND_SIMULATION_TOGGLE,
-                ));
+            egui::Grid::new(KEYBIND_HEADER).show(ui, |ui| {
+                for keybind in enum_iterator::all::<Keybind>() {
+                    ui.label(keybind.label());
+                    ui.add(egui_keybind::Keybind::new(
+                        self.get_mut(keybind),
+                        keybind.label(),
+                    ));
+                    if ui.small_button(RESET).clicked() {
+                        *self.get_mut(keybind) = keybind.default_shortcut();
+                    }
+                    ui.end_row();
+                }
             });
         });
     }
@@ -191,6 +712,9 @@ impl Default for FileSettings {
         Self {
             save_location: DEFAULT_SAVE_PATH.clone(),
             blueprint_location: DEFAULT_BLUEPRINT_PATH.clone(),
+            recording_location: DEFAULT_RECORDING_PATH.clone(),
+            autosave_interval: 300,
+            autosave_slots: 3,
             dir_picker: None,
         }
     }
@@ -236,6 +760,37 @@ impl FileSettings {
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.label(FILE_RECORDING_PATH);
+                if ui
+                    .button(get_display_path(&self.recording_location))
+                    .clicked()
+                {
+                    self.dir_picker = Some((
+                        {
+                            let mut file_dialog = FileDialog::new();
+                            file_dialog.pick_directory();
+                            file_dialog
+                        },
+                        Selected::Recording,
+                    ));
+                }
+                if ui.button(RESET).clicked() {
+                    self.recording_location = DEFAULT_RECORDING_PATH.clone();
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(AUTOSAVE_INTERVAL);
+                ui.add(egui::DragValue::new(&mut self.autosave_interval).range(0..=3600));
+            });
+            ui.horizontal(|ui| {
+                ui.label(AUTOSAVE_SLOTS);
+                ui.add(
+                    egui::DragValue::new(&mut self.autosave_slots).range(1..=autosave::MAX_SLOTS),
+                );
+            });
+
             if let Some((ref mut file_dialog, ref mut selected)) = self.dir_picker {
                 file_dialog.update(ctx);
 
@@ -243,6 +798,7 @@ impl FileSettings {
                     match selected {
                         Selected::Save => self.save_location = directory.to_path_buf(),
                         Selected::Blueprint => self.blueprint_location = directory.to_path_buf(),
+                        Selected::Recording => self.recording_location = directory.to_path_buf(),
                     }
 
                     // Dir has been picked so remove dir picker
@@ -253,6 +809,26 @@ impl FileSettings {
     }
 }
 
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self { frame_delay: 100 }
+    }
+}
+
+impl RecordingSettings {
+    fn draw(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(RECORDING_HEADER).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RECORDING_FRAME_DELAY);
+                ui.add(egui::Slider::new(&mut self.frame_delay, 10..=1000));
+                if ui.button(RESET).clicked() {
+                    self.frame_delay = RecordingSettings::default().frame_delay;
+                }
+            });
+        });
+    }
+}
+
 /// If a path is short than 40 characters the full path is returned as a string.
 /// Otherwise, the last 40 characters of the path are returned prefixed with "...".
 fn get_display_path(path: &Path) -> String {
@@ -267,3 +843,189 @@ fn get_display_path(path: &Path) -> String {
     let displayed_path: String = graphemes.into_iter().rev().take(40).rev().collect();
     format!("...{displayed_path}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Every gridline is minor, except for multiples of the major interval.
+    fn gridline_stroke() {
+        let settings = CellSettings {
+            major_grid_interval: 5,
+            ..Default::default()
+        };
+        let minor = Some(egui::Stroke::new(1.0, settings.grid_colour));
+        let major = Some(egui::Stroke::new(2.0, settings.major_grid_colour));
+
+        assert_eq!(settings.gridline_stroke(0), major);
+        assert_eq!(settings.gridline_stroke(3), minor);
+        assert_eq!(settings.gridline_stroke(10), major);
+        // Major gridlines are placed the same on either side of the origin.
+        assert_eq!(settings.gridline_stroke(-5), major);
+        assert_eq!(settings.gridline_stroke(-4), minor);
+    }
+
+    #[test]
+    /// Major gridlines can be disabled, & the grid can be disabled entirely.
+    fn gridline_disabled() {
+        let mut settings = CellSettings {
+            major_grid_interval: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.gridline_stroke(0),
+            Some(egui::Stroke::new(1.0, settings.grid_colour))
+        );
+
+        settings.grid = false;
+        assert_eq!(settings.gridline_stroke(0), None);
+        assert_eq!(settings.gridline_stroke(7), None);
+    }
+
+    #[test]
+    /// Bookmarks are kept when the settings are stored & restored.
+    fn bookmarks_round_trip() {
+        let settings = Settings {
+            bookmarks: vec![
+                ("Glider gun".to_owned(), Area::new((-10, -5), (30, 15))),
+                ("Origin".to_owned(), Area::new((-10, -10), (10, 10))),
+            ],
+            ..Default::default()
+        };
+
+        // Settings are stored in the same format as eframe uses.
+        let stored = ron::to_string(&settings).expect("Can serialise");
+        let restored: Settings = ron::from_str(&stored).expect("Can deserialise");
+        assert_eq!(restored.bookmarks, settings.bookmarks);
+
+        // Bookmarks stored before areas were serialised compactly can still be restored.
+        let restored: Settings = ron::from_str(
+            r#"(bookmarks: [("Origin", (min: (x: -10, y: -10), max: (x: 10, y: 10)))])"#,
+        )
+        .expect("Can deserialise");
+        assert_eq!(restored.bookmarks, settings.bookmarks[1..]);
+    }
+
+    #[test]
+    /// Every keybind has a default shortcut, & no two keybinds share one.
+    fn default_keybinds_complete() {
+        let defaults = KeybindSettings::default();
+        let mut shortcuts: Vec<KeyboardShortcut> = Vec::new();
+
+        for keybind in enum_iterator::all::<Keybind>() {
+            let shortcut = defaults
+                .get(keybind)
+                .keyboard()
+                .unwrap_or_else(|| panic!("{keybind:?} has no default shortcut"));
+
+            assert_eq!(defaults.get(keybind), &keybind.default_shortcut());
+            assert!(
+                !shortcuts.contains(&shortcut),
+                "{keybind:?} shares its default shortcut"
+            );
+            shortcuts.push(shortcut);
+        }
+
+        assert_eq!(shortcuts.len(), enum_iterator::cardinality::<Keybind>());
+    }
+
+    #[test]
+    /// Alive cells are coloured along the gradient by age, up to the maximum age.
+    fn aged_colour() {
+        let mut settings = CellSettings::default();
+        assert_eq!(settings.aged_colour(Some(0)), settings.alive_colour);
+
+        settings.age_colours = true;
+        assert_eq!(settings.aged_colour(None), settings.alive_colour);
+        assert_eq!(settings.aged_colour(Some(0)), settings.young_colour);
+        assert_eq!(
+            settings.aged_colour(Some(settings.max_age)),
+            settings.old_colour
+        );
+        assert_eq!(settings.aged_colour(Some(u32::MAX)), settings.old_colour);
+        assert_eq!(
+            settings.aged_colour(Some(settings.max_age / 2)),
+            settings
+                .young_colour
+                .lerp_to_gamma(settings.old_colour, 0.5)
+        );
+    }
+
+    #[test]
+    /// The workspace is kept when the settings are stored & restored, & is optional for older configurations.
+    fn workspace_round_trip() {
+        let settings = Settings {
+            open: true,
+            workspace: WorkspaceSettings {
+                edit_state: EditState::Select,
+                view_centre: Some(GlobalPosition::new(-40, 12)),
+            },
+            ..Default::default()
+        };
+
+        let stored = ron::to_string(&settings).expect("Can serialise");
+        let restored: Settings = ron::from_str(&stored).expect("Can deserialise");
+        assert!(restored.open);
+        assert_eq!(restored.workspace, settings.workspace);
+
+        // Configurations stored before the workspace was added do not contain it.
+        let restored: Settings = ron::from_str("(pause_unfocused: true)").expect("Can deserialise");
+        assert!(!restored.open);
+        assert_eq!(restored.workspace, WorkspaceSettings::default());
+    }
+
+    #[test]
+    /// Cells born or that died are only shown in the change colours once enabled, & otherwise by their state.
+    fn cell_colour() {
+        let mut settings = CellSettings::default();
+        let born = Some(CellChange::Born);
+        let died = Some(CellChange::Died);
+        assert_eq!(
+            settings.cell_colour(Cell::Alive, None, born),
+            settings.alive_colour
+        );
+
+        settings.change_colours = true;
+        assert_eq!(
+            settings.cell_colour(Cell::Alive, None, born),
+            settings.born_colour
+        );
+        assert_eq!(
+            settings.cell_colour(Cell::Dead, None, died),
+            settings.died_colour
+        );
+        assert_eq!(
+            settings.cell_colour(Cell::Alive, None, Some(CellChange::Unchanged)),
+            settings.alive_colour
+        );
+        assert_eq!(
+            settings.cell_colour(Cell::Dead, None, None),
+            settings.dead_colour
+        );
+    }
+
+    #[test]
+    /// Each cell shape fills the cell rect, with only rounded squares & circles having rounded edges.
+    fn cell_shape() {
+        let rect = Rect::from_min_size(egui::pos2(10.0, 20.0), egui::Vec2::splat(8.0));
+        let colour = Color32::WHITE;
+
+        let egui::Shape::Rect(square) = CellShape::Square.shape(rect, colour) else {
+            panic!("A square is a rect");
+        };
+        assert_eq!((square.rect, square.rounding), (rect, egui::Rounding::ZERO));
+
+        let rounded = CellShape::RoundedSquare { radius: 0.25 }.shape(rect, colour);
+        let egui::Shape::Rect(rounded) = rounded else {
+            panic!("A rounded square is a rect");
+        };
+        assert_eq!(rounded.rounding, egui::Rounding::same(2.0));
+
+        let egui::Shape::Circle(circle) = CellShape::Circle.shape(rect, colour) else {
+            panic!("A circle is a circle");
+        };
+        assert_eq!((circle.center, circle.radius), (rect.center(), 4.0));
+        assert_eq!(circle.fill, colour);
+    }
+}
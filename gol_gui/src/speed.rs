@@ -0,0 +1,152 @@
+//! Contains [`SpeedControl`].
+//! See its documentation for more information.
+
+use std::ops::RangeInclusive;
+
+use gol_lib::communication::SimulationSpeed;
+
+use crate::lang;
+
+lang! {
+    SPEED, "Speed:";
+    TICKS_PER_SECOND, " tps";
+    UNCAPPED, "Uncapped";
+    AUTOMATIC, "Keep responsive";
+    AUTOMATIC_TOOLTIP, "Slows the uncapped simulation when ticks are too slow for the board to be displayed smoothly"
+}
+
+/// The range of ticks per second the speed slider can be set to.
+const SPEED_RANGE: RangeInclusive<u32> = 1..=60;
+/// The ticks per second of the quick-preset buttons.
+const SPEED_PRESETS: [u32; 4] = [1, 5, 10, 30];
+
+/// The speed the simulation is set to run at, as chosen by the user.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct SpeedControl {
+    /// The ticks per second selected on the slider, which is kept whilst uncapped.
+    ticks_per_second: u32,
+    /// Whether the simulation runs as fast as possible, ignoring the ticks per second.
+    uncapped: bool,
+    /// Whether the uncapped simulation slows itself when ticks are slow. See [`SimulationSpeed::AUTOMATIC`].
+    automatic: bool,
+}
+
+impl Default for SpeedControl {
+    /// The simulator starts uncapped, so the control does too.
+    fn default() -> Self {
+        Self {
+            ticks_per_second: 30,
+            uncapped: true,
+            automatic: false,
+        }
+    }
+}
+
+impl SpeedControl {
+    /// The speed to send to the simulator.
+    pub(crate) fn speed(&self) -> SimulationSpeed {
+        match (self.uncapped, self.automatic) {
+            (true, true) => SimulationSpeed::AUTOMATIC,
+            (true, false) => SimulationSpeed::UNCAPPED,
+            (false, _) => SimulationSpeed::new(self.ticks_per_second),
+        }
+    }
+
+    /// Sets the simulation to run at the given ticks per second, which is clamped to the range of the slider.
+    pub(crate) fn set_ticks_per_second(&mut self, ticks_per_second: u32) {
+        self.ticks_per_second = ticks_per_second.clamp(*SPEED_RANGE.start(), *SPEED_RANGE.end());
+        self.uncapped = false;
+    }
+
+    /// Sets whether the simulation runs as fast as possible.
+    pub(crate) fn set_uncapped(&mut self, uncapped: bool) {
+        self.uncapped = uncapped;
+    }
+
+    /// Draws the speed controls, returning the new speed if the user changed it.
+    pub(crate) fn draw(&mut self, ui: &mut egui::Ui) -> Option<SimulationSpeed> {
+        let before = *self;
+
+        ui.label(SPEED);
+        let mut ticks_per_second = self.ticks_per_second;
+        let slider = ui.add_enabled(
+            !self.uncapped,
+            egui::Slider::new(&mut ticks_per_second, SPEED_RANGE).suffix(TICKS_PER_SECOND),
+        );
+        if slider.changed() {
+            self.set_ticks_per_second(ticks_per_second);
+        }
+
+        for preset in SPEED_PRESETS {
+            let selected = !self.uncapped && self.ticks_per_second == preset;
+            if ui.selectable_label(selected, preset.to_string()).clicked() {
+                self.set_ticks_per_second(preset);
+            }
+        }
+
+        let mut uncapped = self.uncapped;
+        if ui.toggle_value(&mut uncapped, UNCAPPED).changed() {
+            self.set_uncapped(uncapped);
+        }
+        ui.add_enabled(
+            self.uncapped,
+            egui::Checkbox::new(&mut self.automatic, AUTOMATIC),
+        )
+        .on_hover_text(AUTOMATIC_TOOLTIP);
+
+        (*self != before).then(|| self.speed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The control starts uncapped, matching the simulator.
+    fn default_uncapped() {
+        assert_eq!(SpeedControl::default().speed().get(), None);
+    }
+
+    #[test]
+    /// The slider & presets produce capped speeds, clamped to the slider range.
+    fn capped_speed() {
+        let mut speed = SpeedControl::default();
+
+        for preset in SPEED_PRESETS {
+            speed.set_ticks_per_second(preset);
+            assert_eq!(speed.speed().get().map(|tps| tps.get()), Some(preset));
+        }
+
+        speed.set_ticks_per_second(0);
+        assert_eq!(speed.speed().get().map(|tps| tps.get()), Some(1));
+        speed.set_ticks_per_second(1000);
+        assert_eq!(speed.speed().get().map(|tps| tps.get()), Some(60));
+    }
+
+    #[test]
+    /// Uncapping ignores the selected speed, which is restored when capped again.
+    fn uncapped_toggle() {
+        let mut speed = SpeedControl::default();
+        speed.set_ticks_per_second(10);
+
+        speed.set_uncapped(true);
+        assert_eq!(speed.speed().get(), None);
+
+        speed.set_uncapped(false);
+        assert_eq!(speed.speed().get().map(|tps| tps.get()), Some(10));
+    }
+
+    #[test]
+    /// The automatic speed only applies whilst uncapped.
+    fn automatic_when_uncapped() {
+        let mut speed = SpeedControl {
+            automatic: true,
+            ..Default::default()
+        };
+        assert!(speed.speed().is_automatic());
+
+        speed.set_ticks_per_second(10);
+        assert!(!speed.speed().is_automatic());
+    }
+}
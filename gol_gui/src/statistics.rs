@@ -0,0 +1,215 @@
+//! Contains [`StatisticsRecorder`].
+//! See its documentation for more information.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{lang, toast::Toasts};
+
+lang! {
+    WRITE_THREAD_ERROR, "Unable to create thread for exporting the statistics.";
+    NO_SAMPLES, "No generations were recorded.";
+    EXPORT_SUCCESS, "Saved statistics to: ";
+    EXPORT_FAIL, "Unable to save statistics: "
+}
+
+/// The header row of the exported CSV.
+const CSV_HEADER: &str = "generation,population";
+/// The maximum numbered suffix tried when the name of an export is already taken.
+const MAX_FILENAME_SUFFIX: u32 = 16;
+
+/// The result of writing the statistics on a separate thread.
+type ExportResult = io::Result<Box<Path>>;
+
+/// Records the population of each generation sent by the simulator & exports them as a CSV file.
+pub(crate) struct StatisticsRecorder {
+    /// Whether new samples are being recorded.
+    recording: bool,
+    /// The generation & population of each recorded generation.
+    samples: Vec<(u64, u64)>,
+
+    /// Sender for the writing threads to report their results.
+    result_sender: mpsc::Sender<ExportResult>,
+    /// Receives the results of the writing threads.
+    result_receiver: mpsc::Receiver<ExportResult>,
+}
+
+impl Default for StatisticsRecorder {
+    fn default() -> Self {
+        let (result_sender, result_receiver) = mpsc::channel();
+        Self {
+            recording: false,
+            samples: Vec::new(),
+            result_sender,
+            result_receiver,
+        }
+    }
+}
+
+impl StatisticsRecorder {
+    /// Whether new samples are being recorded.
+    pub(crate) fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Discards any previously recorded samples & starts recording.
+    pub(crate) fn start(&mut self) {
+        self.recording = true;
+        self.samples.clear();
+    }
+
+    /// Records the population of a generation, if recording.
+    pub(crate) fn record(&mut self, generation: u64, population: u64) {
+        if self.recording {
+            self.samples.push((generation, population));
+        }
+    }
+
+    /// Stops recording & writes the recorded samples as a CSV file within the given directory.
+    ///
+    /// The file is written on a separate thread, with the outcome being displayed once it is received by
+    /// [`Self::poll`].
+    pub(crate) fn stop(&mut self, directory: PathBuf, toasts: &mut Toasts) {
+        self.recording = false;
+        let samples = std::mem::take(&mut self.samples);
+
+        if samples.is_empty() {
+            toasts.info(NO_SAMPLES);
+            return;
+        }
+
+        let result_sender = self.result_sender.clone();
+        let spawn = thread::Builder::new()
+            .name("Csv_Writer".into())
+            .spawn(move || {
+                let result = save_csv(&samples, directory);
+                // The ui closing before writing finishes is not an error.
+                let _ = result_sender.send(result);
+            });
+
+        if let Err(err) = spawn {
+            toasts.error(format!("{WRITE_THREAD_ERROR} {err}"));
+        }
+    }
+
+    /// Displays the outcome of any finished exports.
+    pub(crate) fn poll(&mut self, toasts: &mut Toasts) {
+        while let Ok(result) = self.result_receiver.try_recv() {
+            match result {
+                Ok(path) => toasts.info(format!("{EXPORT_SUCCESS}{}", path.display())),
+                Err(err) => toasts.error(format!("{EXPORT_FAIL}{err}")),
+            }
+        }
+    }
+}
+
+/// Creates a new CSV file within the given directory containing the samples.
+///
+/// The returned value is the path to the created file.
+fn save_csv(samples: &[(u64, u64)], directory: PathBuf) -> ExportResult {
+    std::fs::create_dir_all(&directory)?;
+
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let (file, path) = create_unique_file(&directory, &format!("statistics_{}", time.as_secs()))?;
+
+    let mut writer = BufWriter::new(file);
+    write_csv(samples, &mut writer)?;
+    // Errors are only reported by an explicit flush, as dropping the writer ignores them.
+    writer.flush()?;
+
+    Ok(path.into())
+}
+
+/// Creates a new CSV file with the given name within the directory, returning it & its path.
+///
+/// If the file already exists, such as from an export within the same second, a numbered suffix is added to the name
+/// until a file that does not exist is found.
+fn create_unique_file(directory: &Path, name: &str) -> io::Result<(File, PathBuf)> {
+    let mut path = directory.join(name).with_extension("csv");
+    for suffix in 1..=MAX_FILENAME_SUFFIX {
+        match File::create_new(&path) {
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                path = directory
+                    .join(format!("{name}-{suffix}"))
+                    .with_extension("csv");
+            }
+            result => return result.map(|file| (file, path)),
+        }
+    }
+
+    File::create_new(&path).map(|file| (file, path))
+}
+
+/// Writes the samples as CSV, with a header row followed by a row for each sample.
+fn write_csv(samples: &[(u64, u64)], mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "{CSV_HEADER}")?;
+    for (generation, population) in samples {
+        writeln!(writer, "{generation},{population}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Each sample is written on its own row after the header.
+    fn csv_format() {
+        let mut csv = Vec::new();
+        write_csv(&[(0, 3), (1, 3), (2, 12_345)], &mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "generation,population\n0,3\n1,3\n2,12345\n"
+        );
+    }
+
+    /// The name of the file, without its extension.
+    fn file_stem(path: &Path) -> String {
+        path.file_stem().unwrap().to_string_lossy().into_owned()
+    }
+
+    #[test]
+    /// Exports with the same name are given a numbered suffix rather than failing.
+    fn unique_names() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+
+        let first = save_csv(&[(0, 1)], temp_dir.path().to_path_buf()).expect("Can export");
+        let (_, second) = create_unique_file(temp_dir.path(), &file_stem(&first)).unwrap();
+        let (_, third) = create_unique_file(temp_dir.path(), &file_stem(&first)).unwrap();
+
+        assert_eq!(
+            second,
+            temp_dir.path().join(format!("{}-1.csv", file_stem(&first)))
+        );
+        assert_eq!(
+            third,
+            temp_dir.path().join(format!("{}-2.csv", file_stem(&first)))
+        );
+    }
+
+    #[test]
+    /// Samples are only kept whilst recording, & recording again discards the previous samples.
+    fn record_samples() {
+        let mut recorder = StatisticsRecorder::default();
+        recorder.record(0, 5);
+        assert!(recorder.samples.is_empty());
+
+        recorder.start();
+        recorder.record(1, 5);
+        recorder.record(2, 6);
+        assert_eq!(recorder.samples, [(1, 5), (2, 6)]);
+
+        recorder.start();
+        assert!(recorder.samples.is_empty());
+    }
+}
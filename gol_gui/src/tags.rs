@@ -0,0 +1,169 @@
+//! Contains [`KnownTags`].
+//! See its documentation for more information.
+
+use std::{collections::BTreeSet, path::PathBuf, sync::mpsc, thread};
+
+use gol_lib::persistence::load_blueprint_preview;
+
+/// The maximum amount of suggestions given for a partially typed tag.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// The tags used by the existing blueprints, which are suggested when typing tags for a new blueprint.
+///
+/// The blueprints are scanned on a separate thread, with the tags being cached until [`Self::invalidate`] is called.
+#[derive(Default)]
+pub(crate) struct KnownTags {
+    /// The cached tags, or [`None`] if they have not been scanned yet.
+    tags: Option<BTreeSet<Box<str>>>,
+    /// Receives the tags from the scanning thread, if a scan is in progress.
+    scan: Option<mpsc::Receiver<BTreeSet<Box<str>>>>,
+}
+
+impl KnownTags {
+    /// Scans the blueprints within the given directory for their tags, if they are not already cached or being
+    /// scanned.
+    pub(crate) fn scan(&mut self, location: PathBuf) {
+        if self.tags.is_some() || self.scan.is_some() {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let spawn = thread::Builder::new()
+            .name("Tag_Scan".into())
+            .spawn(move || {
+                let previews = load_blueprint_preview(location.as_path());
+                let tags =
+                    collect_tags(previews.iter().flatten().map(|preview| preview.get_tags()));
+                // The ui closing before the scan finishes is not an error.
+                let _ = sender.send(tags);
+            });
+
+        // Suggestions are not critical, so a failed scan is not reported.
+        if spawn.is_ok() {
+            self.scan = Some(receiver);
+        }
+    }
+
+    /// Discards the cached tags, so they are scanned again on the next [`Self::scan`].
+    pub(crate) fn invalidate(&mut self) {
+        self.tags = None;
+        self.scan = None;
+    }
+
+    /// Caches the tags from a finished scan, if any.
+    pub(crate) fn poll(&mut self) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+
+        match scan.try_recv() {
+            Ok(tags) => {
+                self.tags = Some(tags);
+                self.scan = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            // The scanning thread ended without sending any tags.
+            Err(mpsc::TryRecvError::Disconnected) => self.scan = None,
+        }
+    }
+
+    /// The known tags that start with the last tag being typed in the given comma separated list of tags.
+    pub(crate) fn suggestions<'a>(&'a self, input: &str) -> Vec<&'a str> {
+        let typed = last_tag(input);
+        let partial = typed.to_lowercase();
+        if partial.is_empty() {
+            return Vec::new();
+        }
+
+        self.tags
+            .iter()
+            .flatten()
+            // A tag that has been typed in full is not suggested.
+            .filter(|tag| tag.to_lowercase().starts_with(&partial) && &***tag != typed)
+            .map(|tag| &**tag)
+            .take(MAX_SUGGESTIONS)
+            .collect()
+    }
+}
+
+/// Collects the unique tags from each list of tags.
+fn collect_tags<'a>(tag_lists: impl IntoIterator<Item = &'a [Box<str>]>) -> BTreeSet<Box<str>> {
+    tag_lists.into_iter().flatten().cloned().collect()
+}
+
+/// The last tag in a comma separated list of tags, without surrounding whitespace.
+fn last_tag(input: &str) -> &str {
+    input.rsplit(',').next().unwrap_or_default().trim()
+}
+
+/// Replaces the last tag in a comma separated list of tags with the given tag.
+pub(crate) fn complete_tag(input: &str, tag: &str) -> String {
+    match input.rsplit_once(',') {
+        Some((before, _)) => format!("{before}, {tag}"),
+        None => tag.to_owned(),
+    }
+}
+
+/// Splits a comma separated list of tags into each tag, ignoring empty tags.
+pub(crate) fn split_tags(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Converts the tags into the boxed form used by previews.
+    fn boxed(tags: &[&str]) -> Box<[Box<str>]> {
+        tags.iter().map(|tag| (*tag).into()).collect()
+    }
+
+    #[test]
+    /// Tags used by multiple previews are only collected once.
+    fn collect_tags_deduplicates() {
+        let previews = [
+            boxed(&["Oscillator", "Small"]),
+            boxed(&["Spaceship", "Small"]),
+            boxed(&[]),
+            boxed(&["Oscillator"]),
+        ];
+
+        let tags = collect_tags(previews.iter().map(|tags| &**tags));
+        assert_eq!(
+            tags.iter().map(|tag| &**tag).collect::<Vec<_>>(),
+            ["Oscillator", "Small", "Spaceship"]
+        );
+    }
+
+    #[test]
+    /// Known tags starting with the last typed tag are suggested, regardless of case.
+    fn suggestions() {
+        let known = KnownTags {
+            tags: Some(collect_tags([&*boxed(&["Oscillator", "Orphan", "Small"])])),
+            scan: None,
+        };
+
+        assert_eq!(known.suggestions("small, osc"), ["Oscillator"]);
+        assert_eq!(known.suggestions("O"), ["Orphan", "Oscillator"]);
+        // A complete tag & an empty tag are not suggested for.
+        assert!(known.suggestions("Small").is_empty());
+        assert!(known.suggestions("Small, ").is_empty());
+    }
+
+    #[test]
+    /// Completing a tag replaces only the last tag.
+    fn complete() {
+        assert_eq!(complete_tag("osc", "Oscillator"), "Oscillator");
+        assert_eq!(
+            complete_tag("Small, osc", "Oscillator"),
+            "Small, Oscillator"
+        );
+        assert_eq!(
+            split_tags("Small, , Oscillator ").collect::<Vec<_>>(),
+            ["Small", "Oscillator"]
+        );
+    }
+}
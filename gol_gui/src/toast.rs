@@ -0,0 +1,99 @@
+//! Contains [`Toasts`].
+//! See its documentation for more information.
+
+use std::time::{Duration, Instant};
+
+use egui::{Align2, Id};
+
+/// The egui id for the area the toasts are drawn in.
+const TOAST_AREA: &str = "Toast_Area";
+/// How long each toast is displayed for before being removed.
+const TOAST_DURATION: Duration = Duration::from_secs(6);
+
+/// The severity of a toast. This determines the colour of the toast text.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum ToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A single message displayed to the user.
+#[derive(Debug)]
+struct Toast {
+    level: ToastLevel,
+    text: String,
+    created: Instant,
+}
+
+/// Short lived messages displayed in the bottom right corner of the screen.
+///
+/// Unlike unrecoverable errors, toasts do not stop the user from interacting with the application.
+#[derive(Default, Debug)]
+pub(crate) struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    /// Displays an informational message.
+    pub(crate) fn info(&mut self, text: impl Into<String>) {
+        self.add(ToastLevel::Info, text.into());
+    }
+
+    /// Displays a warning message.
+    pub(crate) fn warning(&mut self, text: impl Into<String>) {
+        self.add(ToastLevel::Warning, text.into());
+    }
+
+    /// Displays an error message.
+    pub(crate) fn error(&mut self, text: impl Into<String>) {
+        self.add(ToastLevel::Error, text.into());
+    }
+
+    /// The amount of toasts being displayed.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    fn add(&mut self, level: ToastLevel, text: String) {
+        match level {
+            ToastLevel::Info => log::info!("{text}"),
+            ToastLevel::Warning => log::warn!("{text}"),
+            ToastLevel::Error => log::error!("{text}"),
+        }
+
+        self.toasts.push(Toast {
+            level,
+            text,
+            created: Instant::now(),
+        });
+    }
+
+    /// Draws the current toasts, removing any that have expired.
+    pub(crate) fn draw(&mut self, ctx: &egui::Context) {
+        self.toasts
+            .retain(|toast| toast.created.elapsed() < TOAST_DURATION);
+
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        egui::Area::new(Id::new(TOAST_AREA))
+            .anchor(Align2::RIGHT_BOTTOM, (-10.0, -10.0))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                for toast in &self.toasts {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        let colour = match toast.level {
+                            ToastLevel::Info => ui.visuals().text_color(),
+                            ToastLevel::Warning => ui.visuals().warn_fg_color,
+                            ToastLevel::Error => ui.visuals().error_fg_color,
+                        };
+                        ui.colored_label(colour, &toast.text);
+                    });
+                }
+            });
+    }
+}
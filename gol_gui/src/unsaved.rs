@@ -0,0 +1,142 @@
+//! Contains [`UnsavedChanges`].
+//! See its documentation for more information.
+
+use gol_lib::communication::UiPacket;
+
+/// Tracks whether the user has edited the board since it was last saved or loaded.
+///
+/// Edits are counted from the packets sent to the simulator. As the simulator handles packets in order, a save only
+/// contains the edits sent before it was requested.
+#[derive(Default, Debug)]
+pub(crate) struct UnsavedChanges {
+    /// The amount of edits made to the board.
+    revision: u64,
+    /// The revision of the board when it was last saved or loaded.
+    saved: u64,
+    /// The revision of the board when the pending save was requested.
+    pending: Option<u64>,
+}
+
+impl UnsavedChanges {
+    /// Updates the tracked state with a packet sent to the simulator.
+    ///
+    /// Every packet is matched explicitly, so new packets have to be considered as to whether they edit the board.
+    pub(crate) fn observe(&mut self, packet: &UiPacket) {
+        match packet {
+            UiPacket::Set { .. }
+            | UiPacket::SetMany { .. }
+            | UiPacket::LoadBoard { .. }
+            | UiPacket::LoadBlueprint { .. }
+            | UiPacket::Randomize { .. }
+            | UiPacket::Clear
+            | UiPacket::Mirror { .. }
+            | UiPacket::Rotate { .. }
+            | UiPacket::GotoGeneration { .. } => self.revision += 1,
+            UiPacket::SaveBoard => self.pending = Some(self.revision),
+            // Running the simulation is not an edit by the user.
+            UiPacket::Start
+            | UiPacket::StartUntil { .. }
+            | UiPacket::Stop
+            | UiPacket::ClearTarget
+            | UiPacket::Step { .. } => {}
+            // Settings & requests for information leave the board unchanged.
+            UiPacket::DisplayArea { .. }
+            | UiPacket::SaveBlueprint { .. }
+            | UiPacket::RequestBoardArea
+            | UiPacket::SimulationSpeed { .. }
+            | UiPacket::PeriodDetection { .. }
+            | UiPacket::GenerationHistory { .. }
+            | UiPacket::CellLimit { .. }
+            | UiPacket::SetStopCondition { .. }
+            | UiPacket::RecordStatistics { .. }
+            | UiPacket::DisplayAges { .. }
+            | UiPacket::DisplayChanges { .. }
+            | UiPacket::Terminate => {}
+        }
+    }
+
+    /// The pending save has finished, successfully or not.
+    pub(crate) fn save_finished(&mut self, success: bool) {
+        if let (Some(revision), true) = (self.pending.take(), success) {
+            self.saved = revision;
+        }
+    }
+
+    /// A board was loaded, so the board matches a save file.
+    pub(crate) fn loaded(&mut self) {
+        self.saved = self.revision;
+    }
+
+    /// Whether the board has been edited since it was last saved or loaded.
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.revision != self.saved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Edits make the board dirty until it is saved.
+    fn edit_then_save() {
+        let mut unsaved = UnsavedChanges::default();
+        assert!(!unsaved.is_dirty());
+
+        unsaved.observe(&UiPacket::Clear);
+        assert!(unsaved.is_dirty());
+
+        // Packets that don't edit the board are ignored.
+        unsaved.observe(&UiPacket::Start);
+        unsaved.observe(&UiPacket::SaveBoard);
+        assert!(unsaved.is_dirty());
+
+        unsaved.save_finished(true);
+        assert!(!unsaved.is_dirty());
+    }
+
+    #[test]
+    /// Rewinding to another generation changes the board, so is an edit.
+    fn goto_generation() {
+        let mut unsaved = UnsavedChanges::default();
+        unsaved.observe(&UiPacket::GotoGeneration { generation: 3 });
+        assert!(unsaved.is_dirty());
+    }
+
+    #[test]
+    /// A failed save leaves the board dirty.
+    fn failed_save() {
+        let mut unsaved = UnsavedChanges::default();
+        unsaved.observe(&UiPacket::SetMany { cells: [].into() });
+
+        unsaved.observe(&UiPacket::SaveBoard);
+        unsaved.save_finished(false);
+        assert!(unsaved.is_dirty());
+
+        // The failed save is no longer pending.
+        unsaved.save_finished(true);
+        assert!(unsaved.is_dirty());
+    }
+
+    #[test]
+    /// Edits made after a save was requested are not included in it.
+    fn edit_during_save() {
+        let mut unsaved = UnsavedChanges::default();
+        unsaved.observe(&UiPacket::Clear);
+
+        unsaved.observe(&UiPacket::SaveBoard);
+        unsaved.observe(&UiPacket::Clear);
+        unsaved.save_finished(true);
+        assert!(unsaved.is_dirty());
+    }
+
+    #[test]
+    /// Loading a board discards any edits.
+    fn load_board() {
+        let mut unsaved = UnsavedChanges::default();
+        unsaved.observe(&UiPacket::Clear);
+
+        unsaved.loaded();
+        assert!(!unsaved.is_dirty());
+    }
+}
@@ -1,8 +1,11 @@
 use crate::GlobalPosition;
 
 /// A single wrapper struct around the two opposite corners of rectangle.
-#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, Hash)]
-#[cfg_attr(any(test, debug_assertions), derive(Debug))]
+///
+/// Areas are serialised compactly as `[min_x, min_y, max_x, max_y]`. The structured form of `min` & `max` positions
+/// written by older versions is still accepted when deserialising.
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, Hash, Debug)]
+#[serde(into = "AreaFormat", from = "AreaFormat")]
 pub struct Area {
     /// The min x & the min y position.
     pub(crate) min: GlobalPosition,
@@ -10,6 +13,97 @@ pub struct Area {
     pub(crate) max: GlobalPosition,
 }
 
+/// An iterator over every position within an [`Area`], increasing x then y.
+///
+/// See [`Area::iterate_over`] for more information.
+#[derive(Clone, Debug)]
+pub struct AreaIter {
+    /// The x position each row starts at.
+    min_x: i32,
+    /// The last position to iterate over.
+    max: GlobalPosition,
+    /// The position that will be returned next, or [`None`] if the iterator is exhausted.
+    next: Option<GlobalPosition>,
+}
+
+impl Iterator for AreaIter {
+    type Item = GlobalPosition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+
+        // The next position is found without going past the max, so areas at the edge of i32 do not overflow.
+        self.next = match (current.x < self.max.x, current.y < self.max.y) {
+            (true, _) => Some(GlobalPosition::new(current.x + 1, current.y)),
+            (false, true) => Some(GlobalPosition::new(self.min_x, current.y + 1)),
+            (false, false) => None,
+        };
+
+        Some(current)
+    }
+
+    /// The exact amount of positions remaining, unless there are more than fit in a [`usize`].
+    ///
+    /// This is why the iterator is not an [`ExactSizeIterator`], as large areas can contain more positions.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let Some(next) = self.next else {
+            return (0, Some(0));
+        };
+
+        let width = (self.max.x as i64 - self.min_x as i64 + 1) as u64;
+        let remaining_rows = (self.max.y as i64 - next.y as i64) as u64;
+        let remaining_in_row = (self.max.x as i64 - next.x as i64 + 1) as u64;
+
+        let remaining = remaining_rows
+            .checked_mul(width)
+            .and_then(|cells| cells.checked_add(remaining_in_row))
+            .and_then(|cells| usize::try_from(cells).ok());
+        match remaining {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl core::iter::FusedIterator for AreaIter {}
+
+impl core::fmt::Display for Area {
+    /// Formats the area as `(min_x, min_y)..(max_x, max_y)`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}..{}", self.min, self.max)
+    }
+}
+
+/// The formats an [`Area`] can be serialised in.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum AreaFormat {
+    /// The min x, min y, max x & max y positions.
+    Compact([i32; 4]),
+    /// The min & max positions, as written by older versions.
+    Structured {
+        min: GlobalPosition,
+        max: GlobalPosition,
+    },
+}
+
+impl From<Area> for AreaFormat {
+    fn from(area: Area) -> Self {
+        AreaFormat::Compact([area.min.x, area.min.y, area.max.x, area.max.y])
+    }
+}
+
+impl From<AreaFormat> for Area {
+    fn from(format: AreaFormat) -> Self {
+        match format {
+            AreaFormat::Compact([min_x, min_y, max_x, max_y]) => {
+                Area::new((min_x, min_y), (max_x, max_y))
+            }
+            AreaFormat::Structured { min, max } => Area::new(min, max),
+        }
+    }
+}
+
 impl Default for Area {
     /// Constructs a new [`Area`], with zero size.
     fn default() -> Self {
@@ -20,40 +114,105 @@ impl Default for Area {
 impl Area {
     /// Constructs a new [`Area`] covering from the small x & y to the large x & y.
     pub fn new(pos1: impl Into<GlobalPosition>, pos2: impl Into<GlobalPosition>) -> Self {
-        let pos1 = pos1.into();
-        let pos2 = pos2.into();
+        Self::from_corners(pos1.into(), pos2.into())
+    }
 
-        // Construct from with the smallest x & y
-        let min = GlobalPosition {
-            x: pos1.get_x().min(pos2.get_x()),
-            y: pos1.get_y().min(pos2.get_y()),
+    /// Constructs a new [`Area`] covering from the small x & y to the large x & y of the given corners.
+    ///
+    /// This is the same as [`Area::new`], but can be used in const contexts.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use gol_lib::{Area, GlobalPosition};
+    /// const AREA: Area = Area::from_corners(GlobalPosition::new(5, -1), GlobalPosition::new(-2, 3));
+    ///
+    /// assert_eq!(AREA, Area::new((-2, -1), (5, 3)));
+    /// ```
+    pub const fn from_corners(pos1: GlobalPosition, pos2: GlobalPosition) -> Self {
+        // `Ord::min` & `Ord::max` can't be called in const contexts, so the positions are compared directly.
+        let (min_x, max_x) = match pos1.x <= pos2.x {
+            true => (pos1.x, pos2.x),
+            false => (pos2.x, pos1.x),
         };
-        // Construct to with the biggest x & y
-        let max = GlobalPosition {
-            x: pos1.get_x().max(pos2.get_x()),
-            y: pos1.get_y().max(pos2.get_y()),
+        let (min_y, max_y) = match pos1.y <= pos2.y {
+            true => (pos1.y, pos2.y),
+            false => (pos2.y, pos1.y),
         };
 
-        Self { min, max }
+        Self {
+            min: GlobalPosition::new(min_x, min_y),
+            max: GlobalPosition::new(max_x, max_y),
+        }
+    }
+
+    /// Constructs a new [`Area`] centred on the given position, extending by the half width & half height on each
+    /// side.
+    ///
+    /// The area stops at the edges of the board, rather than wrapping around to the other side.
+    pub fn from_center(center: GlobalPosition, half_width: u32, half_height: u32) -> Self {
+        let (x, y) = (center.get_x(), center.get_y());
+        Self::new(
+            (
+                x.saturating_sub_unsigned(half_width),
+                y.saturating_sub_unsigned(half_height),
+            ),
+            (
+                x.saturating_add_unsigned(half_width),
+                y.saturating_add_unsigned(half_height),
+            ),
+        )
+    }
+
+    /// Returns a copy of this area with every side moved outwards by the given amount, or inwards if it is negative.
+    ///
+    /// If the area would be shrunk past zero size along an axis, it is instead shrunk to the centre of that axis. The
+    /// area stops at the edges of the board when grown.
+    pub fn expanded(&self, by: i32) -> Area {
+        /// Expands the range from min to max, collapsing it to its centre if it would be inverted.
+        fn expand(min: i32, max: i32, by: i32) -> (i32, i32) {
+            let (new_min, new_max) = (min.saturating_sub(by), max.saturating_add(by));
+            match new_min > new_max {
+                true => {
+                    // Computed as i64, as the distance between the edges of the board doesn't fit in an i32.
+                    let centre = (min as i64 + (max as i64 - min as i64) / 2) as i32;
+                    (centre, centre)
+                }
+                false => (new_min, new_max),
+            }
+        }
+
+        let (min_x, max_x) = expand(self.min.x, self.max.x, by);
+        let (min_y, max_y) = expand(self.min.y, self.max.y, by);
+        Area::new((min_x, min_y), (max_x, max_y))
+    }
+
+    /// Returns a copy of this area moved by the given amount along each axis.
+    ///
+    /// The area stops at the edges of the board without changing size, as [`Self::translate_x`] does.
+    pub fn translated(&self, dx: i32, dy: i32) -> Area {
+        let mut area = *self;
+        area.translate_x(dx);
+        area.translate_y(dy);
+        area
     }
 
     /// Gets the minimum x & minimum y of the area.
-    pub fn get_min(&self) -> GlobalPosition {
+    pub const fn get_min(&self) -> GlobalPosition {
         self.min
     }
 
     /// Gets the maximum x & biggest y of the area.
-    pub fn get_max(&self) -> GlobalPosition {
+    pub const fn get_max(&self) -> GlobalPosition {
         self.max
     }
 
     /// A range from the minimum x to the maximum x (inclusive).
-    pub fn x_range(&self) -> std::ops::RangeInclusive<i32> {
+    pub fn x_range(&self) -> core::ops::RangeInclusive<i32> {
         self.get_min().get_x()..=self.get_max().get_x()
     }
 
     /// A range from the minimum y to the maximum y (inclusive).
-    pub fn y_range(&self) -> std::ops::RangeInclusive<i32> {
+    pub fn y_range(&self) -> core::ops::RangeInclusive<i32> {
         self.get_min().get_y()..=self.get_max().get_y()
     }
 
@@ -82,64 +241,85 @@ impl Area {
     /// assert_eq!(iterate_over.next().unwrap(), (1, 1).into());
     /// assert!(iterate_over.next().is_none());
     /// ```
-    pub fn iterate_over(&self) -> impl Iterator<Item = GlobalPosition> {
-        let GlobalPosition { x: min_x, y: min_y } = self.get_min();
-        let GlobalPosition { x: max_x, y: max_y } = self.get_max();
-
-        let mut x_pos = min_x - 1;
-        let mut y_pos = min_y;
-        std::iter::from_fn(move || {
-            x_pos += 1;
-
-            if x_pos > max_x {
-                x_pos = min_x;
-                y_pos += 1;
-            }
-
-            if y_pos > max_y {
-                return None;
-            }
-
-            Some(GlobalPosition::new(x_pos, y_pos))
-        })
+    pub const fn iterate_over(&self) -> AreaIter {
+        AreaIter {
+            min_x: self.min.x,
+            max: self.max,
+            next: Some(self.min),
+        }
     }
 
+    /// Moves the area along the x axis by the given amount.
+    ///
+    /// The area stops at the edge of the board without changing size, rather than overflowing.
     pub fn translate_x(&mut self, move_by: i32) {
+        let move_by = clamp_move(self.min.x, self.max.x, move_by);
         self.min.x += move_by;
         self.max.x += move_by;
     }
 
+    /// Moves the area along the y axis by the given amount.
+    ///
+    /// The area stops at the edge of the board without changing size, rather than overflowing.
     pub fn translate_y(&mut self, move_by: i32) {
+        let move_by = clamp_move(self.min.y, self.max.y, move_by);
         self.min.y += move_by;
         self.max.y += move_by;
     }
 
     /// Modifies the area via increasing/decreasing the maximum x position by the given amount.
     ///
-    /// If the modified x would be lower than the minimum x, it will instead be set to the minimum x value.
+    /// If the modified x would be lower than the minimum x, it will instead be set to the minimum x value. It stops at
+    /// the edge of the board rather than overflowing.
     pub fn modify_x(&mut self, x_change: i32) {
-        self.max.x = self.min.x.max(self.max.x + x_change);
+        self.max.x = self.min.x.max(self.max.x.saturating_add(x_change));
     }
 
     /// Modifies the area via increasing/decreasing the maximum y position by the given amount.
     ///
-    /// If the modified y would be lower than the minimum y, it will instead be set to the minimum y value.
+    /// If the modified y would be lower than the minimum y, it will instead be set to the minimum y value. It stops at
+    /// the edge of the board rather than overflowing.
     pub fn modify_y(&mut self, y_change: i32) {
-        self.max.y = self.min.y.max(self.max.y + y_change)
+        self.max.y = self.min.y.max(self.max.y.saturating_add(y_change))
+    }
+
+    /// The amount of cells within the area, calculated without iterating over them.
+    ///
+    /// This is correct for any area, except the area covering every position, which saturates to [`u64::MAX`] as it
+    /// contains exactly one more cell.
+    pub const fn cell_count(&self) -> u64 {
+        let width = (self.max.x as i64 - self.min.x as i64) as u64 + 1;
+        let height = (self.max.y as i64 - self.min.y as i64) as u64 + 1;
+        width.saturating_mul(height)
     }
 
-    pub fn x_difference(&self) -> i32 {
+    pub const fn x_difference(&self) -> i32 {
         self.max.x - self.min.x
     }
 
-    pub fn y_difference(&self) -> i32 {
+    pub const fn y_difference(&self) -> i32 {
         self.max.y - self.min.y
     }
 }
 
+/// Limits the amount a range from min to max is moved by, so that neither end moves past the edge of the board.
+const fn clamp_move(min: i32, max: i32, move_by: i32) -> i32 {
+    let lowest = i32::MIN as i64 - min as i64;
+    let highest = i32::MAX as i64 - max as i64;
+    // The range always fits within the board, so the limits always include 0 & fit within an i32.
+    if (move_by as i64) < lowest {
+        lowest as i32
+    } else if (move_by as i64) > highest {
+        highest as i32
+    } else {
+        move_by
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod area_tests {
     use super::*;
+    use alloc::{string::ToString, vec::Vec};
 
     #[test]
     /// Tests that the fields within the area struct are correctly sorted into the smallest x & y and into the
@@ -227,4 +407,255 @@ pub(crate) mod area_tests {
         area.modify_y(10);
         assert_eq!(area, Area::new((1, 1), (14, 14)));
     }
+
+    #[test]
+    /// The cell count includes both the minimum & maximum positions.
+    fn cell_count() {
+        assert_eq!(Area::default().cell_count(), 1);
+        assert_eq!(Area::new((-1, -2), (1, 2)).cell_count(), 15);
+    }
+
+    #[test]
+    /// The cell count matches the amount of cells iterated over, for a single cell & a single row.
+    fn cell_count_small() {
+        for area in [Area::default(), Area::new((-3, 7), (12, 7))] {
+            assert_eq!(
+                area.cell_count(),
+                area.iterate_over().count() as u64,
+                "{area}"
+            );
+        }
+        assert_eq!(Area::new((-3, 7), (12, 7)).cell_count(), 16);
+    }
+
+    #[test]
+    /// Areas with more cells than fit in a u32 are counted without overflowing.
+    fn cell_count_large() {
+        assert_eq!(
+            Area::new((0, 0), (99_999, 99_999)).cell_count(),
+            10_000_000_000
+        );
+        assert_eq!(
+            Area::new((i32::MIN, 0), (i32::MAX, 0)).cell_count(),
+            u32::MAX as u64 + 1
+        );
+        assert_eq!(
+            Area::new((i32::MIN, i32::MIN), (i32::MAX, i32::MAX)).cell_count(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    /// An area built from a centre extends equally on each side.
+    fn from_center() {
+        let area = Area::from_center((5, -5).into(), 2, 3);
+        assert_eq!(area, Area::new((3, -8), (7, -2)));
+
+        let area = Area::from_center((1, 1).into(), 0, 0);
+        assert_eq!(area, Area::new((1, 1), (1, 1)));
+    }
+
+    #[test]
+    /// An area centred near the edges of the board stops at the edges.
+    fn from_center_saturates() {
+        let area = Area::from_center((i32::MAX - 1, i32::MIN + 1).into(), 5, u32::MAX);
+        assert_eq!(
+            area,
+            Area::new((i32::MAX - 6, i32::MIN), (i32::MAX, i32::MAX))
+        );
+    }
+
+    #[test]
+    /// Expanding moves every side outwards.
+    fn expanded_grows() {
+        let area = Area::new((0, 0), (4, 2));
+        assert_eq!(area.expanded(2), Area::new((-2, -2), (6, 4)));
+        // The original is not modified.
+        assert_eq!(area, Area::new((0, 0), (4, 2)));
+    }
+
+    #[test]
+    /// A zero sized area can be expanded.
+    fn expanded_zero_area() {
+        let area = Area::new((3, 3), (3, 3));
+        assert_eq!(area.expanded(1), Area::new((2, 2), (4, 4)));
+    }
+
+    #[test]
+    /// Shrinking an area can reduce it to zero size, but no further.
+    fn expanded_shrinks_to_zero() {
+        let area = Area::new((0, 0), (4, 4));
+        assert_eq!(area.expanded(-2), Area::new((2, 2), (2, 2)));
+        assert_eq!(area.expanded(-10), Area::new((2, 2), (2, 2)));
+
+        // Each axis is shrunk separately.
+        let area = Area::new((0, 0), (6, 2));
+        assert_eq!(area.expanded(-2), Area::new((2, 1), (4, 1)));
+    }
+
+    #[test]
+    /// Expanding or shrinking an area spanning the board doesn't overflow.
+    fn expanded_extremes() {
+        let area = Area::new((i32::MIN, i32::MIN), (i32::MAX, i32::MAX));
+        assert_eq!(area.expanded(1), area);
+        assert_eq!(area.expanded(i32::MAX), area);
+        assert_eq!(area.expanded(i32::MIN), Area::new((-1, -1), (-1, -1)));
+
+        let area = Area::new((i32::MAX - 2, 0), (i32::MAX, 0));
+        assert_eq!(
+            area.expanded(2),
+            Area::new((i32::MAX - 4, -2), (i32::MAX, 2))
+        );
+    }
+
+    #[test]
+    /// Moving towards the edges of the board stops at the edge without changing the size of the area.
+    fn translate_saturates() {
+        let area = Area::new((-10, -5), (10, 5));
+
+        assert_eq!(
+            area.translated(i32::MAX, i32::MIN),
+            Area::new((i32::MAX - 20, i32::MIN), (i32::MAX, i32::MIN + 10))
+        );
+        assert_eq!(
+            area.translated(i32::MIN, i32::MAX),
+            Area::new((i32::MIN, i32::MAX - 10), (i32::MIN + 20, i32::MAX))
+        );
+
+        let mut at_edge = Area::new((i32::MAX - 1, 0), (i32::MAX, 0));
+        at_edge.translate_x(1);
+        at_edge.translate_y(i32::MIN);
+        assert_eq!(
+            at_edge,
+            Area::new((i32::MAX - 1, i32::MIN), (i32::MAX, i32::MIN))
+        );
+    }
+
+    #[test]
+    /// Expanding the maximum position stops at the edge of the board.
+    fn modify_saturates() {
+        let mut area = Area::new((i32::MAX - 5, i32::MAX - 5), (i32::MAX - 1, i32::MAX - 1));
+        area.modify_x(10);
+        area.modify_y(i32::MAX);
+
+        assert_eq!(area.get_max(), (i32::MAX, i32::MAX).into());
+    }
+
+    #[test]
+    /// Translating moves the area without changing its size.
+    fn translated() {
+        let area = Area::new((-1, -1), (2, 3));
+        let moved = area.translated(10, -5);
+
+        assert_eq!(moved, Area::new((9, -6), (12, -2)));
+        assert_eq!(moved.x_difference(), area.x_difference());
+        assert_eq!(moved.y_difference(), area.y_difference());
+    }
+
+    #[test]
+    /// The size hint of the iterator is the exact amount of positions it has left to return.
+    fn iterate_over_len() {
+        let areas = [
+            Area::default(),
+            Area::new((0, 0), (4, 0)),
+            Area::new((0, 0), (0, 4)),
+            Area::new((-3, -2), (3, 2)),
+            Area::new((10, 20), (12, 25)),
+        ];
+
+        for area in areas {
+            let mut iterate_over = area.iterate_over();
+            let cells = area.cell_count() as usize;
+            assert_eq!(iterate_over.size_hint(), (cells, Some(cells)), "{area:?}");
+
+            for remaining in (0..cells).rev() {
+                iterate_over.next().expect("Has positions remaining");
+                assert_eq!(
+                    iterate_over.size_hint(),
+                    (remaining, Some(remaining)),
+                    "{area:?}"
+                );
+            }
+
+            assert!(iterate_over.next().is_none());
+            assert_eq!(iterate_over.size_hint(), (0, Some(0)));
+        }
+    }
+
+    #[test]
+    /// Areas with more positions than fit in a usize have no upper bound on their size hint.
+    fn iterate_over_len_huge() {
+        let every_position = Area::new((i32::MIN, i32::MIN), (i32::MAX, i32::MAX));
+        assert_eq!(
+            every_position.iterate_over().size_hint(),
+            (usize::MAX, None)
+        );
+    }
+
+    #[test]
+    /// Areas at the edge of the possible positions can be iterated over without overflowing.
+    fn iterate_over_edge() {
+        let area = Area::new((i32::MAX - 1, i32::MAX - 1), (i32::MAX, i32::MAX));
+
+        let positions: Vec<GlobalPosition> = area.iterate_over().collect();
+        assert_eq!(
+            positions,
+            [
+                (i32::MAX - 1, i32::MAX - 1).into(),
+                (i32::MAX, i32::MAX - 1).into(),
+                (i32::MAX - 1, i32::MAX).into(),
+                (i32::MAX, i32::MAX).into(),
+            ]
+        );
+    }
+
+    #[test]
+    /// Areas are displayed as their minimum & maximum positions.
+    fn display() {
+        assert_eq!(Area::new((1, 2), (3, 4)).to_string(), "(1, 2)..(3, 4)");
+        assert_eq!(
+            Area::new((5, -2), (-10, 8)).to_string(),
+            "(-10, -2)..(5, 8)"
+        );
+    }
+
+    #[test]
+    /// Areas are serialised compactly, & deserialised back into the same area.
+    fn serialise_compact() {
+        let area = Area::new((-3, 4), (10, 12));
+
+        let json = serde_json::to_string(&area).expect("Can serialise");
+        assert_eq!(json, "[-3,4,10,12]");
+        assert_eq!(
+            serde_json::from_str::<Area>(&json).expect("Can deserialise"),
+            area
+        );
+    }
+
+    #[test]
+    /// The structured form written by older versions can still be read.
+    fn deserialise_legacy() {
+        let json = r#"{ "min": { "x": -3, "y": 4 }, "max": { "x": 10, "y": 12 } }"#;
+
+        let area: Area = serde_json::from_str(json).expect("Can deserialise");
+        assert_eq!(area, Area::new((-3, 4), (10, 12)));
+    }
+
+    #[test]
+    /// Areas can be constructed & read in const contexts, sorting their corners the same as [`Area::new`].
+    fn const_areas() {
+        const AREAS: [Area; 2] = [
+            Area::from_corners(GlobalPosition::new(10, 5), GlobalPosition::new(5, 10)),
+            Area::from_corners(GlobalPosition::new(-3, -3), GlobalPosition::new(3, 3)),
+        ];
+        const CELLS: u64 = AREAS[1].cell_count();
+        const WIDTH: i32 = AREAS[0].x_difference();
+
+        assert_eq!(AREAS[0], Area::new((10, 5), (5, 10)));
+        assert_eq!(AREAS[0].get_min(), (5, 5).into());
+        assert_eq!(AREAS[0].get_max(), (10, 10).into());
+        assert_eq!(AREAS[1], Area::new((-3, -3), (3, 3)));
+        assert_eq!(CELLS, 49);
+        assert_eq!(WIDTH, 5);
+    }
 }
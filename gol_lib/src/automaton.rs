@@ -0,0 +1,183 @@
+//! Contains [`Automaton`].
+//! See its documentation for more information.
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{rotate, Area, Axis, Cell, CellState, GlobalPosition, RotateError, Rule, Symmetry};
+
+/// A board of cells that can be advanced through the generations of Conways game of life.
+///
+/// This only contains the computation of the board, so it can be used without the standard library. The displaying &
+/// saving of the board, which the simulator thread needs, is provided by [`Simulator`].
+///
+/// [`Simulator`]: crate::Simulator
+pub trait Automaton {
+    /// Advances the simulation by one tick.
+    fn tick(&mut self);
+
+    /// Sets the cell at the given position on the board.
+    fn set(&mut self, position: GlobalPosition, cell: Cell);
+
+    /// Gets the cell at the given position on the board.
+    fn get(&self, position: GlobalPosition) -> Cell;
+
+    /// Sets the state of a cell on the board.
+    ///
+    /// Simulators with only two states set decaying cells as dead.
+    fn set_state(&mut self, position: GlobalPosition, state: CellState) {
+        self.set(position, state.into());
+    }
+
+    /// Gets the state of a cell on the board.
+    fn get_state(&self, position: GlobalPosition) -> CellState {
+        self.get(position).into()
+    }
+
+    /// Gets the cells within the given area of the board.
+    ///
+    /// The cells are in the same order as [`Area::iterate_over`]; by increasing x, then by increasing y.
+    fn get_region(&self, area: Area) -> Box<[Cell]> {
+        area.iterate_over()
+            .map(|position| self.get(position))
+            .collect()
+    }
+
+    /// Gets the positions of every alive cell on the board, in no particular order.
+    ///
+    /// By default every cell within the board area is checked, so this should be overridden by simulators that can
+    /// find their alive cells directly, as the board area may contain far more cells than are alive.
+    fn alive_positions(&self) -> Vec<GlobalPosition> {
+        self.get_board_area()
+            .iterate_over()
+            .filter(|position| self.get(*position).is_alive())
+            .collect()
+    }
+
+    /// Whether the simulator can simulate boards with the given rule.
+    ///
+    /// Only Conway's game of life is supported by default.
+    fn supports_rule(&self, rule: Rule) -> bool {
+        rule == Rule::CONWAY
+    }
+
+    /// Sets the rule the board is simulated with. The rule must be supported, as given by [`Self::supports_rule`].
+    fn set_rule(&mut self, _rule: Rule) {}
+
+    /// Gets the rule the board is simulated with.
+    fn get_rule(&self) -> Rule {
+        Rule::CONWAY
+    }
+
+    /// Gets the current generation of simulation.
+    fn get_generation(&self) -> u64;
+
+    /// Sets the current generation of simulation.
+    fn set_generation(&mut self, generation: u64);
+
+    /// Advances the board by the given amount of generations.
+    ///
+    /// This can be overridden to avoid work that is only needed between individual generations.
+    fn tick_n(&mut self, generations: u64) {
+        for _ in 0..generations {
+            self.tick();
+        }
+    }
+
+    /// Sets all cells on the board to dead & sets the generation to 0.
+    fn reset(&mut self);
+
+    /// Sets all cells on the board to dead, without changing the generation.
+    fn clear(&mut self) {
+        for position in self.get_board_area().iterate_over() {
+            self.set(position, Cell::Dead);
+        }
+    }
+
+    /// Mirrors every alive cell on the board across the axis through the centre of the board area, without changing
+    /// the generation.
+    ///
+    /// See [`Axis::mirror`] for the exact position of the axis.
+    fn mirror(&mut self, axis: Axis) {
+        let area = self.get_board_area();
+        let alive = self.alive_positions();
+
+        self.clear();
+        for position in alive {
+            self.set(axis.mirror(area, position), Cell::Alive);
+        }
+    }
+
+    /// Rotates every alive cell on the board clockwise by the given amount of quarter turns about the centre of the
+    /// board area, without changing the generation.
+    ///
+    /// See [`rotate`] for the exact position of the centre. If any cell would be rotated beyond the range of
+    /// coordinates, an error is returned & the board is unchanged.
+    fn rotate(&mut self, quarter_turns: u8) -> Result<(), RotateError> {
+        let area = self.get_board_area();
+        let rotated = self
+            .alive_positions()
+            .into_iter()
+            .map(|position| rotate(area, quarter_turns, position))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.clear();
+        for position in rotated {
+            self.set(position, Cell::Alive);
+        }
+        Ok(())
+    }
+
+    /// Gets the area taken up by the current board. The area for a board is a rectangle bounding the alive cells.
+    fn get_board_area(&self) -> Area;
+
+    /// Gets the amount of alive cells on the board.
+    fn population(&self) -> u64 {
+        self.get_board_area()
+            .iterate_over()
+            .filter(|position| self.get(*position).is_alive())
+            .count() as u64
+    }
+
+    /// The fraction of the cells within the board area that are alive, from 0 to 1.
+    ///
+    /// This is 0 for an empty board, which has no board area.
+    fn density(&self) -> f64 {
+        match self.population() {
+            0 => 0.0,
+            population => population as f64 / self.get_board_area().cell_count() as f64,
+        }
+    }
+
+    /// Replaces the cells within the area with random cells, each being alive with a chance of the given density.
+    fn randomize(&mut self, area: Area, density: f32, seed: u64) {
+        self.randomize_symmetric(area, density, seed, Symmetry::None);
+    }
+
+    /// Replaces the cells within the area with random cells with the given symmetry about the centre of the area.
+    /// Each cell is alive with a chance of the given density.
+    ///
+    /// Only one cell of each set of symmetric cells is randomly generated, with the rest being copied from it, so the
+    /// generated cells are exactly symmetric. See [`Symmetry::symmetric_area`] for the area that is filled.
+    fn randomize_symmetric(&mut self, area: Area, density: f32, seed: u64, symmetry: Symmetry) {
+        let mut rng = fastrand::Rng::with_seed(seed);
+        let area = symmetry.symmetric_area(area);
+
+        for position in area.iterate_over() {
+            let orbit = symmetry.orbit(area, position);
+            // Only the first position of each orbit, in the order of iteration, is generated.
+            let first = orbit
+                .iter()
+                .min_by_key(|image| (image.get_y(), image.get_x()))
+                .copied()
+                .unwrap_or(position);
+            if first != position {
+                continue;
+            }
+
+            let cell = Cell::from(rng.f32() < density);
+            for image in orbit {
+                self.set(image, cell);
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Contains [`BoardHasher`].
+//! See its documentation for more information.
+
+use crate::Area;
+
+/// The FNV-1a offset basis for 64 bit hashes.
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The FNV-1a prime for 64 bit hashes.
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes the cells of a board with FNV-1a. Unlike the hasher of the standard library, the hash is the same on every
+/// platform & release, so it can be stored in golden tests.
+///
+/// The hash covers the size of the board area, & the index of each alive cell within it in the order of
+/// [`Area::iterate_over`]. Only positional hashes also cover the position of the area:
+/// - A positional hash only matches boards at the same position.
+/// - A hash without the position is translation-invariant, so it matches boards that are translated copies of each
+///   other.
+///
+/// # Examples
+/// ```
+/// # use gol_lib::{Area, BoardHasher};
+/// let hash = |area: Area, positional: bool| {
+///     let mut hasher = BoardHasher::new(area, positional);
+///     hasher.alive(0);
+///     hasher.finish()
+/// };
+/// let area = Area::new((0, 0), (2, 2));
+/// let translated = Area::new((5, -3), (7, -1));
+///
+/// assert_eq!(hash(area, false), hash(translated, false));
+/// assert_ne!(hash(area, true), hash(translated, true));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoardHasher {
+    state: u64,
+}
+
+impl BoardHasher {
+    /// Starts hashing a board within the given area, which includes the position of the area if `positional`.
+    pub fn new(area: Area, positional: bool) -> Self {
+        let mut hasher = Self {
+            state: OFFSET_BASIS,
+        };
+        hasher.write(&[positional as u8]);
+        if positional {
+            hasher.write(&area.get_min().get_x().to_le_bytes());
+            hasher.write(&area.get_min().get_y().to_le_bytes());
+        }
+        hasher.write(&area.x_difference().to_le_bytes());
+        hasher.write(&area.y_difference().to_le_bytes());
+        hasher
+    }
+
+    /// Adds the alive cell at the given index within the area, in the order of [`Area::iterate_over`].
+    ///
+    /// The alive cells must be added in increasing order of index.
+    pub fn alive(&mut self, index: u64) {
+        self.write(&index.to_le_bytes());
+    }
+
+    /// The hash of the board.
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(PRIME);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The hash is FNV-1a, so it matches the published test vectors.
+    fn fnv_1a() {
+        let mut hasher = BoardHasher {
+            state: OFFSET_BASIS,
+        };
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    /// The hash changes with the size of the area & the alive cells.
+    fn content_changes_hash() {
+        let hash = |area: Area, alive: &[u64]| {
+            let mut hasher = BoardHasher::new(area, false);
+            alive.iter().for_each(|index| hasher.alive(*index));
+            hasher.finish()
+        };
+        let area = Area::new((0, 0), (2, 2));
+
+        assert_eq!(hash(area, &[0, 4]), hash(area, &[0, 4]));
+        assert_ne!(hash(area, &[0, 4]), hash(area, &[0, 5]));
+        assert_ne!(hash(area, &[0]), hash(Area::new((0, 0), (2, 3)), &[0]));
+    }
+}
@@ -1,3 +1,6 @@
+use alloc::boxed::Box;
+use core::str::FromStr;
+
 /// Represents the state of a cell within the Conways game of life simulation.
 ///
 /// An alive cell is represented as `true`.
@@ -27,7 +30,50 @@ impl From<bool> for Cell {
     }
 }
 
+/// The text cannot be parsed as a [`Cell`].
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[error("\"{0}\" is not a cell, such as alive or dead")]
+pub struct CellError(Box<str>);
+
+impl FromStr for Cell {
+    type Err = CellError;
+
+    /// Parses a cell from its name, or its symbol in the plaintext & RLE pattern formats, in any case.
+    ///
+    /// Alive cells are `alive`, `O`, `1` or `*`, & dead cells are `dead`, `.`, `b` or `0`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let trimmed = text.trim();
+        let is_any = |names: &[&str]| names.iter().any(|name| name.eq_ignore_ascii_case(trimmed));
+
+        if is_any(&["alive", "o", "1", "*"]) {
+            Ok(Cell::Alive)
+        } else if is_any(&["dead", ".", "b", "0"]) {
+            Ok(Cell::Dead)
+        } else {
+            Err(CellError(text.into()))
+        }
+    }
+}
+
+impl TryFrom<&str> for Cell {
+    type Error = CellError;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
 impl Cell {
+    /// Returns `true` if the cell is alive.
+    pub fn is_alive(self) -> bool {
+        self == Cell::Alive
+    }
+
+    /// Returns `true` if the cell is dead.
+    pub fn is_dead(self) -> bool {
+        self == Cell::Dead
+    }
+
     /// Returns the opposite of the current cell.
     pub fn invert(self) -> Cell {
         match self {
@@ -36,3 +82,188 @@ impl Cell {
         }
     }
 }
+
+/// How a cell changed during the last generation the board was advanced by.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash)]
+pub enum CellChange {
+    /// The cell was alive or dead in both generations.
+    #[default]
+    Unchanged,
+    /// The cell was dead, & is now alive.
+    Born,
+    /// The cell was alive, & is now dead.
+    Died,
+}
+
+/// The state of a cell for rules with more than two states, such as "Generations" rules where cells decay before
+/// dying.
+///
+/// State 0 is dead, state 1 is alive, & every state from 2 upwards is a decaying state. Decaying cells are not alive,
+/// so are converted to [`Cell::Dead`] by simulators that only have two states.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash, PartialOrd, Ord)]
+pub struct CellState(u8);
+
+impl CellState {
+    /// The state of a dead cell.
+    pub const DEAD: CellState = CellState(0);
+    /// The state of an alive cell.
+    pub const ALIVE: CellState = CellState(1);
+
+    /// Creates a new [`CellState`] with the given state.
+    pub const fn new(state: u8) -> Self {
+        Self(state)
+    }
+
+    /// Gets the numerical state.
+    pub fn get(self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if the cell is alive.
+    pub fn is_alive(self) -> bool {
+        self == CellState::ALIVE
+    }
+
+    /// Returns `true` if the cell is decaying.
+    pub fn is_decaying(self) -> bool {
+        self.0 >= 2
+    }
+
+    /// The state of this cell after one generation of a "Generations" rule.
+    ///
+    /// Dead cells with an amount of alive neighbours in `born` become alive, & alive cells with an amount in `survive`
+    /// stay alive. Alive cells that do not survive decay through each state up to `states - 1`, after which they die.
+    /// With two states this is the same as the rules for [`Cell`].
+    ///
+    /// # Examples
+    /// Brian's Brain, where cells are born with two neighbours, never survive, & decay for one generation.
+    /// ```
+    /// # use gol_lib::CellState;
+    /// let (born, survive, states) = (&[2], &[], 3);
+    ///
+    /// let alive = CellState::DEAD.next_generation(2, born, survive, states);
+    /// assert_eq!(alive, CellState::ALIVE);
+    /// let dying = alive.next_generation(2, born, survive, states);
+    /// assert_eq!(dying, CellState::new(2));
+    /// assert_eq!(dying.next_generation(2, born, survive, states), CellState::DEAD);
+    /// ```
+    pub fn next_generation(
+        self,
+        alive_neighbours: u8,
+        born: &[u8],
+        survive: &[u8],
+        states: u8,
+    ) -> CellState {
+        match self.0 {
+            0 if born.contains(&alive_neighbours) => CellState::ALIVE,
+            0 => CellState::DEAD,
+            1 if survive.contains(&alive_neighbours) => CellState::ALIVE,
+            // Decaying cells always continue to decay, regardless of their neighbours.
+            state if state.saturating_add(1) < states => CellState(state + 1),
+            _ => CellState::DEAD,
+        }
+    }
+}
+
+impl From<Cell> for CellState {
+    fn from(value: Cell) -> Self {
+        match value {
+            Cell::Alive => CellState::ALIVE,
+            Cell::Dead => CellState::DEAD,
+        }
+    }
+}
+
+impl From<CellState> for Cell {
+    /// Decaying cells are not alive, so are dead.
+    fn from(value: CellState) -> Self {
+        Cell::from(value.is_alive())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Brian's Brain: born with two neighbours, never survives, & decays for one generation.
+    const BRIANS_BRAIN: (&[u8], &[u8], u8) = (&[2], &[], 3);
+
+    #[test]
+    /// Dead cells are only born with the exact amount of neighbours.
+    fn brians_brain_birth() {
+        let (born, survive, states) = BRIANS_BRAIN;
+        for neighbours in 0..=8 {
+            let expected = match neighbours {
+                2 => CellState::ALIVE,
+                _ => CellState::DEAD,
+            };
+            assert_eq!(
+                CellState::DEAD.next_generation(neighbours, born, survive, states),
+                expected,
+                "{neighbours} neighbours"
+            );
+        }
+    }
+
+    #[test]
+    /// Alive cells always start decaying, & decaying cells always die, regardless of neighbours.
+    fn brians_brain_decay() {
+        let (born, survive, states) = BRIANS_BRAIN;
+        for neighbours in 0..=8 {
+            let dying = CellState::ALIVE.next_generation(neighbours, born, survive, states);
+            assert_eq!(dying, CellState::new(2));
+            assert!(dying.is_decaying());
+            assert_eq!(
+                dying.next_generation(neighbours, born, survive, states),
+                CellState::DEAD
+            );
+        }
+    }
+
+    #[test]
+    /// With two states, the rule for Conway's game of life matches the rules for [`Cell`].
+    fn two_states_are_binary() {
+        let (born, survive) = (&[3], &[2, 3]);
+        assert_eq!(
+            CellState::ALIVE.next_generation(1, born, survive, 2),
+            CellState::DEAD
+        );
+        assert_eq!(
+            CellState::ALIVE.next_generation(2, born, survive, 2),
+            CellState::ALIVE
+        );
+        assert_eq!(
+            CellState::DEAD.next_generation(3, born, survive, 2),
+            CellState::ALIVE
+        );
+    }
+
+    #[test]
+    /// Decaying states are dead when converted to a binary cell.
+    fn decaying_is_dead() {
+        assert_eq!(Cell::from(CellState::ALIVE), Cell::Alive);
+        assert_eq!(Cell::from(CellState::DEAD), Cell::Dead);
+        assert_eq!(Cell::from(CellState::new(2)), Cell::Dead);
+        assert_eq!(Cell::from(CellState::new(u8::MAX)), Cell::Dead);
+        assert_eq!(CellState::from(Cell::Alive), CellState::ALIVE);
+    }
+
+    #[test]
+    /// Cells parse from their names & pattern symbols, in any case.
+    fn parse() {
+        for text in ["alive", "Alive", "O", "o", "1", "*", " alive\n"] {
+            assert_eq!(Cell::try_from(text), Ok(Cell::Alive), "{text:?}");
+        }
+        for text in ["dead", "DEAD", ".", "b", "0"] {
+            assert_eq!(Cell::try_from(text), Ok(Cell::Dead), "{text:?}");
+        }
+    }
+
+    #[test]
+    /// Text that isn't a cell is rejected.
+    fn parse_invalid() {
+        for text in ["", "2", "alive!", "living"] {
+            assert_eq!(Cell::try_from(text), Err(CellError(text.into())));
+        }
+    }
+}
@@ -2,11 +2,11 @@ use std::num::NonZeroU32;
 
 use crate::{
     persistence::{SimulationBlueprint, SimulationSave},
-    Area, Cell, GlobalPosition,
+    Area, Axis, Cell, GlobalPosition, LoadStatus, RotateError, Symmetry, TickInfo,
 };
 
 /// The data packets that the UI will send to the simulator.
-#[cfg_attr(any(test, debug_assertions), derive(Debug))]
+#[cfg_attr(any(test, debug_assertions, feature = "test-suite"), derive(Debug))]
 pub enum UiPacket {
     /// Requests for a new display area to be rendered.
     DisplayArea { new_area: Area },
@@ -17,6 +17,12 @@ pub enum UiPacket {
         /// The state of the cell to set.
         cell_state: Cell,
     },
+    /// Sets many cells on the board at once.
+    /// This is preferred over sending many [`UiPacket::Set`]s, as the display is only updated once.
+    SetMany {
+        /// The position of each cell to set, along with the state to set it to.
+        cells: Box<[(GlobalPosition, Cell)]>,
+    },
 
     /// Requests for the simulation to send a save of the boards current state to the ui for handling.
     SaveBoard,
@@ -40,50 +46,285 @@ pub enum UiPacket {
         blueprint: SimulationBlueprint,
     },
 
+    /// Replaces the cells within an area with randomly generated cells.
+    Randomize {
+        /// The area to fill.
+        area: Area,
+        /// The chance of each cell being alive, from 0 to 1.
+        density: f32,
+        /// The seed for the random generation. The same seed always generates the same cells.
+        seed: u64,
+        /// The symmetry of the generated cells.
+        symmetry: Symmetry,
+    },
+
+    /// Sets every cell on the board to dead, without changing the generation.
+    Clear,
+    /// Mirrors every alive cell across the axis through the centre of the board.
+    Mirror { axis: Axis },
+    /// Rotates every alive cell clockwise by the given amount of quarter turns about the centre of the board.
+    Rotate { quarter_turns: u8 },
+
+    /// Requests for the simulation to send the area taken up by the board.
+    RequestBoardArea,
+
     /// Starts the simulation.
     Start,
     /// Starts the simulation, with it automatically stopping at the given generation.
     StartUntil { generation: u64 },
-    /// Stops the simulation.
+    /// Stops the simulation. Any target generation set by [`UiPacket::StartUntil`] is cleared.
     Stop,
+    /// Clears the target generation set by [`UiPacket::StartUntil`], without stopping the simulation.
+    ClearTarget,
+    /// Advances a stopped simulation by the given amount of generations.
+    ///
+    /// Large steps are advanced in chunks, with the display updated after each chunk. [`UiPacket::Stop`] or reaching
+    /// the cell limit discards the generations that have not been advanced yet.
+    Step { count: u64 },
+    /// Stops the simulation & returns the board to the given generation, if it is kept by the generation history.
+    /// Otherwise [`SimulatorPacket::GenerationUnavailable`] is sent.
+    ///
+    /// Later generations are kept until the simulation advances, so the board can be returned to them as well. Loading
+    /// or clearing the board discards every generation kept.
+    GotoGeneration { generation: u64 },
 
     /// Sets the current speed of the simulation.
     SimulationSpeed { speed: SimulationSpeed },
+    /// Sets the amount of previous generations each generation is compared against to detect whether the board is
+    /// static or periodic. A value of 0 disables detection.
+    PeriodDetection { history: usize },
+    /// Sets the amount of previous generations kept for [`UiPacket::GotoGeneration`]. A value of 0 disables the
+    /// history, which is the default.
+    GenerationHistory { capacity: usize },
+    /// Sets the maximum amount of alive cells the board may contain, or [`None`] for no limit.
+    /// The simulation is stopped if this is exceeded.
+    CellLimit { limit: Option<usize> },
+    /// Sets the condition for the simulation to stop at, or [`None`] to remove it.
+    /// The condition is checked after every generation whilst the simulation is running.
+    SetStopCondition { condition: Option<StopCondition> },
+    /// Sets whether [`SimulatorPacket::Statistics`] are sent for every generation.
+    RecordStatistics { enabled: bool },
+    /// Sets whether the age of each cell is included in the display.
+    /// Not every simulator tracks ages, so the ages may not be included regardless.
+    DisplayAges { enabled: bool },
+    /// Sets whether the cells born & the cells that died during the last generation are included in the display.
+    /// Not every simulator records them, so they may not be included regardless.
+    DisplayChanges { enabled: bool },
 
-    /// Terminates the simulator thread.
+    /// Terminates the simulator thread, after sending the final state of the board as a
+    /// [`SimulatorPacket::FinalState`].
     /// This is unrecoverable without relaunching the application.
     Terminate,
 }
 
 /// The data packets that the simulator will send to the ui.
-#[cfg_attr(any(test, debug_assertions), derive(Debug))]
+#[cfg_attr(any(test, debug_assertions, feature = "test-suite"), derive(Debug))]
 pub enum SimulatorPacket {
     /// A save of the boards current state.
     BoardSave { board: SimulationSave },
+    /// A save of the board as the simulator terminated, in response to [`UiPacket::Terminate`].
+    ///
+    /// This is the last packet the simulator sends. It is not an error for the ui to stop listening before it is sent.
+    FinalState { board: SimulationSave },
 
     /// A save of a portion of the board.
     BlueprintSave { blueprint: SimulationBlueprint },
+
+    /// The area taken up by the board, or [`None`] if the board is empty.
+    BoardArea { area: Option<Area> },
+
+    /// The outcome of a [`UiPacket::LoadBoard`].
+    BoardLoadResult { status: LoadStatus },
+    /// The outcome of a [`UiPacket::LoadBlueprint`].
+    BlueprintLoadResult { status: LoadStatus },
+    /// The board was not rotated by a [`UiPacket::Rotate`], as cells would have been moved beyond the edge of the
+    /// board.
+    RotateFailed { reason: RotateError },
+
+    /// The board has started repeating.
+    PeriodDetected {
+        /// The amount of generations between repeats. A period of 1 means the board is a still life.
+        period: u64,
+        /// The generation the repeat was detected on.
+        generation: u64,
+    },
+
+    /// The board has started repeating at a different position, so is a spaceship.
+    SpaceshipDetected {
+        /// The amount of generations between repeats.
+        period: u64,
+        /// The distance moved along the x axis each period.
+        dx: i32,
+        /// The distance moved along the y axis each period.
+        dy: i32,
+        /// The generation the repeat was detected on.
+        generation: u64,
+    },
+
+    /// The generation requested by [`UiPacket::GotoGeneration`] is not kept by the generation history.
+    GenerationUnavailable {
+        /// The generation that was requested.
+        requested: u64,
+        /// The oldest generation that is kept.
+        earliest: u64,
+    },
+
+    /// The board exceeded the cell limit, so the simulation was stopped.
+    CellLimitReached {
+        /// The generation the limit was exceeded on.
+        generation: u64,
+        /// The amount of alive cells on that generation.
+        population: u64,
+    },
+
+    /// The simulation was stopped by the simulator.
+    Stopped {
+        /// Why the simulation was stopped.
+        reason: StopReason,
+        /// The generation the simulation was stopped on.
+        generation: u64,
+    },
+
+    /// The statistics of a generation. This is sent for every generation whilst enabled by
+    /// [`UiPacket::RecordStatistics`].
+    Statistics {
+        /// The generation the statistics are of.
+        generation: u64,
+        /// The amount of alive cells on that generation.
+        population: u64,
+    },
+
+    /// The display area contains more cells than the simulator can display, so no cells are displayed.
+    /// This is sent when a display area first exceeds the limit.
+    DisplayTooLarge {
+        /// The amount of cells within the display area.
+        cells: u64,
+        /// The maximum amount of cells that can be displayed.
+        max: usize,
+    },
+
+    /// Progress towards the target generation of [`UiPacket::StartUntil`]. This is sent periodically whilst running
+    /// towards the target, & once more when the target is reached.
+    Progress {
+        /// The current generation.
+        current: u64,
+        /// The generation the simulation will stop at.
+        target: u64,
+    },
+
+    /// The simulator panicked, so has stopped. This is the last packet the simulator sends, instead of
+    /// [`SimulatorPacket::FinalState`].
+    Fatal {
+        /// The message the simulator panicked with.
+        message: String,
+    },
+
+    /// The speed the simulation is running at. This is sent roughly once per second whilst the simulation is running.
+    TickRate {
+        /// The ticks per second the simulation was set to run at, or [`None`] if the speed is uncapped.
+        ///
+        /// Whilst the speed is [`SimulationSpeed::AUTOMATIC`], this is the ticks per second the simulation has been
+        /// limited to, or [`None`] if it is running as fast as possible.
+        target: Option<u32>,
+        /// The smoothed ticks per second the simulation is actually running at.
+        achieved: f32,
+    },
+}
+
+/// A condition for the simulation to stop at, set with [`UiPacket::SetStopCondition`].
+///
+/// The condition stays set after it stops the simulation, so it will stop the simulation again if it is still met
+/// once restarted.
+pub struct StopCondition(Box<dyn FnMut(&TickInfo) -> bool + Send>);
+
+impl StopCondition {
+    /// Creates a condition that stops the simulation once the predicate returns true.
+    ///
+    /// # Examples
+    /// ```
+    /// # use gol_lib::communication::StopCondition;
+    /// // Stops once every cell has died.
+    /// let condition = StopCondition::new(|info| info.population == 0);
+    /// ```
+    pub fn new(predicate: impl FnMut(&TickInfo) -> bool + Send + 'static) -> Self {
+        Self(Box::new(predicate))
+    }
+
+    /// Whether the simulation should stop after the described generation.
+    pub(crate) fn is_met(&mut self, info: &TickInfo) -> bool {
+        (self.0)(info)
+    }
 }
 
-#[cfg_attr(any(test, debug_assertions), derive(Debug))]
+impl std::fmt::Debug for StopCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StopCondition(..)")
+    }
+}
+
+/// Why the simulator stopped the simulation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StopReason {
+    /// The condition set by [`UiPacket::SetStopCondition`] was met.
+    Condition,
+}
+
+/// The speed the simulation runs at.
+#[cfg_attr(
+    any(test, debug_assertions, feature = "test-suite"),
+    derive(Debug, PartialEq)
+)]
 pub struct SimulationSpeed {
     pub(crate) ticks_per_second: Option<NonZeroU32>,
+    /// Whether the uncapped simulation slows itself when ticks are slow, so the ui remains responsive.
+    pub(crate) automatic: bool,
+}
+
+/// The errors that can occur when creating a [`SimulationSpeed`].
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum SpeedError {
+    /// The simulation cannot run at zero ticks per second. [`UiPacket::Stop`] should be used instead.
+    #[error("The simulation speed must be at least one tick per second.")]
+    Zero,
 }
 
 impl SimulationSpeed {
     pub const UNCAPPED: Self = {
         Self {
             ticks_per_second: None,
+            automatic: false,
         }
     };
 
-    pub fn new(ticks_per_second: u32) -> Self {
+    /// Runs as fast as possible, unless each tick takes longer than a frame of the ui. Then the simulation is slowed
+    /// to leave time between ticks for the ui to display the board.
+    pub const AUTOMATIC: Self = {
         Self {
-            ticks_per_second: Some(
-                NonZeroU32::new(ticks_per_second)
-                    .unwrap_or(unsafe { NonZeroU32::new_unchecked(10) }),
-            ),
+            ticks_per_second: None,
+            automatic: true,
         }
+    };
+
+    /// Creates a new [`SimulationSpeed`] running at the given ticks per second.
+    ///
+    /// A speed of 0 ticks per second is clamped to 1 tick per second. Use [`Self::try_new`] to treat this as an error
+    /// instead.
+    pub fn new(ticks_per_second: u32) -> Self {
+        Self::try_new(ticks_per_second).unwrap_or(Self {
+            ticks_per_second: Some(NonZeroU32::MIN),
+            automatic: false,
+        })
+    }
+
+    /// Creates a new [`SimulationSpeed`] running at the given ticks per second.
+    ///
+    /// Returns an error if the ticks per second is 0.
+    pub fn try_new(ticks_per_second: u32) -> Result<Self, SpeedError> {
+        let ticks_per_second = NonZeroU32::new(ticks_per_second).ok_or(SpeedError::Zero)?;
+        Ok(Self {
+            ticks_per_second: Some(ticks_per_second),
+            automatic: false,
+        })
     }
 
     /// Gets the ticks per second the simulation will run at.
@@ -91,4 +332,66 @@ impl SimulationSpeed {
     pub fn get(&self) -> Option<NonZeroU32> {
         self.ticks_per_second
     }
+
+    /// Whether the simulation slows itself when ticks are slow. See [`Self::AUTOMATIC`].
+    pub fn is_automatic(&self) -> bool {
+        self.automatic
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// A speed of zero is an error.
+    fn try_new_zero() {
+        assert_eq!(SimulationSpeed::try_new(0), Err(SpeedError::Zero));
+    }
+
+    #[test]
+    /// A speed of one is the slowest valid speed.
+    fn try_new_one() {
+        let speed = SimulationSpeed::try_new(1).expect("One is a valid speed");
+        assert_eq!(speed.get(), NonZeroU32::new(1));
+    }
+
+    #[test]
+    /// Large speeds are kept as is.
+    fn try_new_large() {
+        let speed = SimulationSpeed::try_new(u32::MAX).expect("Large speeds are valid");
+        assert_eq!(speed.get(), NonZeroU32::new(u32::MAX));
+    }
+
+    #[test]
+    /// A speed of zero is clamped to one.
+    fn new_clamps_zero() {
+        assert_eq!(SimulationSpeed::new(0).get(), NonZeroU32::new(1));
+    }
+
+    #[test]
+    /// Valid speeds are unchanged.
+    fn new_keeps_valid() {
+        assert_eq!(SimulationSpeed::new(1).get(), NonZeroU32::new(1));
+        assert_eq!(SimulationSpeed::new(60).get(), NonZeroU32::new(60));
+        assert_eq!(
+            SimulationSpeed::new(u32::MAX).get(),
+            NonZeroU32::new(u32::MAX)
+        );
+    }
+
+    #[test]
+    /// An uncapped speed has no ticks per second.
+    fn uncapped() {
+        assert_eq!(SimulationSpeed::UNCAPPED.get(), None);
+    }
+
+    #[test]
+    /// The automatic speed is uncapped, unlike every other speed.
+    fn automatic() {
+        assert_eq!(SimulationSpeed::AUTOMATIC.get(), None);
+        assert!(SimulationSpeed::AUTOMATIC.is_automatic());
+        assert!(!SimulationSpeed::UNCAPPED.is_automatic());
+        assert!(!SimulationSpeed::new(60).is_automatic());
+    }
 }
@@ -1,30 +1,53 @@
 //! Contains [`BoardDisplay`].
 //! See its documentation for more information.
 
-use std::{num::NonZeroUsize, sync::Arc};
+use alloc::{boxed::Box, sync::Arc};
+use core::num::NonZeroUsize;
 
-use super::{cell::Cell, position::GlobalPosition};
+use super::{
+    area::Area,
+    cell::{Cell, CellChange, CellState},
+    position::GlobalPosition,
+};
 
 /// Holds the board data for the ui to display.
 ///
 /// This data type assumes that each sub-array has the same length.
 /// The top array can be any length, regardless of the sub-array length.
-#[cfg_attr(any(test, debug_assertions), derive(Debug, PartialEq, Clone))]
-#[derive(Default)]
+#[cfg_attr(
+    any(test, debug_assertions, feature = "test-suite"),
+    derive(Debug, PartialEq)
+)]
+#[derive(Default, Clone)]
 pub struct BoardDisplay {
     /// The generation of the board to be displayed.
     generation: u64,
+    /// The area of the board this display was created from.
+    /// The first cell of the board is at the minimum position of this area.
+    area: Area,
     /// The area of the board to display.
     board: Arc<[Box<[Cell]>]>,
+    /// The amount of generations each cell has been alive for, in the same layout as the board.
+    /// This is [`None`] if the simulator did not include ages.
+    ages: Option<Arc<[Box<[u32]>]>>,
+    /// The state of each cell, in the same layout as the board.
+    /// This is [`None`] if the simulator only has two states.
+    states: Option<Arc<[Box<[CellState]>]>>,
+    /// How each cell changed during the last generation, in the same layout as the board.
+    /// This is [`None`] if the simulator did not include the changes.
+    changes: Option<Arc<[Box<[CellChange]>]>>,
 }
 
 impl BoardDisplay {
     /// Constructs a new [`BoardDisplay`] with the given generation & the given board to display.
     ///
+    /// The area is the area of the board the display was created from. The first cell of the board is at the minimum
+    /// position of the area, & the board contains every cell within the area, including its maximum row & column.
+    ///
     /// # Example
     /// Simple way to create the correct board data type.
     /// ```
-    /// # use gol_lib::{Cell, BoardDisplay};
+    /// # use gol_lib::{Area, Cell, BoardDisplay};
     /// # let generation = 0;
     /// let mut board_build = Vec::new();
     /// for _ in 0..4 {
@@ -37,15 +60,65 @@ impl BoardDisplay {
     ///     board_build.push(array);
     /// }
     ///
-    /// BoardDisplay::new(generation, board_build);
+    /// BoardDisplay::new(generation, Area::new((0, 0), (3, 3)), board_build);
     /// ```
-    pub fn new(generation: u64, board: impl Into<Arc<[Box<[Cell]>]>>) -> Self {
+    pub fn new(generation: u64, area: Area, board: impl Into<Arc<[Box<[Cell]>]>>) -> Self {
         Self {
             generation,
+            area,
             board: board.into(),
+            ages: None,
+            states: None,
+            changes: None,
         }
     }
 
+    /// Includes the age of each cell in the display.
+    ///
+    /// The ages must be in the same layout as the board, with the age of each cell being the amount of generations it
+    /// has been alive for.
+    pub fn with_ages(mut self, ages: impl Into<Arc<[Box<[u32]>]>>) -> Self {
+        self.ages = Some(ages.into());
+        self
+    }
+
+    /// Gets the area of the board this display was created from.
+    pub fn get_area(&self) -> Area {
+        self.area
+    }
+
+    /// Gets the generation of the board being displayed.
+    pub fn get_generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Sets the generation of the board being displayed.
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    /// Gets the columns of cells being displayed, ordered by increasing x.
+    pub fn get_columns(&self) -> &[Box<[Cell]>] {
+        &self.board
+    }
+
+    /// Sets the cell at the given position **relative** to this [BoardDisplay].
+    ///
+    /// Positions outside the bounds of the display board are ignored. Any other information about the cell, such as
+    /// its age, is unchanged. The cells are only copied if they are shared with another display.
+    pub fn set_cell(&mut self, position: impl Into<GlobalPosition>, cell: Cell) {
+        let position: GlobalPosition = position.into();
+        if self
+            .try_get_cell(position)
+            .is_none_or(|current| current == cell)
+        {
+            return;
+        }
+
+        // The position is within the bounds of the display, so isn't negative.
+        Arc::make_mut(&mut self.board)[position.get_x() as usize][position.get_y() as usize] = cell;
+    }
+
     /// Gets the amount of cells in the x axis.
     ///
     /// If the board is 0 sized then an amount of 10 will be returned.
@@ -65,21 +138,135 @@ impl BoardDisplay {
 
     /// Gets the cell at the given position **relative** to this [BoardDisplay].
     ///
-    /// If the given position is outside the bounds of the display board then [`Cell::Dead`] will be returned.
+    /// If the given position is outside the bounds of the display board, in any direction, then [`Cell::Dead`] will be
+    /// returned. Use [`BoardDisplay::try_get_cell`] to distinguish these positions from dead cells.
     pub fn get_cell(&self, position: impl Into<GlobalPosition>) -> Cell {
+        self.try_get_cell(position).unwrap_or_default()
+    }
+
+    /// Gets the cell at the given position **relative** to this [BoardDisplay].
+    ///
+    /// Returns [`None`] if the given position is outside the bounds of the display board, including negative
+    /// positions.
+    pub fn try_get_cell(&self, position: impl Into<GlobalPosition>) -> Option<Cell> {
         let position: GlobalPosition = position.into();
+        let x = usize::try_from(position.get_x()).ok()?;
+        let y = usize::try_from(position.get_y()).ok()?;
 
-        self.board
+        self.board.get(x)?.get(y).copied()
+    }
+
+    /// Includes the state of each cell in the display, for simulators with more than two states.
+    ///
+    /// The states must be in the same layout as the board.
+    pub fn with_states(mut self, states: impl Into<Arc<[Box<[CellState]>]>>) -> Self {
+        self.states = Some(states.into());
+        self
+    }
+
+    /// Gets the state of the cell at the given position **relative** to this [BoardDisplay].
+    ///
+    /// If the display does not include states then the state of the cell is returned. If the given position is outside
+    /// the bounds of the display board then [`CellState::DEAD`] will be returned.
+    pub fn get_state(&self, position: impl Into<GlobalPosition>) -> CellState {
+        let position: GlobalPosition = position.into();
+        let Some(states) = &self.states else {
+            return self.get_cell(position).into();
+        };
+
+        states
             .get(position.get_x() as usize)
             .and_then(|sub_array| sub_array.get(position.get_y() as usize))
             .copied()
             .unwrap_or_default()
     }
+
+    /// Gets the age of the cell at the given position **relative** to this [BoardDisplay].
+    ///
+    /// Returns [`None`] if the display does not include ages. If the given position is outside the bounds of the
+    /// display board then 0 will be returned.
+    pub fn get_age(&self, position: impl Into<GlobalPosition>) -> Option<u32> {
+        let position: GlobalPosition = position.into();
+        let ages = self.ages.as_ref()?;
+
+        Some(
+            ages.get(position.get_x() as usize)
+                .and_then(|sub_array| sub_array.get(position.get_y() as usize))
+                .copied()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Includes how each cell changed during the last generation in the display.
+    ///
+    /// The changes must be in the same layout as the board.
+    pub fn with_changes(mut self, changes: impl Into<Arc<[Box<[CellChange]>]>>) -> Self {
+        self.changes = Some(changes.into());
+        self
+    }
+
+    /// Gets how the cell at the given position **relative** to this [BoardDisplay] changed during the last generation.
+    ///
+    /// Returns [`None`] if the display does not include changes. If the given position is outside the bounds of the
+    /// display board then [`CellChange::Unchanged`] will be returned.
+    pub fn get_change(&self, position: impl Into<GlobalPosition>) -> Option<CellChange> {
+        let position: GlobalPosition = position.into();
+        let changes = self.changes.as_ref()?;
+
+        Some(
+            changes
+                .get(position.get_x() as usize)
+                .and_then(|sub_array| sub_array.get(position.get_y() as usize))
+                .copied()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Returns an iterator over every cell in this display, along with the position of the cell on the board.
+    ///
+    /// The cells are iterated over one column at a time; by increasing x, then by increasing y within each column.
+    /// Cells that would be past the edges of the board are skipped.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use gol_lib::{Area, BoardDisplay, Cell};
+    /// let board: Vec<Box<[Cell]>> = vec![
+    ///     Box::new([Cell::Alive, Cell::Dead]),
+    ///     Box::new([Cell::Dead, Cell::Dead]),
+    /// ];
+    /// let display = BoardDisplay::new(0, Area::new((5, 5), (7, 7)), board);
+    ///
+    /// let mut iter_cells = display.iter_cells();
+    /// assert_eq!(iter_cells.next(), Some(((5, 5).into(), Cell::Alive)));
+    /// assert_eq!(iter_cells.next(), Some(((5, 6).into(), Cell::Dead)));
+    /// ```
+    pub fn iter_cells(&self) -> impl Iterator<Item = (GlobalPosition, Cell)> + '_ {
+        let origin = self.area.get_min();
+
+        self.board
+            .iter()
+            .enumerate()
+            .flat_map(move |(x, sub_array)| {
+                sub_array.iter().enumerate().filter_map(move |(y, cell)| {
+                    let x = origin.get_x().checked_add(i32::try_from(x).ok()?)?;
+                    let y = origin.get_y().checked_add(i32::try_from(y).ok()?)?;
+                    Some((GlobalPosition::new(x, y), *cell))
+                })
+            })
+    }
+
+    /// Returns an iterator over the positions of the alive cells in this display.
+    pub fn alive_positions(&self) -> impl Iterator<Item = GlobalPosition> + '_ {
+        self.iter_cells()
+            .filter(|(_, cell)| cell.is_alive())
+            .map(|(position, _)| position)
+    }
 }
 
 #[cfg(test)]
 mod board_display_tests {
     use super::*;
+    use alloc::{boxed::Box, vec, vec::Vec};
 
     fn generate_board() -> BoardDisplay {
         let mut board_build = Vec::new();
@@ -99,13 +286,16 @@ mod board_display_tests {
             board_build.push(array);
         }
 
-        BoardDisplay::new(0, board_build)
+        BoardDisplay::new(0, Area::new((-2, -3), (2, 2)), board_build)
     }
 
     #[test]
     fn default_is_correct() {
         let board_build: Vec<Box<[Cell]>> = Vec::new();
-        assert_eq!(BoardDisplay::default(), BoardDisplay::new(0, board_build))
+        assert_eq!(
+            BoardDisplay::default(),
+            BoardDisplay::new(0, Area::default(), board_build)
+        )
     }
 
     #[test]
@@ -146,4 +336,141 @@ mod board_display_tests {
         assert_eq!(board_display.get_cell((1, 1)), Cell::Alive);
         assert_eq!(board_display.get_cell((3, 4)), Cell::Dead);
     }
+
+    #[test]
+    /// Every cell is iterated over with its position on the board.
+    fn iter_cells() {
+        let board: Vec<Box<[Cell]>> = vec![
+            Box::new([Cell::Alive, Cell::Dead]),
+            Box::new([Cell::Dead, Cell::Alive]),
+            Box::new([Cell::Alive, Cell::Alive]),
+        ];
+        let board_display = BoardDisplay::new(7, Area::new((-1, -1), (2, 1)), board);
+
+        let cells: Vec<(GlobalPosition, Cell)> = board_display.iter_cells().collect();
+        assert_eq!(
+            cells,
+            vec![
+                ((-1, -1).into(), Cell::Alive),
+                ((-1, 0).into(), Cell::Dead),
+                ((0, -1).into(), Cell::Dead),
+                ((0, 0).into(), Cell::Alive),
+                ((1, -1).into(), Cell::Alive),
+                ((1, 0).into(), Cell::Alive),
+            ]
+        );
+    }
+
+    #[test]
+    /// Only the positions of alive cells are returned.
+    fn alive_positions() {
+        let board_display = generate_board();
+
+        let alive: Vec<GlobalPosition> = board_display.alive_positions().collect();
+        // Every odd y is alive, over 5 x positions.
+        assert_eq!(alive.len(), 15);
+        assert_eq!(alive.first(), Some(&(-2, -2).into()));
+        assert!(alive.iter().all(|position| (position.get_y() + 3) % 2 == 1));
+    }
+
+    #[test]
+    /// An empty display has no cells.
+    fn iter_cells_empty() {
+        assert_eq!(BoardDisplay::default().iter_cells().count(), 0);
+    }
+
+    #[test]
+    /// Ages are only returned if they were included.
+    fn get_age() {
+        assert_eq!(generate_board().get_age((1, 1)), None);
+
+        let ages: Vec<Box<[u32]>> = vec![Box::new([0, 4]), Box::new([2, 0])];
+        let board_display = generate_board().with_ages(ages);
+        assert_eq!(board_display.get_age((0, 1)), Some(4));
+        assert_eq!(board_display.get_age((1, 0)), Some(2));
+        assert_eq!(board_display.get_age((7, 7)), Some(0));
+    }
+
+    #[test]
+    /// Changes are only returned if they were included.
+    fn get_change() {
+        assert_eq!(generate_board().get_change((1, 1)), None);
+
+        let changes: Vec<Box<[CellChange]>> = vec![Box::new([CellChange::Born, CellChange::Died])];
+        let board_display = generate_board().with_changes(changes);
+        assert_eq!(board_display.get_change((0, 0)), Some(CellChange::Born));
+        assert_eq!(board_display.get_change((0, 1)), Some(CellChange::Died));
+        assert_eq!(
+            board_display.get_change((7, 7)),
+            Some(CellChange::Unchanged)
+        );
+    }
+
+    #[test]
+    /// States fall back to the binary cells if they were not included.
+    fn get_state() {
+        assert_eq!(generate_board().get_state((1, 1)), CellState::ALIVE);
+        assert_eq!(generate_board().get_state((1, 0)), CellState::DEAD);
+
+        let states: Vec<Box<[CellState]>> = vec![Box::new([CellState::new(2), CellState::ALIVE])];
+        let board_display = generate_board().with_states(states);
+        assert_eq!(board_display.get_state((0, 0)), CellState::new(2));
+        assert_eq!(board_display.get_state((0, 1)), CellState::ALIVE);
+        assert_eq!(board_display.get_state((7, 7)), CellState::DEAD);
+    }
+
+    #[test]
+    /// Positions outside the display in every direction are dead.
+    fn get_cell_out_of_bounds() {
+        let board_display = generate_board();
+
+        for position in [(-1, 1), (5, 1), (1, -1), (1, 6), (i32::MIN, i32::MAX)] {
+            assert_eq!(board_display.get_cell(position), Cell::Dead, "{position:?}");
+            assert_eq!(board_display.try_get_cell(position), None, "{position:?}");
+        }
+    }
+
+    #[test]
+    /// Positions within the display return their cell.
+    fn try_get_cell() {
+        let board_display = generate_board();
+
+        assert_eq!(board_display.try_get_cell((0, 0)), Some(Cell::Dead));
+        assert_eq!(board_display.try_get_cell((4, 5)), Some(Cell::Alive));
+        assert_eq!(BoardDisplay::default().try_get_cell((0, 0)), None);
+    }
+
+    #[test]
+    /// Setting a cell only copies the cells if they are shared with another display.
+    fn set_cell_copy_on_write() {
+        let mut board = generate_board();
+        let columns = board.get_columns().as_ptr();
+
+        board.set_cell((0, 0), Cell::Alive);
+        assert_eq!(board.get_cell((0, 0)), Cell::Alive);
+        assert_eq!(board.get_columns().as_ptr(), columns);
+
+        let shared = board.clone();
+        board.set_cell((0, 0), Cell::Dead);
+        board.set_cell((10, -1), Cell::Alive);
+        assert_eq!(board.get_cell((0, 0)), Cell::Dead);
+        assert_eq!(shared.get_cell((0, 0)), Cell::Alive);
+        assert_ne!(board.get_columns().as_ptr(), shared.get_columns().as_ptr());
+    }
+
+    #[test]
+    /// A display ending at the edge of the board iterates over its cells without overflowing.
+    fn iter_cells_at_edge() {
+        let board: Vec<Box<[Cell]>> = vec![Box::new([Cell::Alive, Cell::Dead]); 3];
+        let area = Area::new((i32::MAX - 1, i32::MAX - 1), (i32::MAX, i32::MAX));
+        let display = BoardDisplay::new(0, area, board);
+
+        let positions: Vec<GlobalPosition> =
+            display.iter_cells().map(|(position, _)| position).collect();
+        let (max, before) = (i32::MAX, i32::MAX - 1);
+        assert_eq!(
+            positions,
+            [(before, before), (before, max), (max, before), (max, max)].map(GlobalPosition::from)
+        );
+    }
 }
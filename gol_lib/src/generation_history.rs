@@ -0,0 +1,154 @@
+//! Contains [`GenerationHistory`].
+//! See its documentation for more information.
+
+use std::collections::VecDeque;
+
+use crate::persistence::SimulationSave;
+
+/// Keeps saves of the most recent generations, so the board can be returned to them.
+///
+/// Saves are kept in order of generation, with the newest at the back. Recording a generation discards any saves of
+/// the same or later generations, as they are no longer the past of the current board.
+pub(crate) struct GenerationHistory {
+    /// The saves of the most recent generations, oldest first.
+    saves: VecDeque<SimulationSave>,
+    /// The maximum amount of saves kept.
+    capacity: usize,
+}
+
+impl GenerationHistory {
+    /// Creates a new [`GenerationHistory`] keeping the given amount of generations.
+    ///
+    /// A capacity of 0 disables the history.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            saves: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Sets the amount of generations kept, discarding the oldest saves if there are too many.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.saves.len() > capacity {
+            self.saves.pop_front();
+        }
+    }
+
+    /// Discards every save, for when the board is replaced.
+    pub(crate) fn clear(&mut self) {
+        self.saves.clear();
+    }
+
+    /// Whether any generations are kept.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Records the save as the newest generation, replacing any saves of the same or later generations.
+    pub(crate) fn record(&mut self, save: SimulationSave) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        while self
+            .saves
+            .back()
+            .is_some_and(|last| last.generation >= save.generation)
+        {
+            self.saves.pop_back();
+        }
+        if self.saves.len() >= self.capacity {
+            self.saves.pop_front();
+        }
+        self.saves.push_back(save);
+    }
+
+    /// The save of the given generation, if it is kept.
+    pub(crate) fn get(&self, generation: u64) -> Option<&SimulationSave> {
+        self.saves
+            .binary_search_by_key(&generation, |save| save.generation)
+            .ok()
+            .map(|index| &self.saves[index])
+    }
+
+    /// The oldest generation kept, if any are.
+    pub(crate) fn earliest(&self) -> Option<u64> {
+        self.saves.front().map(|save| save.generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Area;
+
+    /// A save of a single dead cell on the given generation.
+    fn save(generation: u64) -> SimulationSave {
+        SimulationSave::new(
+            generation,
+            Area::default(),
+            bitvec::bitbox![usize, bitvec::order::Lsb0; 0],
+        )
+    }
+
+    #[test]
+    /// Only the most recent generations are kept.
+    fn oldest_discarded() {
+        let mut history = GenerationHistory::new(2);
+        for generation in 0..3 {
+            history.record(save(generation));
+        }
+
+        assert_eq!(history.earliest(), Some(1));
+        assert!(history.get(0).is_none());
+        assert_eq!(history.get(2).map(|save| save.generation), Some(2));
+    }
+
+    #[test]
+    /// Recording an earlier generation discards the generations after it.
+    fn later_discarded() {
+        let mut history = GenerationHistory::new(10);
+        for generation in [0, 1, 2, 1] {
+            history.record(save(generation));
+        }
+
+        assert!(history.get(1).is_some());
+        assert!(history.get(2).is_none());
+    }
+
+    #[test]
+    /// A capacity of 0 keeps nothing.
+    fn disabled() {
+        let mut history = GenerationHistory::new(0);
+        history.record(save(0));
+
+        assert_eq!(history.earliest(), None);
+    }
+
+    #[test]
+    /// Clearing discards every generation, but keeps recording new ones.
+    fn clear() {
+        let mut history = GenerationHistory::new(10);
+        for generation in 0..3 {
+            history.record(save(generation));
+        }
+        history.clear();
+        assert_eq!(history.earliest(), None);
+
+        history.record(save(5));
+        assert_eq!(history.earliest(), Some(5));
+    }
+
+    #[test]
+    /// Reducing the capacity discards the oldest generations.
+    fn reduce_capacity() {
+        let mut history = GenerationHistory::new(5);
+        for generation in 0..5 {
+            history.record(save(generation));
+        }
+        history.set_capacity(2);
+
+        assert_eq!(history.earliest(), Some(3));
+    }
+}
@@ -0,0 +1,70 @@
+//! Contains [`run_headless`] & [`HeadlessRunner`].
+//! See their documentation for more information.
+
+use crate::{persistence::SimulationSave, Simulator};
+
+/// Advances the simulator by the given amount of generations, returning a save of the final board.
+///
+/// The simulator is ticked directly on the current thread, without the ui or any channels. This is useful for
+/// benchmarking & scripting.
+pub fn run_headless(simulator: &mut impl Simulator, generations: u64) -> SimulationSave {
+    HeadlessRunner::new(generations).run(simulator).save
+}
+
+/// Builder for running a simulator without the ui.
+///
+/// See [`run_headless`] for the simplest usage.
+#[cfg_attr(any(test, debug_assertions, feature = "test-suite"), derive(Debug))]
+pub struct HeadlessRunner {
+    /// The amount of generations to advance by.
+    generations: u64,
+    /// Whether the population is recorded after each generation.
+    track_population: bool,
+}
+
+/// The outcome of a [`HeadlessRunner`].
+#[cfg_attr(any(test, debug_assertions, feature = "test-suite"), derive(Debug))]
+pub struct HeadlessResult {
+    /// The board after the final generation.
+    pub save: SimulationSave,
+    /// The population after each generation, if it was tracked.
+    pub population: Option<Vec<u64>>,
+}
+
+impl HeadlessRunner {
+    /// Creates a new runner that advances by the given amount of generations.
+    pub fn new(generations: u64) -> Self {
+        Self {
+            generations,
+            track_population: false,
+        }
+    }
+
+    /// Whether to record the population after each generation.
+    pub fn track_population(mut self, track_population: bool) -> Self {
+        self.track_population = track_population;
+        self
+    }
+
+    /// Advances the simulator, returning the final board & any tracked data.
+    pub fn run(self, simulator: &mut impl Simulator) -> HeadlessResult {
+        let mut population = self
+            .track_population
+            .then(|| Vec::with_capacity(self.generations.try_into().unwrap_or_default()));
+
+        match &mut population {
+            Some(population) => {
+                for _ in 0..self.generations {
+                    simulator.tick();
+                    population.push(simulator.population());
+                }
+            }
+            None => simulator.tick_n(self.generations),
+        }
+
+        HeadlessResult {
+            save: simulator.save_board(),
+            population,
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Contains [`PeriodDetector`].
+//! See its documentation for more information.
+
+use std::collections::VecDeque;
+
+use crate::{persistence::SimulationSave, GlobalPosition};
+
+/// The default amount of previous generations the current generation is compared against.
+pub(crate) const DEFAULT_PERIOD_HISTORY: usize = 30;
+
+/// How the board repeats, as found by [`PeriodDetector`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Repeat {
+    /// The board returns to the same state every `period` generations.
+    Period(u64),
+    /// The board returns to the same state every `period` generations, but moved by `dx` & `dy`.
+    Spaceship { period: u64, dx: i32, dy: i32 },
+}
+
+/// Detects when the board has become static, periodic, or a spaceship.
+///
+/// The board data of the most recent generations is hashed, relative to the minimum position of the board area, & kept
+/// in a ring buffer along with that position. If the current generation matches the generation from `period`
+/// generations ago, then the board repeats with that period. A period of 1 is a still life. If the matching
+/// generation was at a different position, the board is a spaceship moving by the difference in position.
+///
+/// Periods longer than the amount of generations kept cannot be detected.
+pub(crate) struct PeriodDetector {
+    /// The hashes & minimum positions of the most recent generations, with the newest at the back.
+    history: VecDeque<(u64, GlobalPosition)>,
+    /// The maximum amount of hashes kept.
+    capacity: usize,
+    /// The last repeat that was detected. This stops the same repeat being reported every generation.
+    reported: Option<Repeat>,
+}
+
+impl PeriodDetector {
+    /// Creates a new [`PeriodDetector`] comparing against the given amount of previous generations.
+    ///
+    /// A capacity of 0 disables detection.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            history: VecDeque::with_capacity(capacity),
+            capacity,
+            reported: None,
+        }
+    }
+
+    /// Sets the amount of previous generations compared against, discarding the current history.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        *self = Self::new(capacity);
+    }
+
+    /// Whether any previous generations are compared against.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Discards the current history.
+    ///
+    /// This must be called whenever the board is modified outside of a tick.
+    pub(crate) fn clear(&mut self) {
+        self.history.clear();
+        self.reported = None;
+    }
+
+    /// Records the state of the board for the next generation.
+    ///
+    /// Returns how the board repeats if it has just started repeating, or repeats differently than previously
+    /// reported.
+    pub(crate) fn update(&mut self, save: &SimulationSave) -> Option<Repeat> {
+        // The position is not hashed, so boards that have moved can be matched.
+        self.update_hash(save.shape_hash(), save.board_area.get_min())
+    }
+
+    fn update_hash(&mut self, hash: u64, position: GlobalPosition) -> Option<Repeat> {
+        let repeat = self
+            .history
+            .iter()
+            .rev()
+            .enumerate()
+            .find(|(_, (previous, _))| *previous == hash)
+            .and_then(|(index, (_, previous_position))| {
+                let period = index as u64 + 1;
+                // A board that moved further than an i32 can hold has wrapped around the edge of the board, so it
+                // is not reported as a spaceship.
+                let dx = position.get_x().checked_sub(previous_position.get_x())?;
+                let dy = position.get_y().checked_sub(previous_position.get_y())?;
+                match (dx, dy) {
+                    (0, 0) => Some(Repeat::Period(period)),
+                    (dx, dy) => Some(Repeat::Spaceship { period, dx, dy }),
+                }
+            });
+
+        if self.capacity > 0 {
+            if self.history.len() >= self.capacity {
+                self.history.pop_front();
+            }
+            self.history.push_back((hash, position));
+        }
+
+        match repeat {
+            Some(repeat) if self.reported != Some(repeat) => {
+                self.reported = Some(repeat);
+                Some(repeat)
+            }
+            Some(_) => None,
+            None => {
+                self.reported = None;
+                None
+            }
+        }
+    }
+}
+
+impl Default for PeriodDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_PERIOD_HISTORY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The position of boards that don't move.
+    const ORIGIN: GlobalPosition = GlobalPosition::new(0, 0);
+
+    #[test]
+    /// A board that does not change is a still life, which is reported once.
+    fn still_life() {
+        let mut detector = PeriodDetector::default();
+
+        assert_eq!(detector.update_hash(1, ORIGIN), None);
+        assert_eq!(detector.update_hash(1, ORIGIN), Some(Repeat::Period(1)));
+        assert_eq!(detector.update_hash(1, ORIGIN), None);
+    }
+
+    #[test]
+    /// A board alternating between two states has a period of 2.
+    fn period_two() {
+        let mut detector = PeriodDetector::default();
+
+        assert_eq!(detector.update_hash(1, ORIGIN), None);
+        assert_eq!(detector.update_hash(2, ORIGIN), None);
+        assert_eq!(detector.update_hash(1, ORIGIN), Some(Repeat::Period(2)));
+        assert_eq!(detector.update_hash(2, ORIGIN), None);
+    }
+
+    #[test]
+    /// A period longer than the history cannot be detected.
+    fn period_exceeds_history() {
+        let mut detector = PeriodDetector::new(2);
+
+        for _ in 0..3 {
+            assert_eq!(detector.update_hash(1, ORIGIN), None);
+            assert_eq!(detector.update_hash(2, ORIGIN), None);
+            assert_eq!(detector.update_hash(3, ORIGIN), None);
+        }
+    }
+
+    #[test]
+    /// A capacity of 0 disables detection.
+    fn disabled() {
+        let mut detector = PeriodDetector::new(0);
+        assert!(!detector.is_enabled());
+
+        assert_eq!(detector.update_hash(1, ORIGIN), None);
+        assert_eq!(detector.update_hash(1, ORIGIN), None);
+    }
+
+    #[test]
+    /// The period is reported again if the board stops repeating & then repeats once more.
+    fn reported_after_change() {
+        let mut detector = PeriodDetector::default();
+
+        detector.update_hash(1, ORIGIN);
+        assert_eq!(detector.update_hash(1, ORIGIN), Some(Repeat::Period(1)));
+        assert_eq!(detector.update_hash(2, ORIGIN), None);
+        assert_eq!(detector.update_hash(2, ORIGIN), Some(Repeat::Period(1)));
+    }
+
+    #[test]
+    /// Clearing the history stops previous generations from being matched.
+    fn clear() {
+        let mut detector = PeriodDetector::default();
+
+        detector.update_hash(1, ORIGIN);
+        detector.clear();
+        assert_eq!(detector.update_hash(2, ORIGIN), None);
+        assert_eq!(detector.update_hash(1, ORIGIN), None);
+    }
+
+    #[test]
+    /// A board that repeats at a different position is a spaceship, reported with its displacement.
+    fn spaceship() {
+        let mut detector = PeriodDetector::default();
+
+        assert_eq!(detector.update_hash(1, ORIGIN), None);
+        assert_eq!(detector.update_hash(2, (0, 1).into()), None);
+        assert_eq!(
+            detector.update_hash(1, (2, -1).into()),
+            Some(Repeat::Spaceship {
+                period: 2,
+                dx: 2,
+                dy: -1
+            })
+        );
+        assert_eq!(detector.update_hash(2, (2, 0).into()), None);
+    }
+
+    #[test]
+    /// A displacement across the whole board doesn't overflow, & isn't reported as a spaceship.
+    fn displacement_overflow() {
+        let mut detector = PeriodDetector::default();
+
+        assert_eq!(detector.update_hash(1, (i32::MIN, 0).into()), None);
+        assert_eq!(detector.update_hash(1, (i32::MAX, 0).into()), None);
+        assert_eq!(detector.update_hash(1, (i32::MIN, 0).into()), None);
+    }
+}
@@ -0,0 +1,202 @@
+//! Contains the format the board data of a [`SimulationSave`] is stored as in save files.
+//! See [`BoardData`] for more information.
+
+use bitvec::{boxed::BitBox, slice::BitSlice};
+
+use super::{rle::MAX_PATTERN_CELLS, SimulationSave};
+use crate::{Area, GlobalPosition, Rule};
+
+/// Boards with fewer than one alive cell in this many cells are stored as a list of the alive cells, rather than
+/// storing the state of every cell.
+const SPARSE_THRESHOLD: usize = 64;
+
+/// How the state of the cells of a board is stored in a save file.
+#[derive(serde::Serialize, serde::Deserialize)]
+enum BoardData<Dense> {
+    /// The state of every cell within the board area, in the order of [`Area::iterate_over`].
+    Dense(Dense),
+    /// The positions of the alive cells. Every other cell is dead.
+    Sparse(Box<[GlobalPosition]>),
+}
+
+/// The format a [`SimulationSave`] is stored as in a save file.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(super) struct SimulationSaveFormat<Dense> {
+    generation: u64,
+    board_area: Area,
+    board_data: BoardData<Dense>,
+    /// Saves from before the rule was stored use the default rule.
+    #[serde(default)]
+    rule: Rule,
+}
+
+/// The reasons a sparse save cannot be expanded into the state of every cell.
+#[derive(thiserror::Error, Debug)]
+pub(super) enum SparseError {
+    /// An alive cell is outside of the board area.
+    #[error("Alive cell at ({x}, {y}) is outside of the board area")]
+    OutOfArea { x: i32, y: i32 },
+    /// The board area contains more cells than [`MAX_PATTERN_CELLS`], so can't be allocated.
+    #[error("The board area contains {0} cells, exceeding the limit of {MAX_PATTERN_CELLS}")]
+    TooLarge(u64),
+}
+
+impl serde::Serialize for SimulationSave {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let alive = self.board_data.count_ones();
+
+        let board_data: BoardData<&BitSlice> =
+            match alive.saturating_mul(SPARSE_THRESHOLD) < self.board_data.len() {
+                true => BoardData::Sparse(
+                    self.board_area
+                        .iterate_over()
+                        .zip(self.board_data.iter().by_vals())
+                        .filter_map(|(position, alive)| alive.then_some(position))
+                        .collect(),
+                ),
+                false => BoardData::Dense(&self.board_data),
+            };
+
+        SimulationSaveFormat {
+            generation: self.generation,
+            board_area: self.board_area,
+            board_data,
+            rule: self.rule,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl TryFrom<SimulationSaveFormat<BitBox>> for SimulationSave {
+    type Error = SparseError;
+
+    fn try_from(format: SimulationSaveFormat<BitBox>) -> Result<Self, Self::Error> {
+        let SimulationSaveFormat {
+            generation,
+            board_area,
+            board_data,
+            rule,
+        } = format;
+
+        let board_data = match board_data {
+            BoardData::Dense(board_data) => board_data,
+            BoardData::Sparse(positions) => {
+                // The area comes from the file, so it is checked before every cell within it is allocated.
+                let cells = board_area.cell_count();
+                if cells > MAX_PATTERN_CELLS {
+                    return Err(SparseError::TooLarge(cells));
+                }
+
+                let min = board_area.get_min();
+                let width = board_area.x_difference() as usize + 1;
+                let mut board_data = bitvec::bitbox![0; usize::try_from(cells).map_err(|_| SparseError::TooLarge(cells))?];
+                for position in positions.iter() {
+                    let (x, y) = (position.get_x(), position.get_y());
+                    if !board_area.x_range().contains(&x) || !board_area.y_range().contains(&y) {
+                        return Err(SparseError::OutOfArea { x, y });
+                    }
+
+                    let (x_offset, y_offset) = *position - min;
+                    board_data.set(y_offset as usize * width + x_offset as usize, true);
+                }
+                board_data
+            }
+        };
+
+        Ok(SimulationSave {
+            generation,
+            board_area,
+            board_data,
+            rule,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// A board with many alive cells is stored densely & loads unchanged.
+    fn dense_round_trip() {
+        let save = SimulationSave::new(7, Area::new((-2, -2), (1, 1)), bitvec::bitbox![1; 16]);
+
+        let json = serde_json::to_value(&save).expect("Can serialise");
+        assert!(json["board_data"].get("Dense").is_some(), "{json}");
+
+        let loaded: SimulationSave = serde_json::from_value(json).expect("Can deserialise");
+        assert_eq!(loaded, save);
+    }
+
+    #[test]
+    /// A board with few alive cells is stored sparsely, takes less space than storing it densely, & loads unchanged.
+    fn sparse_round_trip() {
+        let board_area = Area::new((-50, -50), (49, 49));
+        let mut board_data = bitvec::bitbox![0; 100 * 100];
+        board_data.set(0, true);
+        board_data.set(5050, true);
+        let save = SimulationSave::new(3, board_area, board_data);
+
+        let json = serde_json::to_string(&save).expect("Can serialise");
+        let value: serde_json::Value = serde_json::from_str(&json).expect("Valid json");
+        assert_eq!(value["board_area"], serde_json::json!([-50, -50, 49, 49]));
+        assert_eq!(
+            value["board_data"]["Sparse"],
+            serde_json::json!([{ "x": -50, "y": -50 }, { "x": 0, "y": 0 }])
+        );
+
+        let dense = serde_json::to_string(&SimulationSaveFormat {
+            generation: save.generation,
+            board_area: save.board_area,
+            board_data: BoardData::Dense(&save.board_data),
+            rule: save.rule,
+        })
+        .expect("Can serialise");
+        assert!(
+            json.len() < dense.len(),
+            "{} >= {}",
+            json.len(),
+            dense.len()
+        );
+
+        let loaded: SimulationSave = serde_json::from_str(&json).expect("Can deserialise");
+        assert_eq!(loaded, save);
+    }
+
+    #[test]
+    /// Sparse cells outside of the board area are rejected.
+    fn sparse_out_of_area() {
+        let json = serde_json::json!({
+            "generation": 0,
+            "board_area": { "min": { "x": 0, "y": 0 }, "max": { "x": 1, "y": 1 } },
+            "board_data": { "Sparse": [{ "x": 2, "y": 0 }] }
+        });
+
+        serde_json::from_value::<SimulationSave>(json).expect_err("Must error");
+    }
+
+    #[test]
+    /// Sparse saves with an area too large to allocate are rejected before allocating it.
+    fn sparse_too_large() {
+        let json = serde_json::json!({
+            "generation": 0,
+            "board_area": [i32::MIN, 0, i32::MAX, 0],
+            "board_data": { "Sparse": [{ "x": 0, "y": 0 }] }
+        });
+
+        let error = serde_json::from_value::<SimulationSave>(json).expect_err("Must error");
+        assert!(error.to_string().contains("exceeding the limit"), "{error}");
+    }
+
+    #[test]
+    /// The rule is stored as text, & saves from before the rule was stored use the default rule.
+    fn rule() {
+        let save = SimulationSave::default().with_rule(Rule::HIGHLIFE);
+        let mut json = serde_json::to_value(&save).expect("Can serialise");
+        assert_eq!(json["rule"], "B36/S23");
+
+        json.as_object_mut().unwrap().remove("rule");
+        let loaded: SimulationSave = serde_json::from_value(json).expect("Can deserialise");
+        assert_eq!(loaded.rule, Rule::CONWAY);
+    }
+}
@@ -1,6 +1,9 @@
 use std::path::Path;
 
-use super::SaveData;
+use super::{
+    migrate::{check_version, MigrateError},
+    BlueprintData, SaveData,
+};
 
 /// The possible errors when attempting to parse a save file from disk.
 #[derive(thiserror::Error, Debug)]
@@ -9,11 +12,25 @@ pub enum SaveParseError {
     FileRead(#[from] std::io::Error),
     #[error("File is not a valid save file")]
     InvalidData(#[from] serde_json::Error),
+    #[error("Unable to load save file: {0}")]
+    Migrate(#[from] MigrateError),
 }
 
 /// Attempts to parse a save file from disk at the given path.
+///
+/// Saves made with an older save file version are migrated into the current format.
 pub fn load_save<'a>(save_location: impl Into<&'a Path>) -> Result<SaveData, SaveParseError> {
     let file = std::fs::File::open(save_location.into())?;
     let save = serde_json::from_reader(file)?;
-    Ok(save)
+    Ok(super::migrate(save)?)
+}
+
+/// Attempts to parse a blueprint file from disk at the given path.
+pub fn load_blueprint<'a>(
+    blueprint_location: impl Into<&'a Path>,
+) -> Result<BlueprintData, SaveParseError> {
+    let file = std::fs::File::open(blueprint_location.into())?;
+    let blueprint: BlueprintData = serde_json::from_reader(file)?;
+    check_version(blueprint.version).map_err(MigrateError::from)?;
+    Ok(blueprint)
 }
@@ -1,13 +1,20 @@
-use crate::{persistence::SimulationSave, GlobalPosition};
+use crate::{
+    persistence::{pattern::Pattern, SimulationBlueprint, SimulationSave},
+    GlobalPosition, Rule,
+};
 use std::fs::File;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use super::{SaveData, CURRENT_SAVE_VERSION};
+use super::{BlueprintData, SaveData, CURRENT_SAVE_VERSION};
 
-/// The possible errors when saving a board save.
+/// The possible errors when saving a board save or blueprint.
 #[derive(thiserror::Error, Debug)]
 pub enum BoardSaveError {
     /// The save content cannot be converted into the save file format.
@@ -19,6 +26,37 @@ pub enum BoardSaveError {
     /// Unable to write the save file to disk.
     #[error("Unable to write file.")]
     WriteFail(#[from] std::io::Error),
+    /// The save has no name, but [`SaveBuilder::require_name`] was set.
+    #[error("The save must have a name.")]
+    MissingName,
+    /// The save was cancelled with [`SaveCancel::cancel`] before it was written.
+    #[error("The save was cancelled.")]
+    Cancelled,
+}
+
+/// A flag shared with a save, allowing it to be cancelled from another thread.
+///
+/// The flag is checked before the save file is written. A cancelled save doesn't leave a file behind.
+#[derive(Clone, Default, Debug)]
+pub struct SaveCancel(Arc<AtomicBool>);
+
+impl SaveCancel {
+    /// Cancels any saves sharing this flag that have not yet been written.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the flag has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Flags are only equal if they are shared.
+impl PartialEq for SaveCancel {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 /// Builder for easily creating a save.
@@ -28,6 +66,10 @@ pub struct SaveBuilder {
     save_description: Option<Box<str>>,
     save_time: Option<SystemTime>,
     view_position: Option<GlobalPosition>,
+    /// Whether saving fails if the name is blank.
+    require_name: bool,
+    /// Cancels the save before it is written.
+    cancel: Option<SaveCancel>,
 
     simulation_save: SimulationSave,
 }
@@ -41,6 +83,8 @@ impl SaveBuilder {
             save_description: None,
             save_time: None,
             view_position: None,
+            require_name: false,
+            cancel: None,
         }
     }
 
@@ -67,29 +111,86 @@ impl SaveBuilder {
         self.save_time = Some(time);
         self
     }
+
+    /// Allows the save to be cancelled through the given flag, causing saving to fail with
+    /// [`BoardSaveError::Cancelled`] if it is cancelled before the file is written.
+    pub fn cancel_flag(mut self, cancel: SaveCancel) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Causes saving to fail with [`BoardSaveError::MissingName`] if the name is not set, or is only whitespace.
+    pub fn require_name(mut self) -> Self {
+        self.require_name = true;
+        self
+    }
 }
 
 impl SaveBuilder {
     /// Saves the board at the given save path.
     /// The save path should be the the path to the save location, **without** the filename or extension, as these will be added during the method.
     ///
+    /// If a save with the same filename already exists, a numbered suffix is added to the filename.
+    ///
     /// The returned value is the file path to the saved file, including the filename. Or an error if one occurred.
     pub fn save(self, save_path: impl Into<PathBuf>) -> Result<Box<Path>, BoardSaveError> {
+        let mut save_path: PathBuf = save_path.into();
+        let (file_name, data, cancel) = self.build()?;
+
+        // Need to push to create new file.
+        save_path.push(file_name);
+        save_path.set_extension("save");
+
+        let save_path = write_unique_file(save_path, &data, cancel.as_ref())?;
+        Ok(save_path.into())
+    }
+
+    /// Saves the board to exactly the given file path, replacing the file if it already exists.
+    ///
+    /// Unlike [`Self::save`], the filename is not generated, so this is suited to saves that are regularly replaced,
+    /// such as autosaves. The file is written under a temporary name first, so an existing save is not lost if the
+    /// write fails.
+    pub fn save_to_file(self, file_path: impl Into<PathBuf>) -> Result<Box<Path>, BoardSaveError> {
+        let file_path: PathBuf = file_path.into();
+        let (_, data, cancel) = self.build()?;
+
+        let mut temporary = file_path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+        // A temporary file may be left behind by an earlier failed save.
+        match std::fs::remove_file(&temporary) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+
+        write_new_file(&temporary, &data, cancel.as_ref())?;
+        std::fs::rename(&temporary, &file_path).inspect_err(|_| {
+            let _ = std::fs::remove_file(&temporary);
+        })?;
+        Ok(file_path.into())
+    }
+
+    /// Checks the builder is valid & converts it into the data to save, along with the generated filename.
+    fn build(self) -> Result<(String, SaveData, Option<SaveCancel>), BoardSaveError> {
         let SaveBuilder {
             save_name,
             save_description,
             save_time,
             view_position,
+            require_name,
+            cancel,
             simulation_save,
         } = self;
 
-        let mut save_path: PathBuf = save_path.into();
         let save_name = save_name.unwrap_or("".into());
+        if require_name && save_name.trim().is_empty() {
+            return Err(BoardSaveError::MissingName);
+        }
         let save_description = save_description.unwrap_or("".into());
 
         // Use time to differentiate saves with the same name.
         let save_time = save_time
-            .unwrap_or_else(|| SystemTime::now())
+            .unwrap_or_else(SystemTime::now)
             .duration_since(UNIX_EPOCH)
             .unwrap_or(Duration::default());
 
@@ -106,10 +207,6 @@ impl SaveBuilder {
             hasher.finish().to_string()
         };
 
-        // Need to push to create new file.
-        save_path.push(file_name);
-        save_path.set_extension("save");
-
         let data = SaveData {
             version: CURRENT_SAVE_VERSION,
             save_name,
@@ -119,15 +216,7 @@ impl SaveBuilder {
             simulation_save,
         };
 
-        // Conversion into string can fail somehow?
-        let file_data = serde_json::to_string(&data).map_err(|_| BoardSaveError::SaveFormat)?;
-
-        // Write file if it doesn't exist.
-        File::create_new(&save_path)
-            .map_err(|err| BoardSaveError::FileOpen(err))?
-            .write_all(&file_data.into_bytes())?;
-
-        Ok(save_path.into())
+        Ok((file_name, data, cancel))
     }
 
     /// Generates the save path that the current data will be saved at if [`Self::save`] was called.
@@ -147,9 +236,9 @@ impl SaveBuilder {
         // Use time to differentiate saves with the same name.
         let save_time = self
             .save_time
-            .unwrap_or_else(|| SystemTime::now())
+            .unwrap_or_else(SystemTime::now)
             .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::default());
+            .unwrap_or_default();
 
         // Generate file name from save content.
         let file_name = {
@@ -170,6 +259,176 @@ impl SaveBuilder {
     }
 }
 
+/// Writes the data to a new file at the given path.
+///
+/// An error is returned if the file already exists, or if the save is cancelled before the file is written. The file
+/// is removed if it could not be completely written.
+fn write_new_file(
+    path: &Path,
+    data: &impl serde::Serialize,
+    cancel: Option<&SaveCancel>,
+) -> Result<(), BoardSaveError> {
+    let cancelled = || cancel.is_some_and(SaveCancel::is_cancelled);
+    if cancelled() {
+        return Err(BoardSaveError::Cancelled);
+    }
+
+    // Conversion into string can fail somehow?
+    let file_data = serde_json::to_string(data).map_err(|_| BoardSaveError::SaveFormat)?;
+
+    // Write file if it doesn't exist.
+    let mut file = File::create_new(path).map_err(BoardSaveError::FileOpen)?;
+    // Converting large boards can take a while, so the save may have been cancelled in the meantime.
+    let written = match cancelled() {
+        true => Err(BoardSaveError::Cancelled),
+        false => file.write_all(&file_data.into_bytes()).map_err(Into::into),
+    };
+
+    if written.is_err() {
+        drop(file);
+        // The original error is more useful than a failure to clean up.
+        let _ = std::fs::remove_file(path);
+    }
+    written
+}
+
+/// The amount of numbered suffixes tried by [`write_unique_file`] before giving up.
+const MAX_FILENAME_SUFFIX: u32 = 16;
+
+/// Writes the data to a new file at the given path, returning the path written to.
+///
+/// If the file already exists, a numbered suffix is added to the filename, before the extension, until a file that
+/// does not exist is found.
+fn write_unique_file(
+    path: PathBuf,
+    data: &impl serde::Serialize,
+    cancel: Option<&SaveCancel>,
+) -> Result<PathBuf, BoardSaveError> {
+    let mut attempt = path.clone();
+    for suffix in 1..=MAX_FILENAME_SUFFIX {
+        match write_new_file(&attempt, data, cancel) {
+            Err(BoardSaveError::FileOpen(err))
+                if err.kind() == std::io::ErrorKind::AlreadyExists =>
+            {
+                let mut file_name = path.file_stem().unwrap_or_default().to_owned();
+                file_name.push(format!("-{suffix}"));
+                attempt.set_file_name(file_name);
+                if let Some(extension) = path.extension() {
+                    attempt.set_extension(extension);
+                }
+            }
+            result => return result.map(|_| attempt),
+        }
+    }
+
+    write_new_file(&attempt, data, cancel).map(|_| attempt)
+}
+
+/// Builder for easily creating a blueprint.
+pub struct BlueprintBuilder {
+    blueprint_name: Option<Box<str>>,
+    blueprint_description: Option<Box<str>>,
+    blueprint_tags: Vec<Box<str>>,
+    blueprint_rule: Option<Rule>,
+
+    simulation_blueprint: SimulationBlueprint,
+}
+
+impl BlueprintBuilder {
+    /// Creates a new blueprint builder with no values set.
+    pub fn new(simulation_blueprint: SimulationBlueprint) -> Self {
+        Self {
+            simulation_blueprint,
+            blueprint_name: None,
+            blueprint_description: None,
+            blueprint_tags: Vec::new(),
+            blueprint_rule: None,
+        }
+    }
+
+    /// Creates a new blueprint builder with the name & rule of the pattern.
+    /// The author of the pattern is not stored in blueprints.
+    pub fn from_pattern(pattern: Pattern) -> Self {
+        let name = pattern.get_name().map(Box::from);
+        let rule = pattern.get_rule();
+        Self {
+            blueprint_name: name,
+            blueprint_rule: rule,
+            ..Self::new(pattern.into_blueprint())
+        }
+    }
+
+    /// The name of the blueprint. This is not the filename.
+    pub fn name(mut self, name: impl Into<Box<str>>) -> Self {
+        self.blueprint_name = Some(name.into());
+        self
+    }
+
+    /// A description of the blueprint.
+    pub fn description(mut self, description: impl Into<Box<str>>) -> Self {
+        self.blueprint_description = Some(description.into());
+        self
+    }
+
+    /// Adds a tag the blueprint is categorised by.
+    pub fn tag(mut self, tag: impl Into<Box<str>>) -> Self {
+        self.blueprint_tags.push(tag.into());
+        self
+    }
+
+    /// Saves the blueprint at the given path.
+    /// The path should be the the path to the blueprint location, **without** the filename or extension, as these
+    /// will be added during the method.
+    ///
+    /// The returned value is the file path to the saved file, including the filename. Or an error if one occurred.
+    pub fn save(self, blueprint_path: impl Into<PathBuf>) -> Result<Box<Path>, BoardSaveError> {
+        let BlueprintBuilder {
+            blueprint_name,
+            blueprint_description,
+            blueprint_tags,
+            blueprint_rule,
+            simulation_blueprint,
+        } = self;
+
+        let mut blueprint_path: PathBuf = blueprint_path.into();
+        let blueprint_name = blueprint_name.unwrap_or("".into());
+        let blueprint_description = blueprint_description.unwrap_or("".into());
+
+        // Generate file name from blueprint content.
+        let file_name = {
+            let mut hasher = DefaultHasher::new();
+
+            blueprint_name.hash(&mut hasher);
+            blueprint_description.hash(&mut hasher);
+            blueprint_tags.hash(&mut hasher);
+            // Blueprints without a rule keep the names they had before rules were stored.
+            if let Some(rule) = blueprint_rule {
+                rule.hash(&mut hasher);
+            }
+            simulation_blueprint.x_size.hash(&mut hasher);
+            simulation_blueprint.y_size.hash(&mut hasher);
+            simulation_blueprint.blueprint_data.hash(&mut hasher);
+
+            hasher.finish().to_string()
+        };
+
+        blueprint_path.push(file_name);
+        blueprint_path.set_extension("blueprint");
+
+        let data = BlueprintData {
+            version: CURRENT_SAVE_VERSION,
+            blueprint_name,
+            blueprint_description,
+            blueprint_tags: blueprint_tags.into(),
+            blueprint_rule,
+            simulation_blueprint,
+        };
+
+        write_new_file(&blueprint_path, &data, None)?;
+        Ok(blueprint_path.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,6 +466,50 @@ mod tests {
             .expect("Can save file");
     }
 
+    #[test]
+    /// The view position is restored when the save is loaded.
+    fn view_position_round_trip() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+
+        let save_path = SaveBuilder::new(Default::default())
+            .view_position((-12, 34).into())
+            .save(temp_dir.path())
+            .expect("Can save file");
+
+        let save = crate::persistence::load_save(save_path.as_ref()).expect("Can load save");
+        assert_eq!(save.view_position(), Some((-12, 34).into()));
+    }
+
+    #[test]
+    /// The name & rule of a pattern are kept when saved as a blueprint.
+    fn blueprint_pattern_round_trip() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        let pattern = Pattern::from_rle("#N Blinker\nx = 3, y = 1, rule = B36/S23\n3o!")
+            .expect("Valid pattern");
+
+        let blueprint_path = BlueprintBuilder::from_pattern(pattern.clone())
+            .save(temp_dir.path())
+            .expect("Can save file");
+
+        let blueprint = crate::persistence::load_blueprint(blueprint_path.as_ref())
+            .expect("Can load blueprint");
+        assert_eq!(blueprint.rule(), Some(Rule::HIGHLIFE));
+        assert_eq!(blueprint.into_pattern(), pattern);
+    }
+
+    #[test]
+    /// Saves without a view position load without one.
+    fn view_position_none() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+
+        let save_path = SaveBuilder::new(Default::default())
+            .save(temp_dir.path())
+            .expect("Can save file");
+
+        let save = crate::persistence::load_save(save_path.as_ref()).expect("Can load save");
+        assert_eq!(save.view_position(), None);
+    }
+
     #[test]
     fn save_board_name() {
         let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
@@ -243,8 +546,13 @@ mod tests {
 
         let save_path = save_builder.generate_save_name(temp_dir.path());
 
-        // Write file with same name
-        std::fs::write(save_path, "").expect("Can write file");
+        // Write file with same name, & every suffixed name.
+        std::fs::write(&save_path, "").expect("Can write file");
+        for suffix in 1..=MAX_FILENAME_SUFFIX {
+            let mut file_name = save_path.file_stem().unwrap().to_owned();
+            file_name.push(format!("-{suffix}.save"));
+            std::fs::write(save_path.with_file_name(file_name), "").expect("Can write file");
+        }
 
         // Try save
         let save_board = save_builder
@@ -261,6 +569,136 @@ mod tests {
             BoardSaveError::WriteFail(..) => {
                 false
             }
+            BoardSaveError::MissingName => {
+                false
+            }
+            BoardSaveError::Cancelled => {
+                false
+            }
         });
     }
+
+    #[test]
+    /// Saves with identical content are written to separate files.
+    fn save_collision() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        let save = || {
+            SaveBuilder::new(Default::default())
+                .name("save")
+                .time(SystemTime::UNIX_EPOCH)
+                .save(temp_dir.path())
+                .expect("Can save file")
+        };
+
+        let first = save();
+        let second = save();
+        let third = save();
+
+        assert_ne!(first, second);
+        assert_eq!(
+            second.file_name().unwrap().to_string_lossy(),
+            format!("{}-1.save", first.file_stem().unwrap().to_string_lossy())
+        );
+        assert_eq!(
+            third.file_name().unwrap().to_string_lossy(),
+            format!("{}-2.save", first.file_stem().unwrap().to_string_lossy())
+        );
+        assert!(crate::persistence::load_save(second.as_ref()).is_ok());
+    }
+
+    #[test]
+    /// Saves without a name are allowed by default.
+    fn unnamed_save_permissive() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+
+        SaveBuilder::new(Default::default())
+            .name("  ")
+            .save(temp_dir.path())
+            .expect("Can save file");
+    }
+
+    #[test]
+    /// Saves without a name are rejected if a name is required.
+    fn unnamed_save_strict() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+
+        for builder in [
+            SaveBuilder::new(Default::default()),
+            SaveBuilder::new(Default::default()).name(" \t"),
+        ] {
+            let error = builder
+                .require_name()
+                .save(temp_dir.path())
+                .expect_err("Must error as there is no name");
+            assert!(matches!(error, BoardSaveError::MissingName), "{error:?}");
+        }
+
+        SaveBuilder::new(Default::default())
+            .name("Name")
+            .require_name()
+            .save(temp_dir.path())
+            .expect("Can save named file");
+        // Nothing is written for the rejected saves.
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    /// A save cancelled before it is written fails without leaving a file.
+    fn save_cancelled() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        let cancel = SaveCancel::default();
+
+        let save_builder = SaveBuilder::new(Default::default()).cancel_flag(cancel.clone());
+        cancel.cancel();
+
+        let error = save_builder
+            .save(temp_dir.path())
+            .expect_err("Must error as the save was cancelled");
+        assert!(matches!(error, BoardSaveError::Cancelled), "{error:?}");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    /// A save that is not cancelled is written as normal.
+    fn save_not_cancelled() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+
+        let save_board = SaveBuilder::new(Default::default())
+            .cancel_flag(SaveCancel::default())
+            .save(temp_dir.path())
+            .expect("Can save");
+        assert!(save_board.exists());
+    }
+
+    #[test]
+    /// Saving to a file replaces the existing file, without leaving a temporary file behind.
+    fn save_to_file_replaces() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        let path = temp_dir.path().join("autosave_0.save");
+        std::fs::write(&path, "old").expect("Can write file");
+
+        let saved = SaveBuilder::new(Default::default())
+            .name("new")
+            .save_to_file(&path)
+            .expect("Can save");
+        assert_eq!(*saved, *path);
+
+        let save = crate::persistence::load_save(path.as_path()).expect("Can load");
+        assert_eq!(save.name(), "new");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    /// The rule of the board is stored in the save file.
+    fn save_rule() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        let simulation_save = SimulationSave::default().with_rule(crate::Rule::HIGHLIFE);
+
+        let path = SaveBuilder::new(simulation_save)
+            .save(temp_dir.path())
+            .expect("Can save");
+
+        let save = crate::persistence::load_save(&*path).expect("Can load");
+        assert_eq!(save.simulation_save().get_rule(), crate::Rule::HIGHLIFE);
+    }
 }
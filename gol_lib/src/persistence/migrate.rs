@@ -0,0 +1,153 @@
+//! Contains [`migrate`].
+//! See its documentation for more information.
+
+use std::time::Duration;
+
+use bitvec::boxed::BitBox;
+
+use super::{SaveData, SimulationSave, CURRENT_SAVE_VERSION};
+use crate::{Area, GlobalPosition};
+
+/// The save file was made by a newer version of the program than this one.
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[error(
+    "Save version {version} is newer than the latest supported version ({CURRENT_SAVE_VERSION})"
+)]
+pub struct SaveVersionError {
+    /// The version of the save file.
+    pub version: u16,
+}
+
+/// The possible errors when migrating save data into the current format.
+#[derive(thiserror::Error, Debug)]
+#[cfg_attr(test, derive(kinded::Kinded))]
+pub enum MigrateError {
+    /// The save data is of an unsupported version.
+    #[error(transparent)]
+    Version(#[from] SaveVersionError),
+    /// The save data is invalid for its version.
+    #[error("Save data is invalid: {0}")]
+    InvalidData(#[from] serde_json::Error),
+}
+
+/// Used to read the version of save data before parsing the rest of it.
+#[derive(serde::Deserialize)]
+struct Version {
+    version: u16,
+}
+
+/// A version 0 save, which always stores the state of every cell within the board area.
+#[derive(serde::Deserialize)]
+struct SaveDataV0 {
+    save_name: Box<str>,
+    save_description: Box<str>,
+    save_time: Duration,
+    view_position: Option<GlobalPosition>,
+
+    generation: u64,
+    board_area: Area,
+    board_data: BitBox,
+}
+
+impl From<SaveDataV0> for SaveData {
+    fn from(save: SaveDataV0) -> Self {
+        SaveData {
+            version: 0,
+            save_name: save.save_name,
+            save_description: save.save_description,
+            save_time: save.save_time,
+            view_position: save.view_position,
+            simulation_save: SimulationSave::new(save.generation, save.board_area, save.board_data),
+        }
+    }
+}
+
+/// Returns an error if the given save version cannot be read by this version of the program.
+pub(crate) fn check_version(version: u16) -> Result<(), SaveVersionError> {
+    match version > CURRENT_SAVE_VERSION {
+        true => Err(SaveVersionError { version }),
+        false => Ok(()),
+    }
+}
+
+/// Parses save data of any supported version into the current save format.
+///
+/// The version of the data is read first, then the data is parsed according to the format of that version.
+pub fn migrate(save: serde_json::Value) -> Result<SaveData, MigrateError> {
+    let Version { version } = serde::Deserialize::deserialize(&save)?;
+    check_version(version)?;
+
+    match version {
+        0 => Ok(serde_json::from_value::<SaveDataV0>(save)?.into()),
+        // Version 2 only added the rule, which version 1 saves are parsed without, so both use the current format.
+        1 | 2 => Ok(serde_json::from_value(save)?),
+        // Any newer versions are rejected by the check above.
+        _ => Err(SaveVersionError { version }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A version 0 save, as written by the program when version 0 was current.
+    const VERSION_0: &str = r#"{
+        "version": 0,
+        "save_name": "Name",
+        "save_description": "Description",
+        "save_time": { "secs": 5, "nanos": 0 },
+        "view_position": { "x": 1, "y": 2 },
+        "generation": 3,
+        "board_area": { "min": { "x": 0, "y": 0 }, "max": { "x": 1, "y": 1 } },
+        "board_data": {
+            "order": "bitvec::order::Lsb0",
+            "head": { "width": 64, "index": 0 },
+            "bits": 4,
+            "data": [13]
+        }
+    }"#;
+
+    #[test]
+    /// A version 0 save is parsed into the current format.
+    fn version_0() {
+        let save = migrate(serde_json::from_str(VERSION_0).unwrap()).expect("Migrates");
+
+        assert_eq!(save.version(), 0);
+        assert_eq!(save.name(), "Name");
+        assert_eq!(save.description(), "Description");
+        assert_eq!(save.time().as_secs(), 5);
+        assert_eq!(save.view_position(), Some(GlobalPosition::new(1, 2)));
+
+        let simulation = save.simulation_save();
+        assert_eq!(simulation.generation, 3);
+        assert_eq!(simulation.board_area, Area::new((0, 0), (1, 1)));
+        assert_eq!(
+            simulation.board_data.iter().by_vals().collect::<Vec<_>>(),
+            [true, false, true, true]
+        );
+    }
+
+    #[test]
+    /// A save made by a newer version is rejected.
+    fn newer_version() {
+        let save = VERSION_0.replace(r#""version": 0"#, r#""version": 65535"#);
+
+        let error = migrate(serde_json::from_str(&save).unwrap()).expect_err("Must error");
+        assert!(
+            matches!(
+                error,
+                MigrateError::Version(SaveVersionError { version: u16::MAX })
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    /// Data without a version is invalid.
+    fn missing_version() {
+        let save = VERSION_0.replace(r#""version": 0,"#, "");
+
+        let error = migrate(serde_json::from_str(&save).unwrap()).expect_err("Must error");
+        assert_eq!(error.kind(), MigrateErrorKind::InvalidData);
+    }
+}
@@ -1,48 +1,140 @@
 //! Contains the data structures used for handling blueprint & save data.
+mod board_data;
 pub mod board_load;
 pub mod board_save;
+pub mod migrate;
+pub mod parse_error;
+pub mod pattern;
+pub mod plaintext;
+pub mod presets;
 pub mod preview;
+pub mod rle;
+mod writable;
 
-use std::{
-    fs::File,
-    path::Path,
-    time::{Duration, SystemTime},
-};
+use std::{fs::File, path::Path, time::Duration};
 
-pub use board_load::load_save;
-pub use board_save::SaveBuilder;
-pub use preview::load_preview;
+pub use board_load::{load_blueprint, load_save};
+pub use board_save::{BlueprintBuilder, SaveBuilder, SaveCancel};
+pub use migrate::{migrate, SaveVersionError};
+pub use preview::{
+    iter_blueprint_previews, iter_previews, load_blueprint_preview, load_preview, quarantine,
+};
 use serde::de::DeserializeOwned;
 use walkdir::WalkDir;
+pub use writable::{check_writable, WriteCheckError};
 
-use crate::{Area, GlobalPosition};
-use bitvec::boxed::BitBox;
+use crate::{rotate, Area, Axis, BoardHasher, GlobalPosition, LoadError, Rule};
+use bitvec::{boxed::BitBox, slice::BitSlice};
 
 /// The latest supported save format version.
-const CURRENT_SAVE_VERSION: u16 = 0;
+///
+/// - Version 0 stores the state of every cell within the board area.
+/// - Version 1 stores boards with few alive cells as a list of the alive cells.
+/// - Version 2 stores the rule of the board.
+const CURRENT_SAVE_VERSION: u16 = 2;
 
 /// The board data that a simulation consists of.
-#[derive(serde::Deserialize, serde::Serialize)]
-#[cfg_attr(any(test, debug_assertions), derive(Debug, PartialEq, Default))]
+#[derive(serde::Deserialize, Clone)]
+#[serde(try_from = "board_data::SimulationSaveFormat<BitBox>")]
+#[cfg_attr(
+    any(test, debug_assertions, feature = "test-suite"),
+    derive(Debug, PartialEq, Default)
+)]
 pub struct SimulationSave {
     pub(crate) generation: u64,
     pub(crate) board_area: Area,
     pub(crate) board_data: BitBox,
+    pub(crate) rule: Rule,
 }
 
 impl SimulationSave {
+    /// Creates a save of a board simulated with the default rule.
     pub fn new(generation: u64, board_area: Area, board_data: impl Into<BitBox>) -> Self {
         Self {
             generation,
             board_area,
             board_data: board_data.into(),
+            rule: Rule::default(),
+        }
+    }
+
+    /// Sets the rule the board is simulated with.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// The rule the board was simulated with.
+    pub fn get_rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// The generation the board was saved on.
+    pub fn get_generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The area of the board that was saved.
+    pub fn get_board_area(&self) -> Area {
+        self.board_area
+    }
+
+    /// The state of each cell within the board area, in the order of [`Area::iterate_over`].
+    pub fn get_board_data(&self) -> &BitSlice {
+        &self.board_data
+    }
+
+    /// A stable hash of the saved board area & the cells within it, which ignores the generation & the rule.
+    ///
+    /// This hash is positional, so only matches saves of the same board area. See [`Self::shape_hash`] for a
+    /// translation-invariant hash, & [`BoardHasher`] for how both are computed.
+    pub fn content_hash(&self) -> u64 {
+        self.hash(true)
+    }
+
+    /// A stable hash of the size of the saved board area & the cells within it, which ignores the generation & the rule.
+    ///
+    /// This hash is translation-invariant, so matches saves of translated copies of the same board, as long as the
+    /// saved areas are the same size.
+    pub fn shape_hash(&self) -> u64 {
+        self.hash(false)
+    }
+
+    fn hash(&self, positional: bool) -> u64 {
+        let mut hasher = BoardHasher::new(self.board_area, positional);
+        for index in self.board_data.iter_ones() {
+            hasher.alive(index as u64);
         }
+        hasher.finish()
+    }
+
+    /// A blueprint of the board area, with its top left at the minimum position of the area.
+    ///
+    /// # Examples
+    /// ```
+    /// # use gol_lib::{persistence::SimulationSave, Area};
+    /// # use bitvec::prelude::*;
+    /// let save = SimulationSave::new(5, Area::new((-1, 3), (0, 3)), bitbox![1, 0]);
+    ///
+    /// let blueprint = save.to_blueprint();
+    /// assert_eq!(blueprint.get_area(), Area::new((0, 0), (1, 0)));
+    /// assert_eq!(blueprint.get_blueprint_data(), save.get_board_data());
+    /// ```
+    pub fn to_blueprint(&self) -> SimulationBlueprint {
+        SimulationBlueprint::new(
+            self.board_area.x_difference(),
+            self.board_area.y_difference(),
+            self.board_data.clone(),
+        )
     }
 }
 
 /// The board data that a blueprint consists of.
-#[derive(serde::Deserialize)]
-#[cfg_attr(any(test, debug_assertions), derive(Debug))]
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[cfg_attr(
+    any(test, debug_assertions, feature = "test-suite"),
+    derive(Debug, PartialEq)
+)]
 pub struct SimulationBlueprint {
     pub(crate) x_size: i32,
     pub(crate) y_size: i32,
@@ -57,6 +149,83 @@ impl SimulationBlueprint {
             blueprint_data: blueprint_data.into(),
         }
     }
+
+    /// The area the blueprint covers, relative to the position it is loaded at.
+    pub fn get_area(&self) -> Area {
+        Area::new((0, 0), (self.x_size, self.y_size))
+    }
+
+    /// The state of each cell within the blueprint area, in the order of [`Area::iterate_over`].
+    pub fn get_blueprint_data(&self) -> &BitSlice {
+        &self.blueprint_data
+    }
+
+    /// Checks that the blueprint data contains a cell for every position within the blueprint area.
+    pub fn validate(&self) -> Result<(), LoadError> {
+        let expected = self.get_area().cell_count();
+        match self.blueprint_data.len() as u64 {
+            found if found == expected => Ok(()),
+            found => Err(LoadError::BlueprintDataLength { expected, found }),
+        }
+    }
+
+    /// The positions of the alive cells within the blueprint, when it is loaded with its top left at the position.
+    ///
+    /// Cells that would be past the edges of the board are skipped.
+    pub fn alive_cells(
+        &self,
+        load_position: GlobalPosition,
+    ) -> impl Iterator<Item = GlobalPosition> + '_ {
+        self.get_area()
+            .iterate_over()
+            .zip(self.blueprint_data.iter())
+            .filter(|(_, cell)| **cell)
+            .filter_map(move |(position, _)| {
+                let x = position.get_x().checked_add(load_position.get_x())?;
+                let y = position.get_y().checked_add(load_position.get_y())?;
+                Some(GlobalPosition::new(x, y))
+            })
+    }
+
+    /// The blueprint mirrored across the axis through its centre.
+    pub fn mirrored(&self, axis: Axis) -> Self {
+        let area = self.get_area();
+        self.transformed(area, |position| axis.mirror(area, position))
+    }
+
+    /// The blueprint rotated clockwise by the given amount of quarter turns, as with [`rotate`].
+    pub fn rotated(&self, quarter_turns: u8) -> Self {
+        let area = self.get_area();
+        let turn = |position| {
+            rotate(area, quarter_turns, position)
+                .expect("Blueprints start at the origin, so are always turned within the range of coordinates")
+        };
+        let turned = Area::new(turn(area.get_min()), turn(area.get_max()));
+        // The turned area is moved back to start at the origin, as blueprints are relative to their top left.
+        let offset = turned.get_min() - area.get_min();
+        self.transformed(
+            Area::new((0, 0), (turned.x_difference(), turned.y_difference())),
+            |position| turn(position) - offset,
+        )
+    }
+
+    /// Moves each alive cell to the position given by `transform`, which must be within the transformed area.
+    fn transformed(
+        &self,
+        area: Area,
+        transform: impl Fn(GlobalPosition) -> GlobalPosition,
+    ) -> Self {
+        let cells = usize::try_from(area.cell_count())
+            .expect("The transformed blueprint has as many cells as the original");
+        let mut blueprint_data = bitvec::bitbox![0; cells];
+        for position in self.alive_cells(GlobalPosition::new(0, 0)) {
+            let (x, y) = transform(position) - area.get_min();
+            let index = y as usize * (area.x_difference() as usize + 1) + x as usize;
+            blueprint_data.set(index, true);
+        }
+
+        Self::new(area.x_difference(), area.y_difference(), blueprint_data)
+    }
 }
 
 /// The errors that can occur when attempting to parse data from a file.
@@ -75,6 +244,7 @@ pub enum ParseError {
 }
 
 /// Finds and parses `Data` recursively from the given directory.
+#[expect(dead_code, reason = "Save & blueprint loading is not yet implemented.")]
 fn load<'a, Data: DeserializeOwned>(
     save_location: impl Into<&'a Path>,
 ) -> Box<[Result<Data, ParseError>]> {
@@ -115,24 +285,105 @@ pub struct SaveData {
 }
 
 impl SaveData {
+    /// The save file version this save was made with.
     pub fn version(&self) -> u16 {
-        todo!()
+        self.version
     }
 
+    /// The name of the save. This is not the name of the save file.
     pub fn name(&self) -> &str {
-        todo!()
+        &self.save_name
     }
+
+    /// The description for the save.
     pub fn description(&self) -> &str {
-        todo!()
+        &self.save_description
     }
+
+    /// The time the save was made.
     pub fn time(&self) -> Duration {
-        todo!()
+        self.save_time
+    }
+
+    /// The position the ui was viewing when the save was made, if any.
+    pub fn view_position(&self) -> Option<GlobalPosition> {
+        self.view_position
+    }
+
+    /// The board data of the save.
+    pub fn simulation_save(&self) -> &SimulationSave {
+        &self.simulation_save
+    }
+
+    /// Consumes the save, returning the board data.
+    pub fn into_simulation_save(self) -> SimulationSave {
+        self.simulation_save
+    }
+}
+
+/// The data that a blueprint file consists of.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(test, derive(Debug))]
+pub struct BlueprintData {
+    version: u16,
+
+    blueprint_name: Box<str>,
+    blueprint_description: Box<str>,
+    blueprint_tags: Box<[Box<str>]>,
+    /// The rule the blueprint is for. Blueprints saved before this was stored have no rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    blueprint_rule: Option<Rule>,
+
+    #[serde(flatten)]
+    simulation_blueprint: SimulationBlueprint,
+}
+
+impl BlueprintData {
+    /// The save file version this blueprint was made with.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// The name of the blueprint. This is not the name of the blueprint file.
+    pub fn name(&self) -> &str {
+        &self.blueprint_name
+    }
+
+    /// The description for the blueprint.
+    pub fn description(&self) -> &str {
+        &self.blueprint_description
+    }
+
+    /// The tags the blueprint is categorised by.
+    pub fn tags(&self) -> &[Box<str>] {
+        &self.blueprint_tags
     }
-    pub fn view_position(&self) -> GlobalPosition {
-        todo!()
+
+    /// The rule the blueprint is for, if it has one.
+    pub fn rule(&self) -> Option<Rule> {
+        self.blueprint_rule
     }
-    pub fn simulation_save(&self) -> SimulationSave {
-        todo!()
+
+    /// The board data of the blueprint.
+    pub fn simulation_blueprint(&self) -> &SimulationBlueprint {
+        &self.simulation_blueprint
+    }
+
+    /// Consumes the blueprint, returning the board data.
+    pub fn into_simulation_blueprint(self) -> SimulationBlueprint {
+        self.simulation_blueprint
+    }
+
+    /// Consumes the blueprint, returning it as a pattern with its name & rule.
+    pub fn into_pattern(self) -> pattern::Pattern {
+        let mut pattern = pattern::Pattern::new(self.simulation_blueprint);
+        if !self.blueprint_name.is_empty() {
+            pattern = pattern.with_name(self.blueprint_name);
+        }
+        match self.blueprint_rule {
+            Some(rule) => pattern.with_rule(rule),
+            None => pattern,
+        }
     }
 }
 
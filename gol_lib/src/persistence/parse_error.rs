@@ -0,0 +1,57 @@
+//! Contains [`PatternParseError`].
+//! See its documentation for more information.
+
+/// An error parsing a pattern from text, along with where within the text it occurred.
+///
+/// Lines & columns are counted from 1, with columns counted in characters. Comment lines are included in the count, so
+/// the position matches the original text.
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[error("Line {line}, column {column}: {reason}")]
+pub struct PatternParseError<E> {
+    /// The line the error occurred on.
+    pub line: usize,
+    /// The column of the line the error occurred at.
+    pub column: usize,
+    /// Why the pattern could not be parsed.
+    pub reason: E,
+}
+
+impl<E> PatternParseError<E> {
+    /// Creates an error at the given line & column.
+    pub(super) fn new(line: usize, column: usize, reason: E) -> Self {
+        Self {
+            line,
+            column,
+            reason,
+        }
+    }
+
+    /// Creates an error just after the last character of the text, for parts of the pattern that are missing.
+    pub(super) fn at_end(text: &str, reason: E) -> Self {
+        let (line, last) = text
+            .lines()
+            .enumerate()
+            .last()
+            .map_or((1, ""), |(index, line)| (index + 1, line));
+
+        Self::new(line, last.chars().count() + 1, reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The end of the text is after the last character of the last line, or the start of an empty text.
+    fn at_end() {
+        assert_eq!(
+            PatternParseError::at_end("ab\ncde\n", ()),
+            PatternParseError::new(2, 4, ())
+        );
+        assert_eq!(
+            PatternParseError::at_end("", ()),
+            PatternParseError::new(1, 1, ())
+        );
+    }
+}
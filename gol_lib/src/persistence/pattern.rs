@@ -0,0 +1,173 @@
+//! Contains [`Pattern`].
+//! See its documentation for more information.
+
+use super::{
+    parse_error::PatternParseError,
+    rle::{self, HeaderRule, RleError},
+    SimulationBlueprint,
+};
+use crate::Rule;
+
+/// A blueprint along with the information needed to share it, such as its name, author & the rule it is for.
+///
+/// Patterns are converted to & from the [RLE format](https://conwaylife.com/wiki/Run_Length_Encoded), which is how
+/// patterns are commonly shared. The name & author are stored in the `#N` & `#O` comment lines, & the rule in the
+/// header.
+#[derive(Clone)]
+#[cfg_attr(
+    any(test, debug_assertions, feature = "test-suite"),
+    derive(Debug, PartialEq)
+)]
+pub struct Pattern {
+    blueprint: SimulationBlueprint,
+    rule: Option<Rule>,
+    name: Option<Box<str>>,
+    author: Option<Box<str>>,
+}
+
+impl Pattern {
+    /// Creates a pattern of the blueprint, with no rule, name or author.
+    pub fn new(blueprint: SimulationBlueprint) -> Self {
+        Self {
+            blueprint,
+            rule: None,
+            name: None,
+            author: None,
+        }
+    }
+
+    /// Sets the rule the pattern is for.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = Some(rule);
+        self
+    }
+
+    /// Sets the name of the pattern.
+    pub fn with_name(mut self, name: impl Into<Box<str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets who created the pattern.
+    pub fn with_author(mut self, author: impl Into<Box<str>>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// The cells of the pattern.
+    pub fn get_blueprint(&self) -> &SimulationBlueprint {
+        &self.blueprint
+    }
+
+    /// The cells of the pattern, discarding the rest of the pattern.
+    pub fn into_blueprint(self) -> SimulationBlueprint {
+        self.blueprint
+    }
+
+    /// The rule the pattern is for, if it has one.
+    pub fn get_rule(&self) -> Option<Rule> {
+        self.rule
+    }
+
+    /// The name of the pattern, if it has one.
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Who created the pattern, if known.
+    pub fn get_author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Parses a pattern in the RLE format, along with the name & author in its comments & the rule in its header.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use gol_lib::{persistence::pattern::Pattern, Rule};
+    /// let pattern = Pattern::from_rle("#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!").expect("Valid pattern");
+    /// assert_eq!(pattern.get_name(), Some("Glider"));
+    /// assert_eq!(pattern.get_rule(), Some(Rule::CONWAY));
+    /// ```
+    pub fn from_rle(text: &str) -> Result<Self, PatternParseError<RleError>> {
+        let (blueprint, rule) = rle::parse(text)?;
+
+        // Only the first of each comment is used, as some patterns repeat them.
+        let comment = |tag: &str| {
+            text.lines()
+                .find_map(|line| line.trim().strip_prefix(tag))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(Box::from)
+        };
+
+        Ok(Self {
+            blueprint,
+            rule: rule.as_ref().map(HeaderRule::parse).transpose()?,
+            name: comment("#N"),
+            author: comment("#O"),
+        })
+    }
+
+    /// Writes the pattern in the RLE format, with the name & author as comments & the rule in the header.
+    ///
+    /// Line breaks in the name & author are replaced with spaces, as comments can only span one line.
+    pub fn to_rle(&self) -> String {
+        let mut text = String::new();
+        for (tag, value) in [("#N", &self.name), ("#O", &self.author)] {
+            if let Some(value) = value {
+                text += &format!("{tag} {}\n", value.replace(['\r', '\n'], " "));
+            }
+        }
+
+        text + &rle::write_rle(&self.blueprint, self.rule)
+    }
+}
+
+impl From<SimulationBlueprint> for Pattern {
+    fn from(blueprint: SimulationBlueprint) -> Self {
+        Self::new(blueprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::presets, *};
+
+    #[test]
+    /// The name, author & rule are kept when written to & parsed from RLE.
+    fn rle_round_trip() {
+        let blueprint = rle::parse_rle(presets::GLIDER).unwrap();
+        let pattern = Pattern::new(blueprint)
+            .with_name("Glider")
+            .with_author("Richard K. Guy")
+            .with_rule(Rule::HIGHLIFE);
+
+        let text = pattern.to_rle();
+        assert_eq!(
+            text,
+            "#N Glider\n#O Richard K. Guy\nx = 3, y = 3, rule = B36/S23\nbo$2bo$3o!\n"
+        );
+        assert_eq!(Pattern::from_rle(&text), Ok(pattern));
+    }
+
+    #[test]
+    /// Patterns without comments or a rule are left without them.
+    fn rle_without_metadata() {
+        let pattern = Pattern::from_rle("#C A comment\nx = 2, y = 1\n2o!").unwrap();
+
+        assert_eq!(pattern.get_name(), None);
+        assert_eq!(pattern.get_author(), None);
+        assert_eq!(pattern.get_rule(), None);
+        assert_eq!(pattern.to_rle(), "x = 2, y = 1\n2o!\n");
+    }
+
+    #[test]
+    /// Names spanning several lines are written on one line.
+    fn rle_multiline_name() {
+        let blueprint = rle::parse_rle("x = 1, y = 1\no!").unwrap();
+        let pattern = Pattern::new(blueprint).with_name("Two\nlines");
+
+        let parsed = Pattern::from_rle(&pattern.to_rle()).unwrap();
+        assert_eq!(parsed.get_name(), Some("Two lines"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Contains [`parse_plaintext`].
+//! See its documentation for more information.
+
+use bitvec::vec::BitVec;
+
+use super::{parse_error::PatternParseError, SimulationBlueprint};
+
+/// The errors that can occur when parsing a pattern in the plaintext format.
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[cfg_attr(test, derive(kinded::Kinded))]
+pub enum PlaintextError {
+    /// There are no rows of cells in the pattern.
+    #[error("The pattern has no cells")]
+    Empty,
+    /// The pattern contains a character that is not part of the plaintext format.
+    #[error("The pattern contains an unexpected character: '{0}'")]
+    UnexpectedCharacter(char),
+    /// The pattern is too large to be stored as a blueprint.
+    #[error("The pattern is too large")]
+    TooLarge,
+}
+
+/// Parses a pattern in the [plaintext format](https://conwaylife.com/wiki/Plaintext) into a blueprint.
+///
+/// Lines starting with `!` are comments, & are ignored. Each other line is a row of cells, where `.` is dead & `O` or
+/// `*` is alive. Rows shorter than the longest row are padded with dead cells. Errors give the line & column of the
+/// text they occurred at.
+///
+/// # Examples
+/// ```rust
+/// # use gol_lib::persistence::plaintext::parse_plaintext;
+/// // A glider.
+/// let blueprint = parse_plaintext("!Name: Glider\n.O.\n..O\nOOO").expect("Valid pattern");
+/// ```
+pub fn parse_plaintext(
+    text: &str,
+) -> Result<SimulationBlueprint, PatternParseError<PlaintextError>> {
+    // Each row along with its line, as comments are skipped but still counted.
+    let rows: Vec<(usize, &str)> = text
+        .lines()
+        .map(str::trim_end)
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.starts_with('!'))
+        .collect();
+    // Blank lines at the end of the text are not part of the pattern, but blank lines within it are empty rows.
+    let height = rows
+        .iter()
+        .rposition(|(_, row)| !row.is_empty())
+        .ok_or_else(|| PatternParseError::at_end(text, PlaintextError::Empty))?
+        + 1;
+    let rows = &rows[..height];
+
+    let too_large = |line, column| PatternParseError::new(line, column, PlaintextError::TooLarge);
+    let (width, widest_line) = rows
+        .iter()
+        .map(|(line, row)| (row.chars().count(), *line))
+        .max()
+        .unwrap_or_default();
+    if width > i32::MAX as usize {
+        return Err(too_large(widest_line, i32::MAX as usize + 1));
+    }
+    if height > i32::MAX as usize {
+        return Err(too_large(rows[i32::MAX as usize].0, 1));
+    }
+
+    let mut cells: BitVec = BitVec::repeat(false, width * height);
+    for (y, (line, row)) in rows.iter().enumerate() {
+        for (x, character) in row.chars().enumerate() {
+            match character {
+                '.' => {}
+                'O' | '*' => cells.set(y * width + x, true),
+                character => {
+                    return Err(PatternParseError::new(
+                        *line,
+                        x + 1,
+                        PlaintextError::UnexpectedCharacter(character),
+                    ))
+                }
+            }
+        }
+    }
+
+    // The blueprint size is the difference between the first & last positions.
+    Ok(SimulationBlueprint::new(
+        width as i32 - 1,
+        height as i32 - 1,
+        cells,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The state of each cell in the blueprint, in rows.
+    fn rows(blueprint: &SimulationBlueprint) -> Vec<String> {
+        blueprint
+            .blueprint_data
+            .chunks(blueprint.x_size as usize + 1)
+            .map(|row| {
+                row.iter()
+                    .by_vals()
+                    .map(|alive| if alive { 'o' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    /// Comments are skipped & each line is a row.
+    fn parse_glider() {
+        let blueprint = parse_plaintext("!Name: Glider\n!\n.O.\n..O\nOOO\n").unwrap();
+
+        assert_eq!((blueprint.x_size, blueprint.y_size), (2, 2));
+        assert_eq!(rows(&blueprint), [".o.", "..o", "ooo"]);
+    }
+
+    #[test]
+    /// Short & blank rows are padded with dead cells.
+    fn parse_short_rows() {
+        let blueprint = parse_plaintext("*\n\n..*").unwrap();
+
+        assert_eq!(rows(&blueprint), ["o..", "...", "..o"]);
+    }
+
+    #[test]
+    /// Invalid patterns are rejected, with the line & column of the error.
+    fn parse_invalid() {
+        assert_eq!(
+            parse_plaintext("!Only a comment\n"),
+            Err(PatternParseError::new(1, 16, PlaintextError::Empty))
+        );
+        assert_eq!(
+            parse_plaintext(".O.\nbob"),
+            Err(PatternParseError::new(
+                2,
+                1,
+                PlaintextError::UnexpectedCharacter('b')
+            ))
+        );
+        // Comments are counted as lines.
+        assert_eq!(
+            parse_plaintext("!Name: Glider\n.O.\n..O\nOO0"),
+            Err(PatternParseError::new(
+                4,
+                3,
+                PlaintextError::UnexpectedCharacter('0')
+            ))
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Contains the built-in blueprints of common patterns.
+//! See [`all`] for more information.
+
+use super::{rle::parse_rle, SimulationBlueprint};
+
+/// The RLE pattern of a glider, the smallest spaceship.
+pub const GLIDER: &str = "x = 3, y = 3\nbob$2bo$3o!";
+/// The RLE pattern of a lightweight spaceship, which travels orthogonally.
+pub const LIGHTWEIGHT_SPACESHIP: &str = "x = 5, y = 4\nbo2bo$o4b$o3bo$4o!";
+/// The RLE pattern of a blinker, the smallest oscillator.
+pub const BLINKER: &str = "x = 3, y = 1\n3o!";
+/// The RLE pattern of a pulsar, an oscillator with a period of 3.
+pub const PULSAR: &str = "x = 13, y = 13\n\
+    2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$2b3o3b3o2b$o4bobo4bo$o4bobo4bo$\n\
+    o4bobo4bo2$2b3o3b3o!";
+/// The RLE pattern of a Gosper glider gun, which emits a glider every 30 generations.
+pub const GOSPER_GLIDER_GUN: &str = "x = 36, y = 9\n\
+    24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$10bo5bo7bo$\n\
+    11bo3bo$12b2o!";
+
+/// The name & RLE pattern of each built-in blueprint.
+const PRESETS: [(&str, &str); 5] = [
+    ("Glider", GLIDER),
+    ("Lightweight spaceship", LIGHTWEIGHT_SPACESHIP),
+    ("Blinker", BLINKER),
+    ("Pulsar", PULSAR),
+    ("Gosper glider gun", GOSPER_GLIDER_GUN),
+];
+
+/// The built-in blueprints of common patterns, along with their names.
+pub fn all() -> Vec<(&'static str, SimulationBlueprint)> {
+    PRESETS
+        .into_iter()
+        .map(|(name, rle)| {
+            let blueprint = parse_rle(rle).expect("Built-in patterns are valid");
+            (name, blueprint)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Every preset parses with the expected size & amount of alive cells.
+    fn presets() {
+        let expected = [
+            ("Glider", 2, 2, 5),
+            ("Lightweight spaceship", 4, 3, 9),
+            ("Blinker", 2, 0, 3),
+            ("Pulsar", 12, 12, 48),
+            ("Gosper glider gun", 35, 8, 36),
+        ];
+
+        let presets = all();
+        assert_eq!(presets.len(), expected.len());
+        for ((name, blueprint), (expected_name, x_size, y_size, alive)) in
+            presets.iter().zip(expected)
+        {
+            assert_eq!(*name, expected_name);
+            assert_eq!(
+                (blueprint.x_size, blueprint.y_size),
+                (x_size, y_size),
+                "{name}"
+            );
+            assert_eq!(blueprint.blueprint_data.count_ones(), alive, "{name}");
+        }
+    }
+}
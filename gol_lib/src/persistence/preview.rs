@@ -1,5 +1,9 @@
-use crate::persistence::CURRENT_SAVE_VERSION;
-use std::{path::Path, time::Duration};
+use crate::persistence::migrate::{check_version, SaveVersionError};
+use serde::de::DeserializeOwned;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use walkdir::WalkDir;
 
 /// The errors that can occur when attempting to parse a [`SavePreview`] from a save file.
@@ -21,6 +25,12 @@ pub enum PreviewParseError {
         error: serde_json::Error,
         path: Box<Path>,
     },
+    /// The save file was made by a newer version of the program.
+    #[error("{error}")]
+    UnsupportedVersion {
+        error: SaveVersionError,
+        path: Box<Path>,
+    },
 }
 
 impl PreviewParseError {
@@ -30,6 +40,7 @@ impl PreviewParseError {
             PreviewParseError::FileSearch(error) => error.path(),
             PreviewParseError::FileParse { path, .. } => Some(path),
             PreviewParseError::InvalidData { path, .. } => Some(path),
+            PreviewParseError::UnsupportedVersion { path, .. } => Some(path),
         }
     }
 }
@@ -38,9 +49,74 @@ impl PreviewParseError {
 pub fn load_preview<'a>(
     save_location: impl Into<&'a Path>,
 ) -> Box<[Result<SavePreview, PreviewParseError>]> {
-    WalkDir::new(save_location.into())
+    iter_previews(save_location.into()).collect()
+}
+
+/// Finds and parses [`BlueprintPreview`]s recursively from the given directory.
+pub fn load_blueprint_preview<'a>(
+    blueprint_location: impl Into<&'a Path>,
+) -> Box<[Result<BlueprintPreview, PreviewParseError>]> {
+    iter_blueprint_previews(blueprint_location.into()).collect()
+}
+
+/// Finds and parses [`SavePreview`]s recursively from the given directory.
+///
+/// Each file is only parsed once the iterator reaches it, so the previews can be used as they are parsed.
+pub fn iter_previews(
+    save_location: impl Into<PathBuf>,
+) -> impl Iterator<Item = Result<SavePreview, PreviewParseError>> {
+    parse_files(save_location.into(), SavePreview::new)
+}
+
+/// Finds and parses [`BlueprintPreview`]s recursively from the given directory.
+///
+/// Each file is only parsed once the iterator reaches it, so the previews can be used as they are parsed.
+pub fn iter_blueprint_previews(
+    blueprint_location: impl Into<PathBuf>,
+) -> impl Iterator<Item = Result<BlueprintPreview, PreviewParseError>> {
+    parse_files(blueprint_location.into(), BlueprintPreview::new)
+}
+
+/// The name of the directory within a save or blueprint location that invalid files are moved into.
+///
+/// Files within this directory are not parsed.
+pub const QUARANTINE_DIR: &str = "corrupt";
+
+/// Moves the file into the [`QUARANTINE_DIR`] within the given location, so it is no longer parsed.
+///
+/// The returned value is the new path of the file.
+pub fn quarantine(location: &Path, file: &Path) -> std::io::Result<PathBuf> {
+    let mut quarantine = location.join(QUARANTINE_DIR);
+    std::fs::create_dir_all(&quarantine)?;
+
+    let file_name = file.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "The path is not a file")
+    })?;
+    quarantine.push(file_name);
+
+    // Renaming would replace a previously quarantined file with the same name.
+    if quarantine.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            "A quarantined file with the same name already exists",
+        ));
+    }
+
+    std::fs::rename(file, &quarantine)?;
+    Ok(quarantine)
+}
+
+/// Lazily parses every file recursively from the given directory with the given parser.
+///
+/// Files within the [`QUARANTINE_DIR`] are skipped.
+fn parse_files<Preview>(
+    location: PathBuf,
+    parse: impl Fn(&Path) -> Result<Preview, PreviewParseError>,
+) -> impl Iterator<Item = Result<Preview, PreviewParseError>> {
+    WalkDir::new(location)
         .follow_links(true)
         .into_iter()
+        .filter_entry(|entry| !(entry.depth() == 1 && entry.file_name() == QUARANTINE_DIR))
         // Only parse files
         .filter_map(|file| match file {
             Ok(file) if file.file_type().is_file() => Some(Ok(file)),
@@ -48,11 +124,35 @@ pub fn load_preview<'a>(
             Err(err) => Some(Err(err.into())),
         })
         // Attempt to parse file
-        .map(|file| match file {
-            Ok(file) => SavePreview::new(file.path()),
+        .map(move |file| match file {
+            Ok(file) => parse(file.path()),
             Err(err) => Err(err),
         })
-        .collect()
+}
+
+/// Reads the file at the given path & parses it as `Data`.
+///
+/// An error is returned if the file was made with an unsupported version.
+fn parse_partial<Data: DeserializeOwned>(
+    path: &Path,
+    version: impl Fn(&Data) -> u16,
+) -> Result<Data, PreviewParseError> {
+    let file_data = std::fs::read_to_string(path).map_err(|err| PreviewParseError::FileParse {
+        error: err,
+        path: path.into(),
+    })?;
+
+    let data = serde_json::from_str(&file_data).map_err(|err| PreviewParseError::InvalidData {
+        error: err,
+        path: path.into(),
+    })?;
+
+    check_version(version(&data)).map_err(|err| PreviewParseError::UnsupportedVersion {
+        error: err,
+        path: path.into(),
+    })?;
+
+    Ok(data)
 }
 
 /// Contains the information about a board save, without actually containing the board save data.
@@ -77,38 +177,28 @@ pub struct SavePreview {
 
 impl SavePreview {
     /// Parses a new instance of [`SavePreview`] from the given filepath.
-    fn new<'a>(save_path: impl Into<&'a Path>) -> Result<SavePreview, PreviewParseError> {
+    fn new(save_path: &Path) -> Result<SavePreview, PreviewParseError> {
         /// Used to parse the data for SaveData instead of manual implementation.
         #[derive(serde::Deserialize)]
         struct PartialData {
+            version: u16,
             save_name: Box<str>,
             save_description: Box<str>,
             generation: u64,
             save_time: Duration,
         }
 
-        let save_path = save_path.into();
-
-        // Parse the file data.
-        let file_data =
-            std::fs::read_to_string(save_path).map_err(|err| PreviewParseError::FileParse {
-                error: err,
-                path: save_path.into(),
-            })?;
-
         let PartialData {
+            version,
             save_name,
             save_description,
             generation,
             save_time,
-        } = serde_json::from_str(&file_data).map_err(|err| PreviewParseError::InvalidData {
-            error: err,
-            path: save_path.into(),
-        })?;
+        } = parse_partial(save_path, |data: &PartialData| data.version)?;
 
         // Construct the finial object.
         Ok(SavePreview {
-            version: CURRENT_SAVE_VERSION,
+            version,
             save_name,
             save_description,
             generation,
@@ -148,11 +238,85 @@ impl SavePreview {
     }
 }
 
+/// Contains the information about a blueprint, without actually containing the blueprint data.
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct BlueprintPreview {
+    /// The save file version.
+    version: u16,
+
+    /// The name of the blueprint. This is not the name of the blueprint file.
+    blueprint_name: Box<str>,
+    /// A description of the blueprint.
+    blueprint_description: Box<str>,
+    /// The tags the blueprint is categorised by.
+    blueprint_tags: Box<[Box<str>]>,
+
+    /// The path to the blueprint file. This includes the filename.
+    blueprint_path: Box<Path>,
+}
+
+impl BlueprintPreview {
+    /// Parses a new instance of [`BlueprintPreview`] from the given filepath.
+    fn new(blueprint_path: &Path) -> Result<BlueprintPreview, PreviewParseError> {
+        /// Used to parse the data for BlueprintData instead of manual implementation.
+        #[derive(serde::Deserialize)]
+        struct PartialData {
+            version: u16,
+            blueprint_name: Box<str>,
+            blueprint_description: Box<str>,
+            blueprint_tags: Box<[Box<str>]>,
+        }
+
+        let PartialData {
+            version,
+            blueprint_name,
+            blueprint_description,
+            blueprint_tags,
+        } = parse_partial(blueprint_path, |data: &PartialData| data.version)?;
+
+        Ok(BlueprintPreview {
+            version,
+            blueprint_name,
+            blueprint_description,
+            blueprint_tags,
+            blueprint_path: blueprint_path.into(),
+        })
+    }
+
+    /// The save file version of the blueprint file.
+    pub fn get_version(&self) -> u16 {
+        self.version
+    }
+
+    /// The name of the blueprint. This is not the name of the blueprint file.
+    pub fn get_blueprint_name(&self) -> &str {
+        &self.blueprint_name
+    }
+
+    /// The description for the blueprint.
+    pub fn get_blueprint_description(&self) -> &str {
+        &self.blueprint_description
+    }
+
+    /// The tags the blueprint is categorised by.
+    pub fn get_tags(&self) -> &[Box<str>] {
+        &self.blueprint_tags
+    }
+
+    /// The path to the blueprint file.
+    pub fn get_blueprint_path(&self) -> &Path {
+        &self.blueprint_path
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    use crate::persistence::board_save::SaveBuilder;
+    use crate::persistence::{
+        board_save::{BlueprintBuilder, SaveBuilder},
+        SimulationBlueprint, CURRENT_SAVE_VERSION,
+    };
 
     use super::*;
 
@@ -179,7 +343,7 @@ mod tests {
         assert_eq!(parse_saves.len(), 1);
 
         // Must return with invalid data error
-        let save_error = parse_saves.get(0).unwrap().as_ref().unwrap_err();
+        let save_error = parse_saves.first().unwrap().as_ref().unwrap_err();
         assert_eq!(save_error.kind(), PreviewParseErrorKind::InvalidData)
     }
 
@@ -200,7 +364,7 @@ mod tests {
         assert_eq!(parse_saves.len(), 1);
 
         // Must return with invalid data error
-        let save_error = parse_saves.get(0).unwrap().as_ref().unwrap_err();
+        let save_error = parse_saves.first().unwrap().as_ref().unwrap_err();
         assert_eq!(save_error.kind(), PreviewParseErrorKind::InvalidData)
     }
 
@@ -223,16 +387,14 @@ mod tests {
         assert_eq!(parse_saves.len(), 1);
 
         assert_eq!(
-            parse_saves.get(0).unwrap().as_ref().unwrap(),
+            parse_saves.first().unwrap().as_ref().unwrap(),
             &SavePreview {
                 version: CURRENT_SAVE_VERSION,
                 save_name: save_name.into(),
                 save_description: save_description.into(),
                 generation: 0,
                 save_path: path,
-                save_time: save_time
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or(Duration::default()),
+                save_time: save_time.duration_since(UNIX_EPOCH).unwrap_or_default(),
             }
         );
     }
@@ -260,16 +422,14 @@ mod tests {
         assert_eq!(parse_saves.len(), 1);
 
         assert_eq!(
-            parse_saves.get(0).unwrap().as_ref().unwrap(),
+            parse_saves.first().unwrap().as_ref().unwrap(),
             &SavePreview {
                 version: CURRENT_SAVE_VERSION,
                 save_name: save_name.into(),
                 save_description: save_description.into(),
                 generation: 0,
                 save_path: path,
-                save_time: save_time
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or(Duration::default()),
+                save_time: save_time.duration_since(UNIX_EPOCH).unwrap_or_default(),
             }
         );
     }
@@ -303,7 +463,7 @@ mod tests {
         assert_eq!(parse_saves.len(), 2);
 
         // Get "correct" saves
-        let save_0 = parse_saves.get(0).unwrap().as_ref();
+        let save_0 = parse_saves.first().unwrap().as_ref();
         let save_1 = parse_saves.get(1).unwrap().as_ref();
 
         let invalid = {
@@ -335,9 +495,7 @@ mod tests {
                 save_description: save_description.into(),
                 generation: 0,
                 save_path: path,
-                save_time: save_time
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or(Duration::default()),
+                save_time: save_time.duration_since(UNIX_EPOCH).unwrap_or_default(),
             }
         );
     }
@@ -356,8 +514,85 @@ mod tests {
         assert_eq!(parse_saves.len(), 1);
 
         // Must return with invalid data error
-        let save_error = parse_saves.get(0).unwrap().as_ref().unwrap_err();
+        let save_error = parse_saves.first().unwrap().as_ref().unwrap_err();
         assert_eq!(save_error.path(), Some(path_buf).as_deref());
         assert_eq!(save_error.kind(), PreviewParseErrorKind::InvalidData)
     }
+
+    #[test]
+    /// A save made by a newer version of the program must be rejected.
+    fn newer_version() {
+        let temp_dir = tempfile::tempdir().expect("Able to create temp dir");
+
+        let path = SaveBuilder::new(Default::default())
+            .name("name")
+            .save(temp_dir.path())
+            .expect("Can save file");
+
+        // Bump the version of the save past the supported version.
+        let data = std::fs::read_to_string(&path).expect("Can read file");
+        let data = data.replace(
+            &format!(r#""version":{CURRENT_SAVE_VERSION}"#),
+            r#""version":65535"#,
+        );
+        std::fs::write(&path, data).expect("Can write file");
+
+        let parse_saves = load_preview(temp_dir.path());
+        assert_eq!(parse_saves.len(), 1);
+
+        let save_error = parse_saves.first().unwrap().as_ref().unwrap_err();
+        assert_eq!(save_error.path(), Some(path).as_deref());
+        assert_eq!(save_error.kind(), PreviewParseErrorKind::UnsupportedVersion)
+    }
+
+    #[test]
+    /// A valid blueprint should parse correctly, including its tags.
+    fn valid_blueprint() {
+        let temp_dir = tempfile::tempdir().expect("Able to create temp dir");
+
+        let path = BlueprintBuilder::new(SimulationBlueprint::new(1, 3, bitvec::bitbox![1; 3]))
+            .name("Blinker")
+            .description("An oscillator")
+            .tag("Oscillator")
+            .tag("Small")
+            .save(temp_dir.path())
+            .expect("Can save file");
+
+        let parse_blueprints = load_blueprint_preview(temp_dir.path());
+        assert_eq!(parse_blueprints.len(), 1);
+
+        assert_eq!(
+            parse_blueprints.first().unwrap().as_ref().unwrap(),
+            &BlueprintPreview {
+                version: CURRENT_SAVE_VERSION,
+                blueprint_name: "Blinker".into(),
+                blueprint_description: "An oscillator".into(),
+                blueprint_tags: ["Oscillator".into(), "Small".into()].into(),
+                blueprint_path: path,
+            }
+        );
+    }
+
+    #[test]
+    /// A quarantined file is moved, & is no longer parsed.
+    fn quarantine_invalid() {
+        let temp_dir = tempfile::tempdir().expect("Able to create temp dir");
+        let path = temp_dir.path().join("Invalid");
+        std::fs::write(&path, "Invalid!!!").expect("Able to write file");
+
+        let parse_saves = load_preview(temp_dir.path());
+        let error_path = parse_saves[0].as_ref().unwrap_err().path().unwrap();
+        let new_path = quarantine(temp_dir.path(), error_path).expect("Can quarantine file");
+
+        assert!(!path.exists());
+        assert_eq!(
+            new_path,
+            temp_dir.path().join(QUARANTINE_DIR).join("Invalid")
+        );
+        assert_eq!(
+            std::fs::read_to_string(new_path).expect("File was moved"),
+            "Invalid!!!"
+        );
+        assert!(load_preview(temp_dir.path()).is_empty());
+    }
 }
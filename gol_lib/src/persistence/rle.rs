@@ -0,0 +1,495 @@
+//! Contains [`parse_rle`] & [`write_rle`].
+//! See their documentation for more information.
+
+use bitvec::vec::BitVec;
+
+use super::{parse_error::PatternParseError, SimulationBlueprint, SimulationSave};
+use crate::{Rule, RuleError};
+
+/// The longest line written by [`write_rle`], as recommended by the format.
+const LINE_LENGTH: usize = 70;
+
+/// The most cells a pattern may contain, as given by its header.
+/// Every cell is stored, so this limits the memory used by a pattern to 32MiB.
+pub const MAX_PATTERN_CELLS: u64 = 1 << 28;
+
+/// The errors that can occur when parsing a pattern in the RLE format.
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[cfg_attr(test, derive(kinded::Kinded))]
+pub enum RleError {
+    /// There is no header line giving the size of the pattern.
+    #[error("The pattern has no header")]
+    MissingHeader,
+    /// The header line does not give a valid size for the pattern.
+    #[error("The pattern header is invalid: {0}")]
+    InvalidHeader(Box<str>),
+    /// The pattern contains a character that is not part of the RLE format.
+    #[error("The pattern contains an unexpected character: '{0}'")]
+    UnexpectedCharacter(char),
+    /// The size given by the header contains more cells than [`MAX_PATTERN_CELLS`].
+    #[error("The pattern is too large, as its {0} cells exceed the limit of {MAX_PATTERN_CELLS}")]
+    TooLarge(u64),
+    /// The pattern has an alive cell outside of the size given by its header.
+    #[error("The pattern has a cell outside of the size given by its header")]
+    OutOfBounds,
+    /// A run has a count of 0, or one too large to be stored.
+    #[error("The pattern has a run with an invalid count")]
+    InvalidCount,
+    /// The pattern does not end with a `!`, so it may have been cut short.
+    #[error("The pattern does not end with '!'")]
+    MissingEnd,
+    /// The rule given in the header is invalid.
+    #[error("The pattern rule is invalid: {0}")]
+    InvalidRule(#[from] RuleError),
+}
+
+/// Parses a pattern in the [RLE format](https://conwaylife.com/wiki/Run_Length_Encoded) into a blueprint.
+///
+/// Lines starting with `#` are comments, & are ignored. Any rule given in the header is ignored, see
+/// [`parse_rle_board`] to also parse the rule. Errors give the line & column of the text they occurred at.
+///
+/// # Examples
+/// ```rust
+/// # use gol_lib::persistence::rle::parse_rle;
+/// // A glider.
+/// let blueprint = parse_rle("x = 3, y = 3\nbob$2bo$3o!").expect("Valid pattern");
+/// ```
+pub fn parse_rle(text: &str) -> Result<SimulationBlueprint, PatternParseError<RleError>> {
+    parse(text).map(|(blueprint, _)| blueprint)
+}
+
+/// Parses a pattern in the [RLE format](https://conwaylife.com/wiki/Run_Length_Encoded) into a board at generation
+/// 0, with the top left of the pattern at the origin.
+///
+/// The board uses the rule given in the header, or the default rule if the header doesn't give one.
+///
+/// # Examples
+/// ```rust
+/// # use gol_lib::{persistence::rle::parse_rle_board, Rule};
+/// // A replicator, which only replicates in HighLife.
+/// let board = parse_rle_board("x = 5, y = 5, rule = B36/S23\n2b3o$bo2bo$o3bo$o2bo$3o!").expect("Valid pattern");
+/// assert_eq!(board.get_rule(), Rule::HIGHLIFE);
+/// ```
+pub fn parse_rle_board(text: &str) -> Result<SimulationSave, PatternParseError<RleError>> {
+    let (blueprint, rule) = parse(text)?;
+    let rule = rule
+        .as_ref()
+        .map(HeaderRule::parse)
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(SimulationSave::new(0, blueprint.get_area(), blueprint.blueprint_data).with_rule(rule))
+}
+
+/// Writes the blueprint as a pattern in the [RLE format](https://conwaylife.com/wiki/Run_Length_Encoded), with the
+/// rule in the header if one is given.
+///
+/// # Examples
+/// ```rust
+/// # use gol_lib::persistence::rle::{parse_rle, write_rle};
+/// let glider = parse_rle("x = 3, y = 3\nbob$2bo$3o!").expect("Valid pattern");
+/// assert_eq!(write_rle(&glider, None), "x = 3, y = 3\nbo$2bo$3o!\n");
+/// ```
+pub fn write_rle(blueprint: &SimulationBlueprint, rule: Option<Rule>) -> String {
+    let width = blueprint.x_size as usize + 1;
+    let mut text = format!("x = {width}, y = {}", blueprint.y_size as usize + 1);
+    if let Some(rule) = rule {
+        text += &format!(", rule = {rule}");
+    }
+    text.push('\n');
+
+    // Each run is written as a separate token, so lines are only broken between runs.
+    let mut tokens = Vec::new();
+    // The row ends that have not been written, as the ends of empty rows are combined into a single run.
+    let mut row_ends = 0;
+    for row in blueprint.blueprint_data.chunks(width) {
+        // Trailing dead cells are implied by the end of the row.
+        let Some(last_alive) = row.last_one() else {
+            row_ends += 1;
+            continue;
+        };
+
+        if row_ends > 0 {
+            tokens.push(run(row_ends, '$'));
+        }
+        row_ends = 1;
+
+        let mut cells = row[..=last_alive].iter().by_vals().peekable();
+        while let Some(alive) = cells.next() {
+            let mut count = 1;
+            while cells.next_if_eq(&alive).is_some() {
+                count += 1;
+            }
+            tokens.push(run(count, if alive { 'o' } else { 'b' }));
+        }
+    }
+    tokens.push("!".to_owned());
+
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length + token.len() > LINE_LENGTH {
+            text.push('\n');
+            line_length = 0;
+        }
+        line_length += token.len();
+        text += &token;
+    }
+    text.push('\n');
+    text
+}
+
+/// A run of the given amount of the tag, where the count is omitted for a single tag.
+fn run(count: usize, tag: char) -> String {
+    match count {
+        1 => tag.to_string(),
+        count => format!("{count}{tag}"),
+    }
+}
+
+/// The rule given in the header of an RLE pattern, along with where it is within the text.
+pub(super) struct HeaderRule<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl HeaderRule<'_> {
+    /// Parses the rule, giving where it is within the text if it is invalid.
+    pub(super) fn parse(&self) -> Result<Rule, PatternParseError<RleError>> {
+        self.text
+            .parse()
+            .map_err(|err: RuleError| PatternParseError::new(self.line, self.column, err.into()))
+    }
+}
+
+/// Parses a pattern in the RLE format into a blueprint, along with the rule given in its header, if any.
+pub(super) fn parse(
+    text: &str,
+) -> Result<(SimulationBlueprint, Option<HeaderRule<'_>>), PatternParseError<RleError>> {
+    // Comments are skipped, but still counted, so errors give the line within the original text.
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        });
+
+    let Some((header_line, header)) = lines.next() else {
+        return Err(PatternParseError::at_end(text, RleError::MissingHeader));
+    };
+    let (width, height, rule) = parse_header(header_line, header)?;
+    let mut cells: BitVec = BitVec::repeat(false, width * height);
+
+    let (mut x, mut y) = (0, 0);
+    // The count of the current run, along with the line & column it starts at.
+    let mut count: Option<(usize, usize, usize)> = None;
+    let mut ended = false;
+    'lines: for (line, characters) in lines {
+        for (index, character) in characters.chars().enumerate() {
+            let column = index + 1;
+
+            if let Some(digit) = character.to_digit(10) {
+                let (run, start_line, start_column) = count.unwrap_or((0, line, column));
+                let run = run
+                    .checked_mul(10)
+                    .and_then(|run| run.checked_add(digit as usize))
+                    .ok_or_else(|| {
+                        PatternParseError::new(start_line, start_column, RleError::InvalidCount)
+                    })?;
+                count = Some((run, start_line, start_column));
+                continue;
+            }
+            if character.is_whitespace() {
+                continue;
+            }
+
+            // A run without a count is of a single cell.
+            let (run, run_line, run_column) = match count.take() {
+                None => (1, line, column),
+                Some((0, start_line, start_column)) => {
+                    return Err(PatternParseError::new(
+                        start_line,
+                        start_column,
+                        RleError::InvalidCount,
+                    ))
+                }
+                Some(run) => run,
+            };
+            match character {
+                'b' | '.' => x = run.saturating_add(x),
+                '$' => {
+                    x = 0;
+                    y = run.saturating_add(y);
+                }
+                '!' => {
+                    ended = true;
+                    break 'lines;
+                }
+                // Any other letter is an alive state, as used by patterns with more than two states.
+                character if character.is_ascii_alphabetic() => {
+                    if run.saturating_add(x) > width || y >= height {
+                        return Err(PatternParseError::new(
+                            run_line,
+                            run_column,
+                            RleError::OutOfBounds,
+                        ));
+                    }
+                    cells[y * width + x..y * width + x + run].fill(true);
+                    x += run;
+                }
+                character => {
+                    return Err(PatternParseError::new(
+                        line,
+                        column,
+                        RleError::UnexpectedCharacter(character),
+                    ))
+                }
+            }
+        }
+    }
+
+    if !ended {
+        return Err(PatternParseError::at_end(text, RleError::MissingEnd));
+    }
+
+    // The blueprint size is the difference between the first & last positions.
+    let blueprint = SimulationBlueprint::new(width as i32 - 1, height as i32 - 1, cells);
+    Ok((blueprint, rule))
+}
+
+/// Parses the width, height & rule from an RLE header on the given line, such as `x = 3, y = 3, rule = B3/S23`.
+fn parse_header(
+    line: usize,
+    header: &str,
+) -> Result<(usize, usize, Option<HeaderRule<'_>>), PatternParseError<RleError>> {
+    let invalid = |column| {
+        PatternParseError::new(line, column, RleError::InvalidHeader(header.trim().into()))
+    };
+    let mut width = None;
+    let mut height = None;
+    let mut rule = None;
+
+    // The column each entry starts at.
+    let mut column = 1;
+    for entry in header.split(',') {
+        let entry_column = column + entry.chars().take_while(|c| c.is_whitespace()).count();
+        column += entry.chars().count() + 1;
+
+        let Some((key, value)) = entry.split_once('=') else {
+            return Err(invalid(entry_column));
+        };
+        let value_column =
+            entry_column + entry.trim_start().chars().count() - value.trim_start().chars().count();
+
+        let size = || {
+            value
+                .trim()
+                .parse::<usize>()
+                .ok()
+                // Blueprint sizes are stored as i32s, & must contain at least one cell.
+                .filter(|size| (1..=i32::MAX as usize).contains(size))
+                .ok_or_else(|| invalid(value_column))
+        };
+        match key.trim() {
+            "x" => width = Some(size()?),
+            "y" => height = Some(size()?),
+            "rule" => {
+                rule = Some(HeaderRule {
+                    text: value.trim(),
+                    line,
+                    column: value_column,
+                })
+            }
+            _ => {}
+        }
+    }
+
+    let header_column = 1 + header.chars().take_while(|c| c.is_whitespace()).count();
+    match (width, height) {
+        // The cells are allocated before the pattern is read, so a header alone could exhaust memory.
+        (Some(width), Some(height)) => match width as u64 * height as u64 {
+            cells if cells > MAX_PATTERN_CELLS => Err(PatternParseError::new(
+                line,
+                header_column,
+                RleError::TooLarge(cells),
+            )),
+            _ => Ok((width, height, rule)),
+        },
+        _ => Err(invalid(header_column)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Area, Rule};
+
+    /// The state of each cell in the blueprint, in rows.
+    fn rows(blueprint: &SimulationBlueprint) -> Vec<String> {
+        blueprint
+            .blueprint_data
+            .chunks(blueprint.x_size as usize + 1)
+            .map(|row| {
+                row.iter()
+                    .by_vals()
+                    .map(|alive| if alive { 'o' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    /// Runs of cells & rows are expanded.
+    fn parse_glider() {
+        let blueprint = parse_rle("#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!").unwrap();
+
+        assert_eq!((blueprint.x_size, blueprint.y_size), (2, 2));
+        assert_eq!(rows(&blueprint), [".o.", "..o", "ooo"]);
+    }
+
+    #[test]
+    /// A run of row ends leaves empty rows, & the pattern may span multiple lines.
+    fn parse_blank_rows() {
+        let blueprint = parse_rle("x = 2, y = 3\n2o2$\nbo!").unwrap();
+
+        assert_eq!(rows(&blueprint), ["oo", "..", ".o"]);
+    }
+
+    #[test]
+    /// Counts with multiple digits are read as one number.
+    fn parse_long_run() {
+        let blueprint = parse_rle("x = 12, y = 1\n11bo!").unwrap();
+
+        assert_eq!(rows(&blueprint), ["...........o"]);
+    }
+
+    #[test]
+    /// The rule in the header is used for the board, or the default rule if there is none.
+    fn parse_board_rule() {
+        let board = parse_rle_board("x = 3, y = 1, rule = B36/S23\n3o!").unwrap();
+        assert_eq!(board.get_rule(), Rule::HIGHLIFE);
+        assert_eq!(board.get_board_area(), Area::new((0, 0), (2, 0)));
+
+        let board = parse_rle_board("x = 3, y = 1\n3o!").unwrap();
+        assert_eq!(board.get_rule(), Rule::CONWAY);
+
+        assert_eq!(
+            parse_rle_board("x = 3, y = 1, rule = B3S23\n3o!")
+                .unwrap_err()
+                .reason
+                .kind(),
+            RleErrorKind::InvalidRule
+        );
+        // The rule is ignored when parsing blueprints.
+        assert!(parse_rle("x = 3, y = 1, rule = B3S23\n3o!").is_ok());
+    }
+
+    #[test]
+    /// Invalid patterns are rejected.
+    fn parse_invalid() {
+        assert_eq!(
+            parse_rle("# Only a comment").unwrap_err().reason,
+            RleError::MissingHeader
+        );
+        assert_eq!(
+            parse_rle("x = 0, y = 3\n!").unwrap_err().reason.kind(),
+            RleErrorKind::InvalidHeader
+        );
+        assert_eq!(
+            parse_rle("y = 3\n!").unwrap_err().reason.kind(),
+            RleErrorKind::InvalidHeader
+        );
+        assert_eq!(
+            parse_rle("x = 3, y = 3\nbo?!").unwrap_err().reason,
+            RleError::UnexpectedCharacter('?')
+        );
+        assert_eq!(
+            parse_rle("x = 3, y = 1\n4o!").unwrap_err().reason,
+            RleError::OutOfBounds
+        );
+    }
+
+    #[test]
+    /// Headers with too many cells are rejected before the cells are allocated.
+    fn parse_too_large() {
+        assert_eq!(
+            parse_rle("x = 2000000000, y = 2000000000\no!")
+                .unwrap_err()
+                .reason,
+            RleError::TooLarge(4_000_000_000_000_000_000)
+        );
+
+        let limit = format!("x = {}, y = 1\no!", MAX_PATTERN_CELLS);
+        assert!(parse_rle(&limit).is_ok());
+        let over = format!("x = {}, y = 2\no!", MAX_PATTERN_CELLS / 2 + 1);
+        assert_eq!(
+            parse_rle(&over).unwrap_err().reason,
+            RleError::TooLarge(MAX_PATTERN_CELLS + 2)
+        );
+    }
+
+    /// The line & column an RLE pattern fails to parse at.
+    fn error_position(text: &str) -> (usize, usize, RleErrorKind) {
+        let err = parse_rle_board(text).unwrap_err();
+        (err.line, err.column, err.reason.kind())
+    }
+
+    #[test]
+    /// Errors give the line & column they occurred at, counting comments.
+    fn parse_error_positions() {
+        // A run count of 0, & one too large to store, are reported at the start of the count.
+        assert_eq!(
+            error_position("#N Blinker\nx = 3, y = 1\nb0o!"),
+            (3, 2, RleErrorKind::InvalidCount)
+        );
+        assert_eq!(
+            error_position("x = 3, y = 2\n3o$\n  99999999999999999999999o!"),
+            (3, 3, RleErrorKind::InvalidCount)
+        );
+        // A pattern without an end is reported after its last character.
+        assert_eq!(
+            error_position("x = 3, y = 3\nbob$2bo$3o\n"),
+            (2, 11, RleErrorKind::MissingEnd)
+        );
+        // Header errors are reported at the invalid entry.
+        assert_eq!(
+            error_position("#C Junk\nx = 3, y = three\n3o!"),
+            (2, 12, RleErrorKind::InvalidHeader)
+        );
+        assert_eq!(
+            error_position("x = 3, junk\n3o!"),
+            (1, 8, RleErrorKind::InvalidHeader)
+        );
+        assert_eq!(
+            error_position("x = 3, y = 1, rule = B3/X\n3o!"),
+            (1, 22, RleErrorKind::InvalidRule)
+        );
+        // Cells outside the size in the header are reported at the start of the run that is too long.
+        assert_eq!(
+            error_position("x = 3, y = 2\nbo$b3o!"),
+            (2, 5, RleErrorKind::OutOfBounds)
+        );
+    }
+
+    #[test]
+    /// Written patterns combine runs & empty rows, & parse back to the same blueprint.
+    fn write_round_trip() {
+        let blueprint = parse_rle("x = 4, y = 5\n$2o3$b3o!").unwrap();
+
+        let text = write_rle(&blueprint, Some(Rule::HIGHLIFE));
+        assert_eq!(text, "x = 4, y = 5, rule = B36/S23\n$2o3$b3o!\n");
+        assert_eq!(parse_rle(&text), Ok(blueprint));
+    }
+
+    #[test]
+    /// Long patterns are split across lines between runs.
+    fn write_long_lines() {
+        let blueprint = parse_rle(&format!("x = 100, y = 1\n{}!", "bo".repeat(50))).unwrap();
+
+        let text = write_rle(&blueprint, None);
+        assert!(text.lines().all(|line| line.len() <= LINE_LENGTH), "{text}");
+        assert_eq!(parse_rle(&text), Ok(blueprint));
+    }
+}
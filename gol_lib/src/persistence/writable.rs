@@ -0,0 +1,142 @@
+//! Contains [`check_writable`].
+//! See its documentation for more information.
+
+use std::{fs::File, io::ErrorKind, path::Path};
+
+/// The start of the name of the file created to test whether a directory can be written to.
+const PROBE_FILE: &str = ".gol_write_check";
+/// The maximum amount of numbered probe names tried when the previous names already exist.
+const MAX_PROBE_SUFFIX: u32 = 16;
+
+/// The reasons a directory cannot be saved into.
+#[derive(thiserror::Error, Debug)]
+#[cfg_attr(test, derive(kinded::Kinded))]
+pub enum WriteCheckError {
+    /// The directory does not exist.
+    #[error("The directory does not exist")]
+    Missing,
+    /// The path exists, but is not a directory.
+    #[error("The path is not a directory")]
+    NotADirectory,
+    /// A file could not be created within the directory, such as due to a lack of permissions or space.
+    #[error("Unable to write to the directory: {0}")]
+    NotWritable(std::io::Error),
+}
+
+/// Checks whether files can be saved into the given directory, so problems can be reported before a save is
+/// attempted.
+///
+/// The check is performed by creating & removing a small file within the directory, so it fails for any reason that
+/// would stop a save being written, such as missing permissions or a read-only filesystem. The file is given a name
+/// that doesn't already exist, so an existing file is never overwritten or removed.
+pub fn check_writable(dir: &Path) -> Result<(), WriteCheckError> {
+    let metadata = match std::fs::metadata(dir) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(WriteCheckError::Missing)
+        }
+        Err(err) => return Err(WriteCheckError::NotWritable(err)),
+    };
+    if !metadata.is_dir() {
+        return Err(WriteCheckError::NotADirectory);
+    }
+
+    // The process id keeps other running instances of the program from trying the same names.
+    let name = format!("{PROBE_FILE}-{}", std::process::id());
+    let mut probe = dir.join(&name);
+    let mut suffix = 0;
+    loop {
+        match File::create_new(&probe) {
+            Err(err) if err.kind() == ErrorKind::AlreadyExists && suffix < MAX_PROBE_SUFFIX => {
+                suffix += 1;
+                probe = dir.join(format!("{name}-{suffix}"));
+            }
+            result => {
+                result.map_err(WriteCheckError::NotWritable)?;
+                break;
+            }
+        }
+    }
+    std::fs::remove_file(&probe).map_err(WriteCheckError::NotWritable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// A writable directory passes, without leaving the probe file behind.
+    fn writable() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+
+        check_writable(temp_dir.path()).expect("Directory is writable");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    /// Existing files with the probe's name are left untouched, & a different name is used instead.
+    fn existing_probe() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        let name = format!("{PROBE_FILE}-{}", std::process::id());
+        let existing = [
+            temp_dir.path().join(&name),
+            temp_dir.path().join(format!("{name}-1")),
+        ];
+        for path in &existing {
+            std::fs::write(path, "Keep me").expect("Able to create file");
+        }
+
+        check_writable(temp_dir.path()).expect("Directory is writable");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 2);
+        for path in &existing {
+            assert_eq!(std::fs::read_to_string(path).unwrap(), "Keep me");
+        }
+    }
+
+    #[test]
+    /// A directory that doesn't exist is reported as missing.
+    fn missing() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+
+        let error = check_writable(&temp_dir.path().join("missing")).expect_err("Must error");
+        assert_eq!(error.kind(), WriteCheckErrorKind::Missing);
+    }
+
+    #[test]
+    /// A file is not a directory that can be saved into.
+    fn not_a_directory() {
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        let path = temp_dir.path().join("file");
+        std::fs::write(&path, "").expect("Able to create file");
+
+        let error = check_writable(&path).expect_err("Must error");
+        assert_eq!(error.kind(), WriteCheckErrorKind::NotADirectory);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    /// A read-only directory is not writable.
+    fn read_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().expect("Able to create a temp dir");
+        std::fs::set_permissions(temp_dir.path(), std::fs::Permissions::from_mode(0o555))
+            .expect("Able to set permissions");
+
+        // Permissions are not enforced for privileged users, in which case the directory is writable.
+        let enforced = std::fs::write(temp_dir.path().join("file"), "").is_err();
+        let result = check_writable(temp_dir.path());
+
+        // Allow the temp dir to be cleaned up.
+        std::fs::set_permissions(temp_dir.path(), std::fs::Permissions::from_mode(0o755))
+            .expect("Able to set permissions");
+
+        match enforced {
+            true => assert_eq!(
+                result.expect_err("Must error").kind(),
+                WriteCheckErrorKind::NotWritable
+            ),
+            false => assert!(result.is_ok(), "{result:?}"),
+        }
+    }
+}
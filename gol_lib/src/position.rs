@@ -11,22 +11,46 @@ pub struct GlobalPosition {
 
 impl GlobalPosition {
     /// Creates a new [`GlobalPosition`] at the given x & y coordinates.
-    pub fn new(x: i32, y: i32) -> Self {
+    pub const fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
 
     /// Gets the represented x position.
-    pub fn get_x(&self) -> i32 {
+    pub const fn get_x(&self) -> i32 {
         self.x
     }
 
     /// Gets the represented y position.
-    pub fn get_y(&self) -> i32 {
+    pub const fn get_y(&self) -> i32 {
         self.y
     }
+
+    /// Moves the position by the given x & y offset, stopping at the edges of the board rather than overflowing.
+    pub const fn saturating_add(self, (x, y): (i32, i32)) -> Self {
+        Self::new(self.x.saturating_add(x), self.y.saturating_add(y))
+    }
+
+    /// Moves the position against the given x & y offset, stopping at the edges of the board rather than overflowing.
+    pub const fn saturating_sub(self, (x, y): (i32, i32)) -> Self {
+        Self::new(self.x.saturating_sub(x), self.y.saturating_sub(y))
+    }
+
+    /// The distance to the other position when moving diagonally counts as a single step.
+    ///
+    /// This is the amount of generations light speed takes to travel between the positions.
+    pub fn chebyshev_distance(&self, other: GlobalPosition) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The distance to the other position when only moving horizontally & vertically.
+    ///
+    /// This is a [`u64`], as the distance between far apart positions does not fit in a [`u32`].
+    pub fn manhattan_distance(&self, other: GlobalPosition) -> u64 {
+        self.x.abs_diff(other.x) as u64 + self.y.abs_diff(other.y) as u64
+    }
 }
 
-impl std::ops::Sub<(i32, i32)> for GlobalPosition {
+impl core::ops::Sub<(i32, i32)> for GlobalPosition {
     type Output = Self;
 
     fn sub(self, rhs: (i32, i32)) -> Self::Output {
@@ -34,7 +58,7 @@ impl std::ops::Sub<(i32, i32)> for GlobalPosition {
     }
 }
 
-impl std::ops::Add<(i32, i32)> for GlobalPosition {
+impl core::ops::Add<(i32, i32)> for GlobalPosition {
     type Output = Self;
 
     fn add(self, rhs: (i32, i32)) -> Self::Output {
@@ -42,6 +66,38 @@ impl std::ops::Add<(i32, i32)> for GlobalPosition {
     }
 }
 
+impl core::ops::Add for GlobalPosition {
+    type Output = Self;
+
+    fn add(self, rhs: GlobalPosition) -> Self::Output {
+        GlobalPosition::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl core::ops::Sub for GlobalPosition {
+    /// The x & y offset from the other position to this one.
+    type Output = (i32, i32);
+
+    fn sub(self, rhs: GlobalPosition) -> Self::Output {
+        (self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl core::ops::Mul<i32> for GlobalPosition {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        GlobalPosition::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl core::fmt::Display for GlobalPosition {
+    /// Formats the position as `(x, y)`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
 impl From<(i32, i32)> for GlobalPosition {
     fn from(value: (i32, i32)) -> Self {
         GlobalPosition {
@@ -50,3 +106,125 @@ impl From<(i32, i32)> for GlobalPosition {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    /// Positions can be added & subtracted, including negative coordinates.
+    fn position_arithmetic() {
+        let a = GlobalPosition::new(3, -4);
+        let b = GlobalPosition::new(-5, -2);
+
+        assert_eq!(a + b, GlobalPosition::new(-2, -6));
+        assert_eq!(a - b, (8, -2));
+        assert_eq!(b - a, (-8, 2));
+        assert_eq!(b + (a - b), a);
+    }
+
+    #[test]
+    /// The tuple based operations are unchanged.
+    fn tuple_arithmetic() {
+        let position = GlobalPosition::new(-1, 1);
+
+        assert_eq!(position + (2, -3), GlobalPosition::new(1, -2));
+        assert_eq!(position - (2, -3), GlobalPosition::new(-3, 4));
+    }
+
+    #[test]
+    /// Multiplying scales both coordinates.
+    fn scalar_multiplication() {
+        let position = GlobalPosition::new(2, -3);
+
+        assert_eq!(position * 3, GlobalPosition::new(6, -9));
+        assert_eq!(position * -1, GlobalPosition::new(-2, 3));
+    }
+
+    #[test]
+    /// The chebyshev distance is the larger of the x & y distances.
+    fn chebyshev_distance() {
+        let origin = GlobalPosition::new(0, 0);
+
+        assert_eq!(origin.chebyshev_distance(origin), 0);
+        assert_eq!(origin.chebyshev_distance((3, -7).into()), 7);
+        assert_eq!(
+            GlobalPosition::new(-2, -2).chebyshev_distance((2, 1).into()),
+            4
+        );
+        // Distance is symmetric.
+        assert_eq!(
+            GlobalPosition::new(2, 1).chebyshev_distance((-2, -2).into()),
+            4
+        );
+    }
+
+    #[test]
+    /// The manhattan distance is the sum of the x & y distances.
+    fn manhattan_distance() {
+        let origin = GlobalPosition::new(0, 0);
+
+        assert_eq!(origin.manhattan_distance(origin), 0);
+        assert_eq!(origin.manhattan_distance((3, -7).into()), 10);
+        assert_eq!(
+            GlobalPosition::new(-2, -2).manhattan_distance((2, 1).into()),
+            7
+        );
+        assert_eq!(
+            GlobalPosition::new(2, 1).manhattan_distance((-2, -2).into()),
+            7
+        );
+    }
+
+    #[test]
+    /// Distances do not overflow at the extremes of the board.
+    fn distance_extremes() {
+        let min = GlobalPosition::new(i32::MIN, i32::MIN);
+        let max = GlobalPosition::new(i32::MAX, i32::MAX);
+
+        assert_eq!(min.chebyshev_distance(max), u32::MAX);
+        assert_eq!(min.manhattan_distance(max), u32::MAX as u64 * 2);
+    }
+
+    #[test]
+    /// Saturating movement stops at the edges of the board.
+    fn saturating_movement() {
+        let position = GlobalPosition::new(i32::MAX - 1, i32::MIN + 1);
+
+        assert_eq!(
+            position.saturating_add((5, -5)),
+            (i32::MAX, i32::MIN).into()
+        );
+        assert_eq!(
+            position.saturating_sub((-5, 5)),
+            (i32::MAX, i32::MIN).into()
+        );
+        assert_eq!(
+            position.saturating_add((-1, 1)),
+            (i32::MAX - 2, i32::MIN + 2).into()
+        );
+    }
+
+    #[test]
+    /// Positions are displayed as their coordinates.
+    fn display() {
+        assert_eq!(GlobalPosition::new(3, 4).to_string(), "(3, 4)");
+        assert_eq!(GlobalPosition::new(-3, -40).to_string(), "(-3, -40)");
+    }
+
+    #[test]
+    /// Positions can be constructed & read in const contexts.
+    fn const_positions() {
+        const POSITIONS: [GlobalPosition; 3] = [
+            GlobalPosition::new(0, 0),
+            GlobalPosition::new(-4, 7),
+            GlobalPosition::new(i32::MAX, i32::MIN),
+        ];
+        const X: i32 = POSITIONS[1].get_x();
+        const Y: i32 = POSITIONS[1].get_y();
+
+        assert_eq!((X, Y), (-4, 7));
+        assert_eq!(POSITIONS[2], (i32::MAX, i32::MIN).into());
+    }
+}
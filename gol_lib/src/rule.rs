@@ -0,0 +1,203 @@
+//! Contains [`Rule`].
+//! See its documentation for more information.
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::{fmt::Display, str::FromStr};
+
+/// The reasons text cannot be parsed as a [`Rule`].
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum RuleError {
+    /// The text is not in the B/S or S/B notation.
+    #[error("The rule \"{0}\" is not in the B/S notation, such as B3/S23")]
+    InvalidFormat(Box<str>),
+    /// A neighbour count is not a digit from 0 to 8.
+    #[error("'{0}' is not a neighbour count from 0 to 8")]
+    InvalidCount(char),
+}
+
+/// The amount of alive neighbours that cause a dead cell to be born, & that allow an alive cell to survive.
+///
+/// Rules are written in the B/S notation, such as `B3/S23` for Conway's game of life, which is the default rule.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Rule {
+    /// Each set bit is a neighbour count that causes a dead cell to be born.
+    birth: u16,
+    /// Each set bit is a neighbour count that allows an alive cell to survive.
+    survival: u16,
+}
+
+impl Rule {
+    /// Conway's game of life, `B3/S23`.
+    pub const CONWAY: Rule = Rule::new(&[3], &[2, 3]);
+    /// HighLife, `B36/S23`, which has a self-replicating pattern.
+    pub const HIGHLIFE: Rule = Rule::new(&[3, 6], &[2, 3]);
+
+    /// Creates a rule from the neighbour counts that cause a dead cell to be born, & that allow an alive cell to
+    /// survive. Counts above 8 are ignored, as a cell has at most 8 neighbours.
+    pub const fn new(birth: &[u8], survival: &[u8]) -> Self {
+        Self {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+
+    /// Whether a dead cell with the given amount of alive neighbours is born.
+    pub const fn born(self, neighbours: u8) -> bool {
+        neighbours <= 8 && self.birth & (1 << neighbours) != 0
+    }
+
+    /// Whether an alive cell with the given amount of alive neighbours survives.
+    pub const fn survives(self, neighbours: u8) -> bool {
+        neighbours <= 8 && self.survival & (1 << neighbours) != 0
+    }
+}
+
+/// The bitmask with a bit set for each of the counts, ignoring counts above 8.
+const fn mask(counts: &[u8]) -> u16 {
+    let mut mask = 0;
+    let mut index = 0;
+    while index < counts.len() {
+        if counts[index] <= 8 {
+            mask |= 1 << counts[index];
+        }
+        index += 1;
+    }
+    mask
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::CONWAY
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let counts = |mask: u16| -> String {
+            (0..=8)
+                .filter(|count| mask & (1 << count) != 0)
+                .map(|count| char::from(b'0' + count))
+                .collect()
+        };
+        write!(f, "B{}/S{}", counts(self.birth), counts(self.survival))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleError;
+
+    /// Parses a rule in the B/S notation, such as `B36/S23`, in either order & any case.
+    ///
+    /// The older S/B notation without letters, such as `23/36`, is also accepted, where the survival counts are first.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || RuleError::InvalidFormat(text.into());
+        let (first, second) = text.trim().split_once('/').ok_or_else(invalid)?;
+
+        let counts = |counts: &str| -> Result<u16, RuleError> {
+            counts.chars().try_fold(0, |mask, count| match count {
+                '0'..='8' => Ok(mask | 1 << (count as u8 - b'0')),
+                count => Err(RuleError::InvalidCount(count)),
+            })
+        };
+        /// Splits the leading letter from the counts.
+        fn split(part: &str) -> (Option<char>, &str) {
+            let mut chars = part.chars();
+            let letter = chars.next().map(|letter| letter.to_ascii_uppercase());
+            (letter, chars.as_str())
+        }
+
+        let (birth, survival) = match (split(first), split(second)) {
+            ((Some('B'), birth), (Some('S'), survival))
+            | ((Some('S'), survival), (Some('B'), birth)) => (birth, survival),
+            _ if !first.starts_with(char::is_alphabetic)
+                && !second.starts_with(char::is_alphabetic) =>
+            {
+                (second, first)
+            }
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            birth: counts(birth)?,
+            survival: counts(survival)?,
+        })
+    }
+}
+
+impl From<Rule> for String {
+    fn from(rule: Rule) -> Self {
+        rule.to_string()
+    }
+}
+
+impl TryFrom<String> for Rule {
+    type Error = RuleError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl TryFrom<&str> for Rule {
+    type Error = RuleError;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Conway's game of life births on 3, & survives on 2 or 3.
+    fn conway() {
+        let rule = Rule::default();
+
+        assert!(rule.born(3));
+        assert!(!rule.born(2));
+        assert!(rule.survives(2) && rule.survives(3));
+        assert!(!rule.survives(4));
+        assert!(!rule.born(9));
+    }
+
+    #[test]
+    /// Rules parse from each notation, & display in the B/S notation.
+    fn parse() {
+        assert_eq!("B3/S23".parse(), Ok(Rule::CONWAY));
+        assert_eq!("s23/b36".parse(), Ok(Rule::HIGHLIFE));
+        assert_eq!("23/36".parse(), Ok(Rule::HIGHLIFE));
+        assert_eq!(Rule::HIGHLIFE.to_string(), "B36/S23");
+        assert_eq!("B/S".parse(), Ok(Rule::new(&[], &[])));
+    }
+
+    #[test]
+    /// Invalid rules are rejected.
+    fn parse_invalid() {
+        assert!(matches!(
+            "B3S23".parse::<Rule>(),
+            Err(RuleError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            "B3/X23".parse::<Rule>(),
+            Err(RuleError::InvalidFormat(_))
+        ));
+        assert_eq!("B39/S23".parse::<Rule>(), Err(RuleError::InvalidCount('9')));
+    }
+
+    #[test]
+    /// Rules can be converted from string slices, such as command line arguments.
+    fn try_from_str() {
+        assert_eq!(Rule::try_from("B36/S23"), Ok(Rule::HIGHLIFE));
+        assert_eq!(Rule::try_from("B3/S2a"), Err(RuleError::InvalidCount('a')));
+        assert_eq!(
+            Rule::try_from("life"),
+            Err(RuleError::InvalidFormat("life".into()))
+        );
+    }
+}
@@ -1,85 +1,193 @@
 use crate::{
     persistence::{SimulationBlueprint, SimulationSave},
-    Area, Cell, GlobalPosition, SharedDisplay,
+    Area, Automaton, GlobalPosition, Rule, SharedDisplay,
 };
 
+/// The outcome of loading a board or blueprint into a [`Simulator`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoadStatus {
+    /// The board or blueprint was loaded.
+    Success,
+    /// The board or blueprint could not be loaded, so the board was left unchanged.
+    Fail {
+        /// Why the load failed.
+        reason: LoadError,
+    },
+}
+
+/// Information about the board after it has been advanced.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TickInfo {
+    /// The generation the board was advanced to.
+    pub generation: u64,
+    /// The amount of alive cells on the board.
+    pub population: u64,
+    /// Whether the simulation is running, rather than being stepped whilst stopped.
+    pub is_running: bool,
+}
+
+/// The reasons a board or blueprint can fail to load.
+#[derive(thiserror::Error, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoadError {
+    /// The amount of cells in the board data does not match the size of the board area.
+    #[error("The board contains {found} cells, but its area contains {expected} cells.")]
+    BoardDataLength {
+        /// The amount of cells within the board area.
+        expected: u64,
+        /// The amount of cells in the board data.
+        found: u64,
+    },
+    /// The amount of cells in the blueprint data does not match the size of the blueprint.
+    #[error("The blueprint contains {found} cells, but its size contains {expected} cells.")]
+    BlueprintDataLength {
+        /// The amount of cells within the size of the blueprint.
+        expected: u64,
+        /// The amount of cells in the blueprint data.
+        found: u64,
+    },
+    /// The board is simulated with a rule that the simulator cannot simulate.
+    #[error("The rule {rule} is not supported by this simulator.")]
+    UnsupportedRule {
+        /// The rule of the board.
+        rule: Rule,
+    },
+}
+
 /// An implementation of [`Simulator`] can simulate Conways game of life.
 ///
 /// Each implementation is guaranteed to correctly simulate Conways game of life, however the performance of any
 /// implementation is not guaranteed.
-pub trait Simulator: Send {
+///
+/// The board itself is advanced through [`Automaton`], whilst this provides what the simulator thread needs to display
+/// & save the board.
+pub trait Simulator: Automaton + Send {
     /// Creates a new simulator.
     fn new(display: SharedDisplay) -> Self
     where
         Self: Sized;
 
-    /// Advances the simulation by one tick.
-    fn tick(&mut self);
-
     /// Updates the board being displayed by the ui.
     fn update_display(&mut self);
 
     /// Sets the display area sent to the ui to the given area.
     fn set_display_area(&mut self, new_area: Area);
 
-    /// Sets the cell at the given position on the board.
-    fn set(&mut self, position: GlobalPosition, cell: Cell);
+    /// Sets the maximum amount of alive cells the board may contain, or [`None`] for no limit.
+    fn set_cell_limit(&mut self, limit: Option<usize>);
 
-    /// Gets the cell at the given position on the board.
-    fn get(&self, position: GlobalPosition) -> Cell;
+    /// Returns the amount of alive cells if it exceeds the cell limit.
+    fn cell_limit_exceeded(&self) -> Option<u64>;
 
-    /// Gets the current generation of simulation.
-    fn get_generation(&self) -> u64;
+    /// The maximum amount of cells the display area may contain for the board to be displayed, or [`None`] for no
+    /// limit.
+    fn max_display_cells(&self) -> Option<usize> {
+        None
+    }
 
-    /// Sets the current generation of simulation.
-    fn set_generation(&mut self, generation: u64);
+    /// Sets whether the age of each cell is included in the display, if the simulator tracks ages.
+    fn set_display_ages(&mut self, _display_ages: bool) {}
 
-    /// Sets all cells on the board to dead & sets the generation to 0.
-    fn reset(&mut self);
+    /// Sets whether the cells born & the cells that died are recorded each generation, & included in the display, if
+    /// the simulator can record them.
+    fn set_display_changes(&mut self, _display_changes: bool) {}
 
-    /// Gets the area taken up by the current board. The area for a board is a rectangle bounding the alive cells.
-    fn get_board_area(&self) -> Area;
+    /// The positions of the cells born, & of the cells that died, during the last generation the board was advanced
+    /// by.
+    ///
+    /// These are only recorded whilst enabled by [`Self::set_display_changes`], & are empty otherwise. Editing the
+    /// board, such as by setting cells, mirroring or rotating it, empties them, as they would no longer match it.
+    fn last_delta(&self) -> (Vec<GlobalPosition>, Vec<GlobalPosition>) {
+        (Vec::new(), Vec::new())
+    }
 
     /// Creates a save of the board in its current state.
     fn save_board(&self) -> SimulationSave {
         let board_area = self.get_board_area();
 
-        let mut board_data = bitvec::vec::BitVec::new();
-        for position in board_area.iterate_over() {
-            board_data.push(self.get(position).into());
-        }
+        let cells = usize::try_from(board_area.cell_count()).expect("The board fits within memory");
+        let mut board_data = bitvec::vec::BitVec::with_capacity(cells);
+        board_data.extend(
+            self.get_region(board_area)
+                .iter()
+                .map(|cell| bool::from(*cell)),
+        );
 
         SimulationSave::new(self.get_generation(), board_area, board_data)
+            .with_rule(self.get_rule())
     }
 
     /// Disgards the current state of the board & overwrites it with the given save.
-    fn load_board(&mut self, board: SimulationSave) {
+    ///
+    /// The rule of the board is used for the rest of the simulation.
+    ///
+    /// The board is left unchanged if the amount of cells in the save does not match its area, or if its rule is not
+    /// supported.
+    fn load_board(&mut self, board: SimulationSave) -> LoadStatus {
         let SimulationSave {
             generation,
             board_area,
             board_data,
+            rule,
         } = board;
+
+        // The save must be checked before the current board is discarded.
+        if board_data.len() as u64 != board_area.cell_count() {
+            return LoadStatus::Fail {
+                reason: LoadError::BoardDataLength {
+                    expected: board_area.cell_count(),
+                    found: board_data.len() as u64,
+                },
+            };
+        }
+
+        if !self.supports_rule(rule) {
+            return LoadStatus::Fail {
+                reason: LoadError::UnsupportedRule { rule },
+            };
+        }
+
         self.reset();
 
+        self.set_rule(rule);
         self.set_generation(generation);
-        for (position, cell) in board_area.iterate_over().zip(board_data.into_iter()) {
+        for (position, cell) in board_area.iterate_over().zip(board_data) {
             self.set(position, cell.into());
         }
+
+        LoadStatus::Success
     }
 
     /// Creates a save of the given area of the board.
     fn save_blueprint(&self, area: Area) -> SimulationBlueprint {
-        let mut blueprint_data = bitvec::vec::BitVec::new();
-        for position in area.iterate_over() {
-            blueprint_data.push(self.get(position).into());
-        }
-
-        SimulationBlueprint::new(area.x_difference(), area.y_difference(), blueprint_data)
+        let blueprint_data: bitvec::vec::BitVec = self
+            .get_region(area)
+            .iter()
+            .map(|cell| bool::from(*cell))
+            .collect();
+
+        let blueprint =
+            SimulationBlueprint::new(area.x_difference(), area.y_difference(), blueprint_data);
+        debug_assert_eq!(
+            blueprint.validate(),
+            Ok(()),
+            "The region of {area:?} must contain every cell within it"
+        );
+        blueprint
     }
 
     /// Overwrites an area of the board with the blueprint. The given position is the "top-left" of the blueprint that
     /// will be loaded in.
-    fn load_blueprint(&mut self, load_position: GlobalPosition, blueprint: SimulationBlueprint) {
+    ///
+    /// The board is left unchanged if the amount of cells in the blueprint does not match its size.
+    fn load_blueprint(
+        &mut self,
+        load_position: GlobalPosition,
+        blueprint: SimulationBlueprint,
+    ) -> LoadStatus {
+        if let Err(reason) = blueprint.validate() {
+            return LoadStatus::Fail { reason };
+        }
+
         let SimulationBlueprint {
             x_size,
             y_size,
@@ -90,8 +198,10 @@ pub trait Simulator: Send {
         area.translate_x(load_position.get_x());
         area.translate_y(load_position.get_y());
 
-        for (position, cell) in area.iterate_over().zip(blueprint_data.into_iter()) {
+        for (position, cell) in area.iterate_over().zip(blueprint_data) {
             self.set(position, cell.into());
         }
+
+        LoadStatus::Success
     }
 }
@@ -0,0 +1,499 @@
+//! Contains [`SharedDisplay`], the channels between the ui & the [`Simulator`], & [`start_simulator`].
+//! See their documentation for more information.
+
+use crate::communication::{self, SimulatorPacket, UiPacket};
+use crate::generation_history::GenerationHistory;
+use crate::period::{PeriodDetector, Repeat};
+use crate::tick_rate::{TickBudget, TickRateMeter};
+use crate::{BoardDisplay, Simulator, TickInfo};
+use std::num::NonZeroU32;
+use std::sync::{mpsc, Arc, Mutex};
+use std::{
+    sync::mpsc::{Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A pointer to the [`Mutex`] used to share the display board.
+/// The time either the ui or the [`Simulator`] will hold a lock on the [`Mutex`] is not guaranteed.
+pub type SharedDisplay = Arc<Mutex<Option<BoardDisplay>>>;
+
+/// The [`Receiver`] for [`UiPacket`]s from the ui.
+///
+/// [`Receiver`]: std::sync::mpsc::Receiver
+pub type UiReceiver = mpsc::Receiver<UiPacket>;
+/// The [`Sender`] for [`UiPacket`]s being sent from the ui.
+/// Only the ui should ever have this [`Sender`].
+///
+/// [`Sender`]: std::sync::mpsc::Sender
+pub type UiSender = mpsc::Sender<UiPacket>;
+/// The [`Receiver`] for [`SimulatorPacket`]s from the [`Simulator`].
+///
+/// [`Receiver`]: std::sync::mpsc::Receiver
+pub type SimulatorReceiver = mpsc::Receiver<SimulatorPacket>;
+/// The [`Sender`] for [`SimulatorPacket`]s being sent from the [`Simulator`].
+/// Only the [`Simulator`] should ever have this [`Sender`].
+///
+/// [`Sender`]: std::sync::mpsc::Sender
+pub type SimulatorSender = mpsc::Sender<SimulatorPacket>;
+
+/// Creates the channels for communication between the [`Simulator`] & the UI.
+pub fn create_channels() -> ((UiSender, UiReceiver), (SimulatorSender, SimulatorReceiver)) {
+    (mpsc::channel(), mpsc::channel())
+}
+
+pub fn start_simulator(
+    board: impl Simulator + 'static,
+    ui_receiver: Receiver<UiPacket>,
+    simulator_sender: Sender<SimulatorPacket>,
+) -> Result<thread::JoinHandle<()>, std::io::Error> {
+    start_simulator_with_callback(board, ui_receiver, simulator_sender, |_| {})
+}
+
+/// Starts the simulator thread, the same as [`start_simulator`], calling the callback after every generation the
+/// board is advanced by.
+///
+/// The callback is run on the simulator thread, so should be quick to avoid slowing the simulation. When multiple
+/// generations are advanced at once by [`UiPacket::Step`], the callback is only called once per chunk of
+/// generations advanced.
+///
+/// If the board or the callback panics, [`SimulatorPacket::Fatal`] is sent before the panic continues to unwind the
+/// thread.
+pub fn start_simulator_with_callback(
+    mut board: impl Simulator + 'static,
+    ui_receiver: Receiver<UiPacket>,
+    simulator_sender: Sender<SimulatorPacket>,
+    mut callback: impl FnMut(&TickInfo) + Send + 'static,
+) -> Result<thread::JoinHandle<()>, std::io::Error> {
+    thread::Builder::new()
+        .name("Simulator_Thread".into())
+        .spawn(move || {
+            // A clone is kept outside of the simulation, so the panic can still be reported once it is caught.
+            let fatal_sender = simulator_sender.clone();
+            let simulation = std::panic::AssertUnwindSafe(move || {
+                let send_packet = |packet: SimulatorPacket| match simulator_sender.send(packet) {
+                    Ok(_) => {}
+                    Err(_) => {
+                        std::panic!("{}", UI_CLOSED_COMS)
+                    }
+                };
+
+                // Used to control the ticks per second.
+                let mut tick_rate_limiter = spin_sleep_util::interval(Duration::from_secs(1));
+                tick_rate_limiter
+                    .set_missed_tick_behavior(spin_sleep_util::MissedTickBehavior::Skip);
+
+                let mut is_running = false;
+                // The amount of generations to step by whilst the simulation is stopped.
+                let mut step: u64 = 0;
+                let mut run_until = None;
+                let mut tick_rate_limited = false;
+                let mut target_tick_rate = None;
+                // The speed of the simulation is only limited by the tick budget whilst it is automatic.
+                let mut tick_budget: Option<TickBudget> = None;
+                let mut tick_rate_meter = TickRateMeter::new(Instant::now());
+                let mut display_needs_updating = false;
+                let mut period_detector = PeriodDetector::default();
+                let mut generation_history = GenerationHistory::new(0);
+                let mut last_progress = Instant::now();
+                let mut display_too_large = false;
+                let mut stop_condition: Option<communication::StopCondition> = None;
+                let mut record_statistics = false;
+
+                loop {
+                    // Process all received packets.
+                    loop {
+                        use std::sync::mpsc::TryRecvError;
+                        let ui_packet = match ui_receiver.try_recv() {
+                            Ok(ui_packet) => ui_packet,
+                            Err(TryRecvError::Empty) => {
+                                break;
+                            }
+                            Err(TryRecvError::Disconnected) => {
+                                std::panic!("{}", UI_CLOSED_COMS);
+                            }
+                        };
+
+                        match ui_packet {
+                            UiPacket::DisplayArea { new_area } => {
+                                board.set_display_area(new_area);
+                                display_needs_updating = true;
+
+                                // Only warn when the limit is first exceeded, rather than for every movement of the display.
+                                let cells = new_area.cell_count();
+                                let too_large =
+                                    board.max_display_cells().filter(|max| cells > *max as u64);
+                                if let (Some(max), false) = (too_large, display_too_large) {
+                                    send_packet(SimulatorPacket::DisplayTooLarge { cells, max });
+                                }
+                                display_too_large = too_large.is_some();
+                            }
+                            UiPacket::Set {
+                                position,
+                                cell_state,
+                            } => {
+                                board.set(position, cell_state);
+                                period_detector.clear();
+                                display_needs_updating = true;
+                            }
+                            UiPacket::SetMany { cells } => {
+                                for (position, cell_state) in cells {
+                                    board.set(position, cell_state);
+                                }
+                                period_detector.clear();
+                                display_needs_updating = true;
+                            }
+                            UiPacket::SaveBoard => {
+                                let board = board.save_board();
+                                send_packet(SimulatorPacket::BoardSave { board });
+                            }
+                            UiPacket::LoadBoard { board: new_board } => {
+                                let status = board.load_board(new_board);
+                                send_packet(SimulatorPacket::BoardLoadResult { status });
+                                // The generations kept are of the previous board.
+                                generation_history.clear();
+                                period_detector.clear();
+                                display_needs_updating = true;
+                            }
+                            UiPacket::SaveBlueprint { area } => {
+                                let blueprint = board.save_blueprint(area);
+                                send_packet(SimulatorPacket::BlueprintSave { blueprint });
+                            }
+                            UiPacket::LoadBlueprint {
+                                load_position,
+                                blueprint,
+                            } => {
+                                let status = board.load_blueprint(load_position, blueprint);
+                                send_packet(SimulatorPacket::BlueprintLoadResult { status });
+                                period_detector.clear();
+                                display_needs_updating = true;
+                            }
+                            UiPacket::Randomize {
+                                area,
+                                density,
+                                seed,
+                                symmetry,
+                            } => {
+                                board.randomize_symmetric(area, density, seed, symmetry);
+                                period_detector.clear();
+                                display_needs_updating = true;
+                            }
+                            UiPacket::Clear => {
+                                board.clear();
+                                generation_history.clear();
+                                period_detector.clear();
+                                display_needs_updating = true;
+                            }
+                            UiPacket::Mirror { axis } => {
+                                board.mirror(axis);
+                                period_detector.clear();
+                                display_needs_updating = true;
+                            }
+                            UiPacket::Rotate { quarter_turns } => {
+                                match board.rotate(quarter_turns) {
+                                    Ok(()) => {
+                                        period_detector.clear();
+                                        display_needs_updating = true;
+                                    }
+                                    Err(reason) => {
+                                        send_packet(SimulatorPacket::RotateFailed { reason })
+                                    }
+                                }
+                            }
+                            UiPacket::RequestBoardArea => {
+                                let area = (board.population() > 0).then(|| board.get_board_area());
+                                send_packet(SimulatorPacket::BoardArea { area });
+                            }
+                            UiPacket::Start => is_running = true,
+                            UiPacket::StartUntil { generation } => {
+                                is_running = true;
+                                run_until = Some(generation);
+                            }
+                            // A target generation only applies to the run it was started with, so stopping discards it.
+                            // Otherwise starting again would unexpectedly stop at the old target.
+                            UiPacket::Stop => {
+                                is_running = false;
+                                run_until = None;
+                                step = 0;
+                            }
+                            UiPacket::ClearTarget => run_until = None,
+                            UiPacket::Step { count } => {
+                                if !is_running {
+                                    step = step.saturating_add(count);
+                                }
+                            }
+                            UiPacket::GotoGeneration { generation } => {
+                                is_running = false;
+                                run_until = None;
+                                // The newest generation is kept so it can be returned to. Generations that have been
+                                // returned to are already kept, & recording them would discard the generations after them.
+                                if generation_history.get(board.get_generation()).is_none() {
+                                    generation_history.record(board.save_board());
+                                }
+
+                                match generation_history.get(generation) {
+                                    Some(save) => {
+                                        board.load_board(save.clone());
+                                        period_detector.clear();
+                                        display_needs_updating = true;
+                                    }
+                                    None => send_packet(SimulatorPacket::GenerationUnavailable {
+                                        requested: generation,
+                                        earliest: generation_history
+                                            .earliest()
+                                            .unwrap_or_else(|| board.get_generation()),
+                                    }),
+                                }
+                            }
+                            UiPacket::SimulationSpeed { speed } => {
+                                target_tick_rate = speed.get().map(|speed| speed.get());
+                                tick_budget = speed.is_automatic().then(TickBudget::default);
+                                match speed.get() {
+                                    Some(ticks_per_second) => {
+                                        tick_rate_limiter.set_period(tick_period(ticks_per_second));
+                                        // The next tick is a full period away, rather than aligned to the
+                                        // previous period.
+                                        tick_rate_limiter.reset();
+                                        tick_rate_limited = true;
+                                    }
+                                    None => {
+                                        tick_rate_limited = false;
+                                    }
+                                }
+                            }
+                            UiPacket::PeriodDetection { history } => {
+                                period_detector.set_capacity(history);
+                            }
+                            UiPacket::GenerationHistory { capacity } => {
+                                generation_history.set_capacity(capacity)
+                            }
+                            UiPacket::CellLimit { limit } => board.set_cell_limit(limit),
+                            UiPacket::SetStopCondition { condition } => stop_condition = condition,
+                            UiPacket::RecordStatistics { enabled } => record_statistics = enabled,
+                            UiPacket::DisplayAges { enabled } => {
+                                board.set_display_ages(enabled);
+                                display_needs_updating = true;
+                            }
+                            UiPacket::DisplayChanges { enabled } => {
+                                board.set_display_changes(enabled);
+                                display_needs_updating = true;
+                            }
+                            UiPacket::Terminate => {
+                                // The ui may have already stopped listening, if it doesn't need the final state.
+                                let _ = simulator_sender.send(SimulatorPacket::FinalState {
+                                    board: board.save_board(),
+                                });
+                                return;
+                            }
+                        }
+                    }
+
+                    // Large steps are advanced in chunks, so packets are still received & the cell limit checked
+                    // whilst they are taken.
+                    let stepping = step.min(STEP_CHUNK);
+                    step -= stepping;
+
+                    // If the game is not running then wait for ≈ 100ms before performing any updates to save resources.
+                    if !is_running && stepping == 0 {
+                        if display_needs_updating {
+                            board.update_display();
+                            display_needs_updating = !display_needs_updating;
+                        }
+
+                        tick_rate_meter.pause();
+                        // Otherwise the first tick once started is cut short to realign with the previous ticks,
+                        // which the tick rate meter measures as a far faster rate.
+                        tick_rate_limiter.reset();
+                        thread::sleep(Duration::from_millis(100));
+                        continue;
+                    }
+
+                    // Steps are taken immediately regardless of the speed or target generation.
+                    if stepping == 0 {
+                        // The simulation may have been started with a target it has already reached.
+                        if let Some(target) = run_until {
+                            let current = board.get_generation();
+                            if current >= target {
+                                is_running = false;
+                                run_until = None;
+                                send_packet(SimulatorPacket::Progress { current, target });
+                                continue;
+                            }
+                        }
+
+                        if tick_rate_limited {
+                            tick_rate_limiter.tick();
+                        } else if let Some(period) =
+                            tick_budget.as_ref().and_then(TickBudget::period)
+                        {
+                            tick_rate_limiter.set_period(period);
+                            tick_rate_limiter.tick();
+                        }
+                    }
+
+                    // The generation being advanced from is recorded, rather than the one advanced to, so edits made
+                    // between generations are included.
+                    if generation_history.is_enabled() {
+                        generation_history.record(board.save_board());
+                    }
+
+                    let tick_start = Instant::now();
+                    match stepping {
+                        0 | 1 => board.tick(),
+                        generations => {
+                            board.tick_n(generations);
+                            // The skipped generations were never seen, so previous states can't be used for the period.
+                            period_detector.clear();
+                        }
+                    }
+                    // Steps are not limited, so they don't count towards the budget.
+                    if let (0, Some(tick_budget)) = (stepping, &mut tick_budget) {
+                        tick_budget.record(tick_start.elapsed());
+                    }
+                    board.update_display();
+
+                    let tick_info = TickInfo {
+                        generation: board.get_generation(),
+                        population: board.population(),
+                        is_running,
+                    };
+                    callback(&tick_info);
+
+                    if record_statistics {
+                        send_packet(SimulatorPacket::Statistics {
+                            generation: tick_info.generation,
+                            population: tick_info.population,
+                        });
+                    }
+
+                    if let (true, Some(condition)) = (is_running, &mut stop_condition) {
+                        if condition.is_met(&tick_info) {
+                            is_running = false;
+                            run_until = None;
+                            send_packet(SimulatorPacket::Stopped {
+                                reason: communication::StopReason::Condition,
+                                generation: tick_info.generation,
+                            });
+                        }
+                    }
+
+                    // Stop boards that grow forever before they exhaust memory.
+                    if let Some(population) = board.cell_limit_exceeded() {
+                        is_running = false;
+                        run_until = None;
+                        step = 0;
+                        send_packet(SimulatorPacket::CellLimitReached {
+                            generation: board.get_generation(),
+                            population,
+                        });
+                    }
+
+                    let now = Instant::now();
+
+                    // Progress towards the target is reported periodically, & always once the target is reached.
+                    if let Some(target) = run_until {
+                        let current = board.get_generation();
+                        if current >= target {
+                            is_running = false;
+                            run_until = None;
+                            send_packet(SimulatorPacket::Progress { current, target });
+                        } else if now.saturating_duration_since(last_progress) >= PROGRESS_INTERVAL
+                        {
+                            last_progress = now;
+                            send_packet(SimulatorPacket::Progress { current, target });
+                        }
+                    }
+
+                    tick_rate_meter.tick(now);
+                    if let Some(achieved) = tick_rate_meter.report(now) {
+                        let limit = tick_budget
+                            .as_ref()
+                            .and_then(TickBudget::limit)
+                            .map(|limit| limit.round() as u32);
+                        send_packet(SimulatorPacket::TickRate {
+                            target: target_tick_rate.or(limit),
+                            achieved,
+                        });
+                    }
+
+                    // Saving the board is expensive, so it is only done when the period is being detected.
+                    if period_detector.is_enabled() {
+                        let generation = board.get_generation();
+                        match period_detector.update(&board.save_board()) {
+                            Some(Repeat::Period(period)) => {
+                                send_packet(SimulatorPacket::PeriodDetected { period, generation })
+                            }
+                            Some(Repeat::Spaceship { period, dx, dy }) => {
+                                send_packet(SimulatorPacket::SpaceshipDetected {
+                                    period,
+                                    dx,
+                                    dy,
+                                    generation,
+                                })
+                            }
+                            None => {}
+                        }
+                    }
+                }
+            });
+
+            if let Err(payload) = std::panic::catch_unwind(simulation) {
+                // The ui may have already stopped listening, which may be why the simulator panicked.
+                let _ = fatal_sender.send(SimulatorPacket::Fatal {
+                    message: panic_message(payload.as_ref()),
+                });
+                std::panic::resume_unwind(payload);
+            }
+        })
+}
+
+/// The time between ticks when running at the given ticks per second.
+///
+/// Speeds above a billion ticks per second would have a period of zero, which the tick rate limiter cannot use, so
+/// the period is at least a nanosecond.
+fn tick_period(ticks_per_second: NonZeroU32) -> Duration {
+    (Duration::from_secs(1) / ticks_per_second.get()).max(MIN_TICK_PERIOD)
+}
+
+/// The message a panic was started with, if it was started with a string as [`panic!`] does.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| UNKNOWN_PANIC.to_owned())
+}
+
+const UI_CLOSED_COMS: &str = "UI closed communication to simulation!";
+/// The message reported for panics that were not started with a string.
+const UNKNOWN_PANIC: &str = "The simulator panicked for an unknown reason.";
+/// The shortest time between ticks. See [`tick_period`].
+const MIN_TICK_PERIOD: Duration = Duration::from_nanos(1);
+/// The most generations advanced at once whilst stepping, before received packets are processed.
+const STEP_CHUNK: u64 = 100;
+/// How often progress towards the target generation of [`UiPacket::StartUntil`] is reported.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The period is exact up to a billion ticks per second.
+    fn tick_period_exact() {
+        let ticks_per_second = NonZeroU32::new(1_000_000_000).unwrap();
+        assert_eq!(tick_period(ticks_per_second), Duration::from_nanos(1));
+        assert_eq!(tick_period(NonZeroU32::MIN), Duration::from_secs(1));
+    }
+
+    #[test]
+    /// Faster speeds are clamped rather than having a period of zero.
+    fn tick_period_clamped() {
+        let ticks_per_second = NonZeroU32::new(1_000_000_001).unwrap();
+        assert_eq!(tick_period(ticks_per_second), MIN_TICK_PERIOD);
+        assert_eq!(tick_period(NonZeroU32::MAX), MIN_TICK_PERIOD);
+
+        // The tick rate limiter accepts the clamped period.
+        spin_sleep_util::interval(Duration::from_secs(1)).set_period(tick_period(NonZeroU32::MAX));
+    }
+}
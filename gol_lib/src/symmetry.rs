@@ -0,0 +1,131 @@
+//! Contains [`Symmetry`].
+//! See its documentation for more information.
+
+use alloc::vec::Vec;
+
+use crate::{Area, GlobalPosition};
+
+/// The symmetry of a randomly generated pattern, about the centre of the area it is generated in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Symmetry {
+    /// No symmetry.
+    #[default]
+    None,
+    /// Two-fold rotational symmetry.
+    C2,
+    /// Four-fold rotational symmetry.
+    C4,
+    /// Reflective symmetry along both the horizontal & vertical axes.
+    D2,
+    /// Four-fold rotational symmetry, along with reflective symmetry along both axes & both diagonals.
+    D4,
+}
+
+impl Symmetry {
+    /// All the symmetries.
+    pub const ALL: [Symmetry; 5] = [
+        Symmetry::None,
+        Symmetry::C2,
+        Symmetry::C4,
+        Symmetry::D2,
+        Symmetry::D4,
+    ];
+
+    /// The area the symmetry is applied within.
+    ///
+    /// Rotating by a quarter turn only maps an area onto itself if it is square, so [`Symmetry::C4`] &
+    /// [`Symmetry::D4`] use the largest square centred within the area. Every other symmetry uses the whole area.
+    pub fn symmetric_area(self, area: Area) -> Area {
+        match self {
+            Symmetry::None | Symmetry::C2 | Symmetry::D2 => area,
+            Symmetry::C4 | Symmetry::D4 => {
+                let x_difference = area.x_difference();
+                let y_difference = area.y_difference();
+                let side = x_difference.min(y_difference);
+
+                let min = area.get_min() + ((x_difference - side) / 2, (y_difference - side) / 2);
+                Area::new(min, min + (side, side))
+            }
+        }
+    }
+
+    /// Returns the position along with every position it is mapped onto by the symmetry about the centre of the given
+    /// area. The area should be the one returned by [`Self::symmetric_area`].
+    ///
+    /// The returned positions may contain duplicates, such as when the position is on an axis of reflection.
+    pub fn orbit(self, area: Area, position: GlobalPosition) -> Vec<GlobalPosition> {
+        // Coordinates are doubled so the centre of areas with an even width is still a whole number.
+        let centre_x = area.get_min().get_x() as i64 + area.get_max().get_x() as i64;
+        let centre_y = area.get_min().get_y() as i64 + area.get_max().get_y() as i64;
+        let u = 2 * position.get_x() as i64 - centre_x;
+        let v = 2 * position.get_y() as i64 - centre_y;
+
+        let images: &[(i64, i64)] = match self {
+            Symmetry::None => &[(u, v)],
+            Symmetry::C2 => &[(u, v), (-u, -v)],
+            Symmetry::C4 => &[(u, v), (-v, u), (-u, -v), (v, -u)],
+            Symmetry::D2 => &[(u, v), (-u, v), (u, -v), (-u, -v)],
+            Symmetry::D4 => &[
+                (u, v),
+                (-v, u),
+                (-u, -v),
+                (v, -u),
+                (-u, v),
+                (u, -v),
+                (v, u),
+                (-v, -u),
+            ],
+        };
+
+        images
+            .iter()
+            .map(|(u, v)| {
+                GlobalPosition::new(((u + centre_x) / 2) as i32, ((v + centre_y) / 2) as i32)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Rotational symmetries only use a square area.
+    fn symmetric_area() {
+        let area = Area::new((0, 0), (9, 5));
+
+        assert_eq!(Symmetry::D2.symmetric_area(area), area);
+        assert_eq!(Symmetry::C4.symmetric_area(area), Area::new((2, 0), (7, 5)));
+    }
+
+    #[test]
+    /// A position is rotated about the centre of an area with an even width.
+    fn orbit_c4_even() {
+        let area = Area::new((0, 0), (3, 3));
+
+        assert_eq!(
+            Symmetry::C4.orbit(area, (0, 1).into()),
+            [(0, 1).into(), (2, 0).into(), (3, 2).into(), (1, 3).into()]
+        );
+    }
+
+    #[test]
+    /// Every position in the orbit is within the area.
+    fn orbit_within_area() {
+        let area = Area::new((-3, 4), (1, 8));
+
+        for symmetry in Symmetry::ALL {
+            let area = symmetry.symmetric_area(area);
+            for position in area.iterate_over() {
+                for image in symmetry.orbit(area, position) {
+                    assert!(
+                        area.x_range().contains(&image.get_x())
+                            && area.y_range().contains(&image.get_y()),
+                        "{symmetry:?} maps {position:?} to {image:?}"
+                    );
+                }
+            }
+        }
+    }
+}
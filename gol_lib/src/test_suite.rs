@@ -0,0 +1,2156 @@
+//! A suite of tests that every [`Simulator`] must pass, so each implementation behaves the same.
+//!
+//! Each test is a function generic over the simulator. [`simulator_test_suite`](crate::simulator_test_suite) runs
+//! every test against a simulator from within its crate's tests:
+//! ```ignore
+//! #[cfg(test)]
+//! mod suite {
+//!     gol_lib::simulator_test_suite!(crate::Board);
+//! }
+//! ```
+//!
+//! Tests of the simulator thread that need a particular board can run it with [`spawn`], which returns a [`Harness`]
+//! to communicate with it.
+
+use std::collections::HashSet;
+use std::{sync::mpsc, thread::JoinHandle, time::Duration};
+
+use bitvec::vec::BitVec;
+
+use crate::{
+    communication::{SimulationSpeed, SimulatorPacket, StopCondition, StopReason, UiPacket},
+    create_channels,
+    persistence::{
+        presets::{BLINKER, GLIDER, PULSAR},
+        rle::parse_rle,
+        SimulationBlueprint, SimulationSave,
+    },
+    start_simulator, start_simulator_with_callback, Area, BoardDisplay, Cell, GlobalPosition,
+    LoadError, LoadStatus, SharedDisplay, Simulator, SimulatorReceiver, SimulatorSender, TickInfo,
+    UiReceiver, UiSender,
+};
+
+/// Generates a `#[test]` for every test of the [suite](crate::test_suite), run against the given [`Simulator`].
+///
+/// The simulator must be given as a path that can be used from within the module the macro is used in.
+#[macro_export]
+macro_rules! simulator_test_suite {
+    ($simulator:ty) => {
+        $crate::simulator_test_suite!(
+            $simulator;
+            dead_by_default,
+            set_cell_alive,
+            set_cell_pattern,
+            generates_correct_display,
+            reset,
+            generation_increases,
+            alive_0_neighbours,
+            dead_0_neighbours,
+            alive_1_neighbour,
+            dead_1_neighbour,
+            alive_2_neighbours,
+            dead_2_neighbours,
+            alive_3_neighbours,
+            dead_3_neighbours,
+            alive_4_neighbours,
+            dead_4_neighbours,
+            alive_5_neighbours,
+            dead_5_neighbours,
+            alive_6_neighbours,
+            dead_6_neighbours,
+            alive_7_neighbours,
+            dead_7_neighbours,
+            alive_8_neighbours,
+            dead_8_neighbours,
+            block,
+            load_board_empty,
+            load_board_full,
+            load_board_mixed,
+            load_board_rule,
+            save_board_empty,
+            save_board_full_area,
+            save_board_mixed,
+            set_generation,
+            get_board_area_full,
+            get_board_area_partial,
+            get_board_area_empty,
+            get_board_area_negative,
+            get_board_area_positive,
+            get_board_area_straddling_origin,
+            get_board_area_single,
+            get_region,
+            randomize_c4,
+            population,
+            population_matches_board,
+            headless_glider,
+            headless_without_tracking,
+            cell_limit,
+            update_display_poisoned,
+            load_blueprint_wrong_length,
+            save_blueprint_sizes,
+            density_empty,
+            density_single,
+            density_rectangles,
+            load_board_wrong_length,
+            update_display_too_large,
+            randomize_seeded,
+            mirror_twice,
+            rotate_glider,
+            translation_helper,
+            blinker,
+            toad,
+            beacon,
+            pulsar,
+            glider,
+            simulator_stops_at_cell_limit,
+            simulator_progress,
+            simulator_stop_clears_target,
+            simulator_step,
+            simulator_step_stopped,
+            simulator_step_cell_limit,
+            simulator_load_result,
+            simulator_set_many,
+            simulator_detects_blinker_period,
+            simulator_detects_glider,
+            simulator_reports_tick_rate,
+            simulator_clear,
+            simulator_final_state,
+            simulator_goto_generation,
+            simulator_goto_generation_after_load,
+            simulator_callback,
+            simulator_stop_condition,
+        );
+    };
+    ($simulator:ty; $($test:ident,)*) => {
+        $(
+            #[test]
+            fn $test() {
+                $crate::test_suite::$test::<$simulator>();
+            }
+        )*
+    };
+}
+
+/// Returns an iterator over [`Cell`], which gives "[`Cell::Alive`], [`Cell::Dead`]" in that order, forever.
+fn generate_cell_iterator() -> std::iter::FromFn<impl FnMut() -> Option<Cell>> {
+    let mut generated_cell = Cell::Dead;
+
+    std::iter::from_fn(move || {
+        generated_cell = match generated_cell {
+            Cell::Alive => Cell::Dead,
+            Cell::Dead => Cell::Alive,
+        };
+        Some(generated_cell)
+    })
+}
+
+/// A toad, an oscillator with a period of 2.
+const TOAD: &str = "x = 4, y = 2\nb3o$3o!";
+/// A beacon, an oscillator with a period of 2.
+const BEACON: &str = "x = 4, y = 4\n2o2b$2o2b$2b2o$2b2o!";
+
+/// Gets the positions of every alive cell on the board.
+fn alive<S: Simulator>(board: &S) -> HashSet<GlobalPosition> {
+    board.alive_positions().into_iter().collect()
+}
+
+/// Creates a board containing the given RLE pattern.
+fn load<S: Simulator>(rle: &str) -> S {
+    let mut board = S::new(Default::default());
+    let blueprint = parse_rle(rle).expect("Pattern is valid");
+    assert_eq!(
+        board.load_blueprint((0, 0).into(), blueprint),
+        LoadStatus::Success
+    );
+    board
+}
+
+/// Returns the offset that moves every alive cell of `from` onto the alive cells of `to`, or [`None`] if the boards
+/// are not the same pattern.
+fn translation<S: Simulator>(from: &S, to: &S) -> Option<(i32, i32)> {
+    let (from_cells, to_cells) = (alive(from), alive(to));
+    if from_cells.len() != to_cells.len() {
+        return None;
+    }
+
+    // The minimum positions of the patterns must line up if they are translations of each other.
+    let offset = to.get_board_area().get_min() - from.get_board_area().get_min();
+    from_cells
+        .iter()
+        .all(|position| to_cells.contains(&(*position + offset)))
+        .then_some(offset)
+}
+
+/// Asserts that the pattern returns to its original state, translated by the given offset, after the given period.
+fn assert_period<S: Simulator>(rle: &str, period: u64, offset: (i32, i32)) {
+    let original = load::<S>(rle);
+    let mut board = load::<S>(rle);
+
+    // The pattern must not repeat before its period.
+    for generation in 1..period {
+        board.tick();
+        assert_eq!(
+            translation(&original, &board),
+            None,
+            "Repeated at generation {generation}"
+        );
+    }
+
+    board.tick();
+    assert_eq!(translation(&original, &board), Some(offset));
+}
+
+/// Creates a board with the given cells alive.
+fn with_cells<S: Simulator>(cells: impl IntoIterator<Item = (i32, i32)>) -> S {
+    let mut board = S::new(Default::default());
+    for position in cells {
+        board.set(position.into(), Cell::Alive);
+    }
+    board
+}
+
+/// How long to wait for a packet from the simulator thread before failing the test.
+const PACKET_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A board running on the simulator thread, along with the channels to communicate with it.
+pub struct Harness {
+    ui_sender: UiSender,
+    simulator_receiver: SimulatorReceiver,
+    simulator: JoinHandle<()>,
+}
+
+/// Starts the simulator thread with the given board.
+pub fn spawn(board: impl Simulator + 'static) -> Harness {
+    spawn_with(board, [])
+}
+
+/// Starts the simulator thread with the given board, having already sent it the given packets.
+///
+/// As the packets are sent before the simulator starts, they are all received together.
+pub fn spawn_with(
+    board: impl Simulator + 'static,
+    packets: impl IntoIterator<Item = UiPacket>,
+) -> Harness {
+    Harness::start(packets, |ui_receiver, simulator_sender| {
+        start_simulator(board, ui_receiver, simulator_sender)
+    })
+}
+
+/// Starts the simulator thread with the given board, calling the callback after every generation.
+pub fn spawn_with_callback(
+    board: impl Simulator + 'static,
+    callback: impl FnMut(&TickInfo) + Send + 'static,
+) -> Harness {
+    Harness::start([], |ui_receiver, simulator_sender| {
+        start_simulator_with_callback(board, ui_receiver, simulator_sender, callback)
+    })
+}
+
+impl Harness {
+    /// Sends the packets, then starts the simulator thread with the channels.
+    fn start(
+        packets: impl IntoIterator<Item = UiPacket>,
+        start: impl FnOnce(UiReceiver, SimulatorSender) -> std::io::Result<JoinHandle<()>>,
+    ) -> Self {
+        let ((ui_sender, ui_receiver), (simulator_sender, simulator_receiver)) = create_channels();
+        for packet in packets {
+            ui_sender.send(packet).unwrap();
+        }
+        let simulator = start(ui_receiver, simulator_sender).expect("Can start simulator");
+
+        Self {
+            ui_sender,
+            simulator_receiver,
+            simulator,
+        }
+    }
+
+    /// Sends the packet to the simulator.
+    pub fn send(&self, packet: UiPacket) {
+        self.ui_sender.send(packet).unwrap();
+    }
+
+    /// Receives the next packet from the simulator, panicking if none is sent in time.
+    pub fn receive(&self) -> SimulatorPacket {
+        self.simulator_receiver
+            .recv_timeout(PACKET_TIMEOUT)
+            .expect("Packet must be sent")
+    }
+
+    /// Receives packets until `matcher` returns [`Some`] for one of them, returning its value.
+    ///
+    /// Packets `matcher` returns [`None`] for are skipped.
+    pub fn expect_packet<T>(&self, mut matcher: impl FnMut(SimulatorPacket) -> Option<T>) -> T {
+        loop {
+            if let Some(value) = matcher(self.receive()) {
+                break value;
+            }
+        }
+    }
+
+    /// Requests a save of the board, returning it once received.
+    pub fn save(&self) -> SimulationSave {
+        self.send(UiPacket::SaveBoard);
+        self.expect_packet(|packet| match packet {
+            SimulatorPacket::BoardSave { board } => Some(board),
+            _ => None,
+        })
+    }
+
+    /// Terminates the simulator & waits for its thread to end, returning the packets that were not yet received.
+    pub fn terminate(self) -> Vec<SimulatorPacket> {
+        self.send(UiPacket::Terminate);
+        let (result, packets) = self.join();
+        result.expect("Simulator terminates");
+        packets
+    }
+
+    /// Waits for the simulator thread to end without terminating it, returning how the thread ended & the packets
+    /// that were not yet received.
+    pub fn join(self) -> (std::thread::Result<()>, Vec<SimulatorPacket>) {
+        let result = self.simulator.join();
+        (result, self.simulator_receiver.try_iter().collect())
+    }
+}
+
+/// A cell will be dead unless it has been set to alive.
+pub fn dead_by_default<S: Simulator>() {
+    let board = S::new(Default::default());
+    for x in -10..=10 {
+        for y in -10..=10 {
+            assert_eq!(board.get((x, y).into()), Cell::Dead)
+        }
+    }
+}
+
+/// Sets a cell to be alive.
+pub fn set_cell_alive<S: Simulator>() {
+    let position = GlobalPosition::new(1, 1);
+    let mut board = S::new(Default::default());
+
+    board.set(position, Cell::Alive);
+    assert_eq!(board.get(position), Cell::Alive);
+}
+
+/// Sets a pattern of alive & dead cells.
+pub fn set_cell_pattern<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    // Populate board
+    let mut cell_iter = generate_cell_iterator();
+    for x in -10..=10 {
+        for y in -10..=10 {
+            let cell = cell_iter.next().unwrap();
+            board.set((x, y).into(), cell)
+        }
+    }
+
+    // Read board
+    let mut cell_iter = generate_cell_iterator();
+    for x in -10..=10 {
+        for y in -10..=10 {
+            let cell = cell_iter.next().unwrap();
+            let get = board.get((x, y).into());
+            assert_eq!(cell, get);
+        }
+    }
+}
+
+/// The derived display will correctly represent the board.
+pub fn generates_correct_display<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    // Populate board
+    let mut cell_iter = generate_cell_iterator();
+    for y in -10..=10 {
+        for x in -10..=10 {
+            board.set((x, y).into(), cell_iter.next().unwrap());
+        }
+    }
+
+    // Display init
+    board.set_display_area(Area::new((-10, -10), (10, 10)));
+    board.update_display();
+
+    let mut mutex_guard = display.lock().unwrap();
+    let take = mutex_guard.take();
+    assert!(take.is_some());
+
+    // Generate expected result
+    let var_name = {
+        use Cell::{Alive, Dead};
+        let mut vec = Vec::new();
+
+        for _ in 0..10 {
+            let a: Box<[Cell]> = Box::new([
+                Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead,
+                Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive,
+            ]);
+            let b = Box::new([
+                Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead,
+                Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead,
+            ]);
+            vec.push(a);
+            vec.push(b);
+        }
+        vec.push(vec[0].clone());
+
+        vec
+    };
+
+    let area = Area::new((-10, -10), (10, 10));
+    let board_display = BoardDisplay::new(0, area, var_name);
+    let take = take.unwrap();
+    // The display includes the maximum row & column of the area.
+    assert_eq!(
+        (take.get_x().get() * take.get_y().get()) as u64,
+        area.cell_count()
+    );
+    assert_eq!(board_display, take)
+}
+
+/// reset must remove all alive cells from board & set the generation to 0.
+pub fn reset<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    // Populate board
+    for position in Area::new((-100, -100), (100, 100)).iterate_over() {
+        board.set(position, Cell::Alive);
+    }
+
+    board.reset();
+
+    // Test reset
+    for position in Area::new((-100, -100), (100, 100)).iterate_over() {
+        assert_eq!(
+            board.get(position),
+            Cell::Dead,
+            "Cell at {position:?} is alive. All cells must be dead after board reset"
+        );
+    }
+}
+
+/// Generation increases by one each time tick is called.
+pub fn generation_increases<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    assert_eq!(board.get_generation(), 0);
+
+    for generation in 1..=100 {
+        board.tick();
+        assert_eq!(
+            board.get_generation(),
+            generation,
+            "Calling tick must incrememnt the generation by one."
+        );
+    }
+}
+
+/// An alive cell with no neighbours will die
+pub fn alive_0_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    board.set((1, 1).into(), Cell::Alive);
+
+    // Tick & test
+    board.tick();
+    assert_eq!(board.get((1, 1).into()), Cell::Dead);
+}
+
+/// A dead cell with no neighbours will stay dead
+pub fn dead_0_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    board.set((1, 1).into(), Cell::Dead);
+
+    // Tick & test
+    board.tick();
+    assert_eq!(board.get((1, 1).into()), Cell::Dead);
+}
+
+/// An alive cell with one neighbour will die
+pub fn alive_1_neighbour<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for position in Area::new((0, 0), (2, 2)).iterate_over() {
+        if position == (1, 1).into() {
+            continue;
+        }
+
+        board.set((1, 1).into(), Cell::Alive);
+        board.set(position, Cell::Alive);
+
+        // Tick & test
+        board.tick();
+        assert_eq!(
+            board.get((1, 1).into()),
+            Cell::Dead,
+            "Cell at (1, 1) must die from one neighbour at {position:?}"
+        );
+    }
+}
+
+/// A dead cell with one neighbour will stay dead
+pub fn dead_1_neighbour<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for position in Area::new((0, 0), (2, 2)).iterate_over() {
+        if position == (1, 1).into() {
+            continue;
+        }
+
+        board.set((1, 1).into(), Cell::Alive);
+        board.set(position, Cell::Alive);
+
+        // Tick & test
+        board.tick();
+        assert_eq!(
+            board.get((1, 1).into()),
+            Cell::Dead,
+            "Cell at (1, 1) must stay dead with neighbour at: {position:?}"
+        );
+    }
+}
+
+/// An alive cell with two neighbours will stay alive
+pub fn alive_2_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+
+            board.set((1, 1).into(), Cell::Alive);
+            board.set(cell_a, Cell::Alive);
+            board.set(cell_b, Cell::Alive);
+
+            // Tick & test
+            board.tick();
+            assert_eq!(
+                board.get((1, 1).into()),
+                Cell::Alive,
+                "Cell at (1, 1) must live from neighbours at: {cell_a:?}, {cell_b:?}"
+            );
+
+            // Remove remenatns
+            board.reset();
+        }
+    }
+}
+
+/// A dead cell with two neighbours will stay dead
+pub fn dead_2_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+
+            board.set((1, 1).into(), Cell::Dead);
+            board.set(cell_a, Cell::Alive);
+            board.set(cell_b, Cell::Alive);
+
+            // Tick & test
+            board.tick();
+            assert_eq!(
+                board.get((1, 1).into()),
+                Cell::Dead,
+                "Cell at (1, 1) must stay dead with neighbours at: {cell_a:?}, {cell_b:?}"
+            );
+
+            // Remove remenatns
+            board.reset();
+        }
+    }
+}
+
+/// An alive cell with three neighbours will stay alive
+pub fn alive_3_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+            for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
+                if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
+                    continue;
+                }
+
+                board.set((1, 1).into(), Cell::Alive);
+                board.set(cell_a, Cell::Alive);
+                board.set(cell_b, Cell::Alive);
+                board.set(cell_c, Cell::Alive);
+
+                // Tick & test
+                board.tick();
+                assert_eq!(
+                    board.get((1, 1).into()),
+                    Cell::Alive,
+                    "Cell at (1, 1) must live from neighbours at: {cell_a:?}, {cell_b:?}, {cell_c:?}"
+                );
+
+                // Remove remenatns
+                board.reset();
+            }
+        }
+    }
+}
+
+/// A dead cell must become alive from three neighbouring cells.
+pub fn dead_3_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+            for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
+                if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
+                    continue;
+                }
+
+                board.set((1, 1).into(), Cell::Dead);
+                board.set(cell_a, Cell::Alive);
+                board.set(cell_b, Cell::Alive);
+                board.set(cell_c, Cell::Alive);
+
+                // Tick & test
+                board.tick();
+                assert_eq!(
+                    board.get((1, 1).into()),
+                    Cell::Alive,
+                    "Cell at (1, 1) must be created from neighbours at: {cell_a:?}, {cell_b:?}, {cell_c:?}"
+                );
+
+                // Remove remenatns
+                board.reset();
+            }
+        }
+    }
+}
+
+/// An alive cell with four neighbours will die
+pub fn alive_4_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+            for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
+                if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
+                    continue;
+                }
+                for cell_d in Area::new((0, 0), (2, 2)).iterate_over() {
+                    if cell_d == (1, 1).into()
+                        || cell_d == cell_a
+                        || cell_d == cell_b
+                        || cell_d == cell_c
+                    {
+                        continue;
+                    }
+
+                    board.set((1, 1).into(), Cell::Alive);
+                    board.set(cell_a, Cell::Alive);
+                    board.set(cell_b, Cell::Alive);
+                    board.set(cell_c, Cell::Alive);
+                    board.set(cell_d, Cell::Alive);
+
+                    // Tick & test
+                    board.tick();
+                    assert_eq!(
+                    board.get((1, 1).into()),
+                    Cell::Dead,
+                    "Cell at (1, 1) must die from neighbours at: {cell_a:?}, {cell_b:?}, {cell_c:?}, {cell_d:?}"
+                );
+
+                    // Remove remenatns
+                    board.reset();
+                }
+            }
+        }
+    }
+}
+
+/// An alive cell with four neighbours will die
+pub fn dead_4_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+            for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
+                if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
+                    continue;
+                }
+                for cell_d in Area::new((0, 0), (2, 2)).iterate_over() {
+                    if cell_d == (1, 1).into()
+                        || cell_d == cell_a
+                        || cell_d == cell_b
+                        || cell_d == cell_c
+                    {
+                        continue;
+                    }
+
+                    board.set((1, 1).into(), Cell::Dead);
+                    board.set(cell_a, Cell::Alive);
+                    board.set(cell_b, Cell::Alive);
+                    board.set(cell_c, Cell::Alive);
+                    board.set(cell_d, Cell::Alive);
+
+                    // Tick & test
+                    board.tick();
+                    assert_eq!(
+                    board.get((1, 1).into()),
+                    Cell::Dead,
+                    "Cell at (1, 1) must stay dead with neighbours at: {cell_a:?}, {cell_b:?}, {cell_c:?}, {cell_d:?}"
+                );
+
+                    // Remove remenatns
+                    board.reset();
+                }
+            }
+        }
+    }
+}
+
+/// An alive cell with five neighbours will die
+pub fn alive_5_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+            for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
+                if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
+                    continue;
+                }
+
+                // Set alive by default
+                for alive in Area::new((0, 0), (2, 2)).iterate_over() {
+                    board.set(alive, Cell::Alive);
+                }
+
+                board.set(cell_a, Cell::Dead);
+                board.set(cell_b, Cell::Dead);
+                board.set(cell_c, Cell::Dead);
+
+                // Tick & test
+                board.tick();
+                assert_eq!(
+                    board.get((1, 1).into()),
+                    Cell::Dead,
+                    "Cell at (1, 1) must die from being surrounded by neighbours expect for cells: {cell_a:?}, {cell_b:?}, {cell_c:?}"
+                );
+
+                // Remove remenatns
+                board.reset();
+            }
+        }
+    }
+}
+
+/// A dead cell with five neighbours stay dead
+pub fn dead_5_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+            for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
+                if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
+                    continue;
+                }
+
+                // Set alive by default
+                for alive in Area::new((0, 0), (2, 2)).iterate_over() {
+                    board.set(alive, Cell::Alive);
+                }
+
+                board.set((1, 1).into(), Cell::Dead);
+                board.set(cell_a, Cell::Dead);
+                board.set(cell_b, Cell::Dead);
+                board.set(cell_c, Cell::Dead);
+
+                // Tick & test
+                board.tick();
+                assert_eq!(
+                    board.get((1, 1).into()),
+                    Cell::Dead,
+                    "Cell at (1, 1) must stay dead with neighbouring dead cells at: {cell_a:?}, {cell_b:?}, {cell_c:?}"
+                );
+
+                // Remove remenatns
+                board.reset();
+            }
+        }
+    }
+}
+
+/// An alive cell with six neighbours will die
+pub fn alive_6_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+
+            // Set alive by default
+            for alive in Area::new((0, 0), (2, 2)).iterate_over() {
+                board.set(alive, Cell::Alive);
+            }
+
+            board.set(cell_a, Cell::Dead);
+            board.set(cell_b, Cell::Dead);
+
+            // Tick & test
+            board.tick();
+            assert_eq!(
+                board.get((1, 1).into()),
+                Cell::Dead,
+                "Cell at (1, 1) must die from being surrounded by neighbours expect for cells: {cell_a:?}, {cell_b:?}"
+            );
+
+            // Remove remenatns
+            board.reset();
+        }
+    }
+}
+
+/// A dead cell with six neighbours will stay dead
+pub fn dead_6_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+        for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
+            if cell_b == (1, 1).into() || cell_b == cell_a {
+                continue;
+            }
+
+            // Set alive by default
+            for alive in Area::new((0, 0), (2, 2)).iterate_over() {
+                board.set(alive, Cell::Alive);
+            }
+
+            board.set((1, 1).into(), Cell::Dead);
+            board.set(cell_a, Cell::Dead);
+            board.set(cell_b, Cell::Dead);
+
+            // Tick & test
+            board.tick();
+            assert_eq!(
+                board.get((1, 1).into()),
+                Cell::Dead,
+                "Cell at (1, 1) must stay dead with dead neighbouring cells at: {cell_a:?}, {cell_b:?}"
+            );
+
+            // Remove remenatns
+            board.reset();
+        }
+    }
+}
+
+/// An alive cell with seven neighbours will die
+pub fn alive_7_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+
+        // Set alive by default
+        for alive in Area::new((0, 0), (2, 2)).iterate_over() {
+            board.set(alive, Cell::Alive);
+        }
+
+        board.set(cell_a, Cell::Dead);
+
+        // Tick & test
+        board.tick();
+        assert_eq!(
+            board.get((1, 1).into()),
+            Cell::Dead,
+            "Cell at (1, 1) must die from being surrounded by neighbours expect for cells: {cell_a:?}"
+        );
+
+        // Remove remenatns
+        board.reset();
+    }
+}
+
+/// A dead cell with seven neighbours will stay dead.
+pub fn dead_7_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
+        if cell_a == (1, 1).into() {
+            continue;
+        }
+
+        // Set alive by default
+        for alive in Area::new((0, 0), (2, 2)).iterate_over() {
+            board.set(alive, Cell::Alive);
+        }
+
+        board.set((1, 1).into(), Cell::Dead);
+        board.set(cell_a, Cell::Dead);
+
+        // Tick & test
+        board.tick();
+        assert_eq!(
+            board.get((1, 1).into()),
+            Cell::Dead,
+            "Cell at (1, 1) must stay dead with dead neighbouring cells at: {cell_a:?}"
+        );
+
+        // Remove remenatns
+        board.reset();
+    }
+}
+
+/// An alive cell with all neighbours will die
+pub fn alive_8_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    // Set alive by default
+    for alive in Area::new((0, 0), (2, 2)).iterate_over() {
+        board.set(alive, Cell::Alive);
+    }
+
+    // Tick & test
+    board.tick();
+    assert_eq!(
+        board.get((1, 1).into()),
+        Cell::Dead,
+        "Cell at (1, 1) must die from being fully surrounded by neighbours"
+    );
+
+    // Remove remenatns
+    board.reset();
+}
+
+/// An alive cell with all neighbours will die
+pub fn dead_8_neighbours<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    // Set alive by default
+    for alive in Area::new((0, 0), (2, 2)).iterate_over() {
+        board.set(alive, Cell::Alive);
+    }
+
+    board.set((1, 1).into(), Cell::Dead);
+
+    // Tick & test
+    board.tick();
+    assert_eq!(
+        board.get((1, 1).into()),
+        Cell::Dead,
+        "Cell at (1, 1) must die from being fully surrounded by neighbours"
+    );
+
+    // Remove remenatns
+    board.reset();
+}
+
+/// Correctly simulates the "Block" pattern.
+pub fn block<S: Simulator>() {
+    let display: SharedDisplay = Default::default();
+    let mut board = S::new(display.clone());
+
+    // Create block pattern
+    board.set((1, 1).into(), Cell::Alive);
+    board.set((1, 2).into(), Cell::Alive);
+    board.set((2, 1).into(), Cell::Alive);
+    board.set((2, 2).into(), Cell::Alive);
+
+    // Tick & test
+    board.tick();
+    for x in 0..4 {
+        for y in 0..4 {
+            let found = board.get((x, y).into());
+            let expected = match (x, y) {
+                (1, 1) | (1, 2) | (2, 1) | (2, 2) => Cell::Alive,
+                _ => Cell::Dead,
+            };
+            assert_eq!(found, expected)
+        }
+    }
+
+    // Once again for good measure
+    board.tick();
+    for x in 0..4 {
+        for y in 0..4 {
+            let found = board.get((x, y).into());
+            let expected = match (x, y) {
+                (1, 1) | (1, 2) | (2, 1) | (2, 2) => Cell::Alive,
+                _ => Cell::Dead,
+            };
+            assert_eq!(found, expected)
+        }
+    }
+}
+
+/// Correctly loads empty board.
+pub fn load_board_empty<S: Simulator>() {
+    // Generate board with alive cells.
+    let mut board = S::new(Default::default());
+    for position in Area::new((-10, -10), (10, 10)).iterate_over() {
+        board.set(position, Cell::Alive);
+    }
+
+    // Load empty board.
+    let generation = 0;
+    let area = Area::new((-4, -6), (4, 6));
+    let mut board_data = BitVec::new();
+    for _ in area.iterate_over() {
+        board_data.push(Cell::Dead.into());
+    }
+    let simulation_save = SimulationSave::new(generation, area, board_data);
+    assert_eq!(board.load_board(simulation_save), LoadStatus::Success);
+
+    assert_eq!(
+        board.get((8, 8).into()),
+        Cell::Dead,
+        "Cells outside the new board area must be set to dead."
+    );
+
+    for position in area.iterate_over() {
+        assert_eq!(
+            board.get(position),
+            Cell::Dead,
+            "Cell at {position:?} must be dead as loaded board only contained dead cells."
+        )
+    }
+}
+
+/// Correctly loads full board.
+pub fn load_board_full<S: Simulator>() {
+    let mut board = S::new(Default::default());
+
+    // Load full board.
+    let generation = 0;
+    let area = Area::new((-4, -6), (4, 6));
+    let mut board_data = BitVec::new();
+    for _ in area.iterate_over() {
+        board_data.push(Cell::Alive.into());
+    }
+
+    let simulation_save = SimulationSave::new(generation, area, board_data);
+    assert_eq!(board.load_board(simulation_save), LoadStatus::Success);
+
+    assert_eq!(
+        board.get((8, 8).into()),
+        Cell::Dead,
+        "Cells outside the new board area must be set to dead."
+    );
+
+    for position in area.iterate_over() {
+        assert_eq!(
+            board.get(position),
+            Cell::Alive,
+            "Cell at {position:?} must be alive as loaded board only contained alive cells."
+        )
+    }
+}
+
+/// Correctly loads mixed board.
+pub fn load_board_mixed<S: Simulator>() {
+    let mut board = S::new(Default::default());
+
+    // Load mixed board.
+    let generation = 0;
+    let area = Area::new((-4, -6), (4, 6));
+    let mut board_data = BitVec::new();
+    for (_, cell) in area.iterate_over().zip(generate_cell_iterator()) {
+        board_data.push(cell.into());
+    }
+
+    let simulation_save = SimulationSave::new(generation, area, board_data);
+    assert_eq!(board.load_board(simulation_save), LoadStatus::Success);
+
+    assert_eq!(
+        board.get((8, 8).into()),
+        Cell::Dead,
+        "Cells outside the new board area must be set to dead."
+    );
+
+    for (position, cell) in area.iterate_over().zip(generate_cell_iterator()) {
+        assert_eq!(
+            board.get(position),
+            cell,
+            "Cell at {position:?} must be {cell:?} as loaded board had this cell in this state."
+        )
+    }
+}
+
+/// A save carrying a non-standard rule reloads with that rule active, if the simulator supports it. Otherwise the save
+/// is rejected, leaving the board unchanged.
+pub fn load_board_rule<S: Simulator>() {
+    use crate::Rule;
+
+    let mut loaded = S::new(Default::default());
+    loaded.set((10, 10).into(), Cell::Alive);
+    // Saved by hand, as a simulator that doesn't support the rule can't save a board with it.
+    let save = SimulationSave::new(0, Area::new((0, 0), (0, 0)), BitVec::repeat(true, 1))
+        .with_rule(Rule::HIGHLIFE);
+
+    if !loaded.supports_rule(Rule::HIGHLIFE) {
+        assert_eq!(
+            loaded.load_board(save),
+            LoadStatus::Fail {
+                reason: LoadError::UnsupportedRule {
+                    rule: Rule::HIGHLIFE
+                }
+            }
+        );
+        assert_eq!(loaded.get((10, 10).into()), Cell::Alive);
+        assert_eq!(loaded.get_rule(), Rule::CONWAY);
+        return;
+    }
+
+    assert_eq!(loaded.load_board(save), LoadStatus::Success);
+    assert_eq!(loaded.get_rule(), Rule::HIGHLIFE);
+    assert_eq!(loaded.save_board().get_rule(), Rule::HIGHLIFE);
+
+    // A dead cell with 6 neighbours is only born in HighLife.
+    for position in [(-1, -1), (0, -1), (1, -1), (-1, 1), (0, 1), (1, 1)] {
+        loaded.set(position.into(), Cell::Alive);
+    }
+    loaded.set((0, 0).into(), Cell::Dead);
+    loaded.tick();
+    assert!(loaded.get((0, 0).into()).is_alive());
+
+    // Rules which birth cells without neighbours can't be simulated.
+    let save = SimulationSave::new(0, Area::default(), BitVec::repeat(false, 1))
+        .with_rule(Rule::new(&[0], &[]));
+    assert_eq!(
+        loaded.load_board(save),
+        LoadStatus::Fail {
+            reason: LoadError::UnsupportedRule {
+                rule: Rule::new(&[0], &[])
+            }
+        }
+    );
+    assert_eq!(loaded.get_rule(), Rule::HIGHLIFE);
+}
+
+/// Correctly saves empty board.
+pub fn save_board_empty<S: Simulator>() {
+    let board = S::new(Default::default());
+
+    let generation = 0;
+    let board_area = Area::new((0, 0), (0, 0));
+    let mut board_data = BitVec::new();
+    for _ in board_area.iterate_over() {
+        board_data.push(Cell::Dead.into());
+    }
+
+    let expected_save = SimulationSave::new(generation, board_area, board_data);
+    let save_board = board.save_board();
+
+    assert_eq!(save_board, expected_save);
+}
+
+/// Correctly saves full board area.
+pub fn save_board_full_area<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    let board_area = Area::new((-6, -6), (5, 5));
+
+    for position in board_area.iterate_over() {
+        board.set(position, Cell::Alive);
+    }
+    let save_board = board.save_board();
+
+    let generation = 0;
+    let mut board_data = BitVec::new();
+    for _ in board_area.iterate_over() {
+        board_data.push(Cell::Alive.into());
+    }
+    let expected_save = SimulationSave::new(generation, board_area, board_data);
+
+    assert_eq!(save_board, expected_save);
+}
+
+/// Correctly saves mixed board area.
+pub fn save_board_mixed<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    let board_area = Area::new((-6, -6), (5, 5));
+
+    for (position, cell) in board_area.iterate_over().zip(generate_cell_iterator()) {
+        board.set(position, cell);
+    }
+    let save_board = board.save_board();
+
+    let generation = 0;
+    let mut board_data = BitVec::new();
+    for (position, cell) in board_area.iterate_over().zip(generate_cell_iterator()) {
+        // The last tile in each row is cut off due to it being empty.
+        // This is intended.
+        if position.get_x() == 5 {
+            continue;
+        }
+
+        board_data.push(cell.into());
+    }
+    // Compensate for last tile being cut off in each row.
+    let board_area = Area::new((-6, -6), (4, 5));
+    let expected_save = SimulationSave::new(generation, board_area, board_data);
+
+    assert_eq!(save_board, expected_save);
+}
+
+/// `set_generation()` correctly sets the generation.
+pub fn set_generation<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    board.set_generation(100);
+    assert_eq!(board.get_generation(), 100);
+}
+
+/// Getting the board area of a filled area will return the filled area.
+pub fn get_board_area_full<S: Simulator>() {
+    let mut board = S::new(Default::default());
+
+    let area = Area::new((-2, -2), (3, 4));
+    for position in area.iterate_over() {
+        board.set(position, Cell::Alive);
+    }
+
+    assert_eq!(board.get_board_area(), area)
+}
+
+/// The board area will included separate cells.
+pub fn get_board_area_partial<S: Simulator>() {
+    let mut board = S::new(Default::default());
+
+    let area = Area::new((0, 0), (4, 4));
+    for position in area.iterate_over() {
+        board.set(position, Cell::Alive);
+    }
+
+    board.set((4, 6).into(), Cell::Alive);
+
+    assert_eq!(board.get_board_area(), Area::new((0, 0), (4, 6)));
+}
+
+/// An empty board has a zero sized area.
+pub fn get_board_area_empty<S: Simulator>() {
+    let board = S::new(Default::default());
+
+    assert_eq!(board.get_board_area(), Area::default());
+}
+
+/// The board area does not include the origin when all cells are in the negative quadrant.
+pub fn get_board_area_negative<S: Simulator>() {
+    let mut board = S::new(Default::default());
+
+    board.set((-10, -3).into(), Cell::Alive);
+    board.set((-4, -8).into(), Cell::Alive);
+    board.set((-6, -5).into(), Cell::Alive);
+
+    assert_eq!(board.get_board_area(), Area::new((-10, -8), (-4, -3)));
+}
+
+/// The board area does not include the origin when all cells are in the positive quadrant.
+pub fn get_board_area_positive<S: Simulator>() {
+    let mut board = S::new(Default::default());
+
+    // A glider away from the origin.
+    board.set((11, 10).into(), Cell::Alive);
+    board.set((12, 11).into(), Cell::Alive);
+    board.set((10, 12).into(), Cell::Alive);
+    board.set((11, 12).into(), Cell::Alive);
+    board.set((12, 12).into(), Cell::Alive);
+
+    assert_eq!(board.get_board_area(), Area::new((10, 10), (12, 12)));
+}
+
+/// The board area tightly bounds cells on both sides of the origin.
+pub fn get_board_area_straddling_origin<S: Simulator>() {
+    let mut board = S::new(Default::default());
+
+    board.set((-3, 2).into(), Cell::Alive);
+    board.set((5, -7).into(), Cell::Alive);
+
+    assert_eq!(board.get_board_area(), Area::new((-3, -7), (5, 2)));
+}
+
+/// A single cell has an area covering only itself.
+pub fn get_board_area_single<S: Simulator>() {
+    let mut board = S::new(Default::default());
+
+    board.set((7, -2).into(), Cell::Alive);
+
+    assert_eq!(board.get_board_area(), Area::new((7, -2), (7, -2)));
+}
+
+/// The optimised region matches getting each cell individually.
+pub fn get_region<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    for position in [
+        (-3, -2),
+        (-1, 0),
+        (0, 0),
+        (2, 1),
+        (4, 4),
+        (10, 10),
+        (-20, 0),
+    ] {
+        board.set(position.into(), Cell::Alive);
+    }
+
+    for area in [
+        Area::new((-3, -2), (4, 4)),
+        Area::new((-1, -1), (1, 1)),
+        Area::new((5, 5), (8, 8)),
+        Area::new((0, 0), (0, 0)),
+    ] {
+        let expected: Box<[Cell]> = area
+            .iterate_over()
+            .map(|position| board.get(position))
+            .collect();
+        assert_eq!(board.get_region(area), expected, "{area:?}");
+    }
+}
+
+/// A C4 symmetric seed is unchanged by a quarter turn about the centre of the area.
+pub fn randomize_c4<S: Simulator>() {
+    use crate::Symmetry;
+
+    let mut board = S::new(Default::default());
+    let area = Area::new((0, 0), (9, 9));
+    board.randomize_symmetric(area, 0.5, 42, Symmetry::C4);
+
+    assert!(board.population() > 0);
+    assert!(board.population() < 100);
+    for position in area.iterate_over() {
+        let rotated = GlobalPosition::new(9 - position.get_y(), position.get_x());
+        assert_eq!(
+            board.get(position),
+            board.get(rotated),
+            "{position:?} does not match {rotated:?}"
+        );
+    }
+}
+
+/// The population is the amount of alive cells.
+pub fn population<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    assert_eq!(board.population(), 0);
+
+    board.set((-5, 3).into(), Cell::Alive);
+    board.set((2, 2).into(), Cell::Alive);
+    board.set((2, 2).into(), Cell::Alive);
+    assert_eq!(board.population(), 2);
+
+    board.set((2, 2).into(), Cell::Dead);
+    assert_eq!(board.population(), 1);
+}
+
+/// The population stays equal to the amount of alive cells on the board through sets, ticks & blueprint loads.
+pub fn population_matches_board<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    let counted = |board: &S| {
+        let area = board.get_board_area();
+        board
+            .get_region(area)
+            .iter()
+            .filter(|cell| cell.is_alive())
+            .count() as u64
+    };
+
+    for position in [(0, 0), (1, 0), (2, 0), (2, -1), (1, -2)] {
+        board.set(position.into(), Cell::Alive);
+    }
+    board.set((0, 0).into(), Cell::Dead);
+    assert_eq!(board.population(), counted(&board));
+
+    board.tick_n(7);
+    assert_eq!(board.population(), counted(&board));
+
+    let (_, glider) = crate::persistence::presets::all().swap_remove(0);
+    board.load_blueprint((20, 20).into(), glider);
+    assert_eq!(board.population(), counted(&board));
+
+    board.tick();
+    assert_eq!(board.population(), counted(&board));
+}
+
+/// A glider loaded from a blueprint travels 25 cells diagonally over 100 headless generations.
+pub fn headless_glider<S: Simulator>() {
+    use crate::{persistence::SimulationBlueprint, HeadlessRunner};
+
+    let mut board = S::new(Default::default());
+    #[rustfmt::skip]
+    let glider = bitvec::bitvec![usize, bitvec::order::Lsb0;
+        0, 1, 0,
+        0, 0, 1,
+        1, 1, 1,
+    ];
+    board.load_blueprint((0, 0).into(), SimulationBlueprint::new(2, 2, glider));
+    let initial = board.save_board();
+
+    let result = HeadlessRunner::new(100)
+        .track_population(true)
+        .run(&mut board);
+
+    let population = result.population.expect("Population is tracked");
+    assert_eq!(population.len(), 100);
+    assert!(population.iter().all(|population| *population == 5));
+
+    let save = result.save;
+    assert_eq!(save.get_generation(), 100);
+    assert_eq!(save.get_board_area(), Area::new((25, 25), (27, 27)));
+    assert_eq!(save.get_board_data(), initial.get_board_data());
+}
+
+/// Running headless without tracking returns only the final board.
+pub fn headless_without_tracking<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    for x in -1..=1 {
+        board.set((x, 0).into(), Cell::Alive);
+    }
+
+    let result = crate::HeadlessRunner::new(3).run(&mut board);
+    assert!(result.population.is_none());
+
+    // A blinker is vertical after an odd amount of generations.
+    let save = crate::run_headless(&mut board, 0);
+    assert_eq!(save.get_board_area(), Area::new((0, -1), (0, 1)));
+    assert_eq!(board.population(), 3);
+}
+
+/// The cell limit is only exceeded by a population larger than it.
+pub fn cell_limit<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    for x in 0..5 {
+        board.set((x, 0).into(), Cell::Alive);
+    }
+    assert_eq!(board.cell_limit_exceeded(), None);
+
+    board.set_cell_limit(Some(5));
+    assert_eq!(board.cell_limit_exceeded(), None);
+
+    board.set_cell_limit(Some(4));
+    assert_eq!(board.cell_limit_exceeded(), Some(5));
+
+    board.set_cell_limit(None);
+    assert_eq!(board.cell_limit_exceeded(), None);
+}
+
+/// The display is still updated after another thread panicked while holding it.
+pub fn update_display_poisoned<S: Simulator>() {
+    let display = SharedDisplay::default();
+    let mut board = S::new(display.clone());
+    board.set_display_area(Area::new((0, 0), (5, 5)));
+    board.set((1, 1).into(), Cell::Alive);
+
+    let poisoner = display.clone();
+    std::thread::spawn(move || {
+        let _guard = poisoner.lock().unwrap();
+        panic!("Poison the display");
+    })
+    .join()
+    .expect_err("Thread panics");
+    assert!(display.is_poisoned());
+
+    board.update_display();
+
+    assert!(!display.is_poisoned());
+    let board_display = display.lock().unwrap().take().expect("Display is updated");
+    assert_eq!(board_display.get_cell((1, 1)), Cell::Alive);
+}
+
+/// A blueprint with less data than its size is rejected without modifying the board.
+pub fn load_blueprint_wrong_length<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    board.set((1, 1).into(), Cell::Alive);
+
+    // A 3x3 blueprint with data for only 4 cells.
+    let blueprint = SimulationBlueprint::new(2, 2, bitvec::bitbox![0; 4]);
+    assert_eq!(
+        board.load_blueprint((0, 0).into(), blueprint),
+        LoadStatus::Fail {
+            reason: LoadError::BlueprintDataLength {
+                expected: 9,
+                found: 4
+            }
+        }
+    );
+    assert_eq!(board.get((1, 1).into()), Cell::Alive);
+}
+
+/// Blueprints contain a cell for every position within the saved area, including its maximum row & column.
+pub fn save_blueprint_sizes<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    board.randomize(Area::new((-20, -20), (20, 20)), 0.5, 0);
+
+    for area in [
+        Area::new((0, 0), (0, 0)),
+        Area::new((0, 0), (1, 0)),
+        Area::new((0, 0), (0, 1)),
+        Area::new((-3, -2), (4, 5)),
+        Area::new((-20, -20), (20, 20)),
+    ] {
+        let blueprint = board.save_blueprint(area);
+        assert_eq!(
+            blueprint.get_blueprint_data().len() as u64,
+            area.cell_count()
+        );
+        assert_eq!(blueprint.get_area().cell_count(), area.cell_count());
+        assert_eq!(blueprint.validate(), Ok(()));
+    }
+}
+
+/// An empty board has no density.
+pub fn density_empty<S: Simulator>() {
+    let board = S::new(Default::default());
+    assert_eq!(board.density(), 0.0);
+}
+
+/// A single cell fills its own board area.
+pub fn density_single<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    board.set((-7, 3).into(), Cell::Alive);
+    assert_eq!(board.density(), 1.0);
+}
+
+/// Filled rectangles have a density of 1, which falls as cells within them die.
+pub fn density_rectangles<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    for position in Area::new((0, 0), (4, 1)).iterate_over() {
+        board.set(position, Cell::Alive);
+    }
+    assert_eq!(board.density(), 1.0);
+
+    // The corners keep the board area the same.
+    board.set((1, 0).into(), Cell::Dead);
+    assert_eq!(board.density(), 0.9);
+}
+
+/// A board with more data than its area is rejected without modifying the board.
+pub fn load_board_wrong_length<S: Simulator>() {
+    let mut board = S::new(Default::default());
+    board.set((10, 10).into(), Cell::Alive);
+
+    let save = SimulationSave::new(4, Area::new((0, 0), (1, 0)), bitvec::bitbox![1; 3]);
+    assert_eq!(
+        board.load_board(save),
+        LoadStatus::Fail {
+            reason: LoadError::BoardDataLength {
+                expected: 2,
+                found: 3
+            }
+        }
+    );
+    assert_eq!(board.get((10, 10).into()), Cell::Alive);
+    assert_eq!(board.get_generation(), 0);
+}
+
+/// A display area too large to allocate is displayed without any cells.
+pub fn update_display_too_large<S: Simulator>() {
+    let display = SharedDisplay::default();
+    let mut board = S::new(display.clone());
+    let area = Area::new(
+        (-1_000_000_000, -1_000_000_000),
+        (1_000_000_000, 1_000_000_000),
+    );
+    board.set_display_area(area);
+    board.set((0, 0).into(), Cell::Alive);
+
+    board.update_display();
+
+    let board_display = display.lock().unwrap().take().expect("Display is updated");
+    assert_eq!(board_display.get_area(), area);
+    assert_eq!(board_display.iter_cells().count(), 0);
+}
+
+/// Randomising replaces the cells in the area, & the same seed generates the same cells.
+pub fn randomize_seeded<S: Simulator>() {
+    let area = Area::new((-5, -5), (5, 5));
+
+    let mut first = S::new(Default::default());
+    for position in area.iterate_over() {
+        first.set(position, Cell::Alive);
+    }
+    first.randomize(area, 0.3, 7);
+
+    let mut second = S::new(Default::default());
+    second.randomize(area, 0.3, 7);
+
+    assert_eq!(alive(&first), alive(&second));
+    assert!(first.population() < area.cell_count());
+}
+
+/// Mirroring an asymmetric pattern twice returns it to the original, without moving its bounding box.
+pub fn mirror_twice<S: Simulator>() {
+    // An R-pentomino with an even width & odd height.
+    let cells = [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2), (3, 2)].map(GlobalPosition::from);
+    let mut board = S::new(Default::default());
+    for position in cells {
+        board.set(position, Cell::Alive);
+    }
+    let area = board.get_board_area();
+
+    for axis in crate::Axis::ALL {
+        board.mirror(axis);
+        assert_eq!(board.get_board_area(), area, "{axis:?}");
+        assert_ne!(alive(&board), HashSet::from(cells), "{axis:?}");
+
+        board.mirror(axis);
+        assert_eq!(alive(&board), HashSet::from(cells), "{axis:?}");
+    }
+
+    board.mirror(crate::Axis::Vertical);
+    assert!(board.get((2, 0).into()).is_alive());
+    assert!(board.get((3, 1).into()).is_alive());
+    assert!(board.get((0, 2).into()).is_alive());
+    assert_eq!(board.population(), 6);
+}
+
+/// Rotating a glider four times returns it to the start, & two quarter turns match a half turn.
+pub fn rotate_glider<S: Simulator>() {
+    let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].map(GlobalPosition::from);
+    let mut board = S::new(Default::default());
+    for position in glider {
+        board.set(position, Cell::Alive);
+    }
+
+    board.rotate(1).unwrap();
+    // The glider now travels down & to the left.
+    let turned = [(0, 0), (0, 1), (2, 1), (0, 2), (1, 2)].map(GlobalPosition::from);
+    assert_eq!(alive(&board), HashSet::from(turned));
+
+    board.rotate(1).unwrap();
+    let mut half_turn = S::new(Default::default());
+    for position in glider {
+        half_turn.set(position, Cell::Alive);
+    }
+    half_turn.rotate(2).unwrap();
+    assert_eq!(alive(&board), alive(&half_turn));
+
+    board.rotate(1).unwrap();
+    board.rotate(1).unwrap();
+    assert_eq!(alive(&board), HashSet::from(glider));
+}
+
+/// A translation is found between the same pattern at different positions, & not between different patterns.
+pub fn translation_helper<S: Simulator>() {
+    let board = load::<S>(GLIDER);
+    let mut moved = S::new(Default::default());
+    moved.load_blueprint((-5, 3).into(), parse_rle(GLIDER).unwrap());
+
+    assert_eq!(translation(&board, &board), Some((0, 0)));
+    assert_eq!(translation(&board, &moved), Some((-5, 3)));
+    assert_eq!(translation(&board, &load::<S>(BLINKER)), None);
+    assert_eq!(translation(&load::<S>(BEACON), &load::<S>(TOAD)), None);
+}
+
+/// A blinker returns to its original state every 2 generations.
+pub fn blinker<S: Simulator>() {
+    assert_period::<S>(BLINKER, 2, (0, 0));
+}
+
+/// A toad returns to its original state every 2 generations.
+pub fn toad<S: Simulator>() {
+    assert_period::<S>(TOAD, 2, (0, 0));
+}
+
+/// A beacon returns to its original state every 2 generations.
+pub fn beacon<S: Simulator>() {
+    assert_period::<S>(BEACON, 2, (0, 0));
+}
+
+/// A pulsar returns to its original state every 3 generations.
+pub fn pulsar<S: Simulator>() {
+    assert_period::<S>(PULSAR, 3, (0, 0));
+}
+
+/// A glider moves one cell diagonally every period.
+pub fn glider<S: Simulator>() {
+    assert_period::<S>(GLIDER, 4, (1, 1));
+}
+
+/// A growing pattern stops the simulation once it exceeds the cell limit.
+pub fn simulator_stops_at_cell_limit<S: Simulator + 'static>() {
+    // The R-pentomino grows for over a thousand generations.
+    let harness = spawn(with_cells::<S>([(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]));
+    harness.send(UiPacket::CellLimit { limit: Some(10) });
+    harness.send(UiPacket::Start);
+
+    let (generation, population) = harness.expect_packet(|packet| match packet {
+        SimulatorPacket::CellLimitReached {
+            generation,
+            population,
+        } => Some((generation, population)),
+        _ => None,
+    });
+    assert!(population > 10);
+
+    // The simulation must have stopped on the generation the limit was reached.
+    assert_eq!(harness.save().get_generation(), generation);
+
+    harness.terminate();
+}
+
+/// Progress is reported whilst running towards a target, ending with the target being reached.
+pub fn simulator_progress<S: Simulator + 'static>() {
+    let harness = spawn(with_cells::<S>([(-1, 0), (0, 0), (1, 0)]));
+    // The run takes roughly half a second, so progress is reported several times.
+    harness.send(UiPacket::SimulationSpeed {
+        speed: SimulationSpeed::new(1000),
+    });
+    harness.send(UiPacket::StartUntil { generation: 500 });
+
+    let mut progress = Vec::new();
+    harness.expect_packet(|packet| match packet {
+        SimulatorPacket::Progress { current, target } => {
+            assert_eq!(target, 500);
+            progress.push(current);
+            (current >= target).then_some(())
+        }
+        _ => None,
+    });
+    assert!(progress.len() > 1, "{progress:?}");
+    assert_eq!(progress.last(), Some(&500));
+    assert!(progress.is_sorted());
+
+    // The simulation stops at the target.
+    assert_eq!(harness.save().get_generation(), 500);
+
+    harness.terminate();
+}
+
+/// Stopping discards the target generation, so starting again runs past it.
+pub fn simulator_stop_clears_target<S: Simulator + 'static>() {
+    // The packets are sent before the simulator starts, so no generations pass between them.
+    let harness = spawn_with(
+        with_cells::<S>([(-1, 0), (0, 0), (1, 0)]),
+        [
+            UiPacket::SimulationSpeed {
+                speed: SimulationSpeed::new(200),
+            },
+            UiPacket::StartUntil { generation: 10 },
+            UiPacket::Stop,
+            UiPacket::Start,
+        ],
+    );
+    std::thread::sleep(Duration::from_millis(500));
+
+    let save = harness.save();
+    assert!(save.get_generation() > 10, "{}", save.get_generation());
+
+    harness.terminate();
+}
+
+/// Stepping a stopped simulation advances it by exactly one generation.
+pub fn simulator_step<S: Simulator + 'static>() {
+    let harness = spawn(with_cells::<S>([(-1, 0), (0, 0), (1, 0)]));
+    harness.send(UiPacket::Step { count: 1 });
+    // Give the simulator time to tick more than once, should it be incorrectly running.
+    std::thread::sleep(Duration::from_millis(300));
+
+    let save = harness.save();
+    assert_eq!(save.get_generation(), 1);
+    // The blinker is vertical after one step.
+    assert_eq!(save.get_board_area(), Area::new((0, -1), (0, 1)));
+
+    harness.terminate();
+}
+
+/// A step too large to finish can be stopped, as the simulator still receives packets whilst stepping.
+pub fn simulator_step_stopped<S: Simulator + 'static>() {
+    let harness = spawn(with_cells::<S>([(-1, 0), (0, 0), (1, 0)]));
+    harness.send(UiPacket::Step { count: u64::MAX });
+    std::thread::sleep(Duration::from_millis(100));
+    harness.send(UiPacket::Stop);
+
+    let save = harness.save();
+    assert!(save.get_generation() > 0);
+
+    // The remaining generations were discarded.
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(harness.save().get_generation(), save.get_generation());
+
+    harness.terminate();
+}
+
+/// A step stops once the board exceeds the cell limit.
+pub fn simulator_step_cell_limit<S: Simulator + 'static>() {
+    // The R-pentomino grows for over a thousand generations.
+    let harness = spawn(with_cells::<S>([(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]));
+    harness.send(UiPacket::CellLimit { limit: Some(10) });
+    harness.send(UiPacket::Step { count: u64::MAX });
+
+    let generation = harness.expect_packet(|packet| match packet {
+        SimulatorPacket::CellLimitReached { generation, .. } => Some(generation),
+        _ => None,
+    });
+    assert!(generation <= 100, "Stopped within the first chunk");
+
+    harness.terminate();
+}
+
+/// Invalid boards & blueprints are reported as failing to load, leaving the board unchanged.
+pub fn simulator_load_result<S: Simulator + 'static>() {
+    let harness = spawn(with_cells::<S>([(0, 0)]));
+
+    // Both contain data for 5 cells in a 2x2 area.
+    harness.send(UiPacket::LoadBoard {
+        board: SimulationSave::new(0, Area::new((0, 0), (1, 1)), bitvec::bitbox![1; 5]),
+    });
+    harness.send(UiPacket::LoadBlueprint {
+        load_position: (5, 5).into(),
+        blueprint: SimulationBlueprint::new(1, 1, bitvec::bitbox![1; 5]),
+    });
+    harness.send(UiPacket::SaveBoard);
+
+    assert!(matches!(
+        harness.receive(),
+        SimulatorPacket::BoardLoadResult {
+            status: LoadStatus::Fail { .. }
+        }
+    ));
+    assert!(matches!(
+        harness.receive(),
+        SimulatorPacket::BlueprintLoadResult {
+            status: LoadStatus::Fail { .. }
+        }
+    ));
+    let SimulatorPacket::BoardSave { board: save } = harness.receive() else {
+        panic!("Board must be saved");
+    };
+    assert_eq!(save.get_board_area(), Area::default());
+    assert!(save.get_board_data()[0]);
+
+    harness.terminate();
+}
+
+/// Every cell in a set many packet is applied before the display is updated.
+pub fn simulator_set_many<S: Simulator + 'static>() {
+    let cells: Vec<(GlobalPosition, Cell)> = (0..5)
+        .map(|x| ((x, x).into(), Cell::Alive))
+        .chain([((2, 2).into(), Cell::Dead)])
+        .collect();
+
+    let display = SharedDisplay::default();
+    let harness = spawn_with(
+        S::new(display.clone()),
+        [
+            UiPacket::DisplayArea {
+                new_area: Area::new((0, 0), (10, 10)),
+            },
+            UiPacket::SetMany {
+                cells: cells.into(),
+            },
+        ],
+    );
+
+    let board_display = (0..50)
+        .find_map(|_| {
+            std::thread::sleep(Duration::from_millis(100));
+            display.lock().unwrap().take()
+        })
+        .expect("Display must be updated");
+    let alive: HashSet<GlobalPosition> = board_display.alive_positions().collect();
+    assert_eq!(
+        alive,
+        HashSet::from([(0, 0).into(), (1, 1).into(), (3, 3).into(), (4, 4).into()])
+    );
+
+    // No further updates are made, as all the cells were set at once.
+    std::thread::sleep(Duration::from_millis(300));
+    assert!(display.lock().unwrap().is_none());
+
+    harness.terminate();
+}
+
+/// A blinker running on the simulator thread is reported as having a period of 2.
+pub fn simulator_detects_blinker_period<S: Simulator + 'static>() {
+    let harness = spawn(with_cells::<S>([(0, -1), (0, 0), (0, 1)]));
+    harness.send(UiPacket::Start);
+
+    let period = harness.expect_packet(|packet| match packet {
+        SimulatorPacket::PeriodDetected { period, .. } => Some(period),
+        _ => None,
+    });
+    harness.terminate();
+
+    assert_eq!(period, 2);
+}
+
+/// A glider running on the simulator thread is reported as a spaceship moving diagonally every 4 generations.
+pub fn simulator_detects_glider<S: Simulator + 'static>() {
+    let harness = spawn(with_cells::<S>([(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]));
+    harness.send(UiPacket::Start);
+
+    let spaceship = harness.expect_packet(|packet| match packet {
+        SimulatorPacket::SpaceshipDetected { period, dx, dy, .. } => Some((period, dx, dy)),
+        SimulatorPacket::PeriodDetected { period, .. } => {
+            panic!("A glider is not periodic in place, but found period {period}")
+        }
+        _ => None,
+    });
+    harness.terminate();
+
+    assert_eq!(spaceship, (4, 1, 1));
+}
+
+/// The simulator thread reports the tick rate it achieves whilst running.
+pub fn simulator_reports_tick_rate<S: Simulator + 'static>() {
+    let harness = spawn(S::new(Default::default()));
+    harness.send(UiPacket::SimulationSpeed {
+        speed: SimulationSpeed::new(50),
+    });
+    harness.send(UiPacket::Start);
+
+    let (target, achieved) = harness.expect_packet(|packet| match packet {
+        SimulatorPacket::TickRate { target, achieved } => Some((target, achieved)),
+        _ => None,
+    });
+    harness.terminate();
+
+    assert_eq!(target, Some(50));
+    assert!(
+        achieved > 0.0 && achieved <= 60.0,
+        "Achieved {achieved} tps"
+    );
+}
+
+/// A clear packet kills every cell on the board.
+pub fn simulator_clear<S: Simulator + 'static>() {
+    let cells: Vec<(GlobalPosition, Cell)> =
+        (0..5).map(|x| ((x * 10, x).into(), Cell::Alive)).collect();
+    let harness = spawn_with(
+        S::new(Default::default()),
+        [
+            UiPacket::SetMany {
+                cells: cells.into(),
+            },
+            UiPacket::Clear,
+            UiPacket::RequestBoardArea,
+        ],
+    );
+
+    let packet = harness.receive();
+    assert!(
+        matches!(packet, SimulatorPacket::BoardArea { area: None }),
+        "{packet:?}"
+    );
+
+    harness.terminate();
+}
+
+/// The final state of the board is sent when the simulator terminates.
+pub fn simulator_final_state<S: Simulator + 'static>() {
+    let harness = spawn(with_cells::<S>([(-1, 0), (0, 0), (1, 0)]));
+    harness.send(UiPacket::StartUntil { generation: 1 });
+    harness.expect_packet(|packet| {
+        matches!(packet, SimulatorPacket::Progress { current: 1, .. }).then_some(())
+    });
+
+    let packets = harness.terminate();
+    // Nothing is sent after the final state.
+    let Some(SimulatorPacket::FinalState { board: save }) = packets.last() else {
+        panic!("{packets:?}");
+    };
+
+    // The blinker has turned vertical.
+    assert_eq!(save.get_generation(), 1);
+    let alive: Vec<GlobalPosition> = save
+        .get_board_area()
+        .iterate_over()
+        .zip(save.get_board_data().iter().by_vals())
+        .filter_map(|(position, alive)| alive.then_some(position))
+        .collect();
+    assert_eq!(alive, [(0, -1).into(), (0, 0).into(), (0, 1).into()]);
+}
+
+/// Generations kept by the history can be returned to, whilst older generations report the earliest one kept.
+pub fn simulator_goto_generation<S: Simulator + 'static>() {
+    // A horizontal blinker.
+    let harness = spawn(with_cells::<S>([(-1, 0), (0, 0), (1, 0)]));
+    let restore = |generation| {
+        harness.send(UiPacket::GotoGeneration { generation });
+        harness.send(UiPacket::SaveBoard);
+        harness.expect_packet(|packet| match packet {
+            SimulatorPacket::BoardSave { board } => Some(board),
+            SimulatorPacket::GenerationUnavailable { .. } => panic!("Generation is kept"),
+            _ => None,
+        })
+    };
+
+    harness.send(UiPacket::GenerationHistory { capacity: 10 });
+    harness.send(UiPacket::StartUntil { generation: 3 });
+    harness.expect_packet(|packet| {
+        matches!(packet, SimulatorPacket::Progress { current: 3, .. }).then_some(())
+    });
+
+    // The blinker is vertical on odd generations.
+    let restored = restore(1);
+    assert_eq!(restored.get_generation(), 1);
+    assert_eq!(restored.get_board_area(), Area::new((0, -1), (0, 1)));
+    // Later generations are kept whilst the simulation has not advanced.
+    assert_eq!(restore(3).get_generation(), 3);
+
+    harness.send(UiPacket::GenerationHistory { capacity: 2 });
+    harness.send(UiPacket::GotoGeneration { generation: 0 });
+    let unavailable = harness.expect_packet(|packet| match packet {
+        SimulatorPacket::GenerationUnavailable {
+            requested,
+            earliest,
+        } => Some((requested, earliest)),
+        _ => None,
+    });
+    assert_eq!(unavailable, (0, 2));
+
+    harness.terminate();
+}
+
+/// Loading a board discards the generations kept of the previous board.
+pub fn simulator_goto_generation_after_load<S: Simulator + 'static>() {
+    // A horizontal blinker.
+    let harness = spawn(with_cells::<S>([(-1, 0), (0, 0), (1, 0)]));
+    harness.send(UiPacket::GenerationHistory { capacity: 10 });
+    harness.send(UiPacket::StartUntil { generation: 3 });
+    harness.expect_packet(|packet| {
+        matches!(packet, SimulatorPacket::Progress { current: 3, .. }).then_some(())
+    });
+
+    // A block on generation 0.
+    let block = with_cells::<S>([(0, 0), (1, 0), (0, 1), (1, 1)]);
+    harness.send(UiPacket::LoadBoard {
+        board: block.save_board(),
+    });
+    harness.send(UiPacket::GotoGeneration { generation: 1 });
+    let unavailable = harness.expect_packet(|packet| match packet {
+        SimulatorPacket::GenerationUnavailable {
+            requested,
+            earliest,
+        } => Some((requested, earliest)),
+        SimulatorPacket::BoardSave { .. } => panic!("The previous board is not kept"),
+        _ => None,
+    });
+    assert_eq!(unavailable, (1, 0));
+
+    // The loaded board is kept.
+    assert_eq!(harness.save().get_board_area(), Area::new((0, 0), (1, 1)));
+
+    harness.terminate();
+}
+
+/// The callback is called with the statistics of every generation.
+pub fn simulator_callback<S: Simulator + 'static>() {
+    // A blinker always has 3 alive cells.
+    let (info_sender, info_receiver) = mpsc::channel();
+    let harness = spawn_with_callback(with_cells::<S>([(-1, 0), (0, 0), (1, 0)]), move |info| {
+        info_sender.send(*info).unwrap()
+    });
+    harness.send(UiPacket::StartUntil { generation: 5 });
+
+    let infos: Vec<TickInfo> = (0..5)
+        .map(|_| {
+            info_receiver
+                .recv_timeout(PACKET_TIMEOUT)
+                .expect("Callback must be called")
+        })
+        .collect();
+    harness.terminate();
+
+    assert_eq!(
+        infos.iter().map(|info| info.generation).collect::<Vec<_>>(),
+        [1, 2, 3, 4, 5]
+    );
+    assert!(infos.iter().all(|info| info.population == 3));
+    assert!(infos.iter().all(|info| info.is_running));
+}
+
+/// The simulation stops once the stop condition is met.
+pub fn simulator_stop_condition<S: Simulator + 'static>() {
+    // A domino dies of under population after one generation.
+    let harness = spawn(with_cells::<S>([(0, 0), (1, 0)]));
+    harness.send(UiPacket::SetStopCondition {
+        condition: Some(StopCondition::new(|info| info.population == 0)),
+    });
+    harness.send(UiPacket::Start);
+
+    let (reason, generation) = harness.expect_packet(|packet| match packet {
+        SimulatorPacket::Stopped { reason, generation } => Some((reason, generation)),
+        _ => None,
+    });
+    assert_eq!(reason, StopReason::Condition);
+    assert_eq!(generation, 1);
+
+    // The simulation is no longer running.
+    assert_eq!(harness.save().get_generation(), 1);
+
+    harness.terminate();
+}
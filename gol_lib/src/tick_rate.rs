@@ -0,0 +1,215 @@
+//! Contains [`TickRateMeter`] & [`TickBudget`].
+//! See their documentation for more information.
+
+use std::time::{Duration, Instant};
+
+/// How much each new measurement affects the average, from 0 to 1.
+/// Higher values respond to changes faster, but fluctuate more.
+const SMOOTHING: f32 = 0.1;
+/// How often the achieved tick rate is reported.
+pub(crate) const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The longest a tick can take whilst the speed is automatic before the simulation is slowed, which is roughly one
+/// frame of the ui at 60 frames per second.
+pub(crate) const TICK_BUDGET: Duration = Duration::from_millis(16);
+
+/// Chooses the speed of the simulation whilst its speed is automatic.
+///
+/// If ticks take longer than [`TICK_BUDGET`], the simulation is slowed so that it spends as long waiting as it does
+/// ticking. This leaves time for the ui to lock the display & repaint, which it otherwise struggles to do when the
+/// simulation holds the display for most of every frame. The limit is smoothed the same way as [`TickRateMeter`].
+#[derive(Default)]
+pub(crate) struct TickBudget {
+    /// The ticks per second the simulation is limited to, or [`None`] if it runs as fast as possible.
+    limit: Option<f32>,
+}
+
+impl TickBudget {
+    /// Records how long a tick took, returning the ticks per second the simulation is now limited to, or [`None`] if it
+    /// can run as fast as possible.
+    pub(crate) fn record(&mut self, duration: Duration) -> Option<f32> {
+        // Waiting as long as the tick took halves the rate the board could be ticked at.
+        let rate = (duration > TICK_BUDGET).then(|| 1.0 / (2.0 * duration.as_secs_f32()));
+
+        self.limit = match (self.limit, rate) {
+            (Some(average), Some(rate)) => Some(average + SMOOTHING * (rate - average)),
+            (_, rate) => rate,
+        };
+        self.limit
+    }
+
+    /// The ticks per second the simulation is limited to, or [`None`] if it is not limited.
+    pub(crate) fn limit(&self) -> Option<f32> {
+        self.limit
+    }
+
+    /// The time to wait between ticks, or [`None`] if the simulation is not limited.
+    pub(crate) fn period(&self) -> Option<Duration> {
+        self.limit.map(|limit| Duration::from_secs_f32(1.0 / limit))
+    }
+}
+
+/// Measures the ticks per second the simulation actually achieves.
+///
+/// The time between consecutive ticks is converted into a tick rate, which is smoothed with an exponential moving
+/// average so that a single slow tick does not cause the reported rate to jump.
+pub(crate) struct TickRateMeter {
+    /// The time of the previous tick, or [`None`] if the simulation has been paused since.
+    last_tick: Option<Instant>,
+    /// The smoothed ticks per second.
+    achieved: Option<f32>,
+    /// The time the achieved rate was last reported.
+    last_report: Instant,
+}
+
+impl TickRateMeter {
+    /// Creates a new [`TickRateMeter`] with no measurements.
+    pub(crate) fn new(now: Instant) -> Self {
+        Self {
+            last_tick: None,
+            achieved: None,
+            last_report: now,
+        }
+    }
+
+    /// Records that a tick occurred at the given time.
+    pub(crate) fn tick(&mut self, now: Instant) {
+        if let Some(last_tick) = self.last_tick {
+            let elapsed = now.saturating_duration_since(last_tick).as_secs_f32();
+
+            if elapsed > 0.0 {
+                let rate = 1.0 / elapsed;
+                self.achieved = Some(match self.achieved {
+                    Some(average) => average + SMOOTHING * (rate - average),
+                    None => rate,
+                });
+            }
+        }
+
+        self.last_tick = Some(now);
+    }
+
+    /// Records that the simulation has stopped, so the time spent stopped is not counted as a slow tick.
+    pub(crate) fn pause(&mut self) {
+        self.last_tick = None;
+    }
+
+    /// Returns the smoothed ticks per second if [`REPORT_INTERVAL`] has passed since it was last reported.
+    pub(crate) fn report(&mut self, now: Instant) -> Option<f32> {
+        if now.saturating_duration_since(self.last_report) < REPORT_INTERVAL {
+            return None;
+        }
+
+        let achieved = self.achieved?;
+        self.last_report = now;
+        Some(achieved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// No rate is available until two ticks have occurred.
+    fn no_rate_without_ticks() {
+        let start = Instant::now();
+        let mut meter = TickRateMeter::new(start);
+
+        meter.tick(start);
+        assert_eq!(meter.report(start + REPORT_INTERVAL), None);
+    }
+
+    #[test]
+    /// The achieved rate is populated after several ticks.
+    fn rate_after_ticks() {
+        let start = Instant::now();
+        let mut meter = TickRateMeter::new(start);
+
+        let mut now = start;
+        for _ in 0..10 {
+            meter.tick(now);
+            now += Duration::from_millis(10);
+        }
+
+        let achieved = meter
+            .report(start + REPORT_INTERVAL)
+            .expect("Rate is populated");
+        assert!((achieved - 100.0).abs() < 1.0, "Achieved {achieved} tps");
+    }
+
+    #[test]
+    /// The rate is only reported once per interval.
+    fn report_interval() {
+        let start = Instant::now();
+        let mut meter = TickRateMeter::new(start);
+
+        meter.tick(start);
+        meter.tick(start + Duration::from_millis(10));
+
+        assert_eq!(meter.report(start + REPORT_INTERVAL / 2), None);
+        assert!(meter.report(start + REPORT_INTERVAL).is_some());
+        assert_eq!(meter.report(start + REPORT_INTERVAL), None);
+    }
+
+    #[test]
+    /// A single slow tick only moves the average part of the way.
+    fn smoothed() {
+        let start = Instant::now();
+        let mut meter = TickRateMeter::new(start);
+
+        meter.tick(start);
+        meter.tick(start + Duration::from_millis(10));
+        // Rate drops from 100 to 10 tps for one tick.
+        meter.tick(start + Duration::from_millis(110));
+
+        let achieved = meter
+            .report(start + REPORT_INTERVAL)
+            .expect("Rate is populated");
+        assert!((achieved - 91.0).abs() < 1.0, "Achieved {achieved} tps");
+    }
+
+    #[test]
+    /// Time spent paused is not measured.
+    fn pause_ignores_gap() {
+        let start = Instant::now();
+        let mut meter = TickRateMeter::new(start);
+
+        meter.tick(start);
+        meter.tick(start + Duration::from_millis(10));
+        meter.pause();
+        meter.tick(start + Duration::from_secs(60));
+        meter.tick(start + Duration::from_secs(60) + Duration::from_millis(10));
+
+        let achieved = meter
+            .report(start + Duration::from_secs(61))
+            .expect("Rate is populated");
+        assert!((achieved - 100.0).abs() < 1.0, "Achieved {achieved} tps");
+    }
+
+    #[test]
+    /// Ticks within the budget don't limit the simulation.
+    fn budget_fast_ticks() {
+        let mut budget = TickBudget::default();
+
+        assert_eq!(budget.record(TICK_BUDGET), None);
+        assert_eq!(budget.period(), None);
+    }
+
+    #[test]
+    /// Slow ticks limit the simulation to wait as long as each tick takes, until ticks are fast again.
+    fn budget_slow_ticks() {
+        let mut budget = TickBudget::default();
+
+        let limit = budget.record(Duration::from_millis(50)).expect("Limited");
+        assert!((limit - 10.0).abs() < 0.01, "Limited to {limit} tps");
+        let period = budget.period().expect("Limited");
+        assert!(period.abs_diff(Duration::from_millis(100)) < Duration::from_micros(10));
+
+        // The limit is smoothed between slow ticks.
+        let limit = budget.record(Duration::from_millis(100)).expect("Limited");
+        assert!((limit - 9.5).abs() < 0.01, "Limited to {limit} tps");
+
+        assert_eq!(budget.record(Duration::from_millis(1)), None);
+    }
+}
@@ -0,0 +1,195 @@
+//! Contains [`Axis`], [`rotate`] & [`RotateError`].
+//! See their documentation for more information.
+
+use crate::{Area, GlobalPosition};
+
+/// The axis the board is mirrored across.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Axis {
+    /// A vertical line through the centre of the board, swapping the left & right of the board.
+    Vertical,
+    /// A horizontal line through the centre of the board, swapping the top & bottom of the board.
+    Horizontal,
+}
+
+impl Axis {
+    /// Both axes.
+    pub const ALL: [Axis; 2] = [Axis::Vertical, Axis::Horizontal];
+
+    /// Mirrors the position across the axis through the centre of the area.
+    ///
+    /// The pivot is exactly halfway between the minimum & maximum of the area, so the minimum is swapped with the
+    /// maximum. This maps the area onto itself whether its width is even or odd, so mirroring twice returns the
+    /// original position.
+    pub fn mirror(self, area: Area, position: GlobalPosition) -> GlobalPosition {
+        let (min, max) = (area.get_min(), area.get_max());
+        // Summed as i64, as the sum of two coordinates can exceed the range of i32.
+        let reflect =
+            |min: i32, max: i32, value: i32| (min as i64 + max as i64 - value as i64) as i32;
+
+        match self {
+            Axis::Vertical => GlobalPosition::new(
+                reflect(min.get_x(), max.get_x(), position.get_x()),
+                position.get_y(),
+            ),
+            Axis::Horizontal => GlobalPosition::new(
+                position.get_x(),
+                reflect(min.get_y(), max.get_y(), position.get_y()),
+            ),
+        }
+    }
+}
+
+/// Rotating would move a cell beyond the range of coordinates.
+#[derive(thiserror::Error, Clone, Copy, Debug, PartialEq, Eq)]
+#[error("Rotating would move cells beyond the edge of the board.")]
+pub struct RotateError;
+
+/// Rotates the position clockwise, as displayed, by the given amount of quarter turns about the centre of the area.
+///
+/// The area is turned in place, with its minimum & maximum being the corners of the rotated area. If the width &
+/// height of the area differ by an odd amount, the centre of the rotated area can't match the centre of the area
+/// exactly, so the rotated area is offset by half the difference rounded towards zero. As this rounds the same way
+/// in both directions, two quarter turns give exactly the same result as one half turn, & four give the original
+/// position.
+///
+/// Returns an error if the rotated position is outside the range of coordinates. Only areas that are not square can
+/// be rotated beyond the range, & only if they are near its edge.
+pub fn rotate(
+    area: Area,
+    quarter_turns: u8,
+    position: GlobalPosition,
+) -> Result<GlobalPosition, RotateError> {
+    let (min, max) = (area.get_min(), area.get_max());
+    let (min_x, min_y) = (min.get_x() as i64, min.get_y() as i64);
+    let (x_difference, y_difference) = (max.get_x() as i64 - min_x, max.get_y() as i64 - min_y);
+    // The position relative to the minimum of the area.
+    let (x, y) = (
+        position.get_x() as i64 - min_x,
+        position.get_y() as i64 - min_y,
+    );
+    // The minimum of the area after a quarter turn.
+    let (turned_x, turned_y) = (
+        min_x + (x_difference - y_difference) / 2,
+        min_y + (y_difference - x_difference) / 2,
+    );
+
+    let (x, y) = match quarter_turns % 4 {
+        0 => (min_x + x, min_y + y),
+        1 => (turned_x + y_difference - y, turned_y + x),
+        2 => (min_x + x_difference - x, min_y + y_difference - y),
+        _ => (turned_x + y, turned_y + x_difference - x),
+    };
+
+    let coordinate = |value: i64| i32::try_from(value).map_err(|_| RotateError);
+    Ok(GlobalPosition::new(coordinate(x)?, coordinate(y)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// The minimum & maximum of the area are swapped, for both even & odd widths.
+    fn mirror_swaps_edges() {
+        let odd = Area::new((-2, 0), (2, 4));
+        assert_eq!(Axis::Vertical.mirror(odd, (-2, 1).into()), (2, 1).into());
+        assert_eq!(Axis::Vertical.mirror(odd, (0, 1).into()), (0, 1).into());
+
+        let even = Area::new((1, -3), (4, 0));
+        assert_eq!(Axis::Horizontal.mirror(even, (2, -3).into()), (2, 0).into());
+        assert_eq!(
+            Axis::Horizontal.mirror(even, (2, -2).into()),
+            (2, -1).into()
+        );
+    }
+
+    #[test]
+    /// Mirroring doesn't overflow for areas spanning the whole range of coordinates.
+    fn mirror_extremes() {
+        let area = Area::new((i32::MIN, 0), (i32::MAX, 0));
+
+        assert_eq!(
+            Axis::Vertical.mirror(area, (i32::MIN, 0).into()),
+            (i32::MAX, 0).into()
+        );
+    }
+
+    /// The area containing all the given positions.
+    fn bounds(positions: &[GlobalPosition]) -> Area {
+        let (mut min, mut max) = (positions[0], positions[0]);
+        for position in positions {
+            min = GlobalPosition::new(
+                min.get_x().min(position.get_x()),
+                min.get_y().min(position.get_y()),
+            );
+            max = GlobalPosition::new(
+                max.get_x().max(position.get_x()),
+                max.get_y().max(position.get_y()),
+            );
+        }
+        Area::new(min, max)
+    }
+
+    /// Rotates every position about the area containing them.
+    fn rotate_all(positions: &[GlobalPosition], quarter_turns: u8) -> Vec<GlobalPosition> {
+        let area = bounds(positions);
+        let mut rotated: Vec<_> = positions
+            .iter()
+            .map(|position| rotate(area, quarter_turns, *position).unwrap())
+            .collect();
+        rotated.sort_by_key(|position| (position.get_y(), position.get_x()));
+        rotated
+    }
+
+    #[test]
+    /// A quarter turn moves the top left corner to the top right.
+    fn rotate_quarter() {
+        let area = Area::new((0, 0), (2, 1));
+
+        // The 3x2 area becomes a 2x3 area, offset by half the difference in size rounded towards 0.
+        assert_eq!(rotate(area, 1, (0, 0).into()), Ok((1, 0).into()));
+        assert_eq!(rotate(area, 1, (2, 0).into()), Ok((1, 2).into()));
+        assert_eq!(rotate(area, 1, (2, 1).into()), Ok((0, 2).into()));
+        assert_eq!(rotate(area, 3, (0, 0).into()), Ok((0, 2).into()));
+    }
+
+    #[test]
+    /// Positions turned beyond the range of coordinates are an error, rather than being moved onto its edge.
+    fn rotate_beyond_edge() {
+        // A wide area along the bottom edge becomes a tall area that extends below it.
+        let area = Area::new((0, i32::MAX - 1), (10, i32::MAX));
+
+        assert_eq!(
+            rotate(area, 1, (0, i32::MAX).into()),
+            Ok((4, i32::MAX - 5).into())
+        );
+        assert_eq!(rotate(area, 1, (10, i32::MAX).into()), Err(RotateError));
+        assert_eq!(
+            rotate(area, 2, (0, i32::MAX).into()),
+            Ok((10, i32::MAX - 1).into())
+        );
+    }
+
+    #[test]
+    /// Successive quarter turns about the new area match a single turn, for sizes with odd & even differences.
+    fn rotate_composes() {
+        let shapes: [&[(i32, i32)]; 3] = [
+            &[(0, 0), (1, 0), (1, 1), (3, 1)],
+            &[(-2, 5), (0, 3), (0, 4), (1, 6)],
+            &[(4, 4), (5, 5), (6, 4)],
+        ];
+
+        for shape in shapes {
+            let shape: Vec<GlobalPosition> =
+                shape.iter().map(|position| (*position).into()).collect();
+            let mut turned = shape.clone();
+            for quarter_turns in 1..=4 {
+                turned = rotate_all(&turned, 1);
+                assert_eq!(turned, rotate_all(&shape, quarter_turns), "{shape:?}");
+            }
+            assert_eq!(turned, rotate_all(&shape, 0), "{shape:?}");
+        }
+    }
+}
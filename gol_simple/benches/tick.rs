@@ -0,0 +1,30 @@
+//! Benchmarks of advancing a [`Board`] by a generation, over boards with differing amounts & arrangements of cells.
+//!
+//! Run with `cargo bench -p gol_simple`. Criterion compares each run against the previous one, so run the benchmarks
+//! before & after a change to measure its effect. A single benchmark can be run by passing its name as a filter, such
+//! as `cargo bench -p gol_simple -- glider_gun`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use gol_lib::Automaton;
+use gol_simple::{workload, Board};
+
+/// Measures a single tick of the board, starting from a clone of the given board each time.
+fn bench_tick(criterion: &mut Criterion, name: &str, board: impl Fn() -> Board) {
+    criterion.bench_function(name, |bencher| {
+        bencher.iter_batched(&board, |mut board| board.tick(), BatchSize::LargeInput)
+    });
+}
+
+fn tick(criterion: &mut Criterion) {
+    let dense = workload::random_field(256, 0.5, 0);
+    bench_tick(criterion, "dense_random_field", || dense.clone());
+
+    let sparse = workload::glider_field(16, 32).expect("The gliders load");
+    bench_tick(criterion, "sparse_glider_field", || sparse.clone());
+
+    let gun = workload::glider_gun(1000).expect("The gun loads");
+    bench_tick(criterion, "glider_gun", || gun.clone());
+}
+
+criterion_group!(benches, tick);
+criterion_main!(benches);
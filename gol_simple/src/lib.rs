@@ -6,109 +6,108 @@ use std::{
     ops::AddAssign,
 };
 
-use gol_lib::{Area, BoardDisplay, Cell, GlobalPosition, SharedDisplay, Simulator};
+use gol_lib::{
+    Area, Automaton, Axis, BoardDisplay, BoardHasher, Cell, CellChange, GlobalPosition,
+    RotateError, Rule, SharedDisplay, Simulator,
+};
+
+pub mod workload;
+
+/// Logged when the display is recovered after the ui panicked while holding it.
+const DISPLAY_POISONED: &str =
+    "The ui panicked while updating the display; recovering the display.";
+
+/// The default maximum amount of cells the display may contain.
+/// At one byte per cell, this limits the display to 16MiB.
+pub const DEFAULT_MAX_DISPLAY_CELLS: usize = 1 << 24;
+
+/// The cells that are counted as the neighbours of a cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Neighborhood {
+    /// The eight cells surrounding a cell, including diagonals.
+    #[default]
+    Moore,
+    /// The four cells orthogonally adjacent to a cell.
+    VonNeumann,
+}
+
+impl Neighborhood {
+    /// The offsets from a cell to each of its neighbours.
+    pub fn offsets(self) -> &'static [(i32, i32)] {
+        match self {
+            Neighborhood::Moore => &[
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (0, 1),
+                (0, -1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+            ],
+            Neighborhood::VonNeumann => &[(1, 0), (0, 1), (0, -1), (-1, 0)],
+        }
+    }
+}
 
 /// Represents a board that the cells inhabit.
+///
+/// A cloned board shares the display of the original, as the display is shared with the ui.
+#[derive(Clone)]
 pub struct Board {
     board: HashSet<GlobalPosition>,
+    /// The amount of generations each alive cell has survived for, whilst ages are displayed.
+    ages: HashMap<GlobalPosition, u32>,
     generation: u64,
+    /// The cells counted as neighbours when ticking.
+    neighborhood: Neighborhood,
+    /// The amount of neighbours that cause cells to be born & to survive.
+    rule: Rule,
 
     display: SharedDisplay,
     display_size_buf: Area,
+    /// The maximum amount of alive cells, or [`None`] for no limit.
+    cell_limit: Option<usize>,
+    /// The maximum amount of cells the display may contain.
+    max_display_cells: usize,
+    /// Whether the age of each cell is included in the display.
+    display_ages: bool,
+    /// Whether the cells born & the cells that died are recorded, & included in the display.
+    display_changes: bool,
+    /// The cells born during the last generation, whilst changes are recorded.
+    births: Vec<GlobalPosition>,
+    /// The cells that died during the last generation, whilst changes are recorded.
+    deaths: Vec<GlobalPosition>,
 }
 
-impl Simulator for Board {
+impl Automaton for Board {
     fn tick(&mut self) {
-        let mut neighbours = HashMap::new();
-        let mut to_die = HashSet::new();
-
-        for position in &self.board {
-            let position = *position;
-            let mut surrounding = 0u8;
-            surrounding += self.board.contains(&(position + (1, 1))) as u8;
-            surrounding += self.board.contains(&(position + (1, 0))) as u8;
-            surrounding += self.board.contains(&(position + (1, -1))) as u8;
-
-            surrounding += self.board.contains(&(position + (0, 1))) as u8;
-            surrounding += self.board.contains(&(position + (0, -1))) as u8;
-
-            surrounding += self.board.contains(&(position + (-1, 1))) as u8;
-            surrounding += self.board.contains(&(position + (-1, 0))) as u8;
-            surrounding += self.board.contains(&(position + (-1, -1))) as u8;
-
-            if surrounding == 0 {
-                to_die.insert(position);
-            }
-
-            neighbours
-                .entry(position + (1, 1))
-                .or_insert(0u8)
-                .add_assign(1);
-            neighbours
-                .entry(position + (1, 0))
-                .or_insert(0u8)
-                .add_assign(1);
-            neighbours
-                .entry(position + (1, -1))
-                .or_insert(0u8)
-                .add_assign(1);
-
-            neighbours
-                .entry(position + (0, 1))
-                .or_insert(0u8)
-                .add_assign(1);
-            neighbours
-                .entry(position + (0, -1))
-                .or_insert(0u8)
-                .add_assign(1);
-
-            neighbours
-                .entry(position + (-1, 1))
-                .or_insert(0u8)
-                .add_assign(1);
-            neighbours
-                .entry(position + (-1, 0))
-                .or_insert(0u8)
-                .add_assign(1);
-            neighbours
-                .entry(position + (-1, -1))
-                .or_insert(0u8)
-                .add_assign(1);
-        }
-
-        for position in to_die {
-            self.board.remove(&position);
-        }
+        self.advance(&mut HashMap::new(), &mut HashSet::new());
+        self.generation += 1;
+    }
 
-        for (position, alive_neighbours) in neighbours {
-            match alive_neighbours {
-                // Under population
-                0 | 1 => {
-                    self.board.remove(&position);
-                }
-                // Nothing happens
-                2 => {}
-                // Cell if created if non-existing
-                3 => {
-                    self.board.insert(position);
-                }
-                // Over population
-                _ => {
-                    self.board.remove(&position);
-                }
-            }
+    fn tick_n(&mut self, generations: u64) {
+        // The buffers are reused between generations, rather than being allocated for each.
+        let mut neighbours = HashMap::new();
+        let mut changed = HashSet::new();
+        for _ in 0..generations {
+            self.advance(&mut neighbours, &mut changed);
         }
-
-        self.generation += 1;
+        self.generation += generations;
     }
 
     fn set(&mut self, position: GlobalPosition, cell: Cell) {
+        self.clear_delta();
         match cell {
             Cell::Alive => {
-                self.board.insert(position);
+                // Setting an already alive cell does not change its age.
+                if self.board.insert(position) && self.display_ages {
+                    self.ages.insert(position, 0);
+                }
             }
             Cell::Dead => {
                 self.board.remove(&position);
+                self.ages.remove(&position);
             }
         };
     }
@@ -120,6 +119,127 @@ impl Simulator for Board {
         }
     }
 
+    fn get_region(&self, area: Area) -> Box<[Cell]> {
+        let min = area.get_min();
+        let max = area.get_max();
+        let width = (area.x_difference() as usize) + 1;
+        let height = (area.y_difference() as usize) + 1;
+
+        // Only the alive cells need to be looked at, as every other cell is dead.
+        let mut region = vec![Cell::Dead; width * height].into_boxed_slice();
+        for position in &self.board {
+            let (x, y) = (position.get_x(), position.get_y());
+            if x < min.get_x() || x > max.get_x() || y < min.get_y() || y > max.get_y() {
+                continue;
+            }
+
+            let (x_offset, y_offset) = *position - min;
+            region[y_offset as usize * width + x_offset as usize] = Cell::Alive;
+        }
+
+        region
+    }
+
+    fn get_generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn reset(&mut self) {
+        self.board = HashSet::new();
+        self.ages = HashMap::new();
+        self.clear_delta();
+        self.generation = 0;
+    }
+
+    fn clear(&mut self) {
+        self.board.clear();
+        self.ages.clear();
+        self.clear_delta();
+    }
+
+    fn population(&self) -> u64 {
+        self.board.len() as u64
+    }
+
+    fn alive_positions(&self) -> Vec<GlobalPosition> {
+        self.board.iter().copied().collect()
+    }
+
+    fn mirror(&mut self, axis: Axis) {
+        let area = self.get_board_area();
+        // Mirroring maps the board area onto itself, so the set is rebuilt rather than cleared & refilled.
+        self.board = self
+            .board
+            .drain()
+            .map(|position| axis.mirror(area, position))
+            .collect();
+        self.ages = self
+            .ages
+            .drain()
+            .map(|(position, age)| (axis.mirror(area, position), age))
+            .collect();
+        self.clear_delta();
+    }
+
+    fn rotate(&mut self, quarter_turns: u8) -> Result<(), RotateError> {
+        let area = self.get_board_area();
+        // The board is rotated before it is replaced, so it is unchanged if any cell can't be rotated.
+        self.board = self
+            .board
+            .iter()
+            .map(|position| gol_lib::rotate(area, quarter_turns, *position))
+            .collect::<Result<_, _>>()?;
+        // The aged cells are alive, so can be rotated as well.
+        self.ages = self
+            .ages
+            .drain()
+            .map(|(position, age)| Ok((gol_lib::rotate(area, quarter_turns, position)?, age)))
+            .collect::<Result<_, RotateError>>()?;
+        self.clear_delta();
+        Ok(())
+    }
+
+    fn get_board_area(&self) -> Area {
+        let mut positions = self.board.iter();
+        let Some(first) = positions.next() else {
+            return Area::default();
+        };
+
+        // (min x, min y, max x, max y)
+        let (min_x, min_y, max_x, max_y) = positions.fold(
+            (first.get_x(), first.get_y(), first.get_x(), first.get_y()),
+            |(min_x, min_y, max_x, max_y), position| {
+                (
+                    min_x.min(position.get_x()),
+                    min_y.min(position.get_y()),
+                    max_x.max(position.get_x()),
+                    max_y.max(position.get_y()),
+                )
+            },
+        );
+
+        Area::new((min_x, min_y), (max_x, max_y))
+    }
+
+    fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    fn supports_rule(&self, rule: Rule) -> bool {
+        // Every dead cell would be born on a board without alive cells, which can't be stored as a set of alive cells.
+        !rule.born(0)
+    }
+
+    fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    fn get_rule(&self) -> Rule {
+        self.rule
+    }
+}
+
+impl Simulator for Board {
     fn update_display(&mut self) {
         // Attempts to acquire the lock on the display.
         // If a lock could not be acquired the method returns early.
@@ -129,8 +249,12 @@ impl Simulator for Board {
             Err(TryLockError::WouldBlock) => {
                 return;
             }
-            Err(TryLockError::Poisoned(_)) => {
-                core::panic!("Ui panicked!");
+            // The ui panicking while holding the display does not affect the board, so the simulation continues.
+            Err(TryLockError::Poisoned(poisoned)) => {
+                log::warn!("{}", DISPLAY_POISONED);
+                let display = poisoned.into_inner();
+                self.display.clear_poison();
+                display
             }
         };
 
@@ -142,20 +266,40 @@ impl Simulator for Board {
         // Get the state of the board within the specified size
         let mut board_build = Vec::new();
 
-        let from = &self.display_size_buf.get_min();
-        let to = &self.display_size_buf.get_max();
-        for x in from.get_x()..to.get_x() {
-            let mut y_builder = Vec::new();
-            for y in from.get_y()..to.get_y() {
-                y_builder.push(self.get((x, y).into()));
-            }
-            // Convert the vec into the correct type
-            let array: Box<[Cell]> = y_builder.into();
-            board_build.push(array);
+        // A display area too large to allocate is displayed without any cells, which the ui shows as dead cells.
+        if self.display_size_buf.cell_count() > self.max_display_cells as u64 {
+            *display = Some(BoardDisplay::new(
+                self.generation,
+                self.display_size_buf,
+                board_build,
+            ));
+            return;
+        }
+
+        let from = self.display_size_buf.get_min();
+        // The display includes the maximum row & column of the area, the same as iterating over it.
+        let width = self.display_size_buf.x_difference() as usize + 1;
+        let height = self.display_size_buf.y_difference() as usize + 1;
+        let region = self.get_region(self.display_size_buf);
+
+        // The region is ordered by row, but the display is ordered by column.
+        board_build.reserve(width);
+        for x in 0..width {
+            let column: Box<[Cell]> = (0..height).map(|y| region[y * width + x]).collect();
+            board_build.push(column);
+        }
+
+        let mut board_display =
+            BoardDisplay::new(self.generation, self.display_size_buf, board_build);
+        if self.display_ages {
+            board_display = board_display.with_ages(self.age_columns(from, width, height));
+        }
+        if self.display_changes {
+            board_display = board_display.with_changes(self.change_columns(from, width, height));
         }
 
         // Updates the board to display.
-        *display = Some(BoardDisplay::new(self.generation, board_build));
+        *display = Some(board_display);
     }
 
     fn new(display: SharedDisplay) -> Self {
@@ -164,6 +308,15 @@ impl Simulator for Board {
             display,
             display_size_buf: Default::default(),
             generation: 0,
+            neighborhood: Neighborhood::default(),
+            rule: Rule::default(),
+            cell_limit: None,
+            max_display_cells: DEFAULT_MAX_DISPLAY_CELLS,
+            ages: HashMap::new(),
+            display_ages: false,
+            display_changes: false,
+            births: Vec::new(),
+            deaths: Vec::new(),
         }
     }
 
@@ -171,1030 +324,876 @@ impl Simulator for Board {
         self.display_size_buf = new_area;
     }
 
-    fn get_generation(&self) -> u64 {
-        self.generation
+    fn set_cell_limit(&mut self, limit: Option<usize>) {
+        self.cell_limit = limit;
     }
 
-    fn reset(&mut self) {
-        self.board = HashSet::new();
-        self.generation = 0;
+    fn cell_limit_exceeded(&self) -> Option<u64> {
+        let limit = self.cell_limit?;
+        (self.board.len() > limit).then_some(self.population())
     }
 
-    fn get_board_area(&self) -> Area {
-        let top_left = self
-            .board
-            .iter()
-            .fold(GlobalPosition::new(0, 0), |mut corner, position| {
-                if position.get_x() > corner.get_x() {
-                    corner = GlobalPosition::new(position.get_x(), corner.get_y());
-                }
-                if position.get_y() > corner.get_y() {
-                    corner = GlobalPosition::new(corner.get_x(), position.get_y());
-                }
+    fn max_display_cells(&self) -> Option<usize> {
+        Some(self.max_display_cells)
+    }
 
-                corner
-            });
+    fn set_display_ages(&mut self, display_ages: bool) {
+        if display_ages == self.display_ages {
+            return;
+        }
+        self.display_ages = display_ages;
+        // Ages are only tracked whilst displayed, so the cells alive once enabled are counted from then on.
+        self.ages = match display_ages {
+            true => self.board.iter().map(|position| (*position, 0)).collect(),
+            false => HashMap::new(),
+        };
+    }
 
-        let bottom_right =
-            self.board
-                .iter()
-                .fold(GlobalPosition::new(0, 0), |mut corner, position| {
-                    if position.get_x() < corner.get_x() {
-                        corner = GlobalPosition::new(position.get_x(), corner.get_y());
-                    }
-                    if position.get_y() < corner.get_y() {
-                        corner = GlobalPosition::new(corner.get_x(), position.get_y());
-                    }
-
-                    corner
-                });
-
-        Area::new(top_left, bottom_right)
+    fn set_display_changes(&mut self, display_changes: bool) {
+        self.display_changes = display_changes;
+        // Changes recorded before they were disabled would be out of date once they are enabled again.
+        self.clear_delta();
     }
 
-    fn set_generation(&mut self, generation: u64) {
-        self.generation = generation;
+    fn last_delta(&self) -> (Vec<GlobalPosition>, Vec<GlobalPosition>) {
+        (self.births.clone(), self.deaths.clone())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use bitvec::vec::BitVec;
+impl Board {
+    /// Forgets the cells born & the cells that died during the last generation.
+    ///
+    /// This is done whenever the board is edited, as the changes would no longer describe the board.
+    fn clear_delta(&mut self) {
+        self.births.clear();
+        self.deaths.clear();
+    }
 
-    use gol_lib::persistence::SimulationSave;
+    /// Advances the board by one generation, without changing the generation count.
+    ///
+    /// The given buffers must be empty, & are left empty.
+    fn advance(
+        &mut self,
+        neighbours: &mut HashMap<GlobalPosition, u8>,
+        changed: &mut HashSet<GlobalPosition>,
+    ) {
+        // Only the changes of the latest generation are kept.
+        self.clear_delta();
+
+        let offsets = self.neighborhood.offsets();
+        for position in &self.board {
+            let position = *position;
+            let surrounding = offsets
+                .iter()
+                .filter(|offset| self.board.contains(&(position + **offset)))
+                .count();
 
-    use super::*;
+            // Cells without any alive neighbours aren't counted below.
+            if surrounding == 0 && !self.rule.survives(0) {
+                changed.insert(position);
+            }
 
-    #[test]
-    /// A cell will be dead unless it has been set to alive.
-    fn dead_by_default() {
-        let board = Board::new(Default::default());
-        for x in -10..=10 {
-            for y in -10..=10 {
-                assert_eq!(board.get((x, y).into()), Cell::Dead)
+            for offset in offsets {
+                neighbours
+                    .entry(position + *offset)
+                    .or_insert(0u8)
+                    .add_assign(1);
             }
         }
-    }
 
-    #[test]
-    /// Sets a cell to be alive.
-    fn set_cell_alive() {
-        let position = GlobalPosition::new(1, 1);
-        let mut board = Board::new(Default::default());
-
-        board.set(position, Cell::Alive);
-        assert_eq!(board.get(position), Cell::Alive);
-    }
-
-    /// Returns an iterator over [`Cell`], which gives "[`Cell::Alive`], [`Cell::Dead`]" in that order, forever.
-    fn generate_cell_iterator() -> std::iter::FromFn<impl FnMut() -> Option<Cell>> {
-        let mut generated_cell = Cell::Dead;
-        let cell_iter = std::iter::from_fn(move || {
-            generated_cell = match generated_cell {
-                Cell::Alive => Cell::Dead,
-                Cell::Dead => Cell::Alive,
+        // Every change is found before any are made, so each cell is judged on the previous generation.
+        for (position, alive_neighbours) in neighbours.drain() {
+            let alive = self.board.contains(&position);
+            let next = match alive {
+                true => self.rule.survives(alive_neighbours),
+                false => self.rule.born(alive_neighbours),
             };
-            Some(generated_cell)
-        });
-        cell_iter
-    }
-
-    #[test]
-    /// Sets a pattern of alive & dead cells.
-    fn set_cell_pattern() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        // Populate board
-        let mut cell_iter = generate_cell_iterator();
-        for x in -10..=10 {
-            for y in -10..=10 {
-                let cell = cell_iter.next().unwrap();
-                board.set((x, y).into(), cell)
+            if alive != next {
+                changed.insert(position);
             }
         }
 
-        // Read board
-        let mut cell_iter = generate_cell_iterator();
-        for x in -10..=10 {
-            for y in -10..=10 {
-                let cell = cell_iter.next().unwrap();
-                let get = board.get((x, y).into());
-                assert_eq!(cell, get);
+        for position in changed.drain() {
+            let died = self.board.remove(&position);
+            if !died {
+                self.board.insert(position);
             }
-        }
-    }
-
-    #[test]
-    /// The derived display will correctly represent the board.
-    fn generates_correct_display() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
 
-        // Populate board
-        let mut cell_iter = generate_cell_iterator();
-        for y in -10..=10 {
-            for x in -10..=10 {
-                board.set((x, y).into(), cell_iter.next().unwrap());
+            if self.display_changes {
+                match died {
+                    true => self.deaths.push(position),
+                    false => self.births.push(position),
+                }
             }
         }
 
-        // Display init
-        board.set_display_area(Area::new((-10, -10), (10, 10)));
-        board.update_display();
+        if !self.display_ages {
+            return;
+        }
+        // Cells that survived are a generation older, & newly born cells start at 0.
+        let board = &self.board;
+        self.ages.retain(|position, _| board.contains(position));
+        for position in board {
+            self.ages
+                .entry(*position)
+                .and_modify(|age| *age = age.saturating_add(1))
+                .or_insert(0);
+        }
+    }
 
-        let mut mutex_guard = display.lock().unwrap();
-        let take = mutex_guard.take();
-        assert!(take.is_some());
-
-        // Generate expected result
-        let var_name = {
-            use Cell::{Alive, Dead};
-            let mut vec = Vec::new();
-
-            for _ in 0..10 {
-                let a: Box<[Cell]> = Box::new([
-                    Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead,
-                    Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead,
-                ]);
-                let b = Box::new([
-                    Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive,
-                    Dead, Alive, Dead, Alive, Dead, Alive, Dead, Alive,
-                ]);
-                vec.push(a);
-                vec.push(b);
+    /// Gets the ages of the cells within the area of the given size, in the column ordered layout of the display.
+    fn age_columns(&self, from: GlobalPosition, width: usize, height: usize) -> Vec<Box<[u32]>> {
+        let mut columns = vec![vec![0; height].into_boxed_slice(); width];
+        // Only the alive cells need to be looked at, as dead cells have no age.
+        for (position, age) in &self.ages {
+            let (x_offset, y_offset) = *position - from;
+            if (0..width as i32).contains(&x_offset) && (0..height as i32).contains(&y_offset) {
+                columns[x_offset as usize][y_offset as usize] = *age;
             }
-
-            vec
-        };
-
-        let board_display = BoardDisplay::new(0, var_name);
-        assert_eq!(board_display, take.unwrap())
+        }
+        columns
     }
 
-    #[test]
-    /// reset must remove all alive cells from board & set the generation to 0.
-    fn reset() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        // Populate board
-        for position in Area::new((-100, -100), (100, 100)).iterate_over() {
-            board.set(position, Cell::Alive);
+    /// Gets how the cells within the area of the given size changed during the last generation, in the column ordered
+    /// layout of the display.
+    fn change_columns(
+        &self,
+        from: GlobalPosition,
+        width: usize,
+        height: usize,
+    ) -> Vec<Box<[CellChange]>> {
+        let mut columns = vec![vec![CellChange::Unchanged; height].into_boxed_slice(); width];
+        let births = self
+            .births
+            .iter()
+            .map(|position| (position, CellChange::Born));
+        let deaths = self
+            .deaths
+            .iter()
+            .map(|position| (position, CellChange::Died));
+        for (position, change) in births.chain(deaths) {
+            let (x_offset, y_offset) = *position - from;
+            if (0..width as i32).contains(&x_offset) && (0..height as i32).contains(&y_offset) {
+                columns[x_offset as usize][y_offset as usize] = change;
+            }
         }
+        columns
+    }
 
-        board.reset();
+    /// Sets the cells that are counted as the neighbours of each cell when ticking.
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+    }
 
-        // Test reset
-        for position in Area::new((-100, -100), (100, 100)).iterate_over() {
-            assert_eq!(
-                board.get(position),
-                Cell::Dead,
-                "Cell at {position:?} is alive. All cells must be dead after board reset"
-            );
-        }
+    /// Sets the maximum amount of cells the display may contain. Display areas with more cells are displayed without
+    /// any cells, to avoid allocating the display.
+    ///
+    /// Defaults to [`DEFAULT_MAX_DISPLAY_CELLS`].
+    pub fn set_max_display_cells(&mut self, max_display_cells: usize) {
+        self.max_display_cells = max_display_cells;
     }
 
-    #[test]
-    /// Generation increases by one each time tick is called.
-    fn generation_increases() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+    /// A stable hash of the alive cells, relative to the bounding box of the board.
+    ///
+    /// This hash is translation-invariant, so boards that are translated copies of each other share it. It matches
+    /// [`gol_lib::persistence::SimulationSave::shape_hash`] for a save of the board.
+    pub fn board_hash(&self) -> u64 {
+        self.hash(false)
+    }
 
-        assert_eq!(board.get_generation(), 0);
+    /// A stable hash of the alive cells & their positions.
+    ///
+    /// Unlike [`Self::board_hash`], this only matches boards with the same cells at the same positions. It matches
+    /// [`gol_lib::persistence::SimulationSave::content_hash`] for a save of the board.
+    pub fn positional_hash(&self) -> u64 {
+        self.hash(true)
+    }
 
-        for generation in 1..=100 {
-            board.tick();
-            assert_eq!(
-                board.get_generation(),
-                generation,
-                "Calling tick must incrememnt the generation by one."
-            );
+    fn hash(&self, positional: bool) -> u64 {
+        let area = self.get_board_area();
+        let width = area.x_difference() as u64 + 1;
+        // The cells are hashed in the order of the board area, rather than the arbitrary order of the set.
+        let mut indices: Vec<u64> = self
+            .board
+            .iter()
+            .map(|position| {
+                let (x, y) = *position - area.get_min();
+                y as u64 * width + x as u64
+            })
+            .collect();
+        indices.sort_unstable();
+
+        let mut hasher = BoardHasher::new(area, positional);
+        for index in indices {
+            hasher.alive(index);
         }
+        hasher.finish()
     }
 
-    #[test]
-    /// An alive cell with no neighbours will die
-    fn alive_0_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+    /// The approximate amount of heap memory used to store the board, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        // Each entry of a set or map has a control byte along with its data.
+        self.board.capacity() * (size_of::<GlobalPosition>() + 1)
+            + self.ages.capacity() * (size_of::<(GlobalPosition, u32)>() + 1)
+            + (self.births.capacity() + self.deaths.capacity()) * size_of::<GlobalPosition>()
+    }
+}
 
-        board.set((1, 1).into(), Cell::Alive);
+#[cfg(test)]
+mod tests {
+    use gol_lib::{test_suite, CellState, LoadStatus};
 
-        // Tick & test
-        board.tick();
-        assert_eq!(board.get((1, 1).into()), Cell::Dead);
-    }
+    use super::*;
 
     #[test]
-    /// A dead cell with no neighbours will stay dead
-    fn dead_0_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+    /// A blinker oscillates with the Moore neighbourhood, but dies out with the von Neumann neighbourhood.
+    fn von_neumann_differs() {
+        let blinker = [(-1, 0), (0, 0), (1, 0)];
+
+        let mut moore = Board::new(Default::default());
+        let mut von_neumann = Board::new(Default::default());
+        von_neumann.set_neighborhood(Neighborhood::VonNeumann);
+        for position in blinker {
+            moore.set(position.into(), Cell::Alive);
+            von_neumann.set(position.into(), Cell::Alive);
+        }
 
-        board.set((1, 1).into(), Cell::Dead);
+        moore.tick();
+        von_neumann.tick();
+        // Only the centre has two orthogonal neighbours.
+        assert_eq!(von_neumann.board, HashSet::from([(0, 0).into()]));
+        assert_eq!(
+            moore.board,
+            HashSet::from([(0, -1).into(), (0, 0).into(), (0, 1).into()])
+        );
 
-        // Tick & test
-        board.tick();
-        assert_eq!(board.get((1, 1).into()), Cell::Dead);
+        moore.tick();
+        von_neumann.tick();
+        assert!(von_neumann.board.is_empty());
+        assert_eq!(moore.population(), 3);
+        assert_ne!(moore.board, von_neumann.board);
     }
 
     #[test]
-    /// An alive cell with one neighbour will die
-    fn alive_1_neighbour() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        for position in Area::new((0, 0), (2, 2)).iterate_over() {
-            if position == (1, 1).into() {
-                continue;
-            }
-
-            board.set((1, 1).into(), Cell::Alive);
+    /// Rotating cells beyond the edge of the board is an error, & leaves the board unchanged.
+    fn rotate_beyond_edge() {
+        // A wide pattern along the bottom edge, which would be turned to extend below it.
+        let cells = [(0, i32::MAX), (10, i32::MAX), (5, i32::MAX - 1)].map(GlobalPosition::from);
+        let mut board = Board::new(Default::default());
+        board.set_display_ages(true);
+        for position in cells {
             board.set(position, Cell::Alive);
-
-            // Tick & test
-            board.tick();
-            assert_eq!(
-                board.get((1, 1).into()),
-                Cell::Dead,
-                "Cell at (1, 1) must die from one neighbour at {position:?}"
-            );
         }
-    }
 
-    #[test]
-    /// A dead cell with one neighbour will stay dead
-    fn dead_1_neighbour() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+        assert_eq!(board.rotate(1), Err(RotateError));
+        assert_eq!(board.board, HashSet::from(cells));
+        assert_eq!(board.ages.len(), cells.len());
 
-        for position in Area::new((0, 0), (2, 2)).iterate_over() {
-            if position == (1, 1).into() {
-                continue;
-            }
+        // A half turn keeps the pattern within the same area.
+        assert_eq!(board.rotate(2), Ok(()));
+    }
 
-            board.set((1, 1).into(), Cell::Alive);
-            board.set(position, Cell::Alive);
+    /// A board that takes a long time to tick, to test how the simulator handles slow ticks.
+    struct SlowBoard(Board);
 
-            // Tick & test
-            board.tick();
-            assert_eq!(
-                board.get((1, 1).into()),
-                Cell::Dead,
-                "Cell at (1, 1) must stay dead with neighbour at: {position:?}"
-            );
-        }
+    impl SlowBoard {
+        /// How long each tick takes.
+        const TICK: std::time::Duration = std::time::Duration::from_millis(40);
     }
 
-    #[test]
-    /// An alive cell with two neighbours will stay alive
-    fn alive_2_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+    impl Automaton for SlowBoard {
+        fn tick(&mut self) {
+            std::thread::sleep(Self::TICK);
+            self.0.tick();
+        }
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
+        fn set(&mut self, position: GlobalPosition, cell: Cell) {
+            self.0.set(position, cell);
+        }
 
-                board.set((1, 1).into(), Cell::Alive);
-                board.set(cell_a.into(), Cell::Alive);
-                board.set(cell_b.into(), Cell::Alive);
+        fn get(&self, position: GlobalPosition) -> Cell {
+            self.0.get(position)
+        }
 
-                // Tick & test
-                board.tick();
-                assert_eq!(
-                    board.get((1, 1).into()),
-                    Cell::Alive,
-                    "Cell at (1, 1) must live from neighbours at: {cell_a:?}, {cell_b:?}"
-                );
+        fn get_generation(&self) -> u64 {
+            self.0.get_generation()
+        }
 
-                // Remove remenatns
-                board.reset();
-            }
+        fn set_generation(&mut self, generation: u64) {
+            self.0.set_generation(generation);
         }
-    }
 
-    #[test]
-    /// A dead cell with two neighbours will stay dead
-    fn dead_2_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+        fn reset(&mut self) {
+            self.0.reset();
+        }
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
+        fn get_board_area(&self) -> Area {
+            self.0.get_board_area()
+        }
+    }
 
-                board.set((1, 1).into(), Cell::Dead);
-                board.set(cell_a.into(), Cell::Alive);
-                board.set(cell_b.into(), Cell::Alive);
+    impl Simulator for SlowBoard {
+        fn new(display: SharedDisplay) -> Self {
+            Self(Board::new(display))
+        }
 
-                // Tick & test
-                board.tick();
-                assert_eq!(
-                    board.get((1, 1).into()),
-                    Cell::Dead,
-                    "Cell at (1, 1) must stay dead with neighbours at: {cell_a:?}, {cell_b:?}"
-                );
+        fn update_display(&mut self) {
+            self.0.update_display();
+        }
 
-                // Remove remenatns
-                board.reset();
-            }
+        fn set_display_area(&mut self, new_area: Area) {
+            self.0.set_display_area(new_area);
         }
-    }
 
-    #[test]
-    /// An alive cell with three neighbours will stay alive
-    fn alive_3_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+        fn set_cell_limit(&mut self, limit: Option<usize>) {
+            self.0.set_cell_limit(limit);
+        }
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
-                for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
-                    if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
-                        continue;
-                    }
-
-                    board.set((1, 1).into(), Cell::Alive);
-                    board.set(cell_a.into(), Cell::Alive);
-                    board.set(cell_b.into(), Cell::Alive);
-                    board.set(cell_c.into(), Cell::Alive);
-
-                    // Tick & test
-                    board.tick();
-                    assert_eq!(
-                        board.get((1, 1).into()),
-                        Cell::Alive,
-                        "Cell at (1, 1) must live from neighbours at: {cell_a:?}, {cell_b:?}, {cell_c:?}"
-                    );
-
-                    // Remove remenatns
-                    board.reset();
-                }
-            }
+        fn cell_limit_exceeded(&self) -> Option<u64> {
+            self.0.cell_limit_exceeded()
         }
     }
 
-    #[test]
-    /// A dead cell must become alive from three neighbouring cells.
-    fn dead_3_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+    /// A board that panics when ticked, to test how the simulator reports panics.
+    struct PanickingBoard(Board);
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
-                for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
-                    if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
-                        continue;
-                    }
-
-                    board.set((1, 1).into(), Cell::Dead);
-                    board.set(cell_a.into(), Cell::Alive);
-                    board.set(cell_b.into(), Cell::Alive);
-                    board.set(cell_c.into(), Cell::Alive);
-
-                    // Tick & test
-                    board.tick();
-                    assert_eq!(
-                        board.get((1, 1).into()),
-                        Cell::Alive,
-                        "Cell at (1, 1) must be created from neighbours at: {cell_a:?}, {cell_b:?}, {cell_c:?}"
-                    );
-
-                    // Remove remenatns
-                    board.reset();
-                }
-            }
-        }
+    impl PanickingBoard {
+        /// The message the board panics with.
+        const MESSAGE: &str = "The board could not be ticked";
     }
 
-    #[test]
-    /// An alive cell with four neighbours will die
-    fn alive_4_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
-                for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
-                    if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
-                        continue;
-                    }
-                    for cell_d in Area::new((0, 0), (2, 2)).iterate_over() {
-                        if cell_d == (1, 1).into()
-                            || cell_d == cell_a
-                            || cell_d == cell_b
-                            || cell_d == cell_c
-                        {
-                            continue;
-                        }
-
-                        board.set((1, 1).into(), Cell::Alive);
-                        board.set(cell_a.into(), Cell::Alive);
-                        board.set(cell_b.into(), Cell::Alive);
-                        board.set(cell_c.into(), Cell::Alive);
-                        board.set(cell_d.into(), Cell::Alive);
-
-                        // Tick & test
-                        board.tick();
-                        assert_eq!(
-                        board.get((1, 1).into()),
-                        Cell::Dead,
-                        "Cell at (1, 1) must die from neighbours at: {cell_a:?}, {cell_b:?}, {cell_c:?}, {cell_d:?}"
-                    );
-
-                        // Remove remenatns
-                        board.reset();
-                    }
-                }
-            }
+    impl Automaton for PanickingBoard {
+        fn tick(&mut self) {
+            panic!("{}", Self::MESSAGE);
         }
-    }
 
-    #[test]
-    /// An alive cell with four neighbours will die
-    fn dead_4_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+        fn set(&mut self, position: GlobalPosition, cell: Cell) {
+            self.0.set(position, cell);
+        }
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
-                for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
-                    if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
-                        continue;
-                    }
-                    for cell_d in Area::new((0, 0), (2, 2)).iterate_over() {
-                        if cell_d == (1, 1).into()
-                            || cell_d == cell_a
-                            || cell_d == cell_b
-                            || cell_d == cell_c
-                        {
-                            continue;
-                        }
-
-                        board.set((1, 1).into(), Cell::Dead);
-                        board.set(cell_a.into(), Cell::Alive);
-                        board.set(cell_b.into(), Cell::Alive);
-                        board.set(cell_c.into(), Cell::Alive);
-                        board.set(cell_d.into(), Cell::Alive);
-
-                        // Tick & test
-                        board.tick();
-                        assert_eq!(
-                        board.get((1, 1).into()),
-                        Cell::Dead,
-                        "Cell at (1, 1) must stay dead with neighbours at: {cell_a:?}, {cell_b:?}, {cell_c:?}, {cell_d:?}"
-                    );
-
-                        // Remove remenatns
-                        board.reset();
-                    }
-                }
-            }
+        fn get(&self, position: GlobalPosition) -> Cell {
+            self.0.get(position)
         }
-    }
 
-    #[test]
-    /// An alive cell with five neighbours will die
-    fn alive_5_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+        fn get_generation(&self) -> u64 {
+            self.0.get_generation()
+        }
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
-                for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
-                    if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
-                        continue;
-                    }
-
-                    // Set alive by default
-                    for alive in Area::new((0, 0), (2, 2)).iterate_over() {
-                        board.set(alive.into(), Cell::Alive);
-                    }
-
-                    board.set(cell_a.into(), Cell::Dead);
-                    board.set(cell_b.into(), Cell::Dead);
-                    board.set(cell_c.into(), Cell::Dead);
-
-                    // Tick & test
-                    board.tick();
-                    assert_eq!(
-                        board.get((1, 1).into()),
-                        Cell::Dead,
-                        "Cell at (1, 1) must die from being surrounded by neighbours expect for cells: {cell_a:?}, {cell_b:?}, {cell_c:?}"
-                    );
-
-                    // Remove remenatns
-                    board.reset();
-                }
-            }
+        fn set_generation(&mut self, generation: u64) {
+            self.0.set_generation(generation);
         }
-    }
 
-    #[test]
-    /// A dead cell with five neighbours stay dead
-    fn dead_5_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+        fn reset(&mut self) {
+            self.0.reset();
+        }
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
-                for cell_c in Area::new((0, 0), (2, 2)).iterate_over() {
-                    if cell_c == (1, 1).into() || cell_c == cell_a || cell_c == cell_b {
-                        continue;
-                    }
-
-                    // Set alive by default
-                    for alive in Area::new((0, 0), (2, 2)).iterate_over() {
-                        board.set(alive.into(), Cell::Alive);
-                    }
-
-                    board.set((1, 1).into(), Cell::Dead);
-                    board.set(cell_a.into(), Cell::Dead);
-                    board.set(cell_b.into(), Cell::Dead);
-                    board.set(cell_c.into(), Cell::Dead);
-
-                    // Tick & test
-                    board.tick();
-                    assert_eq!(
-                        board.get((1, 1).into()),
-                        Cell::Dead,
-                        "Cell at (1, 1) must stay dead with neighbouring dead cells at: {cell_a:?}, {cell_b:?}, {cell_c:?}"
-                    );
-
-                    // Remove remenatns
-                    board.reset();
-                }
-            }
+        fn get_board_area(&self) -> Area {
+            self.0.get_board_area()
         }
     }
 
-    #[test]
-    /// An alive cell with six neighbours will die
-    fn alive_6_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
+    impl Simulator for PanickingBoard {
+        fn new(display: SharedDisplay) -> Self {
+            Self(Board::new(display))
+        }
 
-                // Set alive by default
-                for alive in Area::new((0, 0), (2, 2)).iterate_over() {
-                    board.set(alive.into(), Cell::Alive);
-                }
+        fn update_display(&mut self) {
+            self.0.update_display();
+        }
 
-                board.set(cell_a.into(), Cell::Dead);
-                board.set(cell_b.into(), Cell::Dead);
+        fn set_display_area(&mut self, new_area: Area) {
+            self.0.set_display_area(new_area);
+        }
 
-                // Tick & test
-                board.tick();
-                assert_eq!(
-                    board.get((1, 1).into()),
-                    Cell::Dead,
-                    "Cell at (1, 1) must die from being surrounded by neighbours expect for cells: {cell_a:?}, {cell_b:?}"
-                );
+        fn set_cell_limit(&mut self, limit: Option<usize>) {
+            self.0.set_cell_limit(limit);
+        }
 
-                // Remove remenatns
-                board.reset();
-            }
+        fn cell_limit_exceeded(&self) -> Option<u64> {
+            self.0.cell_limit_exceeded()
         }
     }
 
     #[test]
-    /// A dead cell with six neighbours will stay dead
-    fn dead_6_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
+    /// A panic within the simulator is reported to the ui with its message, as the last packet sent.
+    fn simulator_fatal() {
+        use gol_lib::communication::{SimulatorPacket, UiPacket};
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-            for cell_b in Area::new((0, 0), (2, 2)).iterate_over() {
-                if cell_b == (1, 1).into() || cell_b == cell_a {
-                    continue;
-                }
-
-                // Set alive by default
-                for alive in Area::new((0, 0), (2, 2)).iterate_over() {
-                    board.set(alive.into(), Cell::Alive);
-                }
-
-                board.set((1, 1).into(), Cell::Dead);
-                board.set(cell_a.into(), Cell::Dead);
-                board.set(cell_b.into(), Cell::Dead);
+        let harness = test_suite::spawn_with(
+            PanickingBoard::new(Default::default()),
+            [UiPacket::Step { count: 1 }],
+        );
 
-                // Tick & test
-                board.tick();
-                assert_eq!(
-                    board.get((1, 1).into()),
-                    Cell::Dead,
-                    "Cell at (1, 1) must stay dead with dead neighbouring cells at: {cell_a:?}, {cell_b:?}"
-                );
+        let packet = harness.receive();
+        let SimulatorPacket::Fatal { message } = packet else {
+            panic!("{packet:?}");
+        };
+        assert_eq!(message, PanickingBoard::MESSAGE);
 
-                // Remove remenatns
-                board.reset();
-            }
-        }
+        // The panic still ends the thread.
+        let (result, packets) = harness.join();
+        assert!(result.is_err());
+        assert!(packets.is_empty());
     }
 
     #[test]
-    /// An alive cell with seven neighbours will die
-    fn alive_7_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
+    /// Whilst the speed is automatic, slow ticks lower the rate the simulation runs at.
+    fn simulator_automatic_speed() {
+        use gol_lib::communication::{SimulationSpeed, SimulatorPacket, UiPacket};
+
+        let harness = test_suite::spawn_with(
+            SlowBoard::new(Default::default()),
+            [
+                UiPacket::SimulationSpeed {
+                    speed: SimulationSpeed::AUTOMATIC,
+                },
+                UiPacket::Start,
+            ],
+        );
 
-            // Set alive by default
-            for alive in Area::new((0, 0), (2, 2)).iterate_over() {
-                board.set(alive.into(), Cell::Alive);
-            }
+        let (target, achieved) = harness.expect_packet(|packet| match packet {
+            SimulatorPacket::TickRate { target, achieved } => Some((target, achieved)),
+            _ => None,
+        });
 
-            board.set(cell_a.into(), Cell::Dead);
+        // Unlimited, the board could be ticked 25 times a second, so waiting as long as each tick halves that.
+        let unlimited = 1.0 / SlowBoard::TICK.as_secs_f32();
+        let target = target.expect("The simulation is limited");
+        assert!(
+            target as f32 <= unlimited / 2.0 + 1.0,
+            "Limited to {target} tps"
+        );
+        assert!(achieved < unlimited * 0.75, "Achieved {achieved} tps");
 
-            // Tick & test
-            board.tick();
-            assert_eq!(
-                board.get((1, 1).into()),
-                Cell::Dead,
-                "Cell at (1, 1) must die from being surrounded by neighbours expect for cells: {cell_a:?}"
-            );
-
-            // Remove remenatns
-            board.reset();
-        }
+        harness.terminate();
     }
 
     #[test]
-    /// A dead cell with seven neighbours will stay dead.
-    fn dead_7_neighbours() {
-        let display: SharedDisplay = Default::default();
+    /// The maximum display size can be changed.
+    fn set_max_display_cells() {
+        let display = SharedDisplay::default();
         let mut board = Board::new(display.clone());
+        board.set_display_area(Area::new((0, 0), (10, 10)));
+        board.set((1, 1).into(), Cell::Alive);
+        assert_eq!(board.max_display_cells(), Some(DEFAULT_MAX_DISPLAY_CELLS));
 
-        for cell_a in Area::new((0, 0), (2, 2)).iterate_over() {
-            if cell_a == (1, 1).into() {
-                continue;
-            }
-
-            // Set alive by default
-            for alive in Area::new((0, 0), (2, 2)).iterate_over() {
-                board.set(alive.into(), Cell::Alive);
-            }
-
-            board.set((1, 1).into(), Cell::Dead);
-            board.set(cell_a.into(), Cell::Dead);
+        board.set_max_display_cells(100);
+        board.update_display();
+        let board_display = display.lock().unwrap().take().expect("Display is updated");
+        assert_eq!(board_display.iter_cells().count(), 0);
 
-            // Tick & test
-            board.tick();
-            assert_eq!(
-                board.get((1, 1).into()),
-                Cell::Dead,
-                "Cell at (1, 1) must stay dead with dead neighbouring cells at: {cell_a:?}"
-            );
-
-            // Remove remenatns
-            board.reset();
-        }
+        board.set_max_display_cells(121);
+        board.update_display();
+        let board_display = display.lock().unwrap().take().expect("Display is updated");
+        assert_eq!(board_display.alive_positions().count(), 1);
     }
 
     #[test]
-    /// An alive cell with all neighbours will die
-    fn alive_8_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        // Set alive by default
-        for alive in Area::new((0, 0), (2, 2)).iterate_over() {
-            board.set(alive.into(), Cell::Alive);
+    /// Advancing many generations at once is the same as ticking for each generation.
+    fn tick_n() {
+        let mut ticked = Board::new(Default::default());
+        // A glider, along with an r-pentomino that changes for many generations.
+        for position in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].into_iter().chain([
+            (21, 20),
+            (22, 20),
+            (20, 21),
+            (21, 21),
+            (21, 22),
+        ]) {
+            ticked.set(position.into(), Cell::Alive);
         }
+        let mut skipped = Board::new(Default::default());
+        skipped.board = ticked.board.clone();
 
-        // Tick & test
-        board.tick();
-        assert_eq!(
-            board.get((1, 1).into()),
-            Cell::Dead,
-            "Cell at (1, 1) must die from being fully surrounded by neighbours"
-        );
+        for _ in 0..10 {
+            ticked.tick();
+        }
+        skipped.tick_n(10);
 
-        // Remove remenatns
-        board.reset();
+        assert_eq!(skipped.board, ticked.board);
+        assert_eq!(skipped.get_generation(), 10);
+        assert_eq!(ticked.get_generation(), 10);
     }
 
     #[test]
-    /// An alive cell with all neighbours will die
-    fn dead_8_neighbours() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        // Set alive by default
-        for alive in Area::new((0, 0), (2, 2)).iterate_over() {
-            board.set(alive.into(), Cell::Alive);
+    /// Cells that survive a generation get older.
+    fn age_increments() {
+        let mut board = Board::new(Default::default());
+        board.set_display_ages(true);
+        // A block is a still life, so every cell survives.
+        for position in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            board.set(position.into(), Cell::Alive);
         }
+        assert_eq!(board.ages.get(&(0, 0).into()), Some(&0));
 
-        board.set((1, 1).into(), Cell::Dead);
-
-        // Tick & test
-        board.tick();
-        assert_eq!(
-            board.get((1, 1).into()),
-            Cell::Dead,
-            "Cell at (1, 1) must die from being fully surrounded by neighbours"
-        );
-
-        // Remove remenatns
-        board.reset();
+        board.tick_n(3);
+        assert_eq!(board.ages.len(), 4);
+        assert!(board.ages.values().all(|age| *age == 3));
     }
 
     #[test]
-    /// Correctly simulates the "Block" pattern.
-    fn block() {
-        let display: SharedDisplay = Default::default();
-        let mut board = Board::new(display.clone());
-
-        // Create block pattern
-        board.set((1, 1).into(), Cell::Alive);
-        board.set((1, 2).into(), Cell::Alive);
-        board.set((2, 1).into(), Cell::Alive);
-        board.set((2, 2).into(), Cell::Alive);
+    /// Newly born cells start with an age of 0, & dead cells have no age.
+    fn age_resets_on_birth() {
+        let mut board = Board::new(Default::default());
+        board.set_display_ages(true);
+        // A horizontal blinker.
+        for position in [(0, 1), (1, 1), (2, 1)] {
+            board.set(position.into(), Cell::Alive);
+        }
 
-        // Tick & test
         board.tick();
-        for x in 0..4 {
-            for y in 0..4 {
-                let found = board.get((x, y).into());
-                let expected = match (x, y) {
-                    (1, 1) | (1, 2) | (2, 1) | (2, 2) => Cell::Alive,
-                    _ => Cell::Dead,
-                };
-                assert_eq!(found, expected)
-            }
-        }
+        assert_eq!(board.ages.get(&(1, 1).into()), Some(&1));
+        assert_eq!(board.ages.get(&(1, 0).into()), Some(&0));
+        assert_eq!(board.ages.get(&(1, 2).into()), Some(&0));
+        assert_eq!(board.ages.get(&(0, 1).into()), None);
 
-        // Once again for good measure
+        // The cells at the ends are born again.
         board.tick();
-        for x in 0..4 {
-            for y in 0..4 {
-                let found = board.get((x, y).into());
-                let expected = match (x, y) {
-                    (1, 1) | (1, 2) | (2, 1) | (2, 2) => Cell::Alive,
-                    _ => Cell::Dead,
-                };
-                assert_eq!(found, expected)
-            }
-        }
+        assert_eq!(board.ages.get(&(1, 1).into()), Some(&2));
+        assert_eq!(board.ages.get(&(0, 1).into()), Some(&0));
     }
 
     #[test]
-    /// Correctly loads empty board.
-    fn load_board_empty() {
-        // Generate board with alive cells.
-        let mut board = Board::new(Default::default());
-        for position in Area::new((-10, -10), (10, 10)).iterate_over() {
-            board.set(position, Cell::Alive);
+    /// Ages are only included in the display once enabled, counting from when they were enabled.
+    fn display_ages() {
+        let display = SharedDisplay::default();
+        let mut board = Board::new(display.clone());
+        board.set_display_area(Area::new((0, 0), (3, 3)));
+        for position in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            board.set(position.into(), Cell::Alive);
         }
+        board.tick();
 
-        // Load empty board.
-        let generation = 0;
-        let area = Area::new((-4, -6), (4, 6));
-        let board_data = BitVec::new();
-        let simulation_save = SimulationSave::new(generation, area, board_data);
-        board.load_board(simulation_save);
-
-        assert_eq!(
-            board.get((8, 8).into()),
-            Cell::Dead,
-            "Cells outside the new board area must be set to dead."
-        );
+        board.update_display();
+        let board_display = display.lock().unwrap().take().unwrap();
+        assert_eq!(board_display.get_age((0, 0)), None);
 
-        for position in area.iterate_over() {
-            assert_eq!(
-                board.get(position),
-                Cell::Dead,
-                "Cell at {position:?} must be dead as loaded board only contained dead cells."
-            )
-        }
+        board.set_display_ages(true);
+        board.tick();
+        board.update_display();
+        let board_display = display.lock().unwrap().take().unwrap();
+        assert_eq!(board_display.get_age((1, 1)), Some(1));
+        assert_eq!(board_display.get_age((2, 2)), Some(0));
     }
 
     #[test]
-    /// Correctly loads full board.
-    fn load_board_full() {
+    /// Ages are not tracked whilst they are not displayed.
+    fn ages_untracked() {
         let mut board = Board::new(Default::default());
-
-        // Load full board.
-        let generation = 0;
-        let area = Area::new((-4, -6), (4, 6));
-        let mut board_data = BitVec::new();
-        for _ in area.iterate_over() {
-            board_data.push(Cell::Alive.into());
+        for position in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            board.set(position.into(), Cell::Alive);
         }
+        board.tick();
+        assert!(board.ages.is_empty());
 
-        let simulation_save = SimulationSave::new(generation, area, board_data);
-        board.load_board(simulation_save);
-
-        assert_eq!(
-            board.get((8, 8).into()),
-            Cell::Dead,
-            "Cells outside the new board area must be set to dead."
-        );
-
-        for position in area.iterate_over() {
-            assert_eq!(
-                board.get(position),
-                Cell::Alive,
-                "Cell at {position:?} must be alive as loaded board only contained alive cells."
-            )
-        }
+        board.set_display_ages(true);
+        assert_eq!(board.ages.len(), 4);
+        board.set_display_ages(false);
+        assert!(board.ages.is_empty());
     }
 
     #[test]
-    /// Correctly loads mixed board.
-    fn load_board_mixed() {
-        let mut board = Board::new(Default::default());
-
-        // Load mixed board.
-        let generation = 0;
-        let area = Area::new((-4, -6), (4, 6));
-        let mut board_data = BitVec::new();
-        for (_, cell) in area.iterate_over().zip(generate_cell_iterator()) {
-            board_data.push(cell.into());
+    /// The cells born & the cells that died are recorded once enabled, & are included in the display.
+    fn blinker_delta() {
+        let display = SharedDisplay::default();
+        let mut board = Board::new(display.clone());
+        board.set_display_area(Area::new((0, 0), (2, 2)));
+        // A horizontal blinker.
+        for position in [(0, 1), (1, 1), (2, 1)] {
+            board.set(position.into(), Cell::Alive);
         }
 
-        let simulation_save = SimulationSave::new(generation, area, board_data);
-        board.load_board(simulation_save);
+        board.tick();
+        assert_eq!(board.last_delta(), (Vec::new(), Vec::new()));
+
+        board.set_display_changes(true);
+        board.tick();
+        let (mut births, mut deaths) = board.last_delta();
+        births.sort_by_key(|position| position.get_x());
+        deaths.sort_by_key(|position| position.get_y());
+        // The vertical blinker turns back to horizontal.
+        assert_eq!(births, [(0, 1).into(), (2, 1).into()]);
+        assert_eq!(deaths, [(1, 0).into(), (1, 2).into()]);
 
+        board.update_display();
+        let board_display = display.lock().unwrap().take().unwrap();
+        assert_eq!(board_display.get_change((1, 0)), Some(CellChange::Died));
+        assert_eq!(board_display.get_change((0, 1)), Some(CellChange::Born));
         assert_eq!(
-            board.get((8, 8).into()),
-            Cell::Dead,
-            "Cells outside the new board area must be set to dead."
+            board_display.get_change((1, 1)),
+            Some(CellChange::Unchanged)
         );
-
-        for (position, cell) in area.iterate_over().zip(generate_cell_iterator()) {
-            assert_eq!(
-                board.get(position),
-                cell,
-                "Cell at {position:?} must be {cell:?} as loaded board had this cell in this state."
-            )
-        }
     }
 
     #[test]
-    /// Correctly saves empty board.
-    fn save_board_empty() {
-        let board = Board::new(Default::default());
-
-        let generation = 0;
-        let board_area = Area::new((0, 0), (0, 0));
-        let mut board_data = BitVec::new();
-        for _ in board_area.iterate_over() {
-            board_data.push(Cell::Dead.into());
-        }
-
-        let expected_save = SimulationSave::new(generation, board_area, board_data);
-        let save_board = board.save_board();
+    /// Editing the board forgets the changes of the last generation, as they no longer describe the board.
+    fn delta_cleared_by_edits() {
+        let edits: [fn(&mut Board); 3] = [
+            |board| board.set((5, 5).into(), Cell::Alive),
+            |board| board.mirror(Axis::Vertical),
+            |board| board.rotate(1).unwrap(),
+        ];
+
+        for edit in edits {
+            let mut board = Board::new(Default::default());
+            board.set_display_changes(true);
+            for position in [(0, 1), (1, 1), (2, 1)] {
+                board.set(position.into(), Cell::Alive);
+            }
+            board.tick();
+            assert_ne!(board.last_delta(), (Vec::new(), Vec::new()));
 
-        assert_eq!(save_board, expected_save);
+            edit(&mut board);
+            assert_eq!(board.last_delta(), (Vec::new(), Vec::new()));
+        }
     }
 
     #[test]
-    /// Correctly saves full board area.
-    fn save_board_full_area() {
-        let mut board = Board::new(Default::default());
-        let board_area = Area::new((-6, -6), (5, 5));
-
-        for position in board_area.iterate_over() {
-            board.set(position, Cell::Alive);
-        }
-        let save_board = board.save_board();
-
-        let generation = 0;
-        let mut board_data = BitVec::new();
-        for _ in board_area.iterate_over() {
-            board_data.push(Cell::Alive.into());
+    /// The simulator responds to every packet the ui can send.
+    fn simulator_handles_every_packet() {
+        use gol_lib::communication::{SimulationSpeed, SimulatorPacket, UiPacket};
+        use gol_lib::persistence::{presets, rle::parse_rle};
+        use std::time::Duration;
+
+        /// Fails to compile if a packet is added without being covered by this test.
+        fn covered(packet: &UiPacket) {
+            match packet {
+                UiPacket::DisplayArea { .. }
+                | UiPacket::Set { .. }
+                | UiPacket::SetMany { .. }
+                | UiPacket::SaveBoard
+                | UiPacket::LoadBoard { .. }
+                | UiPacket::SaveBlueprint { .. }
+                | UiPacket::LoadBlueprint { .. }
+                | UiPacket::Randomize { .. }
+                | UiPacket::Clear
+                | UiPacket::Mirror { .. }
+                | UiPacket::Rotate { .. }
+                | UiPacket::RequestBoardArea
+                | UiPacket::Start
+                | UiPacket::StartUntil { .. }
+                | UiPacket::Stop
+                | UiPacket::ClearTarget
+                | UiPacket::Step { .. }
+                | UiPacket::GotoGeneration { .. }
+                | UiPacket::SimulationSpeed { .. }
+                | UiPacket::PeriodDetection { .. }
+                | UiPacket::GenerationHistory { .. }
+                | UiPacket::CellLimit { .. }
+                | UiPacket::SetStopCondition { .. }
+                | UiPacket::RecordStatistics { .. }
+                | UiPacket::DisplayAges { .. }
+                | UiPacket::DisplayChanges { .. }
+                | UiPacket::Terminate => {}
+            }
         }
-        let expected_save = SimulationSave::new(generation, board_area, board_data);
 
-        assert_eq!(save_board, expected_save);
-    }
+        // The packets are sent before the simulator starts, so they are all received together.
+        let mut packets = Vec::new();
+        let mut send = |packet: UiPacket| packets.push(packet);
+        send(UiPacket::DisplayArea {
+            new_area: Area::new((0, 0), (10, 10)),
+        });
+        send(UiPacket::PeriodDetection { history: 0 });
+        send(UiPacket::GenerationHistory { capacity: 0 });
+        send(UiPacket::CellLimit { limit: Some(1000) });
+        send(UiPacket::DisplayAges { enabled: true });
+        send(UiPacket::DisplayChanges { enabled: true });
+        send(UiPacket::SetStopCondition { condition: None });
+        send(UiPacket::RecordStatistics { enabled: false });
+        send(UiPacket::Set {
+            position: (50, 50).into(),
+            cell_state: Cell::Alive,
+        });
+        send(UiPacket::Clear);
+        // A horizontal blinker.
+        send(UiPacket::SetMany {
+            cells: Box::new([
+                ((1, 2).into(), Cell::Alive),
+                ((2, 2).into(), Cell::Alive),
+                ((3, 2).into(), Cell::Alive),
+            ]),
+        });
+        // A block.
+        send(UiPacket::Randomize {
+            area: Area::new((100, 100), (101, 101)),
+            density: 1.0,
+            seed: 0,
+            symmetry: Default::default(),
+        });
+        send(UiPacket::SaveBlueprint {
+            area: Area::new((1, 2), (3, 2)),
+        });
+        send(UiPacket::LoadBlueprint {
+            load_position: (1, 6).into(),
+            blueprint: parse_rle(presets::BLINKER).unwrap(),
+        });
+        send(UiPacket::RequestBoardArea);
+        send(UiPacket::Step { count: 2 });
+        send(UiPacket::SaveBoard);
+        packets.iter().for_each(covered);
+
+        let display = SharedDisplay::default();
+        let harness = test_suite::spawn_with(Board::new(display.clone()), packets);
+        let send = |packet: UiPacket| {
+            covered(&packet);
+            harness.send(packet);
+        };
+        // Tick rates are reported whenever the simulation advances, so are skipped.
+        let receive = || {
+            harness.expect_packet(|packet| {
+                (!matches!(packet, SimulatorPacket::TickRate { .. })).then_some(packet)
+            })
+        };
 
-    #[test]
-    /// Correctly saves mixed board area.
-    fn save_board_mixed() {
-        let mut board = Board::new(Default::default());
-        let board_area = Area::new((-6, -6), (5, 5));
+        let packet = receive();
+        assert!(
+            matches!(packet, SimulatorPacket::BlueprintSave { .. }),
+            "{packet:?}"
+        );
+        let packet = receive();
+        assert!(
+            matches!(
+                packet,
+                SimulatorPacket::BlueprintLoadResult {
+                    status: LoadStatus::Success
+                }
+            ),
+            "{packet:?}"
+        );
+        let packet = receive();
+        let SimulatorPacket::BoardArea { area: Some(area) } = packet else {
+            panic!("{packet:?}");
+        };
+        assert_eq!(area, Area::new((1, 2), (101, 101)));
+        let packet = receive();
+        let SimulatorPacket::BoardSave { board: save } = packet else {
+            panic!("{packet:?}");
+        };
+        // The save is requested in the same batch as the step, so is made before the step is taken.
+        assert_eq!(save.get_generation(), 0);
+
+        // The display is first updated once the step is taken.
+        let board_display = (0..500)
+            .find_map(|_| {
+                std::thread::sleep(Duration::from_millis(10));
+                display.lock().unwrap().take()
+            })
+            .expect("Display is updated");
+        assert_eq!(board_display.get_generation(), 2);
+        // The centre of each blinker has survived both generations.
+        assert_eq!(board_display.get_age((2, 2)), Some(2));
+        assert_eq!(board_display.get_age((2, 6)), Some(2));
+
+        send(UiPacket::LoadBoard { board: save });
+        let packet = receive();
+        assert!(
+            matches!(
+                packet,
+                SimulatorPacket::BoardLoadResult {
+                    status: LoadStatus::Success
+                }
+            ),
+            "{packet:?}"
+        );
 
-        for (position, cell) in board_area.iterate_over().zip(generate_cell_iterator()) {
-            board.set(position, cell);
-        }
-        let save_board = board.save_board();
-
-        let generation = 0;
-        let mut board_data = BitVec::new();
-        for (position, cell) in board_area.iterate_over().zip(generate_cell_iterator()) {
-            // The last tile in each row is cut off due to it being empty.
-            // This is intended.
-            if position.get_x() == 5 {
-                continue;
+        send(UiPacket::SimulationSpeed {
+            speed: SimulationSpeed::new(1000),
+        });
+        send(UiPacket::StartUntil { generation: 10 });
+        let progress = harness.expect_packet(|packet| match packet {
+            SimulatorPacket::Progress { current, target } if current >= target => {
+                Some((current, target))
             }
+            _ => None,
+        });
+        assert_eq!(progress, (10, 10));
+
+        // The history is disabled, so only the current generation can be returned to.
+        send(UiPacket::GotoGeneration { generation: 0 });
+        let packet = receive();
+        assert!(
+            matches!(
+                packet,
+                SimulatorPacket::GenerationUnavailable {
+                    requested: 0,
+                    earliest: 10
+                }
+            ),
+            "{packet:?}"
+        );
 
-            board_data.push(cell.into());
-        }
-        // Compensate for last tile being cut off in each row.
-        let board_area = Area::new((-6, -6), (4, 5));
-        let expected_save = SimulationSave::new(generation, board_area, board_data);
+        send(UiPacket::StartUntil { generation: 20 });
+        send(UiPacket::ClearTarget);
+        send(UiPacket::Start);
+        send(UiPacket::Stop);
+        send(UiPacket::SaveBoard);
+        let save = harness.expect_packet(|packet| match packet {
+            SimulatorPacket::BoardSave { board } => Some(board),
+            _ => None,
+        });
+        assert!(save.get_generation() >= 10);
 
-        assert_eq!(save_board, expected_save);
+        harness.terminate();
     }
 
     #[test]
-    /// `set_generation()` correctly sets the generation.
-    fn set_generation() {
+    /// Only alive states are kept, as the board only has two states.
+    fn set_state_binary() {
         let mut board = Board::new(Default::default());
-        board.set_generation(100);
-        assert_eq!(board.get_generation(), 100);
+        board.set_state((0, 0).into(), CellState::ALIVE);
+        board.set_state((1, 0).into(), CellState::new(2));
+
+        assert_eq!(board.get_state((0, 0).into()), CellState::ALIVE);
+        assert_eq!(board.get_state((1, 0).into()), CellState::DEAD);
+        assert_eq!(board.population(), 1);
     }
 
     #[test]
-    /// Getting the board area of a filled area will return the filled area.
-    fn get_board_area_full() {
+    /// Translated copies of a pattern share the board hash, but not the positional hash, & both match a save.
+    fn board_hash_translated() {
         let mut board = Board::new(Default::default());
+        let mut translated = Board::new(Default::default());
+        // A glider.
+        for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            board.set((x, y).into(), Cell::Alive);
+            translated.set((x - 40, y + 7).into(), Cell::Alive);
+        }
 
-        let area = Area::new((-2, -2), (3, 4));
-        for position in area.iterate_over() {
-            board.set(position, Cell::Alive);
+        assert_eq!(board.board_hash(), translated.board_hash());
+        assert_ne!(board.positional_hash(), translated.positional_hash());
+
+        for board in [&board, &translated] {
+            let save = board.save_board();
+            assert_eq!(board.board_hash(), save.shape_hash());
+            assert_eq!(board.positional_hash(), save.content_hash());
         }
 
-        assert_eq!(board.get_board_area(), area)
+        // A different pattern within the same bounding box has a different hash.
+        board.set((0, 0).into(), Cell::Alive);
+        assert_ne!(board.board_hash(), translated.board_hash());
     }
 
     #[test]
-    /// The board area will included separate cells.
-    fn get_board_area_partial() {
+    /// A glider returns to the same shape every 4 generations, having moved.
+    fn board_hash_glider() {
         let mut board = Board::new(Default::default());
-
-        let area = Area::new((0, 0), (4, 4));
-        for position in area.iterate_over() {
-            board.set(position, Cell::Alive);
+        for position in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            board.set(position.into(), Cell::Alive);
         }
+        let (shape, position) = (board.board_hash(), board.positional_hash());
 
-        board.set((4, 6).into(), Cell::Alive);
+        board.tick();
+        assert_ne!(board.board_hash(), shape);
 
-        assert_eq!(board.get_board_area(), Area::new((0, 0), (4, 6)));
+        board.tick_n(3);
+        assert_eq!(board.board_hash(), shape);
+        assert_ne!(board.positional_hash(), position);
     }
 }
+
+/// The tests every simulator must pass, run against this board.
+#[cfg(test)]
+mod suite {
+    gol_lib::simulator_test_suite!(crate::Board);
+}
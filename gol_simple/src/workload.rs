@@ -0,0 +1,74 @@
+//! Boards used to measure the performance of [`Board`], such as in the benchmarks.
+//! Each board is constructed deterministically, so measurements can be compared between runs.
+
+use gol_lib::{
+    persistence::{presets, rle::parse_rle},
+    Area, Automaton, GlobalPosition, LoadError, LoadStatus, Simulator,
+};
+
+use crate::Board;
+
+/// Loads the given built-in RLE pattern onto the board, with its top left at the given position.
+fn load_preset(board: &mut Board, rle: &str, position: GlobalPosition) -> Result<(), LoadError> {
+    let blueprint = parse_rle(rle).expect("Built-in patterns are valid");
+    match board.load_blueprint(position, blueprint) {
+        LoadStatus::Success => Ok(()),
+        LoadStatus::Fail { reason } => Err(reason),
+    }
+}
+
+/// A square board with the given side length, filled with random cells with the given density.
+pub fn random_field(side: i32, density: f32, seed: u64) -> Board {
+    let mut board = Board::new(Default::default());
+    board.randomize(Area::new((0, 0), (side - 1, side - 1)), density, seed);
+    board
+}
+
+/// A grid of gliders, with the given amount of gliders along each side & the given amount of cells between the top
+/// left of each glider.
+///
+/// The gliders all travel in the same direction, so they never collide while the spacing is larger than a glider.
+///
+/// Returns an error if a glider could not be loaded onto the board.
+pub fn glider_field(gliders: i32, spacing: i32) -> Result<Board, LoadError> {
+    let mut board = Board::new(Default::default());
+    for x in 0..gliders {
+        for y in 0..gliders {
+            load_preset(
+                &mut board,
+                presets::GLIDER,
+                (x * spacing, y * spacing).into(),
+            )?;
+        }
+    }
+    Ok(board)
+}
+
+/// A Gosper glider gun that has been advanced by the given amount of generations, so it has produced a stream of
+/// gliders.
+///
+/// Returns an error if the gun could not be loaded onto the board.
+pub fn glider_gun(warmup: u64) -> Result<Board, LoadError> {
+    let mut board = Board::new(Default::default());
+    load_preset(&mut board, presets::GOSPER_GLIDER_GUN, (0, 0).into())?;
+    board.tick_n(warmup);
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The boards are the same each time they are constructed.
+    fn deterministic() {
+        assert_eq!(
+            random_field(32, 0.5, 7).board,
+            random_field(32, 0.5, 7).board
+        );
+        assert_eq!(glider_field(4, 10).unwrap().population(), 4 * 4 * 5);
+        // The gun emits a glider every 30 generations, on top of its own 36 cells.
+        assert_eq!(glider_gun(0).unwrap().population(), 36);
+        assert!(glider_gun(300).unwrap().population() > 36 + 5 * 5);
+    }
+}